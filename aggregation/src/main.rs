@@ -0,0 +1,18 @@
+//! Verifies compressed proofs of other programs, such as several runs of the zktls program, so
+//! that one proof stands for all of them.
+//!
+//! The host writes the [`ChildCommitment`] of every proof, then the proofs themselves as deferred
+//! proofs in the same order. Each commitment is checked against its proof, and the list is
+//! committed as the public values.
+
+#![no_main]
+use zktls_lib::aggregation::ChildCommitment;
+sp1_zkvm::entrypoint!(main);
+
+pub fn main() {
+    let children: Vec<ChildCommitment> = sp1_zkvm::io::read();
+    for child in &children {
+        sp1_zkvm::lib::verify::verify_sp1_proof(&child.vkey_digest, &child.public_values_digest);
+    }
+    sp1_zkvm::io::commit(&children);
+}
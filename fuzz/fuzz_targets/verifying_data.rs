@@ -0,0 +1,27 @@
+//! Feeds arbitrary bytes through the same deserialize-then-verify path the zkVM program runs on
+//! untrusted input. Malformed attestations must be rejected with an error, never a panic.
+//!
+//! ```shell
+//! cargo +nightly fuzz run verifying_data
+//! ```
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+
+const VERIFYING_KEY: &str = include_str!("../../fixtures/zktls/verifying_k256.key");
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(verifying_data) = serde_json::from_slice::<VerifyingDataOpt>(data) {
+        exercise(&verifying_data);
+    }
+    if let Ok(verifying_data) = ciborium::from_reader::<VerifyingDataOpt, _>(data) {
+        exercise(&verifying_data);
+    }
+});
+
+fn exercise(verifying_data: &VerifyingDataOpt) {
+    // The guest reads the records before verifying, so both have to cope with garbage.
+    let _ = verifying_data.get_records();
+    let _ = verifying_data.verify(VERIFYING_KEY);
+}
@@ -0,0 +1,91 @@
+//! Native benchmarks of `VerifyingDataOpt::verify`, outside the zkVM.
+//!
+//! Run with `cargo bench -p zktls-lib --features upstream --bench verify`.
+
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::Aes128;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::{Signature, SigningKey};
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::stream::BlockRun;
+use zktls_lib::verifying_data::{Packet, PacketRecord, VerifyingData};
+
+const RECORD_COUNTS: [usize; 4] = [16, 256, 1024, 2048];
+
+/// The bytes of every record: four full keystream blocks.
+const RECORD_BYTES: usize = 64;
+
+/// A one-packet attestation of `count` records, with the hex key it verifies under.
+fn attestation(count: usize) -> (String, VerifyingDataOpt) {
+    let signing_key = SigningKey::from_bytes(&[0x2a; 32].into()).unwrap();
+    let aes_key = [0x17; 16];
+    let cipher = Aes128::new(&aes_key.into());
+
+    let mut signed = Vec::with_capacity(count * (12 + RECORD_BYTES));
+    let records: Vec<PacketRecord> = (0..count)
+        .map(|index| {
+            let mut nonce = [0u8; 12];
+            nonce[4..].copy_from_slice(&(index as u64).to_be_bytes());
+            let plaintext: Vec<u8> = (0..RECORD_BYTES)
+                .map(|offset| b'0' + ((index + offset) % 10) as u8)
+                .collect();
+            let ciphertext: Vec<u8> = plaintext
+                .chunks(16)
+                .enumerate()
+                .flat_map(|(id, block)| {
+                    let mut keystream = [0u8; 16];
+                    keystream[..12].copy_from_slice(&nonce);
+                    keystream[12..].copy_from_slice(&(id as u32 + 2).to_be_bytes());
+                    let mut keystream = keystream.into();
+                    cipher.encrypt_block(&mut keystream);
+                    block
+                        .iter()
+                        .zip(keystream)
+                        .map(|(byte, key)| byte ^ key)
+                        .collect::<Vec<u8>>()
+                })
+                .collect();
+            signed.extend_from_slice(&nonce);
+            signed.extend_from_slice(&ciphertext);
+            PacketRecord {
+                nonce,
+                ciphertext,
+                blocks: vec![BlockRun {
+                    first_id: 0,
+                    count: (RECORD_BYTES / 16) as u32,
+                    mask: u16::MAX,
+                }],
+                plaintext,
+            }
+        })
+        .collect();
+
+    let signature: Signature = signing_key.sign(&signed);
+    let data = VerifyingData {
+        packets: vec![Packet {
+            aes_key,
+            signature: signature.to_bytes().into(),
+            records,
+        }],
+    };
+    let verifying_key = hex::encode(signing_key.verifying_key().to_encoded_point(true));
+    (verifying_key, data.into())
+}
+
+fn verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify");
+    for count in RECORD_COUNTS {
+        let (verifying_key, data) = attestation(count);
+        assert!(data.verify(&verifying_key).is_ok());
+
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &data, |b, data| {
+            b.iter(|| black_box(data).verify(&verifying_key))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, verify);
+criterion_main!(benches);
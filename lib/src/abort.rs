@@ -0,0 +1,300 @@
+//! Stable codes for every reason the program aborts.
+//!
+//! Operators looking at a failed proving job see the code in the panic message. When the host asks
+//! for status reporting, which it does when only executing, the program instead commits an abort
+//! commitment, [`ABORT_TAG`] followed by the code, and halts cleanly, so the host can read it back
+//! and explain it. The codes are part of the program's interface: existing ones never change
+//! meaning, and new ones are only ever appended.
+//!
+//! Status reporting is set by whoever writes stdin, so a proof of an aborted execution can exist.
+//! Its public values are an abort commitment, which never decodes as
+//! [`PublicValues`](crate::PublicValues) or as a claim: consumers that decode the public values
+//! before trusting them reject it, and those that read them by hand must check for
+//! [`decode_abort`] first.
+
+use std::fmt;
+
+use crate::VerifyError;
+
+/// The status committed first in [`PublicValues`](crate::PublicValues) when nothing aborted.
+pub const STATUS_OK: u32 = 0;
+
+/// The tag an execution with status reporting commits before its abort code, in place of
+/// [`PublicValues`](crate::PublicValues).
+///
+/// Read where [`PublicValues`](crate::PublicValues) holds its `status`, the tag is not
+/// [`STATUS_OK`].
+pub const ABORT_TAG: [u8; 4] = *b"ABRT";
+
+/// The length of an abort commitment: [`ABORT_TAG`], then the code as a little-endian `u32`.
+///
+/// Encoded public values and ABI-encoded claims are always longer, so neither decodes from an
+/// abort commitment.
+pub const ABORT_COMMITMENT_LEN: usize = 8;
+
+/// The abort code in `public_values`, or `None` when they are not an abort commitment.
+pub fn decode_abort(public_values: &[u8]) -> Option<u32> {
+    if public_values.len() != ABORT_COMMITMENT_LEN || public_values[..4] != ABORT_TAG {
+        return None;
+    }
+    Some(u32::from_le_bytes(public_values[4..].try_into().ok()?))
+}
+
+/// Why the program aborted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AbortCode {
+    /// The attestation format tag is not a known [`DataFormat`](crate::DataFormat).
+    UnknownFormat,
+    /// The digest mode tag is not a known
+    /// [`DigestMode`](crate::public_values::DigestMode).
+    UnknownDigestMode,
+    /// A verifying key does not parse, or is not on a curve its use supports.
+    BadKeyEncoding,
+    /// An attestation holds no records.
+    EmptyRecords,
+    /// An attestation or one of its records exceeds a configured size limit.
+    SizeLimitExceeded,
+    /// A predicate or cross-check found no value to compare in a response.
+    ExtractionFailed,
+    /// A redaction range is empty, out of bounds, or overlaps another.
+    InvalidRedaction,
+    /// A response has no complete head, or its chunked body is malformed.
+    MalformedResponse,
+    /// A page of a paginated session does not follow from the previous one.
+    BrokenChain,
+    /// The record sequence numbers do not strictly increase.
+    OutOfOrder,
+    /// A signature does not verify.
+    SignatureFailure,
+    /// A disclosed Merkle chunk is not included under the signed root.
+    BadInclusionProof,
+    /// A response does not declare the expected content type.
+    ContentTypeMismatch,
+    /// The records encoding tag is not a known
+    /// [`RecordsEncoding`](crate::record::RecordsEncoding).
+    UnknownRecordsEncoding,
+    /// A record declared hex-encoded is not valid hex.
+    InvalidRecordEncoding,
+    /// A response body uses an unsupported content coding or does not decode.
+    BadContentEncoding,
+    /// A compressed response body decodes to more than the configured maximum.
+    DecodedTooLarge,
+    /// A response body is not as long as its `Content-Length` declares.
+    BodyLengthMismatch,
+    /// A chunked response body ends before its zero-length chunk.
+    UnterminatedBody,
+    /// A response body to digest canonically is not JSON.
+    BodyNotJson,
+    /// A request or response has no counterpart to pair with.
+    OrphanRecord,
+    /// A response cannot answer the request it is paired with.
+    MismatchedResponse,
+    /// A record to commit as text is not valid UTF-8.
+    InvalidUtf8,
+    /// The request does not match the request template.
+    RequestMismatch,
+    /// An input does not decode as the type the program reads.
+    MalformedInput,
+    /// The response date has no unambiguous timestamp.
+    AmbiguousDate,
+    /// The attestation is in a format version the program does not understand.
+    UnsupportedVersion,
+    /// The host selected a commit mode the program does not know.
+    UnknownCommitMode,
+    /// The host selected a
+    /// [`RecordCommitment`](crate::public_values::RecordCommitment) the program does not know.
+    UnknownRecordCommitment,
+    /// An HTTP/2 record uses a frame type or HPACK feature the program does not support.
+    UnsupportedHttp2,
+    /// The record payload tag is not a known [`RecordPayload`](crate::record::RecordPayload).
+    UnknownRecordPayload,
+    /// The inputs do not match the [`Preamble`](crate::input::Preamble) written before them.
+    BadPreamble,
+    /// The attested records do not hash to the digest the host expects.
+    DigestMismatch,
+    /// A range the host asked to commit is not within its record.
+    RangeOutOfBounds,
+    /// An attestation does not carry exactly one signature per packet.
+    SignatureCountMismatch,
+    /// An attestation does not hold as many records as the prefix the host says it proves.
+    PrefixMismatch,
+}
+
+impl AbortCode {
+    /// The abort commitment an execution with status reporting commits for this reason.
+    pub fn commitment(self) -> [u8; ABORT_COMMITMENT_LEN] {
+        let mut commitment = [0; ABORT_COMMITMENT_LEN];
+        commitment[..4].copy_from_slice(&ABORT_TAG);
+        commitment[4..].copy_from_slice(&self.code().to_le_bytes());
+        commitment
+    }
+
+    /// The stable code committed and printed for this reason.
+    pub const fn code(self) -> u32 {
+        match self {
+            AbortCode::UnknownFormat => 1,
+            AbortCode::UnknownDigestMode => 2,
+            AbortCode::BadKeyEncoding => 3,
+            AbortCode::EmptyRecords => 4,
+            AbortCode::SizeLimitExceeded => 5,
+            AbortCode::ExtractionFailed => 6,
+            AbortCode::InvalidRedaction => 7,
+            AbortCode::MalformedResponse => 8,
+            AbortCode::BrokenChain => 9,
+            AbortCode::OutOfOrder => 10,
+            AbortCode::SignatureFailure => 11,
+            AbortCode::BadInclusionProof => 12,
+            AbortCode::ContentTypeMismatch => 13,
+            AbortCode::UnknownRecordsEncoding => 14,
+            AbortCode::InvalidRecordEncoding => 15,
+            AbortCode::BadContentEncoding => 16,
+            AbortCode::DecodedTooLarge => 17,
+            AbortCode::BodyLengthMismatch => 18,
+            AbortCode::UnterminatedBody => 19,
+            AbortCode::BodyNotJson => 20,
+            AbortCode::OrphanRecord => 21,
+            AbortCode::MismatchedResponse => 22,
+            AbortCode::InvalidUtf8 => 23,
+            AbortCode::RequestMismatch => 24,
+            AbortCode::MalformedInput => 25,
+            AbortCode::AmbiguousDate => 26,
+            AbortCode::UnsupportedVersion => 27,
+            AbortCode::UnknownCommitMode => 28,
+            AbortCode::UnknownRecordCommitment => 29,
+            AbortCode::UnsupportedHttp2 => 30,
+            AbortCode::UnknownRecordPayload => 31,
+            AbortCode::BadPreamble => 32,
+            AbortCode::DigestMismatch => 33,
+            AbortCode::RangeOutOfBounds => 34,
+            AbortCode::SignatureCountMismatch => 35,
+            AbortCode::PrefixMismatch => 36,
+        }
+    }
+
+    /// The reason for `code`, if it is known.
+    pub const fn from_code(code: u32) -> Option<Self> {
+        match code {
+            1 => Some(AbortCode::UnknownFormat),
+            2 => Some(AbortCode::UnknownDigestMode),
+            3 => Some(AbortCode::BadKeyEncoding),
+            4 => Some(AbortCode::EmptyRecords),
+            5 => Some(AbortCode::SizeLimitExceeded),
+            6 => Some(AbortCode::ExtractionFailed),
+            7 => Some(AbortCode::InvalidRedaction),
+            8 => Some(AbortCode::MalformedResponse),
+            9 => Some(AbortCode::BrokenChain),
+            10 => Some(AbortCode::OutOfOrder),
+            11 => Some(AbortCode::SignatureFailure),
+            12 => Some(AbortCode::BadInclusionProof),
+            13 => Some(AbortCode::ContentTypeMismatch),
+            14 => Some(AbortCode::UnknownRecordsEncoding),
+            15 => Some(AbortCode::InvalidRecordEncoding),
+            16 => Some(AbortCode::BadContentEncoding),
+            17 => Some(AbortCode::DecodedTooLarge),
+            18 => Some(AbortCode::BodyLengthMismatch),
+            19 => Some(AbortCode::UnterminatedBody),
+            20 => Some(AbortCode::BodyNotJson),
+            21 => Some(AbortCode::OrphanRecord),
+            22 => Some(AbortCode::MismatchedResponse),
+            23 => Some(AbortCode::InvalidUtf8),
+            24 => Some(AbortCode::RequestMismatch),
+            25 => Some(AbortCode::MalformedInput),
+            26 => Some(AbortCode::AmbiguousDate),
+            27 => Some(AbortCode::UnsupportedVersion),
+            28 => Some(AbortCode::UnknownCommitMode),
+            29 => Some(AbortCode::UnknownRecordCommitment),
+            30 => Some(AbortCode::UnsupportedHttp2),
+            31 => Some(AbortCode::UnknownRecordPayload),
+            32 => Some(AbortCode::BadPreamble),
+            33 => Some(AbortCode::DigestMismatch),
+            34 => Some(AbortCode::RangeOutOfBounds),
+            35 => Some(AbortCode::SignatureCountMismatch),
+            36 => Some(AbortCode::PrefixMismatch),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AbortCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AbortCode::UnknownFormat => "unknown attestation format",
+            AbortCode::UnknownDigestMode => "unknown digest mode",
+            AbortCode::BadKeyEncoding => "verifying key does not parse or is on the wrong curve",
+            AbortCode::EmptyRecords => "attestation holds no records",
+            AbortCode::SizeLimitExceeded => "attestation exceeds a size limit",
+            AbortCode::ExtractionFailed => "no value to compare in the response",
+            AbortCode::InvalidRedaction => "invalid redaction range",
+            AbortCode::MalformedResponse => "malformed HTTP response",
+            AbortCode::BrokenChain => "paginated session is broken",
+            AbortCode::OutOfOrder => "records are out of order",
+            AbortCode::SignatureFailure => "signature does not verify",
+            AbortCode::BadInclusionProof => "chunk is not included under the signed root",
+            AbortCode::ContentTypeMismatch => "unexpected content type",
+            AbortCode::UnknownRecordsEncoding => "unknown records encoding",
+            AbortCode::InvalidRecordEncoding => "record is not valid hex",
+            AbortCode::BadContentEncoding => "response body does not decode",
+            AbortCode::DecodedTooLarge => "decoded response body is too large",
+            AbortCode::BodyLengthMismatch => "response body does not match its Content-Length",
+            AbortCode::UnterminatedBody => "chunked response body is truncated",
+            AbortCode::BodyNotJson => "response body is not JSON",
+            AbortCode::OrphanRecord => "record has no request or response to pair with",
+            AbortCode::MismatchedResponse => "response does not answer its request",
+            AbortCode::InvalidUtf8 => "record is not valid UTF-8",
+            AbortCode::RequestMismatch => "request does not match its template",
+            AbortCode::MalformedInput => "input does not decode",
+            AbortCode::AmbiguousDate => "response date is ambiguous",
+            AbortCode::UnsupportedVersion => "attestation version is not supported",
+            AbortCode::UnknownCommitMode => "unknown commit mode",
+            AbortCode::UnknownRecordCommitment => "unknown record commitment",
+            AbortCode::UnsupportedHttp2 => "unsupported HTTP/2 feature",
+            AbortCode::UnknownRecordPayload => "unknown record payload",
+            AbortCode::BadPreamble => "inputs do not match their preamble",
+            AbortCode::DigestMismatch => "records do not match the expected digest",
+            AbortCode::RangeOutOfBounds => "committed range is out of bounds",
+            AbortCode::SignatureCountMismatch => "wrong number of signatures",
+            AbortCode::PrefixMismatch => "attestation does not match the proven prefix",
+        })
+    }
+}
+
+impl From<&VerifyError> for AbortCode {
+    fn from(err: &VerifyError) -> Self {
+        match err {
+            VerifyError::RedactionOverlap { .. } => AbortCode::InvalidRedaction,
+            VerifyError::MissingResponse
+            | VerifyError::PathNotFound { .. }
+            | VerifyError::NotANumber { .. } => AbortCode::ExtractionFailed,
+            VerifyError::KeyParseError { .. } | VerifyError::NoAddress { .. } => {
+                AbortCode::BadKeyEncoding
+            }
+            VerifyError::BadSignature => AbortCode::SignatureFailure,
+            VerifyError::BadInclusionProof { .. } => AbortCode::BadInclusionProof,
+            VerifyError::MalformedResponse { .. } => AbortCode::MalformedResponse,
+            VerifyError::TooLarge { .. } => AbortCode::SizeLimitExceeded,
+            VerifyError::BrokenChain { .. } => AbortCode::BrokenChain,
+            VerifyError::OutOfOrder { .. } => AbortCode::OutOfOrder,
+            VerifyError::ContentTypeMismatch { .. } => AbortCode::ContentTypeMismatch,
+            VerifyError::InvalidRecordEncoding { .. } => AbortCode::InvalidRecordEncoding,
+            VerifyError::BadContentEncoding { .. } => AbortCode::BadContentEncoding,
+            VerifyError::DecodedTooLarge { .. } => AbortCode::DecodedTooLarge,
+            VerifyError::BodyLengthMismatch { .. } => AbortCode::BodyLengthMismatch,
+            VerifyError::UnterminatedBody { .. } => AbortCode::UnterminatedBody,
+            VerifyError::BodyNotJson { .. } => AbortCode::BodyNotJson,
+            VerifyError::OrphanRecord { .. } => AbortCode::OrphanRecord,
+            VerifyError::MismatchedResponse { .. } => AbortCode::MismatchedResponse,
+            VerifyError::InvalidUtf8 { .. } => AbortCode::InvalidUtf8,
+            VerifyError::RequestMismatch { .. } => AbortCode::RequestMismatch,
+            VerifyError::MalformedInput => AbortCode::MalformedInput,
+            VerifyError::AmbiguousDate { .. } => AbortCode::AmbiguousDate,
+            VerifyError::UnsupportedVersion { .. } => AbortCode::UnsupportedVersion,
+            VerifyError::UnsupportedFrame { .. } | VerifyError::HpackDynamicTable { .. } => {
+                AbortCode::UnsupportedHttp2
+            }
+            VerifyError::MalformedFrame => AbortCode::MalformedResponse,
+            VerifyError::BadPreamble => AbortCode::BadPreamble,
+            VerifyError::RangeOutOfBounds { .. } => AbortCode::RangeOutOfBounds,
+            VerifyError::SignatureCountMismatch { .. } => AbortCode::SignatureCountMismatch,
+        }
+    }
+}
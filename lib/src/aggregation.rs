@@ -0,0 +1,34 @@
+//! What the aggregation program commits of every proof it verifies.
+//!
+//! The aggregation program checks compressed proofs of other programs inside its own, so a single
+//! proof stands for several executions. For each, it commits the [`ChildCommitment`]: the digest
+//! of the verifying key the proof is for, and the digest of its public values. A verifier holding
+//! the public values of a child, for example a zktls [`PublicValues`](crate::PublicValues), checks
+//! them against the digest with [`ChildCommitment::commits`].
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A proof verified by the aggregation program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChildCommitment {
+    /// The verifying key of the program proven, as `SP1VerifyingKey::hash_u32` returns it.
+    pub vkey_digest: [u32; 8],
+    /// SHA-256 of the public values the proof commits, as the zkVM's proof verification expects.
+    pub public_values_digest: [u8; 32],
+}
+
+impl ChildCommitment {
+    /// The commitment of a proof of the program with `vkey_digest` that commits `public_values`.
+    pub fn new(vkey_digest: [u32; 8], public_values: &[u8]) -> Self {
+        ChildCommitment {
+            vkey_digest,
+            public_values_digest: Sha256::digest(public_values).into(),
+        }
+    }
+
+    /// Whether this is the commitment of a proof that commits `public_values`.
+    pub fn commits(&self, public_values: &[u8]) -> bool {
+        <[u8; 32]>::from(Sha256::digest(public_values)) == self.public_values_digest
+    }
+}
@@ -0,0 +1,71 @@
+//! Alternate field names some attestors emit for a `VerifyingDataOpt`.
+//!
+//! The upstream container only parses its canonical, snake_case field names. Attestors written
+//! against other conventions emit the same fields in camelCase, or call a packet's records
+//! `data`. [`canonicalize`] renames every such alias in an attestation's JSON to the name it stands
+//! for, so the attestation parses as if it had been written with the canonical names, and is then
+//! only ever serialized with them.
+//!
+//! | Object      | Canonical name    | Accepted aliases  |
+//! |-------------|-------------------|-------------------|
+//! | attestation | `attestor_id`     | `attestorId`      |
+//! | attestation | `tls_version`     | `tlsVersion`      |
+//! | attestation | `cipher_suite`    | `cipherSuite`     |
+//! | packet      | `aes_key`         | `aesKey`          |
+//! | packet      | `record_messages` | `recordMessages`  |
+//! | packet      | `ecdsa_signature` | `ecdsaSignature`  |
+//! | packet      | `records`         | `data`            |
+//! | packet      | `tls_version`     | `tlsVersion`      |
+//! | packet      | `cipher_suite`    | `cipherSuite`     |
+//!
+//! Every other field, such as `packets`, `version` or a record's `nonce`, has a one-word name and
+//! no alias.
+
+use serde_json::{Map, Value};
+
+/// The aliases of the top-level attestation fields, each with the canonical name it stands for.
+pub const ATTESTATION_ALIASES: [(&str, &str); 3] = [
+    ("attestorId", "attestor_id"),
+    ("tlsVersion", "tls_version"),
+    ("cipherSuite", "cipher_suite"),
+];
+
+/// The aliases of the fields of each packet, each with the canonical name it stands for.
+pub const PACKET_ALIASES: [(&str, &str); 6] = [
+    ("aesKey", "aes_key"),
+    ("recordMessages", "record_messages"),
+    ("ecdsaSignature", "ecdsa_signature"),
+    ("data", "records"),
+    ("tlsVersion", "tls_version"),
+    ("cipherSuite", "cipher_suite"),
+];
+
+/// Rename every alias in `attestation` to its canonical name, returning whether any was renamed.
+///
+/// An alias next to the field it stands for is left as is, so the canonical field wins.
+pub fn canonicalize(attestation: &mut Value) -> bool {
+    let Some(attestation) = attestation.as_object_mut() else {
+        return false;
+    };
+    let mut renamed = rename(attestation, &ATTESTATION_ALIASES);
+    if let Some(packets) = attestation.get_mut("packets").and_then(Value::as_array_mut) {
+        for packet in packets.iter_mut().filter_map(Value::as_object_mut) {
+            renamed |= rename(packet, &PACKET_ALIASES);
+        }
+    }
+    renamed
+}
+
+fn rename(object: &mut Map<String, Value>, aliases: &[(&str, &str)]) -> bool {
+    let mut renamed = false;
+    for &(alias, canonical) in aliases {
+        if object.contains_key(canonical) {
+            continue;
+        }
+        if let Some(value) = object.remove(alias) {
+            object.insert(canonical.to_string(), value);
+            renamed = true;
+        }
+    }
+    renamed
+}
@@ -0,0 +1,81 @@
+//! Proving a batch of independent attestations at once.
+//!
+//! In strict mode, the default, the program aborts unless every attestation in the batch
+//! verifies. In lenient mode it verifies each one independently and commits which ones did, so a
+//! monitoring pipeline gets one proof of "7 of 8 verified" instead of a failed batch.
+//!
+//! Either way the program commits the batch size and an [`aggregate_digest`] over the records
+//! digests of its attestations, one 32-byte handle for the whole batch. A batch of one attestation
+//! is distinct from the same attestation proven alone: its handle is the keccak256 of its records
+//! digest, never the records digest itself.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::{Hasher, Keccak256};
+
+/// Committed in place of the records digest of an attestation that did not verify.
+pub const FAILED_DIGEST: [u8; 32] = [0; 32];
+
+/// The committed result of verifying a batch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchOutcome {
+    pub lenient: bool,
+    /// Bit `i % 8` of byte `i / 8` is set when attestation `i` verified. The attestation proven
+    /// by the rest of the public values is attestation 0.
+    pub verified: Vec<u8>,
+    /// The number of attestations in the batch.
+    pub len: u32,
+    /// The records digest of each attestation, or [`FAILED_DIGEST`] for those that did not verify.
+    pub records_digests: Vec<[u8; 32]>,
+    /// The [`aggregate_digest`] of `records_digests`.
+    pub aggregate_digest: [u8; 32],
+}
+
+/// `keccak256(d_1 || d_2 || … || d_N)` over the records digests of a batch, in order, with
+/// [`FAILED_DIGEST`] for the attestations that did not verify.
+///
+/// The digests are all 32 bytes, so unlike the record digests they are hashed without lengths.
+pub fn aggregate_digest(records_digests: &[[u8; 32]]) -> [u8; 32] {
+    let mut hasher = Keccak256::default();
+    for digest in records_digests {
+        hasher.update(digest);
+    }
+    hasher.finalize()
+}
+
+impl BatchOutcome {
+    /// The outcome of a batch from whether each attestation verified and its records digest.
+    pub fn new(lenient: bool, results: &[(bool, [u8; 32])]) -> Self {
+        let mut verified = vec![0; results.len().div_ceil(8)];
+        for (index, (ok, _)) in results.iter().enumerate() {
+            if *ok {
+                verified[index / 8] |= 1 << (index % 8);
+            }
+        }
+
+        let records_digests: Vec<[u8; 32]> = results
+            .iter()
+            .map(|(ok, digest)| if *ok { *digest } else { FAILED_DIGEST })
+            .collect();
+
+        BatchOutcome {
+            lenient,
+            verified,
+            len: results.len() as u32,
+            aggregate_digest: aggregate_digest(&records_digests),
+            records_digests,
+        }
+    }
+
+    /// Whether attestation `index` verified.
+    pub fn is_verified(&self, index: usize) -> bool {
+        self.verified
+            .get(index / 8)
+            .is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+
+    /// The number of attestations that verified.
+    pub fn verified_count(&self) -> u32 {
+        self.verified.iter().map(|byte| byte.count_ones()).sum()
+    }
+}
@@ -0,0 +1,103 @@
+//! A canonical encoding of attestations, so auditors can check which attestation was proven.
+//!
+//! The attestation is serialized to JSON with object keys sorted bytewise at every level, no
+//! whitespace, and numbers and strings as `serde_json` writes them. Sorting keys explicitly keeps
+//! the encoding independent of field order and of whether `serde_json`'s `preserve_order` feature
+//! is enabled anywhere in the build, which would otherwise change the order of map entries.
+//!
+//! The same encoding applies to JSON response bodies, so that responses differing only in
+//! whitespace, key order or the spelling of numbers hash the same. Numbers are re-serialized from
+//! their parsed value: integers in plain decimal, and any other number as the shortest decimal
+//! that round-trips through an `f64`, so `1.50` and `1.5` agree while `1` and `1.0` do not.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::http::{response_body, response_message, Direction};
+use crate::{Record, VerifyError};
+
+/// Digests of the first response's body, as committed when the host asks for a canonical body
+/// digest.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BodyDigests {
+    /// SHA-256 over the body as sent, after removing any chunked transfer-encoding.
+    pub raw_digest: [u8; 32],
+    /// SHA-256 over the canonical JSON encoding of the body.
+    pub canonical_digest: [u8; 32],
+}
+
+/// The canonical JSON encoding of `value`.
+pub fn canonical_json<T: Serialize>(value: &T) -> Vec<u8> {
+    let value = serde_json::to_value(value).expect("value is not representable as JSON");
+    let mut out = Vec::new();
+    write_canonical(&value, &mut out);
+    out
+}
+
+/// SHA-256 over the [`canonical_json`] of `value`.
+pub fn canonical_digest<T: Serialize>(value: &T) -> [u8; 32] {
+    debug_span!("canonical_digest");
+    Sha256::digest(canonical_json(value)).into()
+}
+
+/// The [`BodyDigests`] of the first response in `records`.
+///
+/// Fails with [`VerifyError::MissingResponse`] when there is no response, with
+/// [`VerifyError::MalformedResponse`] when its body cannot be delimited, and with
+/// [`VerifyError::BodyNotJson`] when the body does not parse as JSON.
+pub fn body_digests(records: &[Record]) -> Result<BodyDigests, VerifyError> {
+    debug_span!("canonical_body_digest");
+    let record = records
+        .iter()
+        .position(|record| Direction::of(&record.bytes) == Direction::Response)
+        .ok_or(VerifyError::MissingResponse)?;
+    let message = response_message(records).ok_or(VerifyError::MissingResponse)?;
+    let body = response_body(&message).ok_or(VerifyError::MalformedResponse {
+        record: record as u32,
+    })?;
+    let value: Value = serde_json::from_slice(&body).map_err(|_| {
+        debug!(record, "body is not JSON");
+        VerifyError::BodyNotJson {
+            record: record as u32,
+        }
+    })?;
+
+    let mut canonical = Vec::with_capacity(body.len());
+    write_canonical(&value, &mut canonical);
+    Ok(BodyDigests {
+        raw_digest: Sha256::digest(&body).into(),
+        canonical_digest: Sha256::digest(canonical).into(),
+    })
+}
+
+fn write_canonical(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Array(items) => {
+            out.push(b'[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(b',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(b']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+
+            out.push(b'{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(b',');
+                }
+                out.extend(serde_json::to_vec(key).unwrap());
+                out.push(b':');
+                write_canonical(item, out);
+            }
+            out.push(b'}');
+        }
+        scalar => out.extend(serde_json::to_vec(scalar).unwrap()),
+    }
+}
@@ -0,0 +1,141 @@
+//! A compact claim committed in place of the full public values.
+//!
+//! On-chain consumers typically read three things from a proof: who attested, which server was
+//! queried, and one value from its response. In [`CommitMode::Claim`] the program commits only a
+//! [`Claim`], ABI-encoded so a contract reads it with `abi.decode(publicValues, (Claim))` instead
+//! of parsing the bincode [`PublicValues`](crate::PublicValues):
+//!
+//! ```solidity
+//! struct Claim {
+//!     address attestor;
+//!     string domain;
+//!     string path;
+//!     string value;
+//!     uint64 timestamp;
+//!     bytes32 recordsDigest;
+//! }
+//! ```
+//!
+//! The claim is built from the records as committed, after redaction, and only from an
+//! attestation that verified; the records digest binds it to the full transcript.
+
+use std::fmt;
+use std::str::FromStr;
+
+use alloy_sol_types::sol;
+
+use crate::http::{header, split_message, Direction};
+use crate::json::{response_value, unquote};
+use crate::key::VerifyingKey;
+use crate::{Record, VerifyError};
+
+sol! {
+    /// The claim committed in [`CommitMode::Claim`].
+    #[derive(Debug, PartialEq, Eq)]
+    struct Claim {
+        /// The Ethereum address of the attestor's verifying key.
+        address attestor;
+        /// The `Host` of the first request, or empty when it has none.
+        string domain;
+        /// The path of `value` in the first response's JSON body.
+        string path;
+        /// The value at `path`, without quotes when it is a JSON string.
+        string value;
+        /// The response date as a unix timestamp; see [`crate::date`].
+        uint64 timestamp;
+        /// The records digest of the committed records.
+        bytes32 recordsDigest;
+    }
+}
+
+impl Claim {
+    /// Claim the value at `path` in the first response of `records`, attested under
+    /// `verifying_key`.
+    ///
+    /// Fails like [`response_value`] when there is no response or nothing at `path`.
+    pub fn new(
+        verifying_key: &VerifyingKey,
+        records: &[Record],
+        path: String,
+        timestamp: u64,
+        records_digest: [u8; 32],
+    ) -> Result<Self, VerifyError> {
+        let value = unquote(&response_value(records, &path)?);
+
+        Ok(Claim {
+            attestor: verifying_key
+                .address()
+                .ok_or(VerifyError::NoAddress {
+                    curve: verifying_key.curve(),
+                })?
+                .into(),
+            domain: request_host(records).unwrap_or_default(),
+            path,
+            value,
+            timestamp,
+            recordsDigest: records_digest.into(),
+        })
+    }
+}
+
+/// The `Host` header of the first request in `records`.
+fn request_host(records: &[Record]) -> Option<String> {
+    let request = records
+        .iter()
+        .find(|record| Direction::of(&record.bytes) == Direction::Request)?;
+    let head = split_message(&request.bytes).map_or(&request.bytes[..], |(head, _)| head);
+    header(head, "host")
+}
+
+/// What the program commits, selected by the host.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CommitMode {
+    /// The full [`PublicValues`](crate::PublicValues), records included.
+    #[default]
+    Full,
+    /// Only the ABI-encoded [`Claim`].
+    Claim,
+}
+
+impl CommitMode {
+    /// The tag written to stdin.
+    pub const fn tag(self) -> u8 {
+        match self {
+            CommitMode::Full => 0,
+            CommitMode::Claim => 1,
+        }
+    }
+
+    /// The mode for `tag`, if it is known.
+    pub const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(CommitMode::Full),
+            1 => Some(CommitMode::Claim),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CommitMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommitMode::Full => f.write_str("full"),
+            CommitMode::Claim => f.write_str("claim"),
+        }
+    }
+}
+
+impl FromStr for CommitMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(CommitMode::Full),
+            "claim" => Ok(CommitMode::Claim),
+            _ => Err(format!(
+                "unknown commit mode `{}`, expected `full` or `claim`",
+                s
+            )),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Limits on the size of an attestation, checked before any signature work.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Record, VerifyError};
+
+/// Caps on the size of an attestation, so that oversized inputs are rejected cheaply instead of
+/// being hashed and verified.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyConfig {
+    /// The most records an attestation may hold.
+    pub max_records: usize,
+    /// The most bytes all records may hold together.
+    pub max_total_bytes: usize,
+}
+
+impl VerifyConfig {
+    /// Well above the largest bench fixture, while still bounding the work per proof.
+    pub const DEFAULT: Self = VerifyConfig {
+        max_records: 4096,
+        max_total_bytes: 16 * 1024 * 1024,
+    };
+
+    /// Reject `records` with [`VerifyError::TooLarge`] if they exceed either limit.
+    pub fn check(&self, records: &[Record]) -> Result<(), VerifyError> {
+        let total_bytes = records.iter().map(|record| record.bytes.len()).sum();
+        self.check_totals(records.len(), total_bytes)
+    }
+
+    /// Reject `records` records of `total_bytes` bytes together, as a streamed attestation
+    /// declares them before they are read, with [`VerifyError::TooLarge`] if they exceed either
+    /// limit.
+    pub fn check_totals(&self, records: usize, total_bytes: usize) -> Result<(), VerifyError> {
+        if records > self.max_records || total_bytes > self.max_total_bytes {
+            return Err(VerifyError::TooLarge {
+                records,
+                total_bytes,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for VerifyConfig {
+    fn default() -> Self {
+        VerifyConfig::DEFAULT
+    }
+}
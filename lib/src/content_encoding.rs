@@ -0,0 +1,191 @@
+//! Decoding of compressed response bodies.
+//!
+//! Most APIs answer with `Content-Encoding: gzip`, so the attested body is compressed and no JSON
+//! can be extracted from it. After verification the program decodes every compressed response
+//! into an equivalent identity-encoded one: the body is replaced by its plaintext and the head
+//! loses its `Content-Encoding` and `Transfer-Encoding` headers and declares the plaintext length.
+//! Everything checked or committed afterwards sees the plaintext, and a [`DecodedBody`] records the
+//! digests of the body as attested and as decoded.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::{HashAlgorithm, RecordHasher};
+use crate::http::{dechunk, header, is_chunked, split_message, Direction};
+use crate::inflate::{gunzip, inflate, unzlib, InflateError};
+use crate::{Record, VerifyError};
+
+/// A content coding the program can decode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Identity,
+    Gzip,
+    /// A zlib stream, or a raw DEFLATE stream as some servers send instead.
+    Deflate,
+}
+
+impl ContentEncoding {
+    /// The tag committed in a [`DecodedBody`].
+    pub const fn tag(self) -> u8 {
+        match self {
+            ContentEncoding::Identity => 0,
+            ContentEncoding::Gzip => 1,
+            ContentEncoding::Deflate => 2,
+        }
+    }
+
+    /// The encoding for `tag`, if it is known.
+    pub const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(ContentEncoding::Identity),
+            1 => Some(ContentEncoding::Gzip),
+            2 => Some(ContentEncoding::Deflate),
+            _ => None,
+        }
+    }
+
+    /// The encoding named by a `Content-Encoding` header value, or `None` if it is not supported.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "identity" => Some(ContentEncoding::Identity),
+            "gzip" | "x-gzip" => Some(ContentEncoding::Gzip),
+            "deflate" => Some(ContentEncoding::Deflate),
+            _ => None,
+        }
+    }
+
+    /// Decode `body`, producing at most `max_bytes` bytes.
+    fn decode(self, body: &[u8], max_bytes: usize) -> Result<Vec<u8>, InflateError> {
+        match self {
+            ContentEncoding::Identity => Ok(body.to_vec()),
+            ContentEncoding::Gzip => gunzip(body, max_bytes),
+            ContentEncoding::Deflate => match unzlib(body, max_bytes) {
+                Err(InflateError::Malformed) => {
+                    inflate(body, max_bytes).and_then(|(output, used)| {
+                        (used == body.len())
+                            .then_some(output)
+                            .ok_or(InflateError::Malformed)
+                    })
+                }
+                result => result,
+            },
+        }
+    }
+}
+
+/// A compressed response body the program decoded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedBody {
+    /// The record the response starts in.
+    pub record: u32,
+    /// The [`ContentEncoding`] tag of the attested body.
+    pub encoding: u8,
+    /// Keccak-256 of the body as attested, after removing any chunked transfer-encoding.
+    pub encoded_digest: [u8; 32],
+    /// Keccak-256 of the decoded body.
+    pub plaintext_digest: [u8; 32],
+}
+
+/// Decode every compressed response in `records` in place.
+///
+/// A response spanning several records is decoded as a whole into the record it starts in, and
+/// the records continuing it are left empty, so record indices are unchanged. A body that would
+/// decode to more than `max_bytes` bytes fails with [`VerifyError::DecodedTooLarge`], and one that
+/// uses an unsupported coding or does not decode fails with [`VerifyError::BadContentEncoding`].
+pub fn decode_bodies(
+    records: &mut [Record],
+    max_bytes: usize,
+) -> Result<Vec<DecodedBody>, VerifyError> {
+    debug_span!("decode_bodies", records = records.len());
+    let mut decoded = Vec::new();
+    let mut start = 0;
+    while start < records.len() {
+        if Direction::of(&records[start].bytes) != Direction::Response {
+            start += 1;
+            continue;
+        }
+        let end = start
+            + 1
+            + records[start + 1..]
+                .iter()
+                .take_while(|record| Direction::of(&record.bytes) == Direction::Unknown)
+                .count();
+
+        let message: Vec<u8> = records[start..end]
+            .iter()
+            .flat_map(|record| record.bytes.iter().copied())
+            .collect();
+        if let Some((plaintext, body)) = decode_response(&message, start as u32, max_bytes)? {
+            records[start].bytes = plaintext;
+            for record in &mut records[start + 1..end] {
+                record.bytes.clear();
+            }
+            decoded.push(body);
+        }
+        start = end;
+    }
+    Ok(decoded)
+}
+
+/// The identity-encoded equivalent of the compressed response `message`, or `None` when it is not
+/// compressed.
+fn decode_response(
+    message: &[u8],
+    record: u32,
+    max_bytes: usize,
+) -> Result<Option<(Vec<u8>, DecodedBody)>, VerifyError> {
+    // A response without a complete head is rejected wherever its body is needed.
+    let Some((head, body)) = split_message(message) else {
+        return Ok(None);
+    };
+    let Some(value) = header(head, "content-encoding") else {
+        return Ok(None);
+    };
+    let encoding =
+        ContentEncoding::parse(&value).ok_or(VerifyError::BadContentEncoding { record })?;
+    if encoding == ContentEncoding::Identity {
+        return Ok(None);
+    }
+
+    let body = if is_chunked(head) {
+        dechunk(body).ok_or(VerifyError::MalformedResponse { record })?
+    } else {
+        body.to_vec()
+    };
+    let plaintext = encoding.decode(&body, max_bytes).map_err(|err| match err {
+        InflateError::Malformed => VerifyError::BadContentEncoding { record },
+        InflateError::TooLarge => VerifyError::DecodedTooLarge { record, max_bytes },
+    })?;
+    debug!(
+        record,
+        encoded = body.len(),
+        decoded = plaintext.len(),
+        "decoded body"
+    );
+
+    let mut decoded_message = Vec::with_capacity(head.len() + plaintext.len() + 32);
+    for (index, line) in head.split(|&byte| byte == b'\n').enumerate() {
+        let name = line.split(|&byte| byte == b':').next().unwrap_or_default();
+        let dropped = ["content-encoding", "transfer-encoding", "content-length"]
+            .iter()
+            .any(|dropped| name.trim_ascii().eq_ignore_ascii_case(dropped.as_bytes()));
+        if index == 0 || !dropped {
+            decoded_message.extend_from_slice(line.strip_suffix(b"\r").unwrap_or(line));
+            decoded_message.extend_from_slice(b"\r\n");
+        }
+    }
+    decoded_message
+        .extend_from_slice(format!("Content-Length: {}\r\n\r\n", plaintext.len()).as_bytes());
+    decoded_message.extend_from_slice(&plaintext);
+
+    let body = DecodedBody {
+        record,
+        encoding: encoding.tag(),
+        encoded_digest: keccak(&body),
+        plaintext_digest: keccak(&plaintext),
+    };
+    Ok(Some((decoded_message, body)))
+}
+
+fn keccak(data: &[u8]) -> [u8; 32] {
+    RecordHasher::new(HashAlgorithm::Keccak256).hash_bytes(data)
+}
@@ -0,0 +1,18 @@
+//! The deployment a proof is made for.
+//!
+//! A verifier contract deployed on several chains, or by several applications, accepts any proof
+//! of the program. To keep a proof made for one deployment from being replayed against another,
+//! the host supplies an [`AppContext`] that the program commits verbatim in the public values. The
+//! program gives it no meaning; a contract binds a proof to itself by checking the context against
+//! its own chain id and application id.
+
+use serde::{Deserialize, Serialize};
+
+/// The chain and application a proof is made for.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppContext {
+    /// The EIP-155 chain id of the verifier contract's chain.
+    pub chain_id: u64,
+    /// An identifier of the application, chosen by its deployer.
+    pub app_id: [u8; 32],
+}
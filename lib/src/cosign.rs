@@ -0,0 +1,71 @@
+//! Attestations signed by several attestors over the same records.
+//!
+//! A `VerifyingDataOpt` carries one signature per packet, under the key it is proven with. An
+//! attestation co-signed by other attestors comes with a [`CoSignature`] from each: their key and
+//! their own signature of every packet, over the same nonces and ciphertext. The program checks
+//! every signature with [`verify_cosigned`] and commits a [`CoSigned`] naming the keys, so a
+//! verifier can require that a quorum of attestors saw the same session; one signature that does
+//! not verify fails the proof.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::RecordHasher;
+use crate::key::VerifyingKey;
+use crate::verifying_data::VerifyingData;
+use crate::VerifyError;
+
+/// Another attestor's signatures of an attestation's packets.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoSignature {
+    pub verifying_key: VerifyingKey,
+    /// The hex `r || s` signature of each packet, in packet order.
+    pub signatures: Vec<String>,
+}
+
+/// The keys an attestation verified under and the records they signed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoSigned {
+    /// The [`VerifyingKey::digest`] of the attestation's key, followed by that of each
+    /// co-signer's in the order the host gave them.
+    pub key_digests: Vec<[u8; 32]>,
+    /// The [`RecordHasher`] digest of the records every key signed, as attested.
+    pub records_digest: [u8; 32],
+}
+
+/// Verify `data` under `verifying_key` and under every co-signer's key with its signatures.
+///
+/// Fails with [`VerifyError::BadSignature`] on the first signature that does not verify, or when
+/// a co-signer does not sign every packet.
+pub fn verify_cosigned(
+    data: &VerifyingData,
+    verifying_key: &VerifyingKey,
+    cosignatures: &[CoSignature],
+) -> Result<CoSigned, VerifyError> {
+    data.verify(verifying_key)?;
+    let mut key_digests = vec![verifying_key.digest()];
+
+    for cosignature in cosignatures {
+        if cosignature.signatures.len() != data.packets.len() {
+            debug!(
+                "co-signer {} signed {} of {} packets",
+                cosignature.verifying_key,
+                cosignature.signatures.len(),
+                data.packets.len()
+            );
+            return Err(VerifyError::BadSignature);
+        }
+        let mut cosigned = data.clone();
+        for (packet, signature) in cosigned.packets.iter_mut().zip(&cosignature.signatures) {
+            packet.signature = <[u8; 64] as hex::FromHex>::from_hex(signature)
+                .map_err(|_| VerifyError::BadSignature)?;
+        }
+        cosigned.verify(&cosignature.verifying_key)?;
+        key_digests.push(cosignature.verifying_key.digest());
+    }
+
+    Ok(CoSigned {
+        key_digests,
+        records_digest: RecordHasher::default()
+            .hash_parts(data.records().map(|record| record.plaintext.as_slice())),
+    })
+}
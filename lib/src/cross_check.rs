@@ -0,0 +1,44 @@
+//! Consistency checks between two independently attested responses.
+//!
+//! The same fact is often available from more than one API. A cross-check verifies a second
+//! attestation, possibly from a different attestor, extracts a value from each response and
+//! commits whether they agree, along with the digest of the second attestation's records.
+
+use serde::{Deserialize, Serialize};
+
+use crate::key::VerifyingKey;
+use crate::{json, Record, VerifyError};
+
+/// The paths to compare in the primary and the second attestation's responses.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossCheck {
+    pub path: String,
+    pub other_path: String,
+}
+
+/// The committed result of a [`CrossCheck`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossCheckOutcome {
+    pub path: String,
+    pub other_path: String,
+    pub other_verifying_key: VerifyingKey,
+    pub other_verified: bool,
+    /// The records digest of the second attestation, over its records.
+    pub other_records_digest: [u8; 32],
+    pub equal: bool,
+}
+
+impl CrossCheck {
+    /// Whether the value at `path` in `records` equals the value at `other_path` in
+    /// `other_records`, comparing numbers by value.
+    pub fn evaluate(
+        &self,
+        records: &[Record],
+        other_records: &[Record],
+    ) -> Result<bool, VerifyError> {
+        let value = json::response_value(records, &self.path)?;
+        let other_value = json::response_value(other_records, &self.other_path)?;
+
+        Ok(json::values_equal(&value, &other_value))
+    }
+}
@@ -0,0 +1,29 @@
+//! Constant-time comparison of digests on the host.
+//!
+//! Inside the zkVM a comparison leaks nothing: the proof reveals only the public values, however
+//! long the program took. A server verifying attestations natively is different, since a `==`
+//! that returns at the first differing byte tells a caller timing its requests how much of a
+//! forged digest was right. Digests compared while verifying are therefore wrapped in
+//! [`CtDigest`], which has no `PartialEq` and compares with [`subtle::ConstantTimeEq`] only.
+//!
+//! The signatures of `VerifyingDataOpt` and `VerifyingData` are checked upstream, by k256, whose
+//! ECDSA verification is constant-time itself.
+
+use subtle::ConstantTimeEq;
+
+/// A 32-byte digest that can only be compared in constant time.
+#[derive(Copy, Clone, Debug)]
+pub struct CtDigest(pub [u8; 32]);
+
+impl CtDigest {
+    /// Whether the digests are equal, taking the same time whichever bytes differ.
+    pub fn ct_eq(&self, other: &CtDigest) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+}
+
+impl From<[u8; 32]> for CtDigest {
+    fn from(digest: [u8; 32]) -> Self {
+        CtDigest(digest)
+    }
+}
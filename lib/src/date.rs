@@ -0,0 +1,145 @@
+//! The `Date` of the first response, committed as a unix timestamp.
+//!
+//! Consumers want to know when the server produced the response, and parsing an HTTP date in
+//! Solidity is impractical, so the program parses the header itself. Only IMF-fixdate, the form
+//! RFC 7231 requires servers to send, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`, is accepted. A
+//! missing or unparseable header commits [`NO_RESPONSE_DATE`]. The obsolete RFC 850 form, whose
+//! two-digit year is ambiguous, and a leap second, which a unix timestamp cannot represent, abort
+//! rather than commit a guess.
+//!
+//! The header is read after redaction, so a redacted date commits [`NO_RESPONSE_DATE`].
+
+use crate::http::{response_message, split_message};
+use crate::response_header::header_values;
+use crate::{Record, VerifyError};
+
+/// Committed in place of a timestamp when the response carries no parseable `Date` header.
+pub const NO_RESPONSE_DATE: u64 = 0;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+/// The weekday names of the RFC 850 form, which IMF-fixdate abbreviates.
+const FULL_WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// The timestamp of the first `Date` header of the first response in `records`, or
+/// [`NO_RESPONSE_DATE`].
+///
+/// Fails with [`VerifyError::AmbiguousDate`] when the date is in the RFC 850 form or falls on a
+/// leap second.
+pub fn response_date(records: &[Record]) -> Result<u64, VerifyError> {
+    let Some(message) = response_message(records) else {
+        return Ok(NO_RESPONSE_DATE);
+    };
+    let head = split_message(&message).map_or(&message[..], |(head, _)| head);
+    match header_values(head, "date").first() {
+        Some(value) => parse_http_date(value),
+        None => Ok(NO_RESPONSE_DATE),
+    }
+}
+
+/// Parse an IMF-fixdate into a unix timestamp, or [`NO_RESPONSE_DATE`] when `value` is not one.
+///
+/// Fails with [`VerifyError::AmbiguousDate`] when `value` is in the RFC 850 form or falls on a
+/// leap second.
+pub fn parse_http_date(value: &str) -> Result<u64, VerifyError> {
+    let ambiguous = || {
+        Err(VerifyError::AmbiguousDate {
+            value: value.to_string(),
+        })
+    };
+
+    let Some((weekday, rest)) = value.split_once(", ") else {
+        return Ok(NO_RESPONSE_DATE);
+    };
+    if FULL_WEEKDAYS.contains(&weekday) {
+        return ambiguous();
+    }
+    let Some(weekday) = WEEKDAYS.iter().position(|name| *name == weekday) else {
+        return Ok(NO_RESPONSE_DATE);
+    };
+
+    // `06 Nov 1994 08:49:37 GMT`
+    let fields: Vec<&str> = rest.split(' ').collect();
+    let [day, month, year, time, "GMT"] = fields[..] else {
+        return Ok(NO_RESPONSE_DATE);
+    };
+    let time: Vec<&str> = time.split(':').collect();
+    let [hour, minute, second] = time[..] else {
+        return Ok(NO_RESPONSE_DATE);
+    };
+    let (Some(day), Some(year), Some(hour), Some(minute), Some(second)) = (
+        digits(day, 2),
+        digits(year, 4),
+        digits(hour, 2),
+        digits(minute, 2),
+        digits(second, 2),
+    ) else {
+        return Ok(NO_RESPONSE_DATE);
+    };
+    let Some(month) = MONTHS.iter().position(|name| *name == month) else {
+        return Ok(NO_RESPONSE_DATE);
+    };
+
+    if second == 60 {
+        return ambiguous();
+    }
+    if year < 1970
+        || day == 0
+        || day > days_in_month(year, month as u64 + 1)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Ok(NO_RESPONSE_DATE);
+    }
+
+    let days = days_since_epoch(year, month as u64 + 1, day);
+    // 1 January 1970 was a Thursday.
+    if (days + 3) % 7 != weekday as u64 {
+        return Ok(NO_RESPONSE_DATE);
+    }
+    Ok(days * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+/// `field` as a number, if it is exactly `len` ASCII digits.
+fn digits(field: &str, len: usize) -> Option<u64> {
+    (field.len() == len && field.bytes().all(|byte| byte.is_ascii_digit()))
+        .then(|| field.parse().ok())
+        .flatten()
+}
+
+fn is_leap_year(year: u64) -> bool {
+    match (year % 400, year % 100, year % 4) {
+        (0, _, _) => true,
+        (_, 0, _) => false,
+        (_, _, remainder) => remainder == 0,
+    }
+}
+
+fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The days from 1 January 1970 to `year-month-day`, for a year from 1970 on.
+fn days_since_epoch(year: u64, month: u64, day: u64) -> u64 {
+    let years: u64 = (1970..year)
+        .map(|year| if is_leap_year(year) { 366 } else { 365 })
+        .sum();
+    let months: u64 = (1..month).map(|month| days_in_month(year, month)).sum();
+    years + months + day - 1
+}
@@ -0,0 +1,102 @@
+//! EIP-712 typed-data hashing of the records, for contracts that compare the attested records
+//! against an EIP-712 signature verified elsewhere.
+//!
+//! The records are hashed as the struct
+//!
+//! ```text
+//! ZkTlsRecords(bytes[] records)
+//! ```
+//!
+//! under the domain
+//!
+//! ```text
+//! EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)
+//! ```
+//!
+//! so the hash matches `_hashTypedDataV4(keccak256(abi.encode(RECORDS_TYPEHASH, recordsHash)))` in
+//! a contract using OpenZeppelin's `EIP712`, where `recordsHash` is the keccak256 of the
+//! concatenated keccak256 of each record. Unlike the plain record digests, the domain binds the
+//! hash to one contract on one chain, so it cannot be replayed elsewhere.
+
+use serde::{Deserialize, Serialize};
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::Record;
+
+/// The EIP-712 type of the records struct.
+pub const RECORDS_TYPE: &str = "ZkTlsRecords(bytes[] records)";
+
+/// The EIP-712 type of the domain.
+pub const DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+
+/// The EIP-712 domain of the consuming contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Eip712Domain {
+    pub name: String,
+    pub version: String,
+    pub chain_id: u64,
+    /// The contract address, written as `0x`-prefixed hex in JSON.
+    #[serde(with = "prefixed_hex")]
+    pub verifying_contract: [u8; 20],
+}
+
+impl Eip712Domain {
+    /// The domain separator, `hashStruct(domain)`.
+    pub fn separator(&self) -> [u8; 32] {
+        let mut chain_id = [0u8; 32];
+        chain_id[24..].copy_from_slice(&self.chain_id.to_be_bytes());
+        let mut verifying_contract = [0u8; 32];
+        verifying_contract[12..].copy_from_slice(&self.verifying_contract);
+
+        keccak(&[
+            &keccak(&[DOMAIN_TYPE.as_bytes()]),
+            &keccak(&[self.name.as_bytes()]),
+            &keccak(&[self.version.as_bytes()]),
+            &chain_id,
+            &verifying_contract,
+        ])
+    }
+}
+
+/// The EIP-712 hash of `records` as a `ZkTlsRecords` struct under `domain`, ready to compare with
+/// the digest a contract recovers a typed-data signature from.
+pub fn eip712_records_hash(records: &[Record], domain: &Eip712Domain) -> [u8; 32] {
+    debug_span!("eip712_records_hash", records = records.len());
+    let mut hasher = Keccak::v256();
+    for record in records {
+        hasher.update(&keccak(&[&record.bytes]));
+    }
+    let mut records_hash = [0; 32];
+    hasher.finalize(&mut records_hash);
+    let struct_hash = keccak(&[&keccak(&[RECORDS_TYPE.as_bytes()]), &records_hash]);
+
+    keccak(&[b"\x19\x01", &domain.separator(), &struct_hash])
+}
+
+fn keccak(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut output = [0; 32];
+    hasher.finalize(&mut output);
+    output
+}
+
+/// Serde adapter for a `0x`-prefixed hex address.
+mod prefixed_hex {
+    use hex::FromHex;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(address: &[u8; 20], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(address)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 20], D::Error> {
+        let address = String::deserialize(deserializer)?;
+        let digits = address.strip_prefix("0x").unwrap_or(&address);
+        <[u8; 20]>::from_hex(digits).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,308 @@
+use std::fmt;
+
+use crate::key::{Curve, KeyFormat};
+use crate::request_template::TemplateComponent;
+
+/// Reasons an attestation is rejected by the program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    /// A redaction range is empty, out of bounds, or overlaps another range in the same record.
+    RedactionOverlap { record: u32, start: u32, end: u32 },
+    /// The transcript holds no HTTP response with a body.
+    MissingResponse,
+    /// Nothing was found at `path` in the response body.
+    PathNotFound { path: String },
+    /// The value at `path` is not a decimal number.
+    NotANumber { path: String },
+    /// The verifying key or the signature over the attested data is invalid.
+    BadSignature,
+    /// The disclosed chunk at `index` is not included under the signed Merkle root.
+    BadInclusionProof { index: u32 },
+    /// The response in record `record` has no complete head, or its chunked body is malformed.
+    MalformedResponse { record: u32 },
+    /// The attestation holds more records or bytes than the [`VerifyConfig`](crate::VerifyConfig)
+    /// allows.
+    TooLarge { records: usize, total_bytes: usize },
+    /// Page `page` of a paginated session does not request the cursor the previous page returned.
+    BrokenChain { page: u32 },
+    /// The response does not declare the `expected` content type; `found` is the one it declares.
+    ContentTypeMismatch {
+        expected: String,
+        found: Option<String>,
+    },
+    /// A verifying key is not a key on a supported curve in the `format` it was parsed as.
+    KeyParseError { format: KeyFormat },
+    /// The sequence number of record `record` does not exceed that of the record before it.
+    OutOfOrder { record: u32 },
+    /// Record `record` is not valid hex although the host declared the records hex-encoded.
+    InvalidRecordEncoding { record: u32 },
+    /// The response starting in record `record` uses an unsupported content coding, or its body
+    /// does not decode.
+    BadContentEncoding { record: u32 },
+    /// The response body starting in record `record` decodes to more than `max_bytes` bytes.
+    DecodedTooLarge { record: u32, max_bytes: usize },
+    /// The response starting in record `record` declares a `Content-Length` of `declared` bytes
+    /// but carries a body of `actual` bytes.
+    BodyLengthMismatch {
+        record: u32,
+        declared: u64,
+        actual: u64,
+    },
+    /// The chunked body of the response starting in record `record` ends before its zero-length
+    /// chunk.
+    UnterminatedBody { record: u32 },
+    /// The body of the response starting in record `record` is not JSON, although the host asked
+    /// for its canonical JSON digest.
+    BodyNotJson { record: u32 },
+    /// Record `index` starts a request with no response after it, or a response or continuation
+    /// with no request before it.
+    OrphanRecord { index: u32 },
+    /// The response starting in record `record` cannot answer the request before it.
+    MismatchedResponse { record: u32 },
+    /// Record `record` is not valid UTF-8 from byte `offset` on, although the host asked for the
+    /// records to be committed as text.
+    InvalidUtf8 { record: u32, offset: u32 },
+    /// The `component` of the first request does not match the request template.
+    RequestMismatch { component: TemplateComponent },
+    /// An input does not decode as the type the program reads, typically because it was
+    /// truncated or its format tag names a different container.
+    MalformedInput,
+    /// The response `Date` is in the obsolete RFC 850 form, with a two-digit year, or falls on a
+    /// leap second.
+    AmbiguousDate { value: String },
+    /// The attestation states format version `got`, which is not one of the `supported` ones.
+    UnsupportedVersion { got: u16, supported: Vec<u16> },
+    /// An HTTP/2 record holds a frame of type `frame_type`, a server push or an extension.
+    UnsupportedFrame { frame_type: u8 },
+    /// An HTTP/2 record holds a truncated frame, or a header block that does not decode.
+    MalformedFrame,
+    /// An HPACK header block refers to entry `index` of the dynamic table, which is only kept
+    /// with the `hpack-dynamic` feature.
+    HpackDynamicTable { index: u64 },
+    /// The first input is not a [`Preamble`](crate::input::Preamble), or the verifying key and
+    /// attestation after it are not the length or checksum it declares.
+    BadPreamble,
+    /// Bytes `start..end` of record `record` were requested for a range commitment or extraction,
+    /// but the range is reversed or the record is shorter.
+    RangeOutOfBounds { record: u32, start: u32, end: u32 },
+    /// The attestation does not carry exactly one signature per packet: a signature is missing,
+    /// truncated or duplicated.
+    SignatureCountMismatch { records: usize, signatures: usize },
+    /// A claim names its attestor by Ethereum address, which only a secp256k1 key has.
+    NoAddress { curve: Curve },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::RedactionOverlap { record, start, end } => write!(
+                f,
+                "invalid redaction: bytes {}..{} of record {} are empty, out of bounds or overlap \
+                 another range. Ranges are end-exclusive byte offsets into the record plaintext \
+                 (see `--redactions` in the README)",
+                start, end, record
+            ),
+            VerifyError::MissingResponse => f.write_str(
+                "no HTTP response with a body in the transcript. Check that the attestation \
+                 discloses the response records, not only the request",
+            ),
+            VerifyError::PathNotFound { path } => write!(
+                f,
+                "nothing at `{}` in the response body. Check the path against the attested JSON \
+                 (see `--predicate` and `--extract-path` in the README)",
+                path
+            ),
+            VerifyError::NotANumber { path } => write!(
+                f,
+                "the value at `{}` is not a decimal number. Predicates compare numbers or numeric \
+                 strings only (see `--predicate` in the README)",
+                path
+            ),
+            VerifyError::BadSignature => f.write_str(
+                "invalid signature: the k256 ECDSA signature did not verify against the provided \
+                 key and record hash. Check that the key matches the attestation issuer (see \
+                 \"Verifying Keys\" in the README)",
+            ),
+            VerifyError::BadInclusionProof { index } => write!(
+                f,
+                "chunk {} is not included under the signed Merkle root. Check that the transcript \
+                 was not edited after it was signed (see \"Attestation Formats\" in the README)",
+                index
+            ),
+            VerifyError::MalformedResponse { record } => write!(
+                f,
+                "malformed HTTP response in record {}: its head is incomplete or its chunked body \
+                 does not parse. Check that the attestation covers the whole response",
+                record
+            ),
+            VerifyError::TooLarge {
+                records,
+                total_bytes,
+            } => write!(
+                f,
+                "attestation too large: {} records, {} bytes in total. Raise `--max-records` or \
+                 `--max-bytes` if this size is expected",
+                records, total_bytes
+            ),
+            VerifyError::BrokenChain { page } => write!(
+                f,
+                "page {} does not request the cursor the previous page returned. Check that the \
+                 pages are given in the order they were fetched",
+                page
+            ),
+            VerifyError::ContentTypeMismatch { expected, found } => match found {
+                Some(found) => write!(
+                    f,
+                    "the response is `{}`, not `{}`. Check the endpoint the attestation was made \
+                     against",
+                    found, expected
+                ),
+                None => write!(
+                    f,
+                    "the response declares no content type, expected `{}`. Check the endpoint the \
+                     attestation was made against",
+                    expected
+                ),
+            },
+            VerifyError::KeyParseError { format } => write!(
+                f,
+                "the verifying key is not a secp256k1, P-256 or Ed25519 key in {} format. Check \
+                 the key file's encoding (see \"Verifying Keys\" in the README)",
+                format
+            ),
+            VerifyError::OutOfOrder { record } => write!(
+                f,
+                "record {} does not follow the record before it. Check that the records have not \
+                 been reordered or replayed",
+                record
+            ),
+            VerifyError::InvalidRecordEncoding { record } => write!(
+                f,
+                "record {} is not valid hex. Drop `--records-encoding hex` if the attestor signs \
+                 raw records",
+                record
+            ),
+            VerifyError::BadContentEncoding { record } => write!(
+                f,
+                "the response body in record {} does not decode. Only gzip and deflate content \
+                 codings are supported",
+                record
+            ),
+            VerifyError::DecodedTooLarge { record, max_bytes } => write!(
+                f,
+                "the response body in record {} decodes to more than {} bytes. Raise \
+                 `--max-decoded-bytes` if this size is expected",
+                record, max_bytes
+            ),
+            VerifyError::BodyLengthMismatch {
+                record,
+                declared,
+                actual,
+            } => write!(
+                f,
+                "the response in record {} declares a Content-Length of {} bytes but carries {}. \
+                 The attestation probably ends before the response does",
+                record, declared, actual
+            ),
+            VerifyError::UnterminatedBody { record } => write!(
+                f,
+                "the chunked body of the response in record {} has no final zero-length chunk. \
+                 The attestation probably ends before the response does",
+                record
+            ),
+            VerifyError::BodyNotJson { record } => write!(
+                f,
+                "the response body in record {} is not JSON, so it has no canonical digest. Drop \
+                 `--canonical-body`, or check that no redaction cuts into the body",
+                record
+            ),
+            VerifyError::OrphanRecord { index } => write!(
+                f,
+                "record {} is not part of a request/response pair. Check that the attestation \
+                 discloses every response together with its request",
+                index
+            ),
+            VerifyError::MismatchedResponse { record } => write!(
+                f,
+                "the response in record {} cannot answer the request before it: its HTTP version \
+                 differs, or it has a body in answer to HEAD. Check that the records have not \
+                 been reordered",
+                record
+            ),
+            VerifyError::InvalidUtf8 { record, offset } => write!(
+                f,
+                "record {} is not valid UTF-8 at byte {}. Drop `--text-records` to commit the \
+                 records as bytes, or check that no redaction cuts a character in half",
+                record, offset
+            ),
+            VerifyError::RequestMismatch { component } => write!(
+                f,
+                "the request does not match its template: the {} differs. Check the template \
+                 against the attested request, and list headers whose values vary under \
+                 `ignore_headers` (see `--request-template` in the README)",
+                component
+            ),
+            VerifyError::MalformedInput => f.write_str(
+                "the input does not decode: it ends early or is not the container its format tag \
+                 names. Check `--format` against the attestation file, and that the file was not \
+                 truncated",
+            ),
+            VerifyError::AmbiguousDate { value } => write!(
+                f,
+                "the response Date `{}` has a two-digit year or a leap second, so it has no \
+                 unambiguous timestamp. Redact the header to commit no date",
+                value
+            ),
+            VerifyError::UnsupportedVersion { got, supported } => write!(
+                f,
+                "the attestation is in format version {}, but only versions {:?} are supported. \
+                 Upgrade the verifier, or have the attestor produce a supported version",
+                got, supported
+            ),
+            VerifyError::UnsupportedFrame { frame_type } => write!(
+                f,
+                "the HTTP/2 transcript holds a frame of type {:#04x}, which is not supported: \
+                 server push and extension frames cannot be inspected",
+                frame_type
+            ),
+            VerifyError::MalformedFrame => f.write_str(
+                "an HTTP/2 frame is cut short or its HPACK header block does not decode. Check \
+                 that every record holds whole frames",
+            ),
+            VerifyError::HpackDynamicTable { index } => write!(
+                f,
+                "the HPACK header block refers to dynamic table entry {}. Build with the \
+                 `hpack-dynamic` feature to decode it",
+                index
+            ),
+            VerifyError::BadPreamble => f.write_str(
+                "the inputs do not match their preamble: one was cut short, or they were written \
+                 in a different order than the program reads them. Build stdin from a \
+                 `ZkTlsInput` rather than writing items by hand",
+            ),
+            VerifyError::RangeOutOfBounds { record, start, end } => write!(
+                f,
+                "cannot commit bytes {}..{} of record {}: the range is reversed or out of bounds. \
+                 Ranges are end-exclusive byte offsets into the committed record",
+                start, end, record
+            ),
+            VerifyError::SignatureCountMismatch {
+                records,
+                signatures,
+            } => write!(
+                f,
+                "attestation carries {} signatures over {} records, but every packet must be signed \
+                 exactly once over all of its records",
+                signatures, records
+            ),
+            VerifyError::NoAddress { curve } => write!(
+                f,
+                "a claim names its attestor by Ethereum address, but the verifying key is a {} \
+                 key. Commit the full public values instead of a claim",
+                curve
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
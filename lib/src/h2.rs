@@ -0,0 +1,235 @@
+//! Minimal inspection of the HTTP/2 messages carried in records.
+//!
+//! An HTTP/2 record is a sequence of binary frames (RFC 9113 section 4), rather than the text of
+//! an HTTP/1.x message. A message's header fields are HPACK-coded in a `HEADERS` frame and any
+//! `CONTINUATION` frames that follow it, and its body is carried in `DATA` frames on the same
+//! stream. Every frame of a record must be complete, and the header blocks of a record are
+//! decoded on their own, so a dynamic table entry added in an earlier record is not available;
+//! see [`hpack`](crate::hpack).
+//!
+//! Server push (`PUSH_PROMISE`) and extension frame types are rejected with
+//! [`VerifyError::UnsupportedFrame`] instead of being skipped, so a transcript is never read
+//! without frames that may carry its messages.
+
+use crate::hpack::{Decoder, HeaderField};
+use crate::VerifyError;
+
+/// The connection preface a client sends before its first frame.
+pub const PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+/// The length of a frame header.
+pub const FRAME_HEADER_LEN: usize = 9;
+
+/// The largest frame payload a peer may send before it raises `SETTINGS_MAX_FRAME_SIZE`.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 1 << 14;
+
+const END_STREAM: u8 = 0x1;
+const END_HEADERS: u8 = 0x4;
+const PADDED: u8 = 0x8;
+const PRIORITY: u8 = 0x20;
+
+/// The frame types a transcript may hold.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameType {
+    Data,
+    Headers,
+    Priority,
+    RstStream,
+    Settings,
+    Ping,
+    GoAway,
+    WindowUpdate,
+    Continuation,
+}
+
+impl FrameType {
+    /// The frame type of `tag`, or `None` for `PUSH_PROMISE` and extension types.
+    pub const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0x0 => Some(FrameType::Data),
+            0x1 => Some(FrameType::Headers),
+            0x2 => Some(FrameType::Priority),
+            0x3 => Some(FrameType::RstStream),
+            0x4 => Some(FrameType::Settings),
+            0x6 => Some(FrameType::Ping),
+            0x7 => Some(FrameType::GoAway),
+            0x8 => Some(FrameType::WindowUpdate),
+            0x9 => Some(FrameType::Continuation),
+            _ => None,
+        }
+    }
+}
+
+/// A frame borrowed from a record.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Frame<'a> {
+    pub frame_type: FrameType,
+    pub flags: u8,
+    pub stream_id: u32,
+    pub payload: &'a [u8],
+}
+
+impl Frame<'_> {
+    fn has(&self, flag: u8) -> bool {
+        self.flags & flag != 0
+    }
+
+    /// The payload without its padding, and without the priority fields of a `HEADERS` frame.
+    fn data(&self) -> Result<&[u8], VerifyError> {
+        let mut data = self.payload;
+        if self.has(PADDED) && matches!(self.frame_type, FrameType::Data | FrameType::Headers) {
+            let (&padding, rest) = data.split_first().ok_or(VerifyError::MalformedFrame)?;
+            let len = rest
+                .len()
+                .checked_sub(padding as usize)
+                .ok_or(VerifyError::MalformedFrame)?;
+            data = &rest[..len];
+        }
+        if self.has(PRIORITY) && self.frame_type == FrameType::Headers {
+            data = data.get(5..).ok_or(VerifyError::MalformedFrame)?;
+        }
+        Ok(data)
+    }
+}
+
+/// Whether `record` starts like an HTTP/2 transcript: with the client preface, or with a
+/// complete frame header of a known type.
+pub fn is_http2(record: &[u8]) -> bool {
+    if record.starts_with(PREFACE) {
+        return true;
+    }
+    let Some(header) = record.get(..FRAME_HEADER_LEN) else {
+        return false;
+    };
+    let len = u32::from_be_bytes([0, header[0], header[1], header[2]]) as usize;
+    FrameType::from_tag(header[3]).is_some()
+        && header[5] & 0x80 == 0
+        && len <= DEFAULT_MAX_FRAME_SIZE
+        && FRAME_HEADER_LEN + len <= record.len()
+}
+
+/// Split `record` into its frames, skipping the client preface if it starts with one.
+///
+/// Fails with [`VerifyError::UnsupportedFrame`] on a `PUSH_PROMISE` or extension frame, and with
+/// [`VerifyError::MalformedFrame`] when the last frame is cut short.
+pub fn frames(record: &[u8]) -> Result<Vec<Frame<'_>>, VerifyError> {
+    let mut rest = record.strip_prefix(PREFACE).unwrap_or(record);
+    let mut frames = Vec::new();
+    while !rest.is_empty() {
+        let header = rest
+            .get(..FRAME_HEADER_LEN)
+            .ok_or(VerifyError::MalformedFrame)?;
+        let len = u32::from_be_bytes([0, header[0], header[1], header[2]]) as usize;
+        let frame_type = FrameType::from_tag(header[3]).ok_or(VerifyError::UnsupportedFrame {
+            frame_type: header[3],
+        })?;
+        let stream_id =
+            u32::from_be_bytes([header[5], header[6], header[7], header[8]]) & 0x7fff_ffff;
+        let payload = rest
+            .get(FRAME_HEADER_LEN..FRAME_HEADER_LEN + len)
+            .ok_or(VerifyError::MalformedFrame)?;
+
+        frames.push(Frame {
+            frame_type,
+            flags: header[4],
+            stream_id,
+            payload,
+        });
+        rest = &rest[FRAME_HEADER_LEN + len..];
+    }
+    Ok(frames)
+}
+
+/// The first message in an HTTP/2 record.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    /// The stream the message was sent on.
+    pub stream_id: u32,
+    /// The header fields in order, pseudo-headers such as `:status` included.
+    pub fields: Vec<HeaderField>,
+    /// The payload of the stream's `DATA` frames in the record, without padding.
+    pub body: Vec<u8>,
+    /// Whether the record holds the end of the stream.
+    pub complete: bool,
+}
+
+impl Message {
+    /// The value of the first field named `name`, compared case-insensitively.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The status code of a response, or `None` for a request.
+    pub fn status(&self) -> Option<u16> {
+        self.field(":status")?.parse().ok()
+    }
+}
+
+/// Decode the first message in `record`: the header block of its first `HEADERS` frame and the
+/// `DATA` frames on the same stream.
+///
+/// Returns `None` when the record holds no `HEADERS` frame, e.g. when it only carries settings.
+/// The header blocks after the message's, such as its trailers or other streams' headers, are
+/// decoded too, so a malformed block anywhere in the record is an error.
+pub fn message(record: &[u8]) -> Result<Option<Message>, VerifyError> {
+    let frames = frames(record)?;
+    let mut decoder = Decoder::new();
+    let mut message: Option<Message> = None;
+
+    let mut index = 0;
+    while index < frames.len() {
+        let frame = frames[index];
+        index += 1;
+        match frame.frame_type {
+            FrameType::Headers => {
+                // The block continues in CONTINUATION frames until one ends the headers.
+                let mut block = frame.data()?.to_vec();
+                let mut last = frame;
+                while !last.has(END_HEADERS) {
+                    last = *frames
+                        .get(index)
+                        .filter(|next| {
+                            next.frame_type == FrameType::Continuation
+                                && next.stream_id == frame.stream_id
+                        })
+                        .ok_or(VerifyError::MalformedFrame)?;
+                    block.extend_from_slice(last.payload);
+                    index += 1;
+                }
+                let fields = decoder.decode(&block)?;
+
+                match &mut message {
+                    None => {
+                        message = Some(Message {
+                            stream_id: frame.stream_id,
+                            fields,
+                            body: Vec::new(),
+                            complete: frame.has(END_STREAM),
+                        })
+                    }
+                    // Trailers end the stream.
+                    Some(message) if message.stream_id == frame.stream_id => {
+                        message.complete |= frame.has(END_STREAM)
+                    }
+                    Some(_) => {}
+                }
+            }
+            FrameType::Data => {
+                if let Some(message) = message
+                    .as_mut()
+                    .filter(|message| message.stream_id == frame.stream_id)
+                {
+                    message.body.extend_from_slice(frame.data()?);
+                    message.complete |= frame.has(END_STREAM);
+                }
+            }
+            // A CONTINUATION frame that does not follow a HEADERS frame is out of place.
+            FrameType::Continuation => return Err(VerifyError::MalformedFrame),
+            _ => {}
+        }
+    }
+    Ok(message)
+}
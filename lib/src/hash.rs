@@ -0,0 +1,253 @@
+//! The hash function behind the committed record digests, selected when the program is built.
+//!
+//! Contracts on the EVM recompute the digests cheaply with keccak256, the default. Verifiers in
+//! other environments may prefer SHA-256, or Poseidon over BN254 inside another SNARK. Building
+//! with the `sha256` or `poseidon` feature makes [`RecordsHasher`] that hash instead; when several
+//! are enabled, `poseidon` wins over `sha256`, which wins over `keccak256`.
+//!
+//! Changing the hash changes every record digest the program commits, and so the public values
+//! and the program's verifying key: an on-chain verifier must recompute the digests with the same
+//! hash, and proofs from builds with different hashes are not interchangeable. The EIP-712 hash,
+//! the bodies digest and the decoded body digests are keccak256 whichever hash is selected, as
+//! their consumers expect.
+//!
+//! Whatever the hash, the program and the host compute the digest of a run of records with a
+//! [`RecordHasher`], so the two always commit to records the same way: each record is preceded
+//! by its length as a big-endian `u64`, so no two runs of records share a digest.
+
+use sha2::Digest;
+use tiny_keccak::Hasher as _;
+
+use crate::Record;
+
+/// A hash function producing 32-byte digests, fed incrementally.
+pub trait Hasher: Default {
+    /// Absorb `data`.
+    fn update(&mut self, data: &[u8]);
+
+    /// The digest of everything absorbed.
+    fn finalize(self) -> [u8; 32];
+}
+
+/// The hash the record digests are computed with in this build.
+#[cfg(not(any(feature = "sha256", feature = "poseidon")))]
+pub type RecordsHasher = Keccak256;
+/// The hash the record digests are computed with in this build.
+#[cfg(all(feature = "sha256", not(feature = "poseidon")))]
+pub type RecordsHasher = Sha256;
+/// The hash the record digests are computed with in this build.
+#[cfg(feature = "poseidon")]
+pub type RecordsHasher = Poseidon;
+
+/// Keccak-256, as the EVM's `keccak256`.
+pub struct Keccak256(tiny_keccak::Keccak);
+
+impl Default for Keccak256 {
+    fn default() -> Self {
+        Keccak256(tiny_keccak::Keccak::v256())
+    }
+}
+
+impl Hasher for Keccak256 {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        let mut output = [0; 32];
+        self.0.finalize(&mut output);
+        output
+    }
+}
+
+/// SHA-256.
+#[derive(Default)]
+pub struct Sha256(sha2::Sha256);
+
+impl Hasher for Sha256 {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+/// BLAKE3 with its default 32-byte output.
+#[derive(Default)]
+pub struct Blake3(blake3::Hasher);
+
+impl Hasher for Blake3 {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+/// Poseidon over the BN254 scalar field with the circom parameters, as circomlib's `Poseidon(2)`.
+///
+/// The input is split into 31-byte big-endian field elements, each absorbed as
+/// `state = Poseidon(state, chunk)` starting from a zero state; the digest is
+/// `Poseidon(state, length)` with the input length in bytes, so inputs that differ only in
+/// trailing zero bytes hash differently. The digest is the big-endian encoding of the final state.
+#[cfg(feature = "poseidon")]
+#[derive(Default)]
+pub struct Poseidon {
+    state: [u8; 32],
+    pending: Vec<u8>,
+    length: u64,
+}
+
+#[cfg(feature = "poseidon")]
+impl Poseidon {
+    /// The bytes of each field element absorbed, few enough to stay below the field modulus.
+    const CHUNK_BYTES: usize = 31;
+
+    fn absorb(&mut self, chunk: &[u8]) {
+        use light_poseidon::PoseidonBytesHasher;
+
+        let mut poseidon = light_poseidon::Poseidon::<ark_bn254::Fr>::new_circom(2)
+            .expect("circom parameters exist for two inputs");
+        self.state = poseidon
+            .hash_bytes_be(&[&self.state, chunk])
+            .expect("inputs are below the field modulus");
+    }
+}
+
+#[cfg(feature = "poseidon")]
+impl Hasher for Poseidon {
+    fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+        self.pending.extend_from_slice(data);
+        let full = self.pending.len() / Self::CHUNK_BYTES * Self::CHUNK_BYTES;
+        let pending = std::mem::take(&mut self.pending);
+        for chunk in pending[..full].chunks(Self::CHUNK_BYTES) {
+            self.absorb(chunk);
+        }
+        self.pending = pending[full..].to_vec();
+    }
+
+    fn finalize(mut self) -> [u8; 32] {
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            self.absorb(&pending);
+        }
+        let length = self.length.to_be_bytes();
+        self.absorb(&length);
+        self.state
+    }
+}
+
+/// A hash function a [`RecordHasher`] can commit to records with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    /// [`Keccak256`].
+    Keccak256,
+    /// [`Sha256`].
+    Sha256,
+    /// [`Blake3`].
+    Blake3,
+    /// [`Poseidon`].
+    #[cfg(feature = "poseidon")]
+    Poseidon,
+}
+
+impl HashAlgorithm {
+    /// The algorithm of [`RecordsHasher`], which this build commits record digests with.
+    #[cfg(not(any(feature = "sha256", feature = "poseidon")))]
+    pub const RECORDS: HashAlgorithm = HashAlgorithm::Keccak256;
+    /// The algorithm of [`RecordsHasher`], which this build commits record digests with.
+    #[cfg(all(feature = "sha256", not(feature = "poseidon")))]
+    pub const RECORDS: HashAlgorithm = HashAlgorithm::Sha256;
+    /// The algorithm of [`RecordsHasher`], which this build commits record digests with.
+    #[cfg(feature = "poseidon")]
+    pub const RECORDS: HashAlgorithm = HashAlgorithm::Poseidon;
+}
+
+impl Default for HashAlgorithm {
+    fn default() -> Self {
+        HashAlgorithm::RECORDS
+    }
+}
+
+/// The canonical commitment to a run of records: the digest of their bytes in transcript order,
+/// each record preceded by its length as a big-endian `u64`.
+///
+/// The lengths keep the digest unambiguous: moving bytes from the end of one record to the start
+/// of the next changes it, as does adding or dropping an empty record. Redacted bytes are hashed
+/// as the zeros they were replaced with, so the digest can be recomputed from the committed
+/// records alone. The default hasher uses [`HashAlgorithm::RECORDS`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RecordHasher {
+    algorithm: HashAlgorithm,
+}
+
+impl RecordHasher {
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        RecordHasher { algorithm }
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// The digest of `records`.
+    pub fn hash_records(&self, records: &[Record]) -> [u8; 32] {
+        self.hash_parts(records.iter().map(|record| record.bytes.as_slice()))
+    }
+
+    /// The digest of `parts`, each preceded by its length as [`hash_records`] does for records,
+    /// for records that are not contiguous in a slice, or bytes that are not records at all.
+    ///
+    /// [`hash_records`]: RecordHasher::hash_records
+    pub fn hash_parts<'a>(&self, parts: impl IntoIterator<Item = &'a [u8]>) -> [u8; 32] {
+        match self.algorithm {
+            HashAlgorithm::Keccak256 => hash_with::<Keccak256>(parts),
+            HashAlgorithm::Sha256 => hash_with::<Sha256>(parts),
+            HashAlgorithm::Blake3 => hash_with::<Blake3>(parts),
+            #[cfg(feature = "poseidon")]
+            HashAlgorithm::Poseidon => hash_with::<Poseidon>(parts),
+        }
+    }
+
+    /// The plain digest of `data`, with no length before it, for a single byte string whose
+    /// consumers hash it as it is.
+    pub fn hash_bytes(&self, data: &[u8]) -> [u8; 32] {
+        match self.algorithm {
+            HashAlgorithm::Keccak256 => hash_bytes_with::<Keccak256>(data),
+            HashAlgorithm::Sha256 => hash_bytes_with::<Sha256>(data),
+            HashAlgorithm::Blake3 => hash_bytes_with::<Blake3>(data),
+            #[cfg(feature = "poseidon")]
+            HashAlgorithm::Poseidon => hash_bytes_with::<Poseidon>(data),
+        }
+    }
+}
+
+/// Absorb `part` into `hasher` preceded by its length, as the record digests take each record.
+pub fn update_part<H: Hasher>(hasher: &mut H, part: &[u8]) {
+    update_length(hasher, part.len() as u64);
+    hasher.update(part);
+}
+
+/// Absorb the length prefix of a part of `length` bytes, for parts absorbed in pieces.
+pub fn update_length<H: Hasher>(hasher: &mut H, length: u64) {
+    hasher.update(&length.to_be_bytes());
+}
+
+fn hash_with<'a, H: Hasher>(parts: impl IntoIterator<Item = &'a [u8]>) -> [u8; 32] {
+    let mut hasher = H::default();
+    for part in parts {
+        update_part(&mut hasher, part);
+    }
+    hasher.finalize()
+}
+
+fn hash_bytes_with<H: Hasher>(data: &[u8]) -> [u8; 32] {
+    let mut hasher = H::default();
+    hasher.update(data);
+    hasher.finalize()
+}
@@ -0,0 +1,381 @@
+//! Decoding of HPACK header blocks (RFC 7541), as carried in HTTP/2 `HEADERS` frames.
+//!
+//! Header fields are either literals, optionally Huffman-coded, or indices into a table of known
+//! fields. The 61 entries of the static table and Huffman decoding are always supported. Literals
+//! marked for incremental indexing are also added to a per-connection dynamic table that later
+//! blocks may refer to. Keeping that table costs cycles in the program, so it is only maintained
+//! with the `hpack-dynamic` feature; without it, a reference to a dynamic entry fails with
+//! [`VerifyError::HpackDynamicTable`].
+
+use crate::VerifyError;
+
+/// The static table of RFC 7541 Appendix A, from index 1.
+const STATIC_TABLE: [(&str, &str); 61] = [
+    (":authority", ""),
+    (":method", "GET"),
+    (":method", "POST"),
+    (":path", "/"),
+    (":path", "/index.html"),
+    (":scheme", "http"),
+    (":scheme", "https"),
+    (":status", "200"),
+    (":status", "204"),
+    (":status", "206"),
+    (":status", "304"),
+    (":status", "400"),
+    (":status", "404"),
+    (":status", "500"),
+    ("accept-charset", ""),
+    ("accept-encoding", "gzip, deflate"),
+    ("accept-language", ""),
+    ("accept-ranges", ""),
+    ("accept", ""),
+    ("access-control-allow-origin", ""),
+    ("age", ""),
+    ("allow", ""),
+    ("authorization", ""),
+    ("cache-control", ""),
+    ("content-disposition", ""),
+    ("content-encoding", ""),
+    ("content-language", ""),
+    ("content-length", ""),
+    ("content-location", ""),
+    ("content-range", ""),
+    ("content-type", ""),
+    ("cookie", ""),
+    ("date", ""),
+    ("etag", ""),
+    ("expect", ""),
+    ("expires", ""),
+    ("from", ""),
+    ("host", ""),
+    ("if-match", ""),
+    ("if-modified-since", ""),
+    ("if-none-match", ""),
+    ("if-range", ""),
+    ("if-unmodified-since", ""),
+    ("last-modified", ""),
+    ("link", ""),
+    ("location", ""),
+    ("max-forwards", ""),
+    ("proxy-authenticate", ""),
+    ("proxy-authorization", ""),
+    ("range", ""),
+    ("referer", ""),
+    ("refresh", ""),
+    ("retry-after", ""),
+    ("server", ""),
+    ("set-cookie", ""),
+    ("strict-transport-security", ""),
+    ("transfer-encoding", ""),
+    ("user-agent", ""),
+    ("vary", ""),
+    ("via", ""),
+    ("www-authenticate", ""),
+];
+
+/// The length in bits of the Huffman code of every byte, then of end-of-string, from RFC 7541
+/// Appendix B. The code is canonical, so the lengths determine the codes.
+const HUFFMAN_LENGTHS: [u8; 257] = [
+    13, 23, 28, 28, 28, 28, 28, 28, 28, 24, 30, 28, 28, 30, 28, 28, //
+    28, 28, 28, 28, 28, 28, 30, 28, 28, 28, 28, 28, 28, 28, 28, 28, //
+    6, 10, 10, 12, 13, 6, 8, 11, 10, 10, 8, 11, 8, 6, 6, 6, //
+    5, 5, 5, 6, 6, 6, 6, 6, 6, 6, 7, 8, 15, 6, 12, 10, //
+    13, 6, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, 7, //
+    7, 7, 7, 7, 7, 7, 7, 7, 8, 7, 8, 13, 19, 13, 14, 6, //
+    15, 5, 6, 5, 6, 5, 6, 6, 6, 5, 7, 7, 6, 6, 6, 5, //
+    6, 7, 6, 5, 5, 6, 7, 7, 7, 7, 7, 15, 11, 14, 13, 28, //
+    20, 22, 20, 20, 22, 22, 22, 23, 22, 23, 23, 23, 23, 23, 24, 23, //
+    24, 24, 22, 23, 24, 23, 23, 23, 23, 21, 22, 23, 22, 23, 23, 24, //
+    22, 21, 20, 22, 22, 23, 23, 21, 23, 22, 22, 24, 21, 22, 23, 23, //
+    21, 21, 22, 21, 23, 22, 23, 23, 20, 22, 22, 22, 23, 22, 22, 23, //
+    26, 26, 20, 19, 22, 23, 22, 25, 26, 26, 26, 27, 27, 26, 24, 25, //
+    19, 21, 26, 27, 27, 26, 27, 24, 21, 21, 26, 26, 28, 27, 27, 27, //
+    20, 24, 20, 21, 22, 21, 21, 23, 22, 22, 25, 25, 24, 24, 26, 23, //
+    26, 27, 26, 26, 27, 27, 27, 27, 27, 28, 27, 27, 27, 27, 27, 26, //
+    30,
+];
+
+/// The longest Huffman code.
+const MAX_CODE_BITS: usize = 30;
+
+/// The end-of-string symbol, which must never appear in a string.
+const EOS: u16 = 256;
+
+/// The dynamic table size a connection starts with, before any `SETTINGS_HEADER_TABLE_SIZE`.
+pub const DEFAULT_DYNAMIC_TABLE_SIZE: usize = 4096;
+
+/// A decoded header field.
+pub type HeaderField = (String, String);
+
+/// A decoder for the header blocks of one direction of a connection.
+///
+/// The blocks must be decoded in the order they were sent, since each may add entries to the
+/// dynamic table that later ones refer to.
+#[derive(Clone, Debug)]
+pub struct Decoder {
+    huffman: Huffman,
+    #[cfg(feature = "hpack-dynamic")]
+    dynamic: DynamicTable,
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Decoder::new()
+    }
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Decoder {
+            huffman: Huffman::new(),
+            #[cfg(feature = "hpack-dynamic")]
+            dynamic: DynamicTable::new(DEFAULT_DYNAMIC_TABLE_SIZE),
+        }
+    }
+
+    /// Decode the header fields of `block`, in order.
+    ///
+    /// Fails with [`VerifyError::MalformedFrame`] when the block is truncated or refers to an
+    /// entry that does not exist.
+    pub fn decode(&mut self, block: &[u8]) -> Result<Vec<HeaderField>, VerifyError> {
+        let mut fields = Vec::new();
+        let mut rest = block;
+        while let Some(&first) = rest.first() {
+            if first & 0x80 != 0 {
+                // Indexed header field.
+                let index = integer(&mut rest, 7)?;
+                fields.push(self.entry(index)?);
+            } else if first & 0x40 != 0 {
+                // Literal with incremental indexing.
+                let field = self.literal(&mut rest, 6)?;
+                self.insert(&field);
+                fields.push(field);
+            } else if first & 0x20 != 0 {
+                // Dynamic table size update.
+                let size = integer(&mut rest, 5)?;
+                self.resize(size)?;
+            } else {
+                // Literal without indexing, or never indexed.
+                fields.push(self.literal(&mut rest, 4)?);
+            }
+        }
+        Ok(fields)
+    }
+
+    /// A literal field whose name is indexed by a `prefix`-bit integer, or follows as a string
+    /// when the index is 0.
+    fn literal(&self, rest: &mut &[u8], prefix: u8) -> Result<HeaderField, VerifyError> {
+        let index = integer(rest, prefix)?;
+        let name = if index == 0 {
+            self.string(rest)?
+        } else {
+            self.entry(index)?.0
+        };
+        let value = self.string(rest)?;
+        Ok((name, value))
+    }
+
+    fn string(&self, rest: &mut &[u8]) -> Result<String, VerifyError> {
+        let huffman = rest.first().ok_or(VerifyError::MalformedFrame)? & 0x80 != 0;
+        let len = integer(rest, 7)?;
+        let len = usize::try_from(len).map_err(|_| VerifyError::MalformedFrame)?;
+        let bytes = rest.get(..len).ok_or(VerifyError::MalformedFrame)?;
+        *rest = &rest[len..];
+
+        let bytes = if huffman {
+            self.huffman.decode(bytes)?
+        } else {
+            bytes.to_vec()
+        };
+        String::from_utf8(bytes).map_err(|_| VerifyError::MalformedFrame)
+    }
+
+    fn entry(&self, index: u64) -> Result<HeaderField, VerifyError> {
+        let static_entry = usize::try_from(index)
+            .ok()
+            .and_then(|index| index.checked_sub(1))
+            .and_then(|index| STATIC_TABLE.get(index));
+        if let Some((name, value)) = static_entry {
+            return Ok((name.to_string(), value.to_string()));
+        }
+        if index == 0 {
+            return Err(VerifyError::MalformedFrame);
+        }
+        self.dynamic_entry(index)
+    }
+
+    #[cfg(feature = "hpack-dynamic")]
+    fn dynamic_entry(&self, index: u64) -> Result<HeaderField, VerifyError> {
+        let index = usize::try_from(index).map_err(|_| VerifyError::MalformedFrame)?;
+        self.dynamic
+            .get(index - STATIC_TABLE.len() - 1)
+            .cloned()
+            .ok_or(VerifyError::MalformedFrame)
+    }
+
+    #[cfg(not(feature = "hpack-dynamic"))]
+    fn dynamic_entry(&self, index: u64) -> Result<HeaderField, VerifyError> {
+        Err(VerifyError::HpackDynamicTable { index })
+    }
+
+    #[cfg(feature = "hpack-dynamic")]
+    fn insert(&mut self, field: &HeaderField) {
+        self.dynamic.insert(field.clone());
+    }
+
+    #[cfg(not(feature = "hpack-dynamic"))]
+    fn insert(&mut self, _field: &HeaderField) {}
+
+    #[cfg(feature = "hpack-dynamic")]
+    fn resize(&mut self, size: u64) -> Result<(), VerifyError> {
+        // Without the peer's SETTINGS, the table may only shrink from its initial size.
+        let size = usize::try_from(size)
+            .ok()
+            .filter(|&size| size <= DEFAULT_DYNAMIC_TABLE_SIZE)
+            .ok_or(VerifyError::MalformedFrame)?;
+        self.dynamic.resize(size);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "hpack-dynamic"))]
+    fn resize(&mut self, _size: u64) -> Result<(), VerifyError> {
+        Ok(())
+    }
+}
+
+/// Decode an integer with a `prefix`-bit prefix (RFC 7541 section 5.1) from the start of `rest`.
+fn integer(rest: &mut &[u8], prefix: u8) -> Result<u64, VerifyError> {
+    let (&first, mut tail) = rest.split_first().ok_or(VerifyError::MalformedFrame)?;
+    let max = (1u64 << prefix) - 1;
+    let mut value = u64::from(first) & max;
+    if value == max {
+        let mut shift = 0;
+        loop {
+            let (&byte, after) = tail.split_first().ok_or(VerifyError::MalformedFrame)?;
+            tail = after;
+            value = u64::from(byte & 0x7f)
+                .checked_shl(shift)
+                .and_then(|bits| value.checked_add(bits))
+                .filter(|_| shift < 63)
+                .ok_or(VerifyError::MalformedFrame)?;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+    }
+    *rest = tail;
+    Ok(value)
+}
+
+/// The canonical Huffman code of [`HUFFMAN_LENGTHS`], as a count of codes of every length and the
+/// symbols in code order.
+#[derive(Clone, Debug)]
+struct Huffman {
+    counts: [u16; MAX_CODE_BITS + 1],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new() -> Self {
+        let mut counts = [0; MAX_CODE_BITS + 1];
+        for &len in &HUFFMAN_LENGTHS {
+            counts[len as usize] += 1;
+        }
+        let mut symbols: Vec<u16> = (0..HUFFMAN_LENGTHS.len() as u16).collect();
+        symbols.sort_by_key(|&symbol| HUFFMAN_LENGTHS[symbol as usize]);
+        Huffman { counts, symbols }
+    }
+
+    /// Decode `bytes`, which must end with at most 7 bits of padding, all ones.
+    fn decode(&self, bytes: &[u8]) -> Result<Vec<u8>, VerifyError> {
+        let mut decoded = Vec::with_capacity(bytes.len() * 8 / 5);
+        let bits = bytes
+            .iter()
+            .flat_map(|&byte| (0..8).rev().map(move |bit| (byte >> bit) & 1));
+
+        let (mut code, mut first, mut index, mut len) = (0i32, 0i32, 0i32, 0usize);
+        let mut all_ones = true;
+        for bit in bits {
+            len += 1;
+            all_ones &= bit == 1;
+            code |= i32::from(bit);
+            let count = i32::from(self.counts[len]);
+            if code - count < first {
+                let symbol = self.symbols[(index + code - first) as usize];
+                if symbol == EOS {
+                    return Err(VerifyError::MalformedFrame);
+                }
+                decoded.push(symbol as u8);
+                (code, first, index, len) = (0, 0, 0, 0);
+                all_ones = true;
+                continue;
+            }
+            if len == MAX_CODE_BITS {
+                return Err(VerifyError::MalformedFrame);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        // Padding is the most significant bits of end-of-string, which are all ones.
+        if len > 7 || !all_ones {
+            return Err(VerifyError::MalformedFrame);
+        }
+        Ok(decoded)
+    }
+}
+
+/// The entries added by literals with incremental indexing, newest first, evicted oldest first
+/// once their size exceeds the table's.
+#[cfg(feature = "hpack-dynamic")]
+#[derive(Clone, Debug)]
+struct DynamicTable {
+    entries: std::collections::VecDeque<HeaderField>,
+    size: usize,
+    max_size: usize,
+}
+
+#[cfg(feature = "hpack-dynamic")]
+impl DynamicTable {
+    fn new(max_size: usize) -> Self {
+        DynamicTable {
+            entries: std::collections::VecDeque::new(),
+            size: 0,
+            max_size,
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<&HeaderField> {
+        self.entries.get(index)
+    }
+
+    fn insert(&mut self, field: HeaderField) {
+        let size = entry_size(&field);
+        self.entries.push_front(field);
+        self.size += size;
+        self.evict();
+    }
+
+    fn resize(&mut self, max_size: usize) {
+        self.max_size = max_size;
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.size > self.max_size {
+            let Some(evicted) = self.entries.pop_back() else {
+                break;
+            };
+            self.size -= entry_size(&evicted);
+        }
+    }
+}
+
+/// The size RFC 7541 section 4.1 counts an entry as.
+#[cfg(feature = "hpack-dynamic")]
+fn entry_size((name, value): &HeaderField) -> usize {
+    name.len() + value.len() + 32
+}
@@ -0,0 +1,376 @@
+//! Minimal inspection of the HTTP/1.x messages carried in records.
+//!
+//! Records of an HTTP/2 session hold binary frames instead; [`HttpVersion`] tells the two apart,
+//! and [`h2`](crate::h2) decodes the frames.
+
+use std::fmt;
+
+use crate::h2::{is_http2, PREFACE};
+use crate::{Record, VerifyError};
+
+/// The direction of the HTTP message a record carries.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// A request sent by the client, e.g. `GET / HTTP/1.1`.
+    Request,
+    /// A response sent by the server, e.g. `HTTP/1.1 200 OK`.
+    Response,
+    /// Anything that does not start with a request or status line.
+    Unknown,
+}
+
+impl Direction {
+    /// Classify `record` by its first line.
+    pub fn of(record: &[u8]) -> Self {
+        let line_end = record
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .unwrap_or(record.len());
+        let line = &record[..line_end];
+
+        if line.starts_with(b"HTTP/") {
+            return Direction::Response;
+        }
+
+        let mut parts = line.split(|&byte| byte == b' ');
+        let method = parts.next().unwrap_or_default();
+        let target = parts.next();
+        let version = parts.next();
+        let is_request = !method.is_empty()
+            && method.iter().all(u8::is_ascii_uppercase)
+            && target.is_some_and(|target| !target.is_empty())
+            && version.is_some_and(|version| version.starts_with(b"HTTP/"))
+            && parts.next().is_none();
+
+        if is_request {
+            Direction::Request
+        } else {
+            Direction::Unknown
+        }
+    }
+}
+
+/// The HTTP version whose framing a record uses.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HttpVersion {
+    /// HTTP/1.0 or HTTP/1.1 text, or anything that is not HTTP/2.
+    Http1,
+    /// HTTP/2 binary frames.
+    Http2,
+}
+
+impl HttpVersion {
+    /// Detect the framing of `record`.
+    ///
+    /// A record starting with the client connection preface is HTTP/2, although the preface reads
+    /// like a request line. Any other record with a request or status line is HTTP/1.x, and the
+    /// rest are HTTP/2 when they start with a complete frame header of a known type; see
+    /// [`is_http2`](crate::h2::is_http2).
+    pub fn of(record: &[u8]) -> Self {
+        if record.starts_with(PREFACE)
+            || (Direction::of(record) == Direction::Unknown && is_http2(record))
+        {
+            HttpVersion::Http2
+        } else {
+            HttpVersion::Http1
+        }
+    }
+}
+
+/// The status code in the status line of an HTTP/1.x response `head`, or `None` for anything
+/// else.
+pub fn status(head: &[u8]) -> Option<u16> {
+    let line = head.split(|&byte| byte == b'\r').next()?;
+    let mut parts = line.split(|&byte| byte == b' ');
+    if !parts.next()?.starts_with(b"HTTP/") {
+        return None;
+    }
+    let code = parts.next()?;
+    if code.len() != 3 || !code.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    std::str::from_utf8(code).ok()?.parse().ok()
+}
+
+/// Split an HTTP message into its head (start line and headers) and body at the first blank line.
+pub fn split_message(message: &[u8]) -> Option<(&[u8], &[u8])> {
+    let separator = message
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")?;
+    Some((&message[..separator], &message[separator + 4..]))
+}
+
+/// The bytes of the first response in the transcript.
+///
+/// A response may span several records, so this concatenates the first record with a status
+/// line and every following record up to the next request.
+pub fn response_message(records: &[Record]) -> Option<Vec<u8>> {
+    let start = records
+        .iter()
+        .position(|record| Direction::of(&record.bytes) == Direction::Response)?;
+
+    let mut message = Vec::new();
+    for record in records[start..]
+        .iter()
+        .take_while(|record| Direction::of(&record.bytes) != Direction::Request)
+    {
+        message.extend_from_slice(&record.bytes);
+    }
+    Some(message)
+}
+
+/// The body of the response in `record`, de-chunked when it uses chunked transfer-encoding.
+///
+/// Returns `None` when the record has no blank line ending its head, or its chunked body is
+/// malformed or truncated.
+pub fn response_body(record: &[u8]) -> Option<Vec<u8>> {
+    let (head, body) = split_message(record)?;
+
+    if is_chunked(head) {
+        dechunk(body)
+    } else {
+        Some(body.to_vec())
+    }
+}
+
+/// Whether the message `head` declares a chunked transfer-encoding.
+pub fn is_chunked(head: &[u8]) -> bool {
+    header(head, "transfer-encoding").is_some_and(|value| {
+        value
+            .split(',')
+            .any(|coding| coding.trim().eq_ignore_ascii_case("chunked"))
+    })
+}
+
+/// Decode a chunked transfer-encoded `body`, discarding chunk extensions and trailers.
+pub fn dechunk(body: &[u8]) -> Option<Vec<u8>> {
+    try_dechunk(body).ok()
+}
+
+/// Why a chunked body did not decode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ChunkedError {
+    /// A chunk size line or delimiter is malformed, or bytes follow the end of the body.
+    Malformed,
+    /// The body ends before its zero-length chunk and closing empty line.
+    Unterminated,
+}
+
+fn try_dechunk(body: &[u8]) -> Result<Vec<u8>, ChunkedError> {
+    let mut decoded = Vec::new();
+    let mut rest = body;
+    loop {
+        let (line, after) = split_line(rest).ok_or(ChunkedError::Unterminated)?;
+        let size = line
+            .split(|&byte| byte == b';')
+            .next()
+            .unwrap_or_default()
+            .trim_ascii();
+        let size = std::str::from_utf8(size)
+            .ok()
+            .filter(|size| !size.is_empty() && size.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .and_then(|size| usize::from_str_radix(size, 16).ok())
+            .ok_or(ChunkedError::Malformed)?;
+        rest = after;
+
+        if size == 0 {
+            break;
+        }
+
+        let chunk = rest.get(..size).ok_or(ChunkedError::Unterminated)?;
+        rest = &rest[size..];
+        if rest.len() < 2 {
+            return Err(ChunkedError::Unterminated);
+        }
+        rest = rest.strip_prefix(b"\r\n").ok_or(ChunkedError::Malformed)?;
+        decoded.extend_from_slice(chunk);
+    }
+
+    // Trailer fields, if any, end with an empty line that must close the body.
+    loop {
+        let (line, after) = split_line(rest).ok_or(ChunkedError::Unterminated)?;
+        rest = after;
+        if line.is_empty() {
+            return if rest.is_empty() {
+                Ok(decoded)
+            } else {
+                Err(ChunkedError::Malformed)
+            };
+        }
+    }
+}
+
+/// Check that every response in `records` holds exactly the body its head frames, and return the
+/// length of the first response's body.
+///
+/// A chunked body must end with its zero-length chunk, or the check fails with
+/// [`VerifyError::UnterminatedBody`]; any other body must be as long as its `Content-Length`, or
+/// it fails with [`VerifyError::BodyLengthMismatch`]. A response spanning several records is
+/// checked as a whole. The length is `None` when there is no response, or the first one declares
+/// neither framing and so runs until the connection closes.
+pub fn check_body_lengths(records: &[Record]) -> Result<Option<u64>, VerifyError> {
+    let mut first = None;
+    let mut start = 0;
+    while start < records.len() {
+        if Direction::of(&records[start].bytes) != Direction::Response {
+            start += 1;
+            continue;
+        }
+        let end = start
+            + 1
+            + records[start + 1..]
+                .iter()
+                .take_while(|record| Direction::of(&record.bytes) == Direction::Unknown)
+                .count();
+
+        let message: Vec<u8> = records[start..end]
+            .iter()
+            .flat_map(|record| record.bytes.iter().copied())
+            .collect();
+        let length = body_length(&message, start as u32)?;
+        first = first.or(Some(length));
+        start = end;
+    }
+    Ok(first.flatten())
+}
+
+/// The checked body length of the response `message`, which starts in record `record`.
+fn body_length(message: &[u8], record: u32) -> Result<Option<u64>, VerifyError> {
+    let (head, body) = split_message(message).ok_or(VerifyError::MalformedResponse { record })?;
+
+    if is_chunked(head) {
+        return match try_dechunk(body) {
+            Ok(decoded) => Ok(Some(decoded.len() as u64)),
+            Err(ChunkedError::Unterminated) => Err(VerifyError::UnterminatedBody { record }),
+            Err(ChunkedError::Malformed) => Err(VerifyError::MalformedResponse { record }),
+        };
+    }
+
+    let Some(declared) = header(head, "content-length") else {
+        return Ok(None);
+    };
+    let declared = Some(declared.as_str())
+        .filter(|value| !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit()))
+        .and_then(|value| value.parse::<u64>().ok())
+        .ok_or(VerifyError::MalformedResponse { record })?;
+    let actual = body.len() as u64;
+    if declared != actual {
+        debug!(record, declared, actual, "body length does not match");
+        return Err(VerifyError::BodyLengthMismatch {
+            record,
+            declared,
+            actual,
+        });
+    }
+    Ok(Some(actual))
+}
+
+/// The value of the first header named `name` in the message `head`, compared case-insensitively.
+pub fn header(head: &[u8], name: &str) -> Option<String> {
+    head.split(|&byte| byte == b'\n').skip(1).find_map(|line| {
+        let line = String::from_utf8_lossy(line);
+        let (field, value) = line.split_once(':')?;
+        field
+            .trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim().to_string())
+    })
+}
+
+/// A media type such as `application/json; charset=utf-8`, as found in a `Content-Type` header.
+///
+/// The type, subtype and parameter names are lowercased, as are the values of `charset`, which
+/// RFC 9110 defines as case-insensitive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mime {
+    /// The type and subtype, e.g. `application/json`.
+    pub essence: String,
+    /// The parameters in header order, with quotes removed from their values.
+    pub params: Vec<(String, String)>,
+}
+
+impl Mime {
+    /// Parse a media type, or `None` if it has no `type/subtype`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.split(';');
+        let essence = parts.next()?.trim().to_ascii_lowercase();
+        let (kind, subtype) = essence.split_once('/')?;
+        if kind.is_empty() || subtype.is_empty() || subtype.contains('/') {
+            return None;
+        }
+
+        let params = parts
+            .filter(|param| !param.trim().is_empty())
+            .map(|param| {
+                let (name, value) = param.split_once('=')?;
+                let name = name.trim().to_ascii_lowercase();
+                let value = value.trim().trim_matches('"');
+                let value = if name == "charset" {
+                    value.to_ascii_lowercase()
+                } else {
+                    value.to_string()
+                };
+                Some((name, value))
+            })
+            .collect::<Option<_>>()?;
+
+        Some(Mime { essence, params })
+    }
+
+    /// The value of the parameter `name`, if present.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether this media type satisfies `expected`: the essence must be equal, and so must every
+    /// parameter `expected` lists. Parameters `expected` leaves out are ignored.
+    pub fn matches(&self, expected: &Mime) -> bool {
+        self.essence == expected.essence
+            && expected
+                .params
+                .iter()
+                .all(|(name, value)| self.param(name) == Some(value))
+    }
+}
+
+impl fmt::Display for Mime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.essence)?;
+        for (name, value) in &self.params {
+            write!(f, "; {}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check that the first response in `records` declares a content type matching `expected`.
+///
+/// Returns [`VerifyError::ContentTypeMismatch`] when `expected` is not a media type, there is no
+/// response, or the response has a different or no `Content-Type`.
+pub fn check_content_type(records: &[Record], expected: &str) -> Result<(), VerifyError> {
+    let found = records
+        .iter()
+        .find(|record| Direction::of(&record.bytes) == Direction::Response)
+        .and_then(Record::content_type);
+    let matches = Mime::parse(expected)
+        .zip(found.as_ref())
+        .is_some_and(|(expected, found)| found.matches(&expected));
+
+    if matches {
+        Ok(())
+    } else {
+        Err(VerifyError::ContentTypeMismatch {
+            expected: expected.to_string(),
+            found: found.map(|found| found.to_string()),
+        })
+    }
+}
+
+/// Split off the line at the start of `bytes`, without its `\r\n`.
+fn split_line(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let end = bytes.windows(2).position(|window| window == b"\r\n")?;
+    Some((&bytes[..end], &bytes[end + 2..]))
+}
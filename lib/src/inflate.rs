@@ -0,0 +1,128 @@
+//! DEFLATE (RFC 1951) decoding with the zlib (RFC 1950) and gzip (RFC 1952) wrappers.
+//!
+//! The DEFLATE and zlib streams are decoded by `miniz_oxide`, which needs nothing but `alloc`;
+//! only the gzip header and trailer are parsed here. Every decoder takes a cap on its output and
+//! stops as soon as the cap would be exceeded, so a small body cannot expand into an unbounded
+//! allocation.
+
+use miniz_oxide::inflate::core::inflate_flags::{
+    TINFL_FLAG_PARSE_ZLIB_HEADER, TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF,
+};
+use miniz_oxide::inflate::core::{decompress, DecompressorOxide};
+use miniz_oxide::inflate::TINFLStatus;
+
+use crate::input::crc32;
+
+/// Why a compressed stream could not be decoded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum InflateError {
+    /// The stream is truncated, corrupt, or fails its checksum.
+    Malformed,
+    /// The stream decodes to more bytes than the cap allows.
+    TooLarge,
+}
+
+type Result<T> = std::result::Result<T, InflateError>;
+
+/// Decode a gzip member, checking its CRC-32 and length.
+pub(crate) fn gunzip(data: &[u8], max_bytes: usize) -> Result<Vec<u8>> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+
+    let header = data.get(..10).ok_or(InflateError::Malformed)?;
+    let flags = header[3];
+    if header[..3] != [0x1f, 0x8b, 8] || flags & 0xe0 != 0 {
+        return Err(InflateError::Malformed);
+    }
+
+    let mut rest = &data[10..];
+    if flags & FEXTRA != 0 {
+        let len = u16::from_le_bytes(take(&mut rest, 2)?.try_into().unwrap());
+        take(&mut rest, len as usize)?;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let end = rest
+                .iter()
+                .position(|&byte| byte == 0)
+                .ok_or(InflateError::Malformed)?;
+            take(&mut rest, end + 1)?;
+        }
+    }
+    if flags & FHCRC != 0 {
+        take(&mut rest, 2)?;
+    }
+
+    let (output, used) = inflate(rest, max_bytes)?;
+    let mut trailer = &rest[used..];
+    let crc = u32::from_le_bytes(take(&mut trailer, 4)?.try_into().unwrap());
+    let size = u32::from_le_bytes(take(&mut trailer, 4)?.try_into().unwrap());
+    if !trailer.is_empty() || crc != crc32([output.as_slice()]) || size != output.len() as u32 {
+        return Err(InflateError::Malformed);
+    }
+    Ok(output)
+}
+
+/// Decode a zlib stream, checking its Adler-32.
+///
+/// A preset dictionary is never used by HTTP content codings, so a stream that asks for one is
+/// rejected, as is anything after the checksum.
+pub(crate) fn unzlib(data: &[u8], max_bytes: usize) -> Result<Vec<u8>> {
+    let (output, used) = decompress_with_limit(data, TINFL_FLAG_PARSE_ZLIB_HEADER, max_bytes)?;
+    if used != data.len() {
+        return Err(InflateError::Malformed);
+    }
+    Ok(output)
+}
+
+/// Decode a raw DEFLATE stream, returning the output and the number of input bytes used.
+pub(crate) fn inflate(data: &[u8], max_bytes: usize) -> Result<(Vec<u8>, usize)> {
+    decompress_with_limit(data, 0, max_bytes)
+}
+
+/// Run the decompressor over all of `data` with `flags`, growing the output up to `max_bytes`,
+/// and return the output and the number of input bytes used.
+fn decompress_with_limit(
+    mut data: &[u8],
+    flags: u32,
+    max_bytes: usize,
+) -> Result<(Vec<u8>, usize)> {
+    // The whole output stays in one buffer, so back-references never need a wrapping window.
+    let flags = flags | TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF;
+    let mut decompressor = Box::<DecompressorOxide>::default();
+    let mut output = vec![0; data.len().saturating_mul(2).min(max_bytes)];
+    let (mut written, mut used) = (0, 0);
+
+    loop {
+        let (status, consumed, produced) =
+            decompress(&mut decompressor, data, &mut output, written, flags);
+        written += produced;
+        used += consumed;
+        match status {
+            TINFLStatus::Done => {
+                output.truncate(written);
+                return Ok((output, used));
+            }
+            TINFLStatus::HasMoreOutput => {
+                if output.len() >= max_bytes {
+                    return Err(InflateError::TooLarge);
+                }
+                data = data.get(consumed..).ok_or(InflateError::Malformed)?;
+                let len = output.len().saturating_mul(2).max(64).min(max_bytes);
+                output.resize(len, 0);
+            }
+            _ => return Err(InflateError::Malformed),
+        }
+    }
+}
+
+fn take<'a>(rest: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if rest.len() < len {
+        return Err(InflateError::Malformed);
+    }
+    let (taken, after) = rest.split_at(len);
+    *rest = after;
+    Ok(taken)
+}
@@ -0,0 +1,149 @@
+//! Decoding the program's inputs without panicking.
+//!
+//! `sp1_zkvm::io::read` panics when its input does not deserialize, which leaves a truncated or
+//! mistagged attestation indistinguishable from a crash. The program instead reads the raw bytes
+//! the host wrote with `SP1Stdin::write`, which are the value's bincode encoding, and decodes them
+//! with [`decode`], so malformed input aborts with [`AbortCode::MalformedInput`] like any other
+//! rejected attestation. The host decodes with the same function to test that path outside the
+//! zkVM.
+//!
+//! Before it decodes anything, the program checks the verifying key and attestation against the
+//! [`Preamble`] the host writes first.
+//!
+//! [`AbortCode::MalformedInput`]: crate::abort::AbortCode::MalformedInput
+
+use serde::de::DeserializeOwned;
+
+use crate::VerifyError;
+
+/// Decode `bytes` as the bincode encoding of a `T`, as `SP1Stdin::write` encodes it.
+///
+/// Fails with [`VerifyError::MalformedInput`] when `bytes` end early, carry trailing bytes, or do
+/// not encode a `T`.
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, VerifyError> {
+    let mut reader = bytes;
+    let value = bincode::deserialize_from(&mut reader).map_err(|_| VerifyError::MalformedInput)?;
+    if !reader.is_empty() {
+        return Err(VerifyError::MalformedInput);
+    }
+    Ok(value)
+}
+
+/// The bytes every preamble starts with.
+pub const PREAMBLE_MAGIC: [u8; 4] = *b"zkTL";
+
+/// The first input the program reads, declaring the verifying key and attestation items it is
+/// followed by.
+///
+/// A stdin written in the wrong order, or with an item cut short, otherwise only shows when an
+/// item fails to decode, possibly long after the first one was read. The program checks the key
+/// and attestation items against their preamble before it decodes either or runs any
+/// cryptography, and aborts with [`AbortCode::BadPreamble`] when they do not match. The checksum
+/// is a CRC-32, cheap enough to run over the whole attestation first: it catches accidents, and
+/// the signature catches the rest.
+///
+/// [`AbortCode::BadPreamble`]: crate::abort::AbortCode::BadPreamble
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Preamble {
+    /// Whether the program commits its abort code rather than panicking; see
+    /// [`abort`](crate::abort).
+    pub report_status: bool,
+    /// The length of the verifying key item.
+    pub key_bytes: u32,
+    /// The length of the attestation item.
+    pub data_bytes: u32,
+    /// The CRC-32 of the key item followed by the attestation item.
+    pub checksum: u32,
+}
+
+impl Preamble {
+    /// The length of an encoded preamble: the magic, the status flag, the two lengths and the
+    /// checksum.
+    pub const LEN: usize = 17;
+
+    /// The preamble of the verifying key item `key` and the attestation item `data`, as they are
+    /// written to stdin.
+    pub fn new(report_status: bool, key: &[u8], data: &[u8]) -> Self {
+        Preamble {
+            report_status,
+            key_bytes: key.len() as u32,
+            data_bytes: data.len() as u32,
+            checksum: crc32([key, data]),
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[..4].copy_from_slice(&PREAMBLE_MAGIC);
+        bytes[4] = u8::from(self.report_status);
+        bytes[5..9].copy_from_slice(&self.key_bytes.to_le_bytes());
+        bytes[9..13].copy_from_slice(&self.data_bytes.to_le_bytes());
+        bytes[13..].copy_from_slice(&self.checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Decode a preamble, failing with [`VerifyError::BadPreamble`] when `bytes` are not
+    /// [`Preamble::LEN`] long, do not start with [`PREAMBLE_MAGIC`], or state a status flag other
+    /// than 0 or 1.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, VerifyError> {
+        let bytes: &[u8; Self::LEN] = bytes.try_into().map_err(|_| VerifyError::BadPreamble)?;
+        if bytes[..4] != PREAMBLE_MAGIC || bytes[4] > 1 {
+            return Err(VerifyError::BadPreamble);
+        }
+        let word = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+        Ok(Preamble {
+            report_status: bytes[4] == 1,
+            key_bytes: word(5),
+            data_bytes: word(9),
+            checksum: word(13),
+        })
+    }
+
+    /// Check the key item `key` and the attestation item `data` against the preamble, failing
+    /// with [`VerifyError::BadPreamble`] when either length or the checksum differs.
+    ///
+    /// The lengths are compared first, so a truncated item is rejected without reading it.
+    pub fn check(&self, key: &[u8], data: &[u8]) -> Result<(), VerifyError> {
+        if key.len() != self.key_bytes as usize || data.len() != self.data_bytes as usize {
+            debug!(
+                key_bytes = key.len(),
+                data_bytes = data.len(),
+                "inputs are not the length their preamble declares"
+            );
+            return Err(VerifyError::BadPreamble);
+        }
+        if crc32([key, data]) != self.checksum {
+            debug!("inputs do not match their preamble checksum");
+            return Err(VerifyError::BadPreamble);
+        }
+        Ok(())
+    }
+}
+
+/// The CRC-32 (IEEE) of `parts` concatenated.
+pub(crate) fn crc32<'a>(parts: impl IntoIterator<Item = &'a [u8]>) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut index = 0;
+        while index < 256 {
+            let mut crc = index as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xedb8_8320
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            table[index] = crc;
+            index += 1;
+        }
+        table
+    };
+
+    let crc = parts.into_iter().flatten().fold(!0u32, |crc, &byte| {
+        TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    });
+    !crc
+}
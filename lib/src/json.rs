@@ -0,0 +1,141 @@
+//! Extraction of values from JSON response bodies by path.
+//!
+//! Paths use a small JSONPath subset: `$` followed by `.key` and `[index]` segments, e.g.
+//! `$.accounts[0].balance`. Values are returned as raw JSON text so numbers keep their exact
+//! decimal representation.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use serde_json::value::RawValue;
+
+use crate::{http, Record, VerifyError};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn parse_path(path: &str) -> Option<Vec<Segment>> {
+    let mut rest = path.strip_prefix('$')?;
+    let mut segments = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end == 0 {
+                return None;
+            }
+            segments.push(Segment::Key(after[..end].to_string()));
+            rest = &after[end..];
+        } else {
+            let after = rest.strip_prefix('[')?;
+            let end = after.find(']')?;
+            segments.push(Segment::Index(after[..end].parse().ok()?));
+            rest = &after[end + 1..];
+        }
+    }
+
+    Some(segments)
+}
+
+/// Whether `path` is a valid extraction path.
+pub fn is_valid_path(path: &str) -> bool {
+    parse_path(path).is_some()
+}
+
+/// The raw JSON text at `path` in `json`, or `None` if the path is invalid, `json` does not parse,
+/// or nothing is found there.
+pub fn extract<'a>(json: &'a [u8], path: &str) -> Option<&'a str> {
+    let segments = parse_path(path)?;
+    let mut value: &RawValue = serde_json::from_slice(json).ok()?;
+
+    for segment in segments {
+        value = match segment {
+            Segment::Key(key) => {
+                let mut object: BTreeMap<String, &RawValue> =
+                    serde_json::from_str(value.get()).ok()?;
+                object.remove(&key)?
+            }
+            Segment::Index(index) => {
+                let array: Vec<&RawValue> = serde_json::from_str(value.get()).ok()?;
+                *array.get(index)?
+            }
+        };
+    }
+
+    Some(value.get())
+}
+
+/// The raw JSON text at `path` in the body of the first response in `records`.
+pub fn response_value(records: &[Record], path: &str) -> Result<String, VerifyError> {
+    let response = http::response_message(records).ok_or(VerifyError::MissingResponse)?;
+    let (_, body) = http::split_message(&response).ok_or(VerifyError::MissingResponse)?;
+
+    extract(body, path)
+        .map(str::to_string)
+        .ok_or_else(|| VerifyError::PathNotFound {
+            path: path.to_string(),
+        })
+}
+
+/// The contents of `raw` if it is a JSON string, otherwise `raw` itself.
+///
+/// APIs often send amounts as strings to avoid float rounding, so numeric helpers accept both.
+pub fn unquote(raw: &str) -> String {
+    serde_json::from_str::<String>(raw).unwrap_or_else(|_| raw.to_string())
+}
+
+/// Parse `-?digits(.digits)?` into an integer and the number of fractional digits.
+pub fn parse_decimal(text: &str) -> Option<(i128, u32)> {
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    if whole.is_empty()
+        || !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    if unsigned.contains('.') && fraction.is_empty() {
+        return None;
+    }
+
+    let mut value: i128 = 0;
+    for digit in whole.bytes().chain(fraction.bytes()) {
+        value = value.checked_mul(10)?.checked_add((digit - b'0') as i128)?;
+    }
+
+    Some((if negative { -value } else { value }, fraction.len() as u32))
+}
+
+/// Compare two decimals from [`parse_decimal`] exactly, or `None` if rescaling overflows.
+pub fn compare_decimals(a: (i128, u32), b: (i128, u32)) -> Option<Ordering> {
+    let rescale =
+        |(value, scale): (i128, u32), to: u32| 10i128.checked_pow(to - scale)?.checked_mul(value);
+    let scale = a.1.max(b.1);
+
+    Some(rescale(a, scale)?.cmp(&rescale(b, scale)?))
+}
+
+/// Whether two raw JSON values are equal, comparing decimal numbers (or strings holding them)
+/// by value so that `"100.0"` and `100` agree.
+pub fn values_equal(a: &str, b: &str) -> bool {
+    let decimals = (parse_decimal(&unquote(a)), parse_decimal(&unquote(b)));
+    if let (Some(a), Some(b)) = decimals {
+        return compare_decimals(a, b) == Some(Ordering::Equal);
+    }
+
+    match (
+        serde_json::from_str::<serde_json::Value>(a),
+        serde_json::from_str::<serde_json::Value>(b),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
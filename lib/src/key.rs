@@ -0,0 +1,333 @@
+//! Verifying keys in the encodings attestors publish them in.
+//!
+//! The attestation containers are signed with ECDSA over secp256k1. The `.key` files in
+//! `fixtures/zktls` hold the key as the hex of its compressed SEC1 encoding, which is what the
+//! containers' `verify` expects. A key can also arrive as raw SEC1 bytes, or as a DER or PEM
+//! `SubjectPublicKeyInfo`, as `openssl ec -pubout` writes it.
+//!
+//! Merkle transcripts and streamed attestations are verified here rather than upstream, so their
+//! attestor may also sign with ECDSA over P-256 or with Ed25519. A DER or PEM key names its curve;
+//! raw bytes and hex are read as secp256k1 unless the curve is given, with the `from_p256_*` and
+//! `from_ed25519_*` constructors or a `p256:` or `ed25519:` prefix before the hex.
+//!
+//! [`VerifyingKey`] serializes as that hex, which for a secp256k1 key is the hex of its compressed
+//! SEC1 encoding, so it stands in for the hex strings the program reads and commits without
+//! changing their encoding.
+
+use std::fmt;
+use std::str::FromStr;
+
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::pkcs8::DecodePublicKey;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::VerifyError;
+
+/// The encoding a verifying key was parsed from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyFormat {
+    /// Hex of a raw encoding, optionally prefixed with its curve.
+    Hex,
+    /// A raw encoding: SEC1, compressed or not, or the 32 bytes of an Ed25519 key.
+    Sec1,
+    /// A DER `SubjectPublicKeyInfo`.
+    Der,
+    /// A PEM `PUBLIC KEY` block.
+    Pem,
+}
+
+impl fmt::Display for KeyFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            KeyFormat::Hex => "hex",
+            KeyFormat::Sec1 => "SEC1",
+            KeyFormat::Der => "DER",
+            KeyFormat::Pem => "PEM",
+        })
+    }
+}
+
+/// A curve an attestor signs on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Curve {
+    /// ECDSA over secp256k1, the curve of every attestation container.
+    Secp256k1,
+    /// ECDSA over NIST P-256.
+    P256,
+    /// EdDSA over edwards25519.
+    Ed25519,
+}
+
+impl Curve {
+    /// The prefix naming the curve before the hex of a key, empty for secp256k1.
+    const fn hex_prefix(self) -> &'static str {
+        match self {
+            Curve::Secp256k1 => "",
+            Curve::P256 => "p256:",
+            Curve::Ed25519 => "ed25519:",
+        }
+    }
+}
+
+impl fmt::Display for Curve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Curve::Secp256k1 => "secp256k1",
+            Curve::P256 => "P-256",
+            Curve::Ed25519 => "Ed25519",
+        })
+    }
+}
+
+/// A verifying key on one of the supported [`Curve`]s.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VerifyingKey {
+    K256(k256::ecdsa::VerifyingKey),
+    P256(p256::ecdsa::VerifyingKey),
+    Ed25519(ed25519_dalek::VerifyingKey),
+}
+
+impl VerifyingKey {
+    /// Parse a secp256k1 key from its SEC1 encoding, compressed or not.
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self, VerifyError> {
+        k256::ecdsa::VerifyingKey::from_sec1_bytes(bytes)
+            .map(VerifyingKey::K256)
+            .map_err(|_| VerifyError::KeyParseError {
+                format: KeyFormat::Sec1,
+            })
+    }
+
+    /// Parse a P-256 key from its SEC1 encoding, compressed or not.
+    pub fn from_p256_sec1_bytes(bytes: &[u8]) -> Result<Self, VerifyError> {
+        p256::ecdsa::VerifyingKey::from_sec1_bytes(bytes)
+            .map(VerifyingKey::P256)
+            .map_err(|_| VerifyError::KeyParseError {
+                format: KeyFormat::Sec1,
+            })
+    }
+
+    /// Parse an Ed25519 key from its 32-byte encoding.
+    pub fn from_ed25519_bytes(bytes: &[u8]) -> Result<Self, VerifyError> {
+        <&[u8; 32]>::try_from(bytes)
+            .ok()
+            .and_then(|bytes| ed25519_dalek::VerifyingKey::from_bytes(bytes).ok())
+            .map(VerifyingKey::Ed25519)
+            .ok_or(VerifyError::KeyParseError {
+                format: KeyFormat::Sec1,
+            })
+    }
+
+    /// Parse a key from the raw encoding of a key on `curve`.
+    pub fn from_curve_bytes(curve: Curve, bytes: &[u8]) -> Result<Self, VerifyError> {
+        match curve {
+            Curve::Secp256k1 => Self::from_sec1_bytes(bytes),
+            Curve::P256 => Self::from_p256_sec1_bytes(bytes),
+            Curve::Ed25519 => Self::from_ed25519_bytes(bytes),
+        }
+    }
+
+    /// Parse a DER `SubjectPublicKeyInfo`, on whichever curve it names.
+    pub fn from_der(bytes: &[u8]) -> Result<Self, VerifyError> {
+        k256::ecdsa::VerifyingKey::from_public_key_der(bytes)
+            .map(VerifyingKey::K256)
+            .or_else(|_| {
+                p256::ecdsa::VerifyingKey::from_public_key_der(bytes).map(VerifyingKey::P256)
+            })
+            .or_else(|_| {
+                ed25519_dalek::VerifyingKey::from_public_key_der(bytes).map(VerifyingKey::Ed25519)
+            })
+            .map_err(|_| VerifyError::KeyParseError {
+                format: KeyFormat::Der,
+            })
+    }
+
+    /// Parse a PEM `PUBLIC KEY` block, on whichever curve it names.
+    pub fn from_pem(pem: &str) -> Result<Self, VerifyError> {
+        let pem = pem.trim();
+        k256::ecdsa::VerifyingKey::from_public_key_pem(pem)
+            .map(VerifyingKey::K256)
+            .or_else(|_| {
+                p256::ecdsa::VerifyingKey::from_public_key_pem(pem).map(VerifyingKey::P256)
+            })
+            .or_else(|_| {
+                ed25519_dalek::VerifyingKey::from_public_key_pem(pem).map(VerifyingKey::Ed25519)
+            })
+            .map_err(|_| VerifyError::KeyParseError {
+                format: KeyFormat::Pem,
+            })
+    }
+
+    /// Parse the hex of a raw encoding: a secp256k1 SEC1 encoding, or the encoding of a key on the
+    /// curve a `p256:` or `ed25519:` prefix names.
+    pub fn from_hex(key: &str) -> Result<Self, VerifyError> {
+        let key = key.trim();
+        let (curve, digits) = [Curve::P256, Curve::Ed25519]
+            .into_iter()
+            .find_map(|curve| Some((curve, key.strip_prefix(curve.hex_prefix())?)))
+            .unwrap_or((Curve::Secp256k1, key));
+        hex::decode(digits)
+            .ok()
+            .and_then(|bytes| Self::from_curve_bytes(curve, &bytes).ok())
+            .ok_or(VerifyError::KeyParseError {
+                format: KeyFormat::Hex,
+            })
+    }
+
+    /// Parse a key given as text: a PEM block if it has one, hex otherwise.
+    pub fn parse(key: &str) -> Result<Self, VerifyError> {
+        if key.trim_start().starts_with("-----BEGIN") {
+            Self::from_pem(key)
+        } else {
+            Self::from_hex(key)
+        }
+    }
+
+    pub fn curve(&self) -> Curve {
+        match self {
+            VerifyingKey::K256(_) => Curve::Secp256k1,
+            VerifyingKey::P256(_) => Curve::P256,
+            VerifyingKey::Ed25519(_) => Curve::Ed25519,
+        }
+    }
+
+    /// The raw encoding: compressed SEC1 for the ECDSA curves, the 32-byte point for Ed25519.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            VerifyingKey::K256(key) => key.to_encoded_point(true).as_bytes().to_vec(),
+            VerifyingKey::P256(key) => key.to_encoded_point(true).as_bytes().to_vec(),
+            VerifyingKey::Ed25519(key) => key.to_bytes().to_vec(),
+        }
+    }
+
+    /// The hex of [`to_bytes`](VerifyingKey::to_bytes), prefixed with the curve unless it is
+    /// secp256k1. For a secp256k1 key this is the form the attestation containers verify with.
+    pub fn to_hex(&self) -> String {
+        format!(
+            "{}{}",
+            self.curve().hex_prefix(),
+            hex::encode(self.to_bytes())
+        )
+    }
+
+    /// The key as a secp256k1 key, the only curve the attestation containers verify.
+    pub fn as_k256(&self) -> Option<&k256::ecdsa::VerifyingKey> {
+        match self {
+            VerifyingKey::K256(key) => Some(key),
+            _ => None,
+        }
+    }
+
+    /// Check `signature` over the 32-byte `digest` of a message.
+    ///
+    /// An ECDSA signature is the 64-byte `r || s` over the digest as a prehash; an Ed25519
+    /// signature signs the digest itself as the message. Fails with
+    /// [`VerifyError::BadSignature`].
+    pub fn verify_digest(&self, digest: &[u8], signature: &[u8]) -> Result<(), VerifyError> {
+        let verified = match self {
+            VerifyingKey::K256(key) => k256::ecdsa::Signature::from_slice(signature)
+                .and_then(|signature| key.verify_prehash(digest, &signature))
+                .is_ok(),
+            VerifyingKey::P256(key) => p256::ecdsa::Signature::from_slice(signature)
+                .and_then(|signature| key.verify_prehash(digest, &signature))
+                .is_ok(),
+            VerifyingKey::Ed25519(key) => ed25519_dalek::Signature::from_slice(signature)
+                .and_then(|signature| key.verify_strict(digest, &signature))
+                .is_ok(),
+        };
+        if verified {
+            Ok(())
+        } else {
+            Err(VerifyError::BadSignature)
+        }
+    }
+
+    /// The Ethereum address of a secp256k1 key: the last 20 bytes of the keccak256 of its
+    /// uncompressed point, without the `0x04` prefix. Keys on other curves have none.
+    pub fn address(&self) -> Option<[u8; 20]> {
+        let point = self.as_k256()?.to_encoded_point(false);
+        let mut keccak = Keccak::v256();
+        keccak.update(&point.as_bytes()[1..]);
+        let mut hash = [0; 32];
+        keccak.finalize(&mut hash);
+
+        let mut address = [0; 20];
+        address.copy_from_slice(&hash[12..]);
+        Some(address)
+    }
+
+    /// The keccak256 committed to name the key in 32 bytes.
+    ///
+    /// For a secp256k1 key it is taken over the compressed SEC1 encoding. For the other curves the
+    /// encoding is preceded by the curve prefix, `p256:` or `ed25519:`, so keys on different
+    /// curves never share a digest.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut keccak = Keccak::v256();
+        keccak.update(self.curve().hex_prefix().as_bytes());
+        keccak.update(&self.to_bytes());
+        let mut digest = [0; 32];
+        keccak.finalize(&mut digest);
+        digest
+    }
+}
+
+impl FromStr for VerifyingKey {
+    type Err = VerifyError;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        VerifyingKey::parse(key)
+    }
+}
+
+impl TryFrom<&str> for VerifyingKey {
+    type Error = VerifyError;
+
+    fn try_from(key: &str) -> Result<Self, Self::Error> {
+        VerifyingKey::parse(key)
+    }
+}
+
+impl fmt::Display for VerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl Serialize for VerifyingKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for VerifyingKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = String::deserialize(deserializer)?;
+        VerifyingKey::from_hex(&key)
+            .map_err(|_| D::Error::custom(format!("`{}` is not a verifying key", key)))
+    }
+}
+
+/// Anything `verify` accepts as a verifying key: a parsed [`VerifyingKey`], or text that
+/// [`VerifyingKey::parse`] understands.
+pub trait AsVerifyingKey {
+    fn verifying_key(&self) -> Result<VerifyingKey, VerifyError>;
+}
+
+impl AsVerifyingKey for VerifyingKey {
+    fn verifying_key(&self) -> Result<VerifyingKey, VerifyError> {
+        Ok(*self)
+    }
+}
+
+impl AsVerifyingKey for str {
+    fn verifying_key(&self) -> Result<VerifyingKey, VerifyError> {
+        VerifyingKey::parse(self)
+    }
+}
+
+impl AsVerifyingKey for String {
+    fn verifying_key(&self) -> Result<VerifyingKey, VerifyError> {
+        VerifyingKey::parse(self)
+    }
+}
@@ -0,0 +1,128 @@
+//! Types shared between the zktls program and the scripts that drive it.
+
+#[macro_use]
+mod trace;
+
+pub mod abort;
+pub mod aggregation;
+pub mod alias;
+pub mod batch;
+pub mod canonical;
+pub mod claim;
+pub mod config;
+pub mod content_encoding;
+pub mod context;
+pub mod cosign;
+pub mod cross_check;
+pub mod ct;
+pub mod date;
+pub mod eip712;
+mod error;
+pub mod h2;
+pub mod hash;
+pub mod hpack;
+pub mod http;
+mod inflate;
+pub mod input;
+pub mod json;
+pub mod key;
+pub mod merkle;
+pub mod metadata;
+pub mod pagination;
+pub mod pair;
+pub mod predicate;
+pub mod preview;
+pub mod public_values;
+pub mod record;
+pub mod request_template;
+pub mod response_header;
+pub mod sanitize;
+pub mod sequence;
+#[cfg(feature = "session")]
+pub mod session;
+pub mod signatures;
+pub mod stream;
+pub mod tls;
+pub mod verifying_data;
+
+use std::fmt;
+use std::str::FromStr;
+
+pub use config::VerifyConfig;
+pub use error::VerifyError;
+pub use public_values::{PublicValues, RecordDigests};
+pub use record::Record;
+
+/// Whether `verifying_key` is the hex of a verifying key, as [`key::VerifyingKey::from_hex`] reads
+/// it.
+pub fn is_verifying_key(verifying_key: &str) -> bool {
+    key::VerifyingKey::from_hex(verifying_key).is_ok()
+}
+
+/// The verification container an attestation is serialized as.
+///
+/// The program reads a one-byte tag ahead of the attestation to know which type to deserialize,
+/// and commits the same tag so verifiers know which structure was proven.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DataFormat {
+    /// `VerifyingDataOpt`, the format of the bench fixtures.
+    Opt,
+    /// `VerifyingData`, the original non-optimized format.
+    Full,
+    /// [`merkle::MerkleTranscript`], a signed Merkle root with selectively disclosed chunks.
+    Merkle,
+    /// `VerifyingDataOpt` as a [`stream::StreamHeader`] followed, after every other input, by the
+    /// ciphertext of its records in chunks, for records too large to hold in memory at once.
+    Stream,
+}
+
+impl DataFormat {
+    /// The tag written to stdin and committed to the public values.
+    pub const fn tag(self) -> u8 {
+        match self {
+            DataFormat::Opt => 0,
+            DataFormat::Full => 1,
+            DataFormat::Merkle => 2,
+            DataFormat::Stream => 3,
+        }
+    }
+
+    /// The format for `tag`, if it is known.
+    pub const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(DataFormat::Opt),
+            1 => Some(DataFormat::Full),
+            2 => Some(DataFormat::Merkle),
+            3 => Some(DataFormat::Stream),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DataFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataFormat::Opt => f.write_str("opt"),
+            DataFormat::Full => f.write_str("full"),
+            DataFormat::Merkle => f.write_str("merkle"),
+            DataFormat::Stream => f.write_str("stream"),
+        }
+    }
+}
+
+impl FromStr for DataFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "opt" => Ok(DataFormat::Opt),
+            "full" => Ok(DataFormat::Full),
+            "merkle" => Ok(DataFormat::Merkle),
+            "stream" => Ok(DataFormat::Stream),
+            _ => Err(format!(
+                "unknown data format `{}`, expected `opt`, `full`, `merkle` or `stream`",
+                s
+            )),
+        }
+    }
+}
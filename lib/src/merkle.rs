@@ -0,0 +1,255 @@
+//! Attestations whose signature covers a Merkle tree over the TLS transcript.
+//!
+//! Instead of signing every record, the attestor splits the transcript into chunks, builds a
+//! Merkle tree over them and signs only the root. The prover then discloses a subset of chunks,
+//! each with an inclusion proof against that root, so the cost of verification grows with what
+//! is disclosed rather than with the size of the session.
+//!
+//! The tree follows RFC 6962 with keccak256 as the hash: leaves are `keccak(0x00 || chunk)`,
+//! interior nodes are `keccak(0x01 || left || right)`, and a tree of `n` leaves splits at the
+//! largest power of two below `n`. The signature is over the root as
+//! [`VerifyingKey::verify_digest`](crate::key::VerifyingKey::verify_digest) checks it: ECDSA with
+//! the root as the prehashed message, on secp256k1 unless the key names another curve.
+
+use serde::{Deserialize, Serialize};
+use tiny_keccak::{Hasher, Keccak};
+
+use crate::ct::CtDigest;
+use crate::key::AsVerifyingKey;
+use crate::{Record, VerifyError};
+
+/// A transcript committed to by a signed Merkle root, with some of its chunks disclosed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleTranscript {
+    #[serde(with = "hex::serde")]
+    root: [u8; 32],
+    /// The number of chunks the attestor split the transcript into.
+    chunk_count: u32,
+    /// The 64-byte `r || s` signature over `root`.
+    #[serde(with = "hex::serde")]
+    signature: Vec<u8>,
+    /// The disclosed chunks, ordered by index.
+    chunks: Vec<DisclosedChunk>,
+}
+
+/// A transcript chunk and the sibling hashes linking it to the root, deepest first.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisclosedChunk {
+    pub index: u32,
+    #[serde(with = "hex::serde")]
+    pub data: Vec<u8>,
+    #[serde(with = "hex_nodes")]
+    pub proof: Vec<[u8; 32]>,
+}
+
+impl MerkleTranscript {
+    pub fn new(
+        root: [u8; 32],
+        chunk_count: u32,
+        signature: Vec<u8>,
+        chunks: Vec<DisclosedChunk>,
+    ) -> Self {
+        MerkleTranscript {
+            root,
+            chunk_count,
+            signature,
+            chunks,
+        }
+    }
+
+    /// The root the attestor signed.
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    /// The number of chunks in the whole transcript, disclosed or not.
+    pub fn chunk_count(&self) -> u32 {
+        self.chunk_count
+    }
+
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    pub fn disclosed_chunks(&self) -> &[DisclosedChunk] {
+        &self.chunks
+    }
+
+    /// The disclosed chunks as records, in transcript order.
+    pub fn get_records(&self) -> Vec<Record> {
+        self.iter_records().collect()
+    }
+
+    /// The disclosed chunks as records, in transcript order, each built only when it is reached.
+    ///
+    /// Cloning the iterator is cheap: it copies a position in the chunks, not the records.
+    pub fn iter_records(&self) -> impl Iterator<Item = Record> + Clone + '_ {
+        self.chunks.iter().map(|chunk| Record {
+            bytes: chunk.data.clone(),
+            redacted: Vec::new(),
+        })
+    }
+
+    /// The number of disclosed chunks, i.e. of records.
+    pub fn record_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Check the signature over the root against `verifying_key`, then check that every disclosed
+    /// chunk is included under that root.
+    ///
+    /// The key is either a parsed [`VerifyingKey`] or text, PEM or hex, that fails to parse with
+    /// [`VerifyError::KeyParseError`].
+    ///
+    /// Chunks must be in strictly increasing index order, so that the records they yield are in
+    /// transcript order.
+    pub fn verify<K: AsVerifyingKey + ?Sized>(&self, verifying_key: &K) -> Result<(), VerifyError> {
+        debug_span!("verify", format = "merkle", chunks = self.chunks.len());
+
+        let verifying_key = {
+            debug_span!("key_parse");
+            verifying_key.verifying_key()
+        };
+        let verifying_key = match verifying_key {
+            Ok(verifying_key) => verifying_key,
+            Err(err) => {
+                debug!("verifying key does not parse");
+                return Err(err);
+            }
+        };
+        {
+            debug_span!("signature_check");
+            verifying_key
+                .verify_digest(&self.root, &self.signature)
+                .inspect_err(|_| {
+                    debug!("signature over the root does not verify");
+                })?;
+        }
+
+        let mut previous = None;
+        for chunk in &self.chunks {
+            debug_span!("inclusion_check", index = chunk.index);
+            let ordered = previous < Some(chunk.index);
+            let root = root_from_proof(
+                chunk.index,
+                self.chunk_count,
+                leaf_hash(&chunk.data),
+                &chunk.proof,
+            );
+            let included = root.is_some_and(|root| CtDigest(root).ct_eq(&CtDigest(self.root)));
+            if !ordered || !included {
+                debug!(ordered, "chunk is not included under the root");
+                return Err(VerifyError::BadInclusionProof { index: chunk.index });
+            }
+            previous = Some(chunk.index);
+        }
+
+        Ok(())
+    }
+}
+
+/// The hash of a leaf holding `chunk`.
+pub fn leaf_hash(chunk: &[u8]) -> [u8; 32] {
+    hash(&[&[0x00], chunk])
+}
+
+/// The hash of an interior node with children `left` and `right`.
+pub fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hash(&[&[0x01], left, right])
+}
+
+/// The root of the tree over `chunks`, which must not be empty.
+pub fn root<C: AsRef<[u8]>>(chunks: &[C]) -> [u8; 32] {
+    match chunks {
+        [] => panic!("a Merkle tree needs at least one chunk"),
+        [chunk] => leaf_hash(chunk.as_ref()),
+        _ => {
+            let (left, right) = chunks.split_at(split_point(chunks.len()));
+            node_hash(&root(left), &root(right))
+        }
+    }
+}
+
+/// The inclusion proof of the chunk at `index` in the tree over `chunks`, deepest sibling first.
+pub fn inclusion_proof<C: AsRef<[u8]>>(chunks: &[C], index: usize) -> Vec<[u8; 32]> {
+    assert!(index < chunks.len(), "chunk {} is out of range", index);
+    if chunks.len() == 1 {
+        return Vec::new();
+    }
+
+    let k = split_point(chunks.len());
+    let (left, right) = chunks.split_at(k);
+    let (mut proof, sibling) = if index < k {
+        (inclusion_proof(left, index), root(right))
+    } else {
+        (inclusion_proof(right, index - k), root(left))
+    };
+    proof.push(sibling);
+    proof
+}
+
+/// Recompute the root from the leaf at `index` of a tree of `size` leaves, or `None` if the proof
+/// does not have the shape such a tree requires.
+fn root_from_proof(index: u32, size: u32, leaf: [u8; 32], proof: &[[u8; 32]]) -> Option<[u8; 32]> {
+    if index >= size {
+        return None;
+    }
+
+    let (mut node, mut last) = (index, size - 1);
+    let mut hash = leaf;
+    for sibling in proof {
+        if last == 0 {
+            return None;
+        }
+        if node & 1 == 1 || node == last {
+            hash = node_hash(sibling, &hash);
+            // A rightmost node without a sibling at this level moves up unchanged.
+            while node & 1 == 0 && node != 0 {
+                node >>= 1;
+                last >>= 1;
+            }
+        } else {
+            hash = node_hash(&hash, sibling);
+        }
+        node >>= 1;
+        last >>= 1;
+    }
+
+    (last == 0).then_some(hash)
+}
+
+/// The size of the left subtree of a tree of `len > 1` leaves: the largest power of two below
+/// `len`.
+fn split_point(len: usize) -> usize {
+    1 << (usize::BITS - 1 - (len - 1).leading_zeros())
+}
+
+fn hash(parts: &[&[u8]]) -> [u8; 32] {
+    let mut keccak = Keccak::v256();
+    for part in parts {
+        keccak.update(part);
+    }
+    let mut output = [0u8; 32];
+    keccak.finalize(&mut output);
+    output
+}
+
+/// Serde adapter encoding a list of hashes as hex strings.
+mod hex_nodes {
+    use hex::FromHex;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(nodes: &[[u8; 32]], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(nodes.iter().map(hex::encode))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<[u8; 32]>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|node| <[u8; 32]>::from_hex(node).map_err(D::Error::custom))
+            .collect()
+    }
+}
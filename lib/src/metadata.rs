@@ -0,0 +1,113 @@
+//! The format version and attestor of an attestation.
+//!
+//! The attestation containers are defined upstream and drop every field they do not declare, so
+//! the metadata is read from the attestation as parsed from its file, before it becomes a
+//! container: a top-level `version` number and `attestor_id` string, and the
+//! [TLS session](crate::tls) the attestation states. An attestation without a
+//! `version` predates versioning and is taken to be [`ATTESTATION_VERSION`]. Any version not in
+//! [`SUPPORTED_VERSIONS`] is rejected with [`VerifyError::UnsupportedVersion`] rather than parsed
+//! as if it were the current format.
+//!
+//! The host writes the metadata of the first attestation to the program as its own input, after
+//! the attestation. The program checks the version before it decodes the attestation and commits
+//! it and the TLS session. None of these fields is covered by the attestor's signature.
+//! Cross-checked, paginated and batched attestations are checked by the host as it loads them.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::tls::TlsSession;
+use crate::VerifyError;
+
+/// The attestation format version this verifier was written against.
+pub const ATTESTATION_VERSION: u16 = 1;
+
+/// Every attestation format version the verifier accepts.
+pub const SUPPORTED_VERSIONS: &[u16] = &[ATTESTATION_VERSION];
+
+/// The metadata an attestation states about itself.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttestationMetadata {
+    version: u16,
+    attestor_id: Option<String>,
+    tls_session: TlsSession,
+}
+
+impl Default for AttestationMetadata {
+    fn default() -> Self {
+        AttestationMetadata {
+            version: ATTESTATION_VERSION,
+            attestor_id: None,
+            tls_session: TlsSession::default(),
+        }
+    }
+}
+
+impl AttestationMetadata {
+    /// The metadata `attestation` states; see the [module documentation](self).
+    ///
+    /// `attestation` is the file's JSON or CBOR value. An upstream container has already dropped
+    /// both fields, and states the default.
+    ///
+    /// Fails with [`VerifyError::MalformedInput`] when `version` is not a number that fits a
+    /// `u16`, which no version of the format writes.
+    pub fn of<T: Serialize>(attestation: &T) -> Result<Self, VerifyError> {
+        let Ok(attestation) = serde_json::to_value(attestation) else {
+            return Ok(AttestationMetadata::default());
+        };
+
+        let version = match attestation.get("version") {
+            None => ATTESTATION_VERSION,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u16::try_from(version).ok())
+                .ok_or(VerifyError::MalformedInput)?,
+        };
+        let attestor_id = attestation
+            .get("attestor_id")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        Ok(AttestationMetadata {
+            version,
+            attestor_id,
+            tls_session: TlsSession::of(&attestation),
+        })
+    }
+
+    /// The metadata of an attestation in `version` of the format, stating no TLS session.
+    pub fn new(version: u16, attestor_id: Option<String>) -> Self {
+        AttestationMetadata {
+            version,
+            attestor_id,
+            tls_session: TlsSession::default(),
+        }
+    }
+
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// The attestor that produced the attestation, when it names itself.
+    pub fn attestor_id(&self) -> Option<&str> {
+        self.attestor_id.as_deref()
+    }
+
+    /// The TLS version and cipher suite the attestation states; see [`TlsSession::of`].
+    pub fn tls_session(&self) -> TlsSession {
+        self.tls_session
+    }
+
+    /// Fails with [`VerifyError::UnsupportedVersion`] unless the version is in
+    /// [`SUPPORTED_VERSIONS`].
+    pub fn check(&self) -> Result<(), VerifyError> {
+        if SUPPORTED_VERSIONS.contains(&self.version) {
+            Ok(())
+        } else {
+            Err(VerifyError::UnsupportedVersion {
+                got: self.version,
+                supported: SUPPORTED_VERSIONS.to_vec(),
+            })
+        }
+    }
+}
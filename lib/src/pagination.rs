@@ -0,0 +1,73 @@
+//! Proving a paginated query, answered by one attestation per page, as a single session.
+//!
+//! Each page's response names the cursor of the next page, and the next page's request must ask
+//! for exactly that cursor. The program verifies every page, checks this chain, and commits the
+//! request digest of the first page, the response digest of the last page and the page count.
+
+use serde::{Deserialize, Serialize};
+
+use crate::http::Direction;
+use crate::{json, Record, VerifyError};
+
+/// How consecutive pages link: the value at `next_cursor_path` in a page's response must equal
+/// the `cursor_param` query parameter of the following page's request.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Linkage {
+    pub next_cursor_path: String,
+    pub cursor_param: String,
+}
+
+/// The committed result of verifying a paginated session.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaginatedSession {
+    pub linkage: Linkage,
+    /// The number of pages, including the first.
+    pub pages: u32,
+    /// Whether every page after the first verified; the first is covered by `verified`.
+    pub pages_verified: bool,
+    pub first_request_digest: [u8; 32],
+    pub last_response_digest: [u8; 32],
+}
+
+impl Linkage {
+    /// Check that every page in `pages` requests the cursor the previous page returned.
+    ///
+    /// Fails with [`VerifyError::BrokenChain`] naming the first page whose request does not
+    /// follow from the page before it.
+    pub fn check(&self, pages: &[Vec<Record>]) -> Result<(), VerifyError> {
+        for (index, pair) in pages.windows(2).enumerate() {
+            let broken = VerifyError::BrokenChain {
+                page: index as u32 + 1,
+            };
+            let cursor = json::response_value(&pair[0], &self.next_cursor_path)
+                .map_err(|_| broken.clone())?;
+            let requested = query_param(&pair[1], &self.cursor_param).ok_or(broken.clone())?;
+            if requested != json::unquote(&cursor) {
+                return Err(broken);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The value of query parameter `name` in the request target of the first request in `records`.
+///
+/// The value is compared as sent, without percent-decoding.
+fn query_param(records: &[Record], name: &str) -> Option<String> {
+    let request = records
+        .iter()
+        .find(|record| Direction::of(&record.bytes) == Direction::Request)?;
+    let line_end = request
+        .bytes
+        .windows(2)
+        .position(|window| window == b"\r\n")?;
+    let line = std::str::from_utf8(&request.bytes[..line_end]).ok()?;
+
+    let target = line.split(' ').nth(1)?;
+    let (_, query) = target.split_once('?')?;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+}
@@ -0,0 +1,112 @@
+//! Pairing each HTTP request in a transcript with the response it received.
+//!
+//! Records are signed as one sequence, but a verifier usually cares about a response as the answer
+//! to a particular request. HTTP/1.x responses do not repeat the method or target they answer, so
+//! a response is bound to its request by position: each request is answered by the response that
+//! follows it, before the next request. A message may span several records, in which case the
+//! records after its first are joined onto it.
+
+use crate::http::{split_message, Direction};
+use crate::record::ByteRange;
+use crate::{Record, VerifyError};
+
+/// A request and the response that answers it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordPair {
+    /// The request, joined from every record it spans.
+    pub request: Record,
+    /// The response, joined from every record it spans.
+    pub response: Record,
+}
+
+/// Pair every request in `records` with the response that follows it.
+///
+/// A request with no response before the next request or the end of the transcript, a response
+/// with no request before it, or a leading record that is neither fails with
+/// [`VerifyError::OrphanRecord`] naming that record. A response that cannot answer its request,
+/// because its HTTP major version differs from the request's or it carries a body in answer to a
+/// `HEAD` request, fails with [`VerifyError::MismatchedResponse`].
+pub fn pairs(records: &[Record]) -> Result<Vec<RecordPair>, VerifyError> {
+    let mut messages = messages(records)?.into_iter();
+
+    let mut pairs = Vec::new();
+    while let Some((index, direction, request)) = messages.next() {
+        if direction != Direction::Request {
+            return Err(VerifyError::OrphanRecord { index });
+        }
+        let response = match messages.next() {
+            Some((record, Direction::Response, response)) => {
+                check_answers(&request, &response, record)?;
+                response
+            }
+            _ => return Err(VerifyError::OrphanRecord { index }),
+        };
+        pairs.push(RecordPair { request, response });
+    }
+    Ok(pairs)
+}
+
+/// Join the records of `records` into messages, each with the index of its first record and its
+/// direction.
+fn messages(records: &[Record]) -> Result<Vec<(u32, Direction, Record)>, VerifyError> {
+    let mut messages: Vec<(u32, Direction, Record)> = Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        match (Direction::of(&record.bytes), messages.last_mut()) {
+            (Direction::Unknown, Some((_, _, message))) => append(message, record),
+            (Direction::Unknown, None) => {
+                return Err(VerifyError::OrphanRecord {
+                    index: index as u32,
+                })
+            }
+            (direction, _) => messages.push((index as u32, direction, record.clone())),
+        }
+    }
+    Ok(messages)
+}
+
+/// Append `record` to `message`, shifting its redacted ranges past the bytes already there.
+fn append(message: &mut Record, record: &Record) {
+    let offset = message.bytes.len() as u32;
+    message.bytes.extend_from_slice(&record.bytes);
+    message
+        .redacted
+        .extend(record.redacted.iter().map(|range| ByteRange {
+            start: range.start + offset,
+            end: range.end + offset,
+        }));
+}
+
+/// Check that `response`, starting in record `record`, can answer `request`.
+fn check_answers(request: &Record, response: &Record, record: u32) -> Result<(), VerifyError> {
+    let request_line = start_line(&request.bytes);
+    let status_line = start_line(&response.bytes);
+
+    let method = request_line.split(|&byte| byte == b' ').next();
+    let request_version = request_line.rsplit(|&byte| byte == b' ').next();
+    let response_version = status_line.split(|&byte| byte == b' ').next();
+    let same_major = major_version(request_version) == major_version(response_version);
+
+    let head_with_body = method == Some(b"HEAD".as_slice())
+        && split_message(&response.bytes).is_some_and(|(_, body)| !body.is_empty());
+
+    if !same_major || head_with_body {
+        debug!(record, "response does not answer its request");
+        return Err(VerifyError::MismatchedResponse { record });
+    }
+    Ok(())
+}
+
+/// The first line of `message`, without its line ending.
+fn start_line(message: &[u8]) -> &[u8] {
+    let end = message
+        .windows(2)
+        .position(|window| window == b"\r\n")
+        .unwrap_or(message.len());
+    &message[..end]
+}
+
+/// The major version of an `HTTP/<major>.<minor>` protocol version, e.g. `1` for `HTTP/1.1`.
+fn major_version(version: Option<&[u8]>) -> Option<&[u8]> {
+    let version = version?.strip_prefix(b"HTTP/")?;
+    version.split(|&byte| byte == b'.').next()
+}
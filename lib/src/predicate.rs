@@ -0,0 +1,134 @@
+//! Numeric threshold predicates over values extracted from the response body.
+//!
+//! A predicate such as `$.balance >= 1000000` lets a proof state that an attested value satisfies
+//! a bound without revealing the value. Thresholds are integers with a decimal scale, so
+//! `>= 10.50` is stored as threshold `1050` with scale `2`, and values are compared exactly
+//! without going through floating point.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{json, Record, VerifyError};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparator {
+    GreaterOrEqual,
+    LessOrEqual,
+    Equal,
+}
+
+impl Comparator {
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Comparator::GreaterOrEqual => ordering != Ordering::Less,
+            Comparator::LessOrEqual => ordering != Ordering::Greater,
+            Comparator::Equal => ordering == Ordering::Equal,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparator::GreaterOrEqual => ">=",
+            Comparator::LessOrEqual => "<=",
+            Comparator::Equal => "==",
+        }
+    }
+}
+
+/// A comparison between the number at `path` and `threshold / 10^scale`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Predicate {
+    pub path: String,
+    pub comparator: Comparator,
+    pub threshold: i128,
+    pub scale: u32,
+}
+
+/// A predicate together with whether it held, as committed by the program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PredicateOutcome {
+    pub predicate: Predicate,
+    pub result: bool,
+}
+
+impl Predicate {
+    /// Evaluate the predicate against the first response in `records`.
+    ///
+    /// Fails if there is no response, the path is not found in its JSON body, or the value
+    /// there is not a plain decimal number (a JSON number or a string holding one).
+    pub fn evaluate(&self, records: &[Record]) -> Result<bool, VerifyError> {
+        let raw = json::response_value(records, &self.path)?;
+        let ordering = json::parse_decimal(&json::unquote(&raw))
+            .and_then(|value| json::compare_decimals(value, (self.threshold, self.scale)))
+            .ok_or_else(|| VerifyError::NotANumber {
+                path: self.path.clone(),
+            })?;
+
+        Ok(self.comparator.holds(ordering))
+    }
+}
+
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.threshold < 0 { "-" } else { "" };
+        let digits = self.threshold.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - scale);
+
+        write!(
+            f,
+            "{} {} {}{}",
+            self.path,
+            self.comparator.symbol(),
+            sign,
+            whole
+        )?;
+        if !fraction.is_empty() {
+            write!(f, ".{}", fraction)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Predicate {
+    type Err = String;
+
+    /// Parse `<path> <comparator> <threshold>`, e.g. `$.balance >= 1000000` or `$.rate <= 0.25`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let [path, comparator, threshold] = parts[..] else {
+            return Err(format!(
+                "expected `<path> <comparator> <threshold>`, got `{}`",
+                s
+            ));
+        };
+
+        if !json::is_valid_path(path) {
+            return Err(format!("invalid path `{}`", path));
+        }
+        let comparator = match comparator {
+            ">=" => Comparator::GreaterOrEqual,
+            "<=" => Comparator::LessOrEqual,
+            "==" => Comparator::Equal,
+            _ => {
+                return Err(format!(
+                    "unknown comparator `{}`, expected `>=`, `<=` or `==`",
+                    comparator
+                ))
+            }
+        };
+        let (threshold, scale) = json::parse_decimal(threshold)
+            .ok_or_else(|| format!("threshold `{}` is not a decimal number", threshold))?;
+
+        Ok(Predicate {
+            path: path.to_string(),
+            comparator,
+            threshold,
+            scale,
+        })
+    }
+}
@@ -0,0 +1,102 @@
+//! A bounded preview of the attested records.
+//!
+//! Explorers show what was attested, but committing every record puts kilobytes into the public
+//! values. In preview mode the program commits a [`RecordsPreview`] instead: at most
+//! [`MAX_PREVIEW_BYTES`] from the start of the records, concatenated, and the keccak256 of all of
+//! them, which anyone holding the full transcript can check the preview against.
+//!
+//! The preview is cut on a character boundary: a multi-byte UTF-8 sequence the limit would split
+//! is left out whole rather than committed as an invalid prefix.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::{HashAlgorithm, RecordHasher};
+use crate::record::Record;
+
+/// The most bytes a preview commits, however many the host asks for.
+pub const MAX_PREVIEW_BYTES: u32 = 256;
+
+/// The start of the concatenated records and the digest of all of them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordsPreview {
+    /// The first bytes of the records, concatenated, ending on a character boundary.
+    pub bytes: Vec<u8>,
+    /// The length of the records, concatenated.
+    pub total_bytes: u64,
+    /// The keccak256 of the records, each preceded by its length.
+    pub digest: [u8; 32],
+}
+
+impl RecordsPreview {
+    /// Whether the preview holds every byte of the records.
+    pub fn is_complete(&self) -> bool {
+        self.bytes.len() as u64 == self.total_bytes
+    }
+}
+
+/// Preview the first `max_bytes` of `records`, capped at [`MAX_PREVIEW_BYTES`].
+///
+/// The bytes are those of the records as given, so a redacted byte previews as zero.
+pub fn preview_records(records: &[Record], max_bytes: u32) -> RecordsPreview {
+    let limit = max_bytes.min(MAX_PREVIEW_BYTES) as usize;
+    let mut bytes: Vec<u8> = records
+        .iter()
+        .flat_map(|record| &record.bytes)
+        .take(limit)
+        .copied()
+        .collect();
+
+    if bytes.len() == limit {
+        // Only a character the limit cut short is dropped; invalid bytes elsewhere, as in a
+        // binary record, are previewed as they are.
+        let next: Vec<u8> = records
+            .iter()
+            .flat_map(|record| &record.bytes)
+            .skip(limit)
+            .take(3)
+            .copied()
+            .collect();
+        let end = char_boundary(&bytes, &next);
+        bytes.truncate(end);
+    }
+
+    RecordsPreview {
+        bytes,
+        total_bytes: records.iter().map(|record| record.bytes.len() as u64).sum(),
+        digest: RecordHasher::new(HashAlgorithm::Keccak256).hash_records(records),
+    }
+}
+
+/// Where to cut `prefix` so that a UTF-8 sequence continuing into `next` is left out whole.
+fn char_boundary(prefix: &[u8], next: &[u8]) -> usize {
+    // A sequence is at most four bytes, so one the cut splits starts in the last three.
+    let tail = prefix.len().saturating_sub(3);
+    let Some(start) = (tail..prefix.len())
+        .rev()
+        .find(|&i| !is_continuation(prefix[i]))
+    else {
+        return prefix.len();
+    };
+    let width = match prefix[start] {
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => return prefix.len(),
+    };
+    let cut = prefix.len() - start;
+    if cut >= width || next.len() < width - cut {
+        return prefix.len();
+    }
+
+    let mut sequence = prefix[start..].to_vec();
+    sequence.extend_from_slice(&next[..width - cut]);
+    if std::str::from_utf8(&sequence).is_ok() {
+        start
+    } else {
+        prefix.len()
+    }
+}
+
+fn is_continuation(byte: u8) -> bool {
+    byte & 0xc0 == 0x80
+}
@@ -0,0 +1,395 @@
+//! The values the program commits, in commit order.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::batch::BatchOutcome;
+use crate::canonical::BodyDigests;
+use crate::content_encoding::DecodedBody;
+use crate::context::AppContext;
+use crate::cosign::CoSigned;
+use crate::cross_check::CrossCheckOutcome;
+use crate::date::NO_RESPONSE_DATE;
+use crate::hash::{self, HashAlgorithm, RecordHasher};
+use crate::http::{response_body, Direction};
+use crate::key::VerifyingKey;
+use crate::pagination::PaginatedSession;
+use crate::predicate::PredicateOutcome;
+use crate::preview::RecordsPreview;
+use crate::record::{ExtractedRange, RangeCommitment};
+use crate::request_template::RequestTemplateOutcome;
+use crate::response_header::ResponseHeader;
+use crate::tls::TlsSession;
+use crate::{Record, VerifyError};
+
+/// Committed in place of a per-direction digest when some record's direction is unknown.
+pub const UNKNOWN_DIRECTION_DIGEST: [u8; 32] = [0; 32];
+
+/// Everything the program commits, decoded on the host with `SP1PublicValues::read`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicValues {
+    /// [`STATUS_OK`](crate::abort::STATUS_OK). An execution that aborted with status reporting
+    /// commits an [`ABORT_TAG`](crate::abort::ABORT_TAG) where this field would be, and is too
+    /// short to decode as the whole struct; see [`decode_abort`](crate::abort::decode_abort).
+    pub status: u32,
+    pub verifying_key: VerifyingKey,
+    pub format_tag: u8,
+    /// The [`canonical_digest`](crate::canonical::canonical_digest) of the attestation, so
+    /// auditors can check exactly which attestation was proven.
+    pub attestation_digest: [u8; 32],
+    /// Whether the attestation signature verified against `verifying_key`.
+    pub verified: bool,
+    /// The records after redaction, or none when a predicate was evaluated so the value it
+    /// checks stays private.
+    pub records: Vec<Record>,
+    /// The deployment the host made the proof for, committed as given; see
+    /// [`context`](crate::context).
+    pub context: Option<AppContext>,
+    /// The size in bytes of the largest record, which is at most the host's `max_record_bytes`.
+    pub largest_record_bytes: u32,
+    pub digests: RecordDigests,
+    /// The numeric predicate requested by the host and whether it held.
+    pub predicate: Option<PredicateOutcome>,
+    /// The comparison against a second attestation, if one was supplied.
+    pub cross_check: Option<CrossCheckOutcome>,
+    /// The [`bodies_digest`] of the records, when the host asked for bodies-only commitment.
+    pub bodies_digest: Option<[u8; 32]>,
+    /// The [`DigestMode`] tag the host selected.
+    pub digest_mode: u8,
+    /// The [`record_digests`] of the records in [`DigestMode::PerRecord`], otherwise empty.
+    pub record_digests: Vec<[u8; 32]>,
+    /// The [`eip712_records_hash`](crate::eip712::eip712_records_hash) of the records under the
+    /// domain the host supplied, if any.
+    pub eip712_records_hash: Option<[u8; 32]>,
+    /// The chain of pages this attestation starts, if the host supplied more pages.
+    pub paginated: Option<PaginatedSession>,
+    /// The sequence number of the first record, or
+    /// [`UNKNOWN_SEQUENCE`](crate::sequence::UNKNOWN_SEQUENCE) when the attestation has none.
+    pub first_sequence: u64,
+    /// The sequence number of the last record, or
+    /// [`UNKNOWN_SEQUENCE`](crate::sequence::UNKNOWN_SEQUENCE) when the attestation has none.
+    pub last_sequence: u64,
+    /// Which attestations of a batch verified, if the host supplied one.
+    pub batch: Option<BatchOutcome>,
+    /// The [`RecordsEncoding`](crate::record::RecordsEncoding) tag the host selected. The
+    /// records and every digest are over the decoded bytes.
+    pub records_encoding: u8,
+    /// The compressed responses the program decoded before checking and committing them.
+    pub decoded_bodies: Vec<DecodedBody>,
+    /// The length of the first response's body, checked against its `Content-Length` or chunked
+    /// framing, or `None` when it declares neither.
+    pub body_length: Option<u64>,
+    /// The response header the host asked for, if it asked for one.
+    pub response_header: Option<ResponseHeader>,
+    /// The raw and canonical JSON digests of the first response's body, when the host asked for
+    /// them.
+    pub body_digests: Option<BodyDigests>,
+    /// Whether the program checked that every committed record is valid UTF-8, which it does
+    /// when the host asks for the records as text and they are committed.
+    pub text_validated: bool,
+    /// The request template the host supplied and whether the request matched it. The request
+    /// records are then left out of `records`.
+    pub request_template: Option<RequestTemplateOutcome>,
+    /// The number of records proven, when the host proved a prefix of a longer session.
+    ///
+    /// The host cuts the prefix before writing the attestation, and the program only checks that
+    /// it received this many records and that the signatures over them verify. Nothing proves what
+    /// followed the prefix in the session, or that the session went on at all.
+    pub prefix_records: Option<u32>,
+    /// Whether the credentials in the request records were masked, which they are unless the
+    /// host opted out; see [`sanitize`](crate::sanitize::sanitize).
+    pub sanitized: bool,
+    /// The unix timestamp of the first response's `Date` header, or
+    /// [`NO_RESPONSE_DATE`](crate::date::NO_RESPONSE_DATE) when it has none that parses.
+    pub response_date: u64,
+    /// The TLS version and cipher suite the attestation states for the session.
+    pub tls_session: TlsSession,
+    /// The format version of the attestation; see [`metadata`](crate::metadata).
+    pub attestation_version: u16,
+    /// The [`RecordCommitment`] tag the host selected. Unless it is
+    /// [`RecordCommitment::Raw`], `records` is empty.
+    pub record_commitment: u8,
+    /// The [`RecordPayload`](crate::record::RecordPayload) tag the host selected. Binary records
+    /// are committed unparsed: no body is decoded, `body_length` is `None`, `response_date` is
+    /// [`NO_RESPONSE_DATE`], `sanitized` is false and both direction digests are
+    /// [`UNKNOWN_DIRECTION_DIGEST`].
+    pub record_payload: u8,
+    /// The total bytes of the records when the attestation was streamed, in which case `records`
+    /// is empty and only `digests.records_digest`, over the records as attested, is meaningful;
+    /// see [`stream`](crate::stream).
+    pub streamed_bytes: Option<u64>,
+    /// The digest of every byte range the host asked to commit, in the order it asked, over the
+    /// records as committed; see [`commit_ranges`](crate::record::commit_ranges).
+    pub ranges: Vec<RangeCommitment>,
+    /// The keys the attestation verified under when the host supplied co-signatures, and the
+    /// records they all signed; see [`cosign`](crate::cosign).
+    pub cosigned: Option<CoSigned>,
+    /// The bytes of every range the host asked to extract, in the order it asked, over the
+    /// records as committed; see [`extract_ranges`](crate::record::extract_ranges). When any is
+    /// extracted, `records` is empty and `digests.records_digest` names the transcript they come
+    /// from.
+    pub extracted: Vec<ExtractedRange>,
+    /// The start of the records as committed and the keccak256 of all of them, when the host
+    /// asked for a preview; see [`preview`](crate::preview). `records` is then empty.
+    pub preview: Option<RecordsPreview>,
+}
+
+impl PublicValues {
+    /// The unix timestamp of the first response's `Date` header, if it had one that parsed.
+    pub fn response_date(&self) -> Option<u64> {
+        (self.response_date != NO_RESPONSE_DATE).then_some(self.response_date)
+    }
+}
+
+/// Which record digests the program commits.
+///
+/// [`RecordDigests`] are always committed. A per-record list costs 32 bytes of public values per
+/// record, so it is only emitted when the host asks for it with a one-byte mode tag.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DigestMode {
+    /// One digest over all records.
+    #[default]
+    Concatenated,
+    /// Additionally one digest per record, in transcript order, so a contract can later check a
+    /// single record without the others.
+    PerRecord,
+}
+
+impl DigestMode {
+    /// The tag written to stdin and committed to the public values.
+    pub const fn tag(self) -> u8 {
+        match self {
+            DigestMode::Concatenated => 0,
+            DigestMode::PerRecord => 1,
+        }
+    }
+
+    /// The mode for `tag`, if it is known.
+    pub const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(DigestMode::Concatenated),
+            1 => Some(DigestMode::PerRecord),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DigestMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DigestMode::Concatenated => f.write_str("concatenated"),
+            DigestMode::PerRecord => f.write_str("per-record"),
+        }
+    }
+}
+
+impl FromStr for DigestMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "concatenated" => Ok(DigestMode::Concatenated),
+            "per-record" => Ok(DigestMode::PerRecord),
+            _ => Err(format!(
+                "unknown digest mode `{}`, expected `concatenated` or `per-record`",
+                s
+            )),
+        }
+    }
+}
+
+/// How the program commits the records themselves.
+///
+/// Selected by the host with a one-byte tag, the first input after the status flag, so one build
+/// of the program serves consumers that want the plaintext and consumers that only check digests.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RecordCommitment {
+    /// The records, after redaction, alongside their [`RecordDigests`].
+    #[default]
+    Raw,
+    /// Only the [`RecordDigests`], which include the digest over all records. With
+    /// [`DigestMode::PerRecord`], the digest of each record is committed too.
+    Digest,
+}
+
+impl RecordCommitment {
+    /// The tag written to stdin and committed to the public values.
+    pub const fn tag(self) -> u8 {
+        match self {
+            RecordCommitment::Raw => 0,
+            RecordCommitment::Digest => 1,
+        }
+    }
+
+    /// The commitment for `tag`, if it is known.
+    pub const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(RecordCommitment::Raw),
+            1 => Some(RecordCommitment::Digest),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RecordCommitment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordCommitment::Raw => f.write_str("raw"),
+            RecordCommitment::Digest => f.write_str("digest"),
+        }
+    }
+}
+
+impl FromStr for RecordCommitment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "raw" => Ok(RecordCommitment::Raw),
+            "digest" => Ok(RecordCommitment::Digest),
+            _ => Err(format!(
+                "unknown commit mode `{}`, expected `raw` or `digest`",
+                s
+            )),
+        }
+    }
+}
+
+/// Digests of the committed record bytes under the [`RecordHasher`] of the build, keccak256 by
+/// default.
+///
+/// Each digest is taken over the matching records in transcript order, after redaction, each
+/// record preceded by its length as a big-endian `u64`, so verifiers can recompute them from the
+/// committed records. When any record is neither a request nor a response, both direction digests
+/// are [`UNKNOWN_DIRECTION_DIGEST`] and only `records_digest` is meaningful.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordDigests {
+    pub request_digest: [u8; 32],
+    pub response_digest: [u8; 32],
+    pub records_digest: [u8; 32],
+}
+
+impl RecordDigests {
+    pub fn new(records: &[Record]) -> Self {
+        debug_span!("record_digests", records = records.len());
+        let directions: Vec<Direction> = records
+            .iter()
+            .map(|record| Direction::of(&record.bytes))
+            .collect();
+        let records_digest = digest(records.iter());
+
+        if directions.contains(&Direction::Unknown) {
+            return RecordDigests {
+                request_digest: UNKNOWN_DIRECTION_DIGEST,
+                response_digest: UNKNOWN_DIRECTION_DIGEST,
+                records_digest,
+            };
+        }
+
+        let with_direction = |wanted: Direction| {
+            digest(
+                records
+                    .iter()
+                    .zip(&directions)
+                    .filter(move |(_, direction)| **direction == wanted)
+                    .map(|(record, _)| record),
+            )
+        };
+
+        RecordDigests {
+            request_digest: with_direction(Direction::Request),
+            response_digest: with_direction(Direction::Response),
+            records_digest,
+        }
+    }
+
+    /// The digests of records that hold no HTTP messages, so neither has a direction.
+    ///
+    /// Only `records_digest` is meaningful, as for a transcript with an unknown record, but no
+    /// record is classified: opaque bytes that start like a status line are not a response.
+    pub fn opaque(records: &[Record]) -> Self {
+        RecordDigests {
+            request_digest: UNKNOWN_DIRECTION_DIGEST,
+            response_digest: UNKNOWN_DIRECTION_DIGEST,
+            records_digest: digest(records.iter()),
+        }
+    }
+
+    /// The digests of streamed records, which are hashed as they are read and never held, so
+    /// neither is classified.
+    pub fn streamed(records_digest: [u8; 32]) -> Self {
+        RecordDigests {
+            request_digest: UNKNOWN_DIRECTION_DIGEST,
+            response_digest: UNKNOWN_DIRECTION_DIGEST,
+            records_digest,
+        }
+    }
+}
+
+/// The [`RecordHasher`] digest of the bytes of `records`, each preceded by its length, under
+/// this build's [`HashAlgorithm::RECORDS`](crate::hash::HashAlgorithm::RECORDS).
+pub fn digest<'a>(records: impl Iterator<Item = &'a Record>) -> [u8; 32] {
+    RecordHasher::default().hash_parts(records.map(|record| record.bytes.as_slice()))
+}
+
+/// The `H` digest of the bytes of `records`, each preceded by its length.
+pub fn digest_with<'a, H: hash::Hasher>(records: impl Iterator<Item = &'a Record>) -> [u8; 32] {
+    let mut hasher = H::default();
+    for record in records {
+        hash::update_part(&mut hasher, &record.bytes);
+    }
+    hasher.finalize()
+}
+
+/// The [`RecordHasher`] digest of the bytes of each record, in transcript order.
+pub fn record_digests(records: &[Record]) -> Vec<[u8; 32]> {
+    debug_span!("per_record_digests", records = records.len());
+    records
+        .iter()
+        .map(|record| digest(std::iter::once(record)))
+        .collect()
+}
+
+/// Keccak-256 over the bodies of the response records in transcript order, each preceded by its
+/// length as the records are in the record digests.
+///
+/// Headers carry per-session noise such as dates and request ids, so this digest is reproducible
+/// across otherwise identical responses where the record digests are not. Chunked bodies are
+/// de-chunked before hashing. A response record without a complete head and body is rejected with
+/// [`VerifyError::MalformedResponse`].
+pub fn bodies_digest(records: &[Record]) -> Result<[u8; 32], VerifyError> {
+    debug_span!("bodies_digest", records = records.len());
+    let mut bodies = Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        if Direction::of(&record.bytes) != Direction::Response {
+            continue;
+        }
+        let body = response_body(&record.bytes).ok_or(VerifyError::MalformedResponse {
+            record: index as u32,
+        })?;
+        bodies.push(body);
+    }
+    Ok(RecordHasher::new(HashAlgorithm::Keccak256).hash_parts(bodies.iter().map(Vec::as_slice)))
+}
+
+/// SHA-256 over the public values exactly as the program commits them, so off-chain verifiers can
+/// reproduce SP1's public-values digest without running the zkVM.
+///
+/// `sp1_zkvm::io::commit` appends the bincode encoding of each committed value to the public
+/// values stream, and the program commits a single [`PublicValues`], so the stream is its bincode
+/// encoding.
+pub fn public_values_digest(public_values: &PublicValues) -> [u8; 32] {
+    debug_span!("public_values_digest");
+    let stream = bincode::serialize(public_values).expect("public values are serializable");
+    RecordHasher::new(HashAlgorithm::Sha256).hash_bytes(&stream)
+}
+
+/// [`public_values_digest`] with its top three bits cleared to fit the BN254 scalar field, as the
+/// SP1 Groth16 and PLONK verifier contracts check it.
+pub fn public_values_digest_bn254(public_values: &PublicValues) -> [u8; 32] {
+    let mut digest = public_values_digest(public_values);
+    digest[0] &= 0x1f;
+    digest
+}
@@ -0,0 +1,378 @@
+//! Attested records and redaction of byte ranges within them.
+//!
+//! Attestors sign the full plaintext of every record. To keep secrets such as auth tokens out of
+//! the public values, the host can ask the program to redact byte ranges: the program verifies the
+//! signature over the untouched records first, then overwrites each redacted range with zero bytes
+//! and commits the ranges alongside the records. A verifier therefore learns that the attestor
+//! signed a transcript matching the committed bytes everywhere outside the listed ranges, and
+//! nothing about the bytes inside them.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::h2;
+use crate::hash::RecordHasher;
+use crate::http::{header, split_message, status, HttpVersion, Mime};
+use crate::VerifyError;
+
+/// A half-open byte range `start..end` within a record.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ByteRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// A request to redact bytes `start..end` of the record at index `record`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Redaction {
+    pub record: u32,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// A request to commit a digest, or the bytes, of bytes `start..end` of the record at index
+/// `record`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeRequest {
+    pub record: u32,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// The digest of bytes `start..end` of the committed record at index `record`, so a contract can
+/// check one slice of a response without the rest of it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeCommitment {
+    pub record: u32,
+    pub start: u32,
+    pub end: u32,
+    pub digest: [u8; 32],
+}
+
+/// Bytes `start..end` of the committed record at index `record`, committed verbatim next to the
+/// records digest, so a verifier learns they appear at that offset of the attested transcript
+/// without seeing the rest of it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractedRange {
+    pub record: u32,
+    pub start: u32,
+    pub end: u32,
+    pub bytes: Vec<u8>,
+}
+
+/// The plaintext of one attested record.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    /// The record bytes, with every redacted range zeroed.
+    pub bytes: Vec<u8>,
+    /// The redacted ranges of `bytes`, sorted by start.
+    pub redacted: Vec<ByteRange>,
+}
+
+impl Record {
+    /// Decode a record from the hex encoding used by `get_records`.
+    pub fn from_hex(record: &str) -> Result<Self, hex::FromHexError> {
+        Ok(Record {
+            bytes: hex::decode(record)?,
+            redacted: Vec::new(),
+        })
+    }
+
+    /// The media type declared by the `Content-Type` header of the HTTP message in this record.
+    ///
+    /// Returns `None` when the record has no complete head, no such header, or one that does not
+    /// parse.
+    pub fn content_type(&self) -> Option<Mime> {
+        let (head, _) = split_message(&self.bytes)?;
+        Mime::parse(&header(head, "content-type")?)
+    }
+
+    /// Whether this record holds HTTP/1.x text or HTTP/2 frames.
+    pub fn http_version(&self) -> HttpVersion {
+        HttpVersion::of(&self.bytes)
+    }
+
+    /// The status code of the response in this record, or `None` when it holds no response.
+    ///
+    /// HTTP/2 records are decoded as [`h2::message`] decodes them, and fail as it fails.
+    pub fn http_status(&self) -> Result<Option<u16>, VerifyError> {
+        match self.http_version() {
+            HttpVersion::Http1 => Ok(status(self.head())),
+            HttpVersion::Http2 => {
+                Ok(h2::message(&self.bytes)?.and_then(|message| message.status()))
+            }
+        }
+    }
+
+    /// The value of the first header named `name` in this record, compared case-insensitively.
+    ///
+    /// HTTP/2 pseudo-headers such as `:authority` are found by their name. HTTP/2 records are
+    /// decoded as [`h2::message`] decodes them, and fail as it fails.
+    pub fn header(&self, name: &str) -> Result<Option<String>, VerifyError> {
+        match self.http_version() {
+            HttpVersion::Http1 => Ok(header(self.head(), name)),
+            HttpVersion::Http2 => Ok(h2::message(&self.bytes)?
+                .and_then(|message| message.field(name).map(str::to_string))),
+        }
+    }
+
+    /// The digest of bytes `start..end` of this record, with the [`RecordHasher`] the record
+    /// digests are computed with.
+    ///
+    /// # Panics
+    ///
+    /// If the range is not within the record; [`commit_ranges`] checks it first.
+    pub fn commit_range(&self, start: u32, end: u32) -> [u8; 32] {
+        RecordHasher::default().hash_parts([&self.bytes[start as usize..end as usize]])
+    }
+
+    /// The head of the HTTP/1.x message in this record, or all of it when it has no blank line.
+    fn head(&self) -> &[u8] {
+        split_message(&self.bytes).map_or(&self.bytes[..], |(head, _)| head)
+    }
+}
+
+/// How the attestation encodes the plaintext of its records.
+///
+/// Some attestors sign records whose plaintext is itself hex text. The signature covers that text,
+/// so the program verifies it as is and only then decodes it, committing digests over the
+/// underlying bytes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RecordsEncoding {
+    /// The records hold the plaintext bytes.
+    #[default]
+    Raw,
+    /// The records hold the hex encoding of the plaintext bytes.
+    Hex,
+}
+
+impl RecordsEncoding {
+    /// The tag written to stdin and committed to the public values.
+    pub const fn tag(self) -> u8 {
+        match self {
+            RecordsEncoding::Raw => 0,
+            RecordsEncoding::Hex => 1,
+        }
+    }
+
+    /// The encoding for `tag`, if it is known.
+    pub const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(RecordsEncoding::Raw),
+            1 => Some(RecordsEncoding::Hex),
+            _ => None,
+        }
+    }
+
+    /// Decode `records` in place from this encoding.
+    ///
+    /// A record that is not valid hex fails with [`VerifyError::InvalidRecordEncoding`], and
+    /// leaves the records before it decoded.
+    pub fn decode(self, records: &mut [Record]) -> Result<(), VerifyError> {
+        match self {
+            RecordsEncoding::Raw => Ok(()),
+            RecordsEncoding::Hex => decode_hex_records(records),
+        }
+    }
+}
+
+impl fmt::Display for RecordsEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordsEncoding::Raw => f.write_str("raw"),
+            RecordsEncoding::Hex => f.write_str("hex"),
+        }
+    }
+}
+
+impl FromStr for RecordsEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "raw" => Ok(RecordsEncoding::Raw),
+            "hex" => Ok(RecordsEncoding::Hex),
+            _ => Err(format!(
+                "unknown records encoding `{}`, expected `raw` or `hex`",
+                s
+            )),
+        }
+    }
+}
+
+/// What the plaintext of the records carries, once decoded from their [`RecordsEncoding`].
+///
+/// The program reads HTTP messages out of records by default: it decodes compressed bodies,
+/// checks body lengths, reads the response date and masks request credentials. Records of
+/// another protocol, such as WebSocket frames, are arbitrary bytes that may happen to look like
+/// HTTP, so for binary records the program skips all of that and only verifies, redacts and
+/// digests the bytes as they are.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RecordPayload {
+    /// The records hold HTTP messages.
+    #[default]
+    Http,
+    /// The records hold opaque bytes, which the program never parses.
+    Binary,
+}
+
+impl RecordPayload {
+    /// The tag written to stdin.
+    pub const fn tag(self) -> u8 {
+        match self {
+            RecordPayload::Http => 0,
+            RecordPayload::Binary => 1,
+        }
+    }
+
+    /// The payload for `tag`, if it is known.
+    pub const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(RecordPayload::Http),
+            1 => Some(RecordPayload::Binary),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RecordPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordPayload::Http => f.write_str("http"),
+            RecordPayload::Binary => f.write_str("binary"),
+        }
+    }
+}
+
+impl FromStr for RecordPayload {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "http" => Ok(RecordPayload::Http),
+            "binary" => Ok(RecordPayload::Binary),
+            _ => Err(format!(
+                "unknown record payload `{}`, expected `http` or `binary`",
+                s
+            )),
+        }
+    }
+}
+
+/// Replace the bytes of every record with the bytes its hex text encodes.
+pub fn decode_hex_records(records: &mut [Record]) -> Result<(), VerifyError> {
+    for (index, record) in records.iter_mut().enumerate() {
+        record.bytes =
+            hex::decode(&record.bytes).map_err(|_| VerifyError::InvalidRecordEncoding {
+                record: index as u32,
+            })?;
+    }
+    Ok(())
+}
+
+/// Check that every record is valid UTF-8, so that committed records render as exactly the text
+/// that was proven.
+///
+/// The first record that is not fails with [`VerifyError::InvalidUtf8`], naming the offset of its
+/// first invalid byte sequence.
+pub fn check_utf8(records: &[Record]) -> Result<(), VerifyError> {
+    for (index, record) in records.iter().enumerate() {
+        if let Err(err) = std::str::from_utf8(&record.bytes) {
+            return Err(VerifyError::InvalidUtf8 {
+                record: index as u32,
+                offset: err.valid_up_to() as u32,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Apply `redactions` to `records`.
+///
+/// Ranges must be non-empty, lie within their record, and not overlap any other range in the same
+/// record; otherwise [`VerifyError::RedactionOverlap`] is returned and `records` is left as is.
+pub fn redact(records: &mut [Record], redactions: &[Redaction]) -> Result<(), VerifyError> {
+    let mut sorted = redactions.to_vec();
+    sorted.sort_by_key(|redaction| (redaction.record, redaction.start));
+
+    let mut previous: Option<&Redaction> = None;
+    for redaction in &sorted {
+        let Redaction { record, start, end } = *redaction;
+        let in_bounds = records
+            .get(record as usize)
+            .is_some_and(|target| start < end && end as usize <= target.bytes.len());
+        let overlaps = previous.is_some_and(|prev| prev.record == record && prev.end > start);
+        if !in_bounds || overlaps {
+            return Err(VerifyError::RedactionOverlap { record, start, end });
+        }
+        previous = Some(redaction);
+    }
+
+    for Redaction { record, start, end } in sorted {
+        let record = &mut records[record as usize];
+        record.bytes[start as usize..end as usize].fill(0);
+        record.redacted.push(ByteRange { start, end });
+    }
+
+    Ok(())
+}
+
+/// Commit the digest of every range in `ranges`, in order.
+///
+/// Ranges may be empty or overlap, but must lie within their record; otherwise
+/// [`VerifyError::RangeOutOfBounds`] is returned for the first that does not. The digests are over
+/// the records as given, so a range over redacted bytes commits the digest of zeros.
+pub fn commit_ranges(
+    records: &[Record],
+    ranges: &[RangeRequest],
+) -> Result<Vec<RangeCommitment>, VerifyError> {
+    ranges
+        .iter()
+        .map(|&RangeRequest { record, start, end }| {
+            let target = range_record(records, record, start, end)?;
+            Ok(RangeCommitment {
+                record,
+                start,
+                end,
+                digest: target.commit_range(start, end),
+            })
+        })
+        .collect()
+}
+
+/// Copy the bytes of every range in `ranges`, in order.
+///
+/// Ranges are checked as [`commit_ranges`] checks them, and the bytes are those of the records as
+/// given, so a range over redacted bytes extracts zeros.
+pub fn extract_ranges(
+    records: &[Record],
+    ranges: &[RangeRequest],
+) -> Result<Vec<ExtractedRange>, VerifyError> {
+    ranges
+        .iter()
+        .map(|&RangeRequest { record, start, end }| {
+            let target = range_record(records, record, start, end)?;
+            Ok(ExtractedRange {
+                record,
+                start,
+                end,
+                bytes: target.bytes[start as usize..end as usize].to_vec(),
+            })
+        })
+        .collect()
+}
+
+/// The record at index `record`, if bytes `start..end` lie within it.
+fn range_record(
+    records: &[Record],
+    record: u32,
+    start: u32,
+    end: u32,
+) -> Result<&Record, VerifyError> {
+    records
+        .get(record as usize)
+        .filter(|target| start <= end && end as usize <= target.bytes.len())
+        .ok_or(VerifyError::RangeOutOfBounds { record, start, end })
+}
@@ -0,0 +1,202 @@
+//! Matching the attested request against a template, without disclosing it.
+//!
+//! A verifier often needs to know which endpoint a response came from, but the request carries
+//! credentials such as a bearer token. The host supplies a [`RequestTemplate`] naming the method,
+//! a path pattern, the query parameters and the headers the request must carry, and the headers
+//! it may carry without their values mattering. The program checks the first request against it
+//! before redaction, aborts naming the first component that does not match, and commits the
+//! template in place of the request records.
+//!
+//! Patterns match literally except for `*`, which in a path matches any run of characters within
+//! one segment, and in a query or header value matches any run of characters at all. Names and
+//! values are compared as sent, without percent-decoding; header names are compared
+//! case-insensitively.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::http::{split_message, Direction};
+use crate::{Record, VerifyError};
+
+/// The shape a request must have, as read from `--request-template`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestTemplate {
+    /// The method, e.g. `GET`, compared exactly.
+    pub method: String,
+    /// A pattern for the path, without the query, e.g. `/v2/accounts/*`.
+    pub path: String,
+    /// Every query parameter the request carries, by name, with a pattern for its value.
+    #[serde(default)]
+    pub query: BTreeMap<String, String>,
+    /// Headers the request must carry, by name, with a pattern for their value.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Headers the request may carry with any value, such as `Authorization`.
+    #[serde(default)]
+    pub ignore_headers: Vec<String>,
+}
+
+/// A template together with whether the request matched it, as committed by the program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestTemplateOutcome {
+    pub template: RequestTemplate,
+    pub matched: bool,
+}
+
+/// The part of a request that does not match its template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplateComponent {
+    /// The transcript holds no request.
+    Request,
+    /// The method differs.
+    Method,
+    /// The path does not match the pattern.
+    Path,
+    /// The query parameter `name` is missing, not in the template, or does not match.
+    Query { name: String },
+    /// The required header `name` is missing or does not match.
+    Header { name: String },
+    /// The request carries the header `name`, which the template neither requires nor ignores.
+    UnexpectedHeader { name: String },
+}
+
+impl fmt::Display for TemplateComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateComponent::Request => f.write_str("request"),
+            TemplateComponent::Method => f.write_str("method"),
+            TemplateComponent::Path => f.write_str("path"),
+            TemplateComponent::Query { name } => write!(f, "query parameter `{}`", name),
+            TemplateComponent::Header { name } => write!(f, "header `{}`", name),
+            TemplateComponent::UnexpectedHeader { name } => {
+                write!(f, "unexpected header `{}`", name)
+            }
+        }
+    }
+}
+
+impl RequestTemplate {
+    /// Check the first request in `records` against the template.
+    ///
+    /// Fails with [`VerifyError::RequestMismatch`] naming the first component that does not
+    /// match, checked in the order method, path, query, required headers, other headers.
+    pub fn check(&self, records: &[Record]) -> Result<(), VerifyError> {
+        let mismatch = |component| Err(VerifyError::RequestMismatch { component });
+
+        let Some(message) = request_message(records) else {
+            return mismatch(TemplateComponent::Request);
+        };
+        let head = split_message(&message).map_or(&message[..], |(head, _)| head);
+        let head = String::from_utf8_lossy(head);
+        let mut lines = head.split("\r\n");
+
+        let mut request_line = lines.next().unwrap_or_default().split(' ');
+        let method = request_line.next().unwrap_or_default();
+        let target = request_line.next().unwrap_or_default();
+        if method != self.method {
+            return mismatch(TemplateComponent::Method);
+        }
+
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        if !matches(&self.path, path, Some(b'/')) {
+            return mismatch(TemplateComponent::Path);
+        }
+
+        let params: Vec<(&str, &str)> = query
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(|param| param.split_once('=').unwrap_or((param, "")))
+            .collect();
+        for (name, pattern) in &self.query {
+            let found = params.iter().any(|(param, _)| param == name);
+            let all_match = params
+                .iter()
+                .filter(|(param, _)| param == name)
+                .all(|(_, value)| matches(pattern, value, None));
+            if !found || !all_match {
+                return mismatch(TemplateComponent::Query { name: name.clone() });
+            }
+        }
+        if let Some((name, _)) = params
+            .iter()
+            .find(|(name, _)| !self.query.contains_key(*name))
+        {
+            return mismatch(TemplateComponent::Query {
+                name: name.to_string(),
+            });
+        }
+
+        let headers: Vec<(&str, &str)> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim(), value.trim()))
+            .collect();
+        for (name, pattern) in &self.headers {
+            let value = headers
+                .iter()
+                .find(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map(|(_, value)| *value);
+            if !value.is_some_and(|value| matches(pattern, value, None)) {
+                return mismatch(TemplateComponent::Header { name: name.clone() });
+            }
+        }
+        let expected = |header: &str| {
+            self.headers
+                .keys()
+                .chain(&self.ignore_headers)
+                .any(|name| name.eq_ignore_ascii_case(header))
+        };
+        if let Some((name, _)) = headers.iter().find(|(name, _)| !expected(name)) {
+            return mismatch(TemplateComponent::UnexpectedHeader {
+                name: name.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Remove every request from `records`, including the records a request continues into, so only
+/// the responses are committed.
+pub fn remove_requests(records: &mut Vec<Record>) {
+    let mut in_request = false;
+    records.retain(|record| {
+        match Direction::of(&record.bytes) {
+            Direction::Request => in_request = true,
+            Direction::Response => in_request = false,
+            Direction::Unknown => {}
+        }
+        !in_request
+    });
+}
+
+/// The bytes of the first request in the transcript, joined with the records it continues into.
+fn request_message(records: &[Record]) -> Option<Vec<u8>> {
+    let start = records
+        .iter()
+        .position(|record| Direction::of(&record.bytes) == Direction::Request)?;
+
+    let mut message = records[start].bytes.clone();
+    for record in records[start + 1..]
+        .iter()
+        .take_while(|record| Direction::of(&record.bytes) == Direction::Unknown)
+    {
+        message.extend_from_slice(&record.bytes);
+    }
+    Some(message)
+}
+
+/// Whether `text` matches `pattern`, where `*` matches any run of characters other than `stop`.
+fn matches(pattern: &str, text: &str, stop: Option<u8>) -> bool {
+    fn glob(pattern: &[u8], text: &[u8], stop: Option<u8>) -> bool {
+        match pattern.split_first() {
+            None => text.is_empty(),
+            Some((b'*', rest)) => (0..=text.len())
+                .take_while(|&taken| taken == 0 || Some(text[taken - 1]) != stop)
+                .any(|taken| glob(rest, &text[taken..], stop)),
+            Some((byte, rest)) => text.first() == Some(byte) && glob(rest, &text[1..], stop),
+        }
+    }
+    glob(pattern.as_bytes(), text.as_bytes(), stop)
+}
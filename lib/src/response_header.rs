@@ -0,0 +1,60 @@
+//! A single response header committed to bind a proof to a record elsewhere.
+//!
+//! Committing an `ETag` or `X-Request-Id` lets a verifier match the proof against their own logs
+//! without the whole response being disclosed alongside it. The value is committed with the
+//! records digest, so it cannot be paired with a different transcript.
+
+use serde::{Deserialize, Serialize};
+
+use crate::http::{response_message, split_message};
+use crate::Record;
+
+/// A header of the first response, as committed by the program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseHeader {
+    /// The name the host asked for, as given.
+    pub name: String,
+    /// The value of the first occurrence, or empty when the response does not carry the header.
+    pub value: String,
+    /// How many times the response carries the header.
+    pub count: u32,
+    /// The digest of the records the header was found in.
+    pub records_digest: [u8; 32],
+}
+
+impl ResponseHeader {
+    /// Find the header `name`, compared case-insensitively, in the first response in `records`.
+    ///
+    /// A missing header, or a missing response, yields an empty value and a count of zero rather
+    /// than an error.
+    pub fn extract(records: &[Record], name: String, records_digest: [u8; 32]) -> Self {
+        let values = response_message(records)
+            .map(|message| {
+                let head = split_message(&message).map_or(&message[..], |(head, _)| head);
+                header_values(head, &name)
+            })
+            .unwrap_or_default();
+
+        ResponseHeader {
+            value: values.first().cloned().unwrap_or_default(),
+            count: values.len() as u32,
+            name,
+            records_digest,
+        }
+    }
+}
+
+/// The values of every header named `name` in the message `head`, in order.
+pub(crate) fn header_values(head: &[u8], name: &str) -> Vec<String> {
+    head.split(|&byte| byte == b'\n')
+        .skip(1)
+        .filter_map(|line| {
+            let line = String::from_utf8_lossy(line);
+            let (field, value) = line.split_once(':')?;
+            field
+                .trim()
+                .eq_ignore_ascii_case(name.trim())
+                .then(|| value.trim().to_string())
+        })
+        .collect()
+}
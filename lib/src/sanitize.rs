@@ -0,0 +1,98 @@
+//! Masking of credentials in the requests before they are committed.
+//!
+//! Requests routinely carry bearer tokens, session cookies and API keys, and committing records
+//! publishes them with the proof. Unless the host opts out, the program masks the value of every
+//! [`SENSITIVE_HEADERS`] header in the request records after verifying the signature, exactly as
+//! if the host had asked to redact it: the value is zeroed and its range added to the record's
+//! redacted ranges. Header names and everything else in the request stay as attested.
+
+use crate::http::Direction;
+use crate::record::ByteRange;
+use crate::Record;
+
+/// The request headers whose values are masked, compared case-insensitively.
+pub const SENSITIVE_HEADERS: [&str; 3] = ["authorization", "cookie", "x-api-key"];
+
+/// Mask the value of every [`SENSITIVE_HEADERS`] header in the head of each request record.
+///
+/// A header whose value already lies partly within a redacted range is merged with that range,
+/// so the redacted ranges stay sorted and disjoint. Only the record a request starts in is
+/// searched, so a header continued into a later record is not masked.
+pub fn sanitize(records: &mut [Record]) {
+    for record in records {
+        if Direction::of(&record.bytes) != Direction::Request {
+            continue;
+        }
+        for (start, end) in sensitive_values(&record.bytes) {
+            mask(record, start, end);
+        }
+    }
+}
+
+/// The byte ranges of the sensitive header values in the head of `request`.
+fn sensitive_values(request: &[u8]) -> Vec<(usize, usize)> {
+    let head_end = request
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .unwrap_or(request.len());
+
+    let mut values = Vec::new();
+    let mut line_start = 0;
+    for line in request[..head_end].split(|&byte| byte == b'\n') {
+        let offset = line_start;
+        line_start += line.len() + 1;
+        if offset == 0 {
+            // The request line.
+            continue;
+        }
+        let Some(colon) = line.iter().position(|&byte| byte == b':') else {
+            continue;
+        };
+        let name = String::from_utf8_lossy(&line[..colon]);
+        if !SENSITIVE_HEADERS
+            .iter()
+            .any(|sensitive| name.trim().eq_ignore_ascii_case(sensitive))
+        {
+            continue;
+        }
+
+        let value = &line[colon + 1..];
+        let leading = value
+            .iter()
+            .take_while(|byte| byte.is_ascii_whitespace())
+            .count();
+        let trailing = value[leading..]
+            .iter()
+            .rev()
+            .take_while(|byte| byte.is_ascii_whitespace())
+            .count();
+        let start = offset + colon + 1 + leading;
+        let end = offset + colon + 1 + value.len() - trailing;
+        if start < end {
+            values.push((start, end));
+        }
+    }
+    values
+}
+
+/// Zero bytes `start..end` of `record` and add them to its redacted ranges.
+fn mask(record: &mut Record, start: usize, end: usize) {
+    record.bytes[start..end].fill(0);
+
+    let mut range = ByteRange {
+        start: start as u32,
+        end: end as u32,
+    };
+    record.redacted.retain(|other| {
+        let overlaps = other.start <= range.end && range.start <= other.end;
+        if overlaps {
+            range.start = range.start.min(other.start);
+            range.end = range.end.max(other.end);
+        }
+        !overlaps
+    });
+    let index = record
+        .redacted
+        .partition_point(|other| other.start < range.start);
+    record.redacted.insert(index, range);
+}
@@ -0,0 +1,73 @@
+//! TLS record sequence numbers, recovered from the record nonces.
+//!
+//! Attestations carry no timestamps, but every encrypted record carries its AES-GCM nonce. In TLS
+//! 1.2 the last eight bytes of that nonce are the explicit nonce, which implementations set to the
+//! record sequence number, so a transcript whose nonces do not strictly increase has been
+//! reordered or spliced. TLS 1.3 instead XORs the sequence number into a fixed IV, so its nonces
+//! need not increase, and the program only checks the nonces of a session the attestation states
+//! to be TLS 1.2. Other sessions, and containers without per-record nonces such as Merkle
+//! transcripts, commit [`UNKNOWN_SEQUENCE`] in place of sequence numbers.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::tls::{TlsSession, TlsVersion};
+use crate::VerifyError;
+
+/// Committed as the first and last sequence number when the attestation has none.
+pub const UNKNOWN_SEQUENCE: u64 = u64::MAX;
+
+/// The sequence number of every record in `attestation`, in transcript order.
+///
+/// Returns `None` unless every record of every packet has a 12-byte hex `nonce`.
+pub fn sequence_numbers<T: Serialize>(attestation: &T) -> Option<Vec<u64>> {
+    let attestation = serde_json::to_value(attestation).ok()?;
+
+    let mut sequence = Vec::new();
+    for packet in attestation.get("packets")?.as_array()? {
+        for record in packet.get("records")?.as_array()? {
+            sequence.push(sequence_number(record)?);
+        }
+    }
+    (!sequence.is_empty()).then_some(sequence)
+}
+
+fn sequence_number(record: &Value) -> Option<u64> {
+    let nonce: [u8; 12] = hex::decode(record.get("nonce")?.as_str()?)
+        .ok()?
+        .try_into()
+        .ok()?;
+    Some(u64::from_be_bytes(nonce[4..].try_into().unwrap()))
+}
+
+/// Check that `sequence` strictly increases, returning its first and last numbers.
+///
+/// Without sequence numbers both are [`UNKNOWN_SEQUENCE`]. A record whose number does not exceed
+/// the previous one is rejected with [`VerifyError::OutOfOrder`].
+pub fn check_sequence(sequence: Option<&[u64]>) -> Result<(u64, u64), VerifyError> {
+    let Some(sequence) = sequence.filter(|sequence| !sequence.is_empty()) else {
+        return Ok((UNKNOWN_SEQUENCE, UNKNOWN_SEQUENCE));
+    };
+
+    if let Some(index) = sequence.windows(2).position(|pair| pair[1] <= pair[0]) {
+        return Err(VerifyError::OutOfOrder {
+            record: index as u32 + 1,
+        });
+    }
+    Ok((sequence[0], sequence[sequence.len() - 1]))
+}
+
+/// Check `sequence` as [`check_sequence`] does if `session` is TLS 1.2, whose explicit nonces are
+/// the sequence numbers.
+///
+/// Any other session, including one whose version the attestation does not state, has both
+/// numbers [`UNKNOWN_SEQUENCE`] without its nonces being checked.
+pub fn check_session_sequence(
+    session: &TlsSession,
+    sequence: Option<&[u64]>,
+) -> Result<(u64, u64), VerifyError> {
+    match session.version() {
+        Some(TlsVersion::Tls12) => check_sequence(sequence),
+        _ => Ok((UNKNOWN_SEQUENCE, UNKNOWN_SEQUENCE)),
+    }
+}
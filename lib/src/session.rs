@@ -0,0 +1,26 @@
+//! An attestation grouped with the key it is signed under and the metadata of the session that
+//! produced it.
+//!
+//! The program reads a verifying key and an attestation as separate stdin items. A
+//! [`ZkTlsSession`] keeps them together with an id and a creation time, so a host can store and
+//! hand around one value per proving job. It crosses stdin as a single bincode item: the host
+//! writes it with `SessionStdin::write_to_stdin` from `zktls-script`, and the program reads it with
+//! `sp1_zkvm::io::read::<ZkTlsSession>()`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+
+use crate::key::VerifyingKey;
+
+/// A verifying key, the attestation signed under it, and the session it belongs to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ZkTlsSession {
+    pub verifying_key: VerifyingKey,
+    pub verifying_data: VerifyingDataOpt,
+    /// Identifies the session across the logs and reports of its proving jobs.
+    pub session_id: Uuid,
+    /// When the host created the session, not when the attestation was signed.
+    pub created_at: DateTime<Utc>,
+}
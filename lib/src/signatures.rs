@@ -0,0 +1,62 @@
+//! The number of signatures an attestation carries.
+//!
+//! Attestors sign in aggregate, never per record: every packet of a `VerifyingDataOpt` carries one
+//! ECDSA signature over all of its records, its `ecdsa_signature` being the hex of the 64-byte
+//! `r || s`. A well-formed attestation so carries exactly one signature per packet, however many
+//! records each packet holds.
+//!
+//! Upstream, a packet whose signature is empty, cut short or repeated only shows up as a
+//! signature that does not verify, if at all. [`check_signature_count`] names the miscount
+//! instead, and the program rejects such an attestation before verifying it.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::VerifyError;
+
+/// The bytes of one `r || s` signature.
+pub const SIGNATURE_BYTES: usize = 64;
+
+/// The number of complete signatures across the packets of a serialized `VerifyingDataOpt`.
+///
+/// A packet's `ecdsa_signature` holds as many signatures as it has whole 64-byte runs, so a
+/// truncated signature counts as none and a duplicated one as two. A field that is absent or not
+/// hex holds none.
+pub fn signature_count<T: Serialize>(attestation: &T) -> usize {
+    packets(&serde_json::to_value(attestation).unwrap_or(Value::Null))
+        .iter()
+        .map(packet_signatures)
+        .sum()
+}
+
+/// Check that every packet of a serialized `VerifyingDataOpt` carries exactly one signature.
+///
+/// Fails with [`VerifyError::SignatureCountMismatch`] otherwise, counting the signatures and
+/// records of the whole attestation.
+pub fn check_signature_count<T: Serialize>(attestation: &T) -> Result<(), VerifyError> {
+    let attestation = serde_json::to_value(attestation).unwrap_or(Value::Null);
+    let packets = packets(&attestation);
+    if packets.iter().all(|packet| packet_signatures(packet) == 1) {
+        return Ok(());
+    }
+
+    Err(VerifyError::SignatureCountMismatch {
+        records: packets
+            .iter()
+            .filter_map(|packet| packet["records"].as_array())
+            .map(Vec::len)
+            .sum(),
+        signatures: packets.iter().map(packet_signatures).sum(),
+    })
+}
+
+fn packets(attestation: &Value) -> &[Value] {
+    attestation["packets"].as_array().map_or(&[], Vec::as_slice)
+}
+
+fn packet_signatures(packet: &Value) -> usize {
+    packet["ecdsa_signature"]
+        .as_str()
+        .and_then(|signature| hex::decode(signature).ok())
+        .map_or(0, |signature| signature.len() / SIGNATURE_BYTES)
+}
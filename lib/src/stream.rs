@@ -0,0 +1,315 @@
+//! Attestations verified as their ciphertext is read, for records too large to hold at once.
+//!
+//! A `VerifyingDataOpt` carries the ciphertext of every record, its plaintext and, per packet, an
+//! AES-128 key and a signature over SHA-256 of every record's nonce followed by its ciphertext.
+//! Holding all of that in the program's memory at once does not scale to multi-megabyte
+//! downloads. Streamed, the attestation is split into a [`StreamHeader`], holding everything but
+//! the ciphertext, and the ciphertext of every record concatenated in transcript order, written
+//! in chunks of at most [`StreamHeader::chunk_bytes`].
+//!
+//! A [`StreamVerifier`] absorbs the chunks one at a time: it feeds the nonces and ciphertext to
+//! the running hash of each packet's signed message, decrypts the ciphertext with the disclosed
+//! bytes of the AES-128 counter-mode keystream blocks the header lists, and feeds the plaintext
+//! to the [`RecordsHasher`], each record after its length as the record digests take it.
+//! Nothing but the chunk being absorbed is held, and the signatures are only checked once the last
+//! chunk is in, against the finished hashes.
+
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::Aes128;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::hash::{self, Hasher, RecordsHasher};
+use crate::key::AsVerifyingKey;
+use crate::VerifyError;
+
+/// The chunk size hosts stream attestations in: small enough to leave the program room for
+/// everything else, large enough that reading a chunk costs little beside hashing it.
+pub const STREAM_CHUNK_BYTES: u32 = 64 * 1024;
+
+/// Everything in a streamed attestation but the ciphertext of its records.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamHeader {
+    /// The most bytes of ciphertext a single chunk may hold.
+    pub chunk_bytes: u32,
+    pub packets: Vec<StreamPacket>,
+}
+
+/// A signed packet of records.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamPacket {
+    #[serde(with = "hex::serde")]
+    pub aes_key: [u8; 16],
+    /// The 64-byte `r || s` signature over the packet's nonces and ciphertext.
+    #[serde(with = "hex::serde")]
+    pub signature: Vec<u8>,
+    pub records: Vec<StreamRecord>,
+}
+
+/// A record whose ciphertext is streamed rather than held.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamRecord {
+    #[serde(with = "hex::serde")]
+    pub nonce: [u8; 12],
+    /// The keystream blocks the disclosed bytes were encrypted with, in ciphertext order.
+    pub blocks: Vec<BlockRun>,
+}
+
+/// `count` consecutive keystream blocks from `first_id` on that disclose the same bytes.
+///
+/// An attestation lists every block with a 16-byte mask; a record disclosed whole is one run of
+/// full blocks and a shorter last one, so runs keep the header small however long the record.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockRun {
+    pub first_id: u32,
+    pub count: u32,
+    /// Bit `i` is set when byte `i` of each block is disclosed.
+    pub mask: u16,
+}
+
+impl StreamHeader {
+    /// The number of records in every packet.
+    pub fn record_count(&self) -> usize {
+        self.records().count()
+    }
+
+    /// The bytes all records hold together.
+    pub fn total_bytes(&self) -> u64 {
+        self.records().map(StreamRecord::len).sum()
+    }
+
+    /// The size in bytes of the largest record.
+    pub fn largest_record_bytes(&self) -> u64 {
+        self.records().map(StreamRecord::len).max().unwrap_or(0)
+    }
+
+    fn records(&self) -> impl Iterator<Item = &StreamRecord> {
+        self.packets.iter().flat_map(|packet| &packet.records)
+    }
+}
+
+impl BlockRun {
+    /// The number of bytes the run discloses.
+    pub fn disclosed_bytes(&self) -> u64 {
+        u64::from(self.count) * u64::from(self.mask.count_ones())
+    }
+}
+
+impl StreamRecord {
+    /// The length of the ciphertext, which is that of the plaintext: one byte per disclosed
+    /// keystream byte.
+    pub fn len(&self) -> u64 {
+        self.blocks.iter().map(BlockRun::disclosed_bytes).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Absorbs the chunks of a streamed attestation, in order.
+pub struct StreamVerifier<'a> {
+    header: &'a StreamHeader,
+    /// The packet and record the next byte of ciphertext belongs to.
+    packet: usize,
+    record: usize,
+    /// The bytes of the current record still to come.
+    remaining: u64,
+    /// The position of the next disclosed byte in the current record's keystream.
+    position: KeystreamPosition,
+    /// The signed message of the current packet, hashed so far.
+    message: Sha256,
+    /// The finished message digest of every packet before the current one.
+    messages: Vec<[u8; 32]>,
+    plaintext: RecordsHasher,
+    total_bytes: u64,
+}
+
+/// A byte of a record's keystream: byte `byte` of block `block` of run `run`, with the keystream
+/// of the block once it has been computed.
+#[derive(Default)]
+struct KeystreamPosition {
+    run: usize,
+    block: u32,
+    byte: u32,
+    keystream: Option<[u8; 16]>,
+}
+
+impl<'a> StreamVerifier<'a> {
+    pub fn new(header: &'a StreamHeader) -> Self {
+        let mut verifier = StreamVerifier {
+            header,
+            packet: 0,
+            record: 0,
+            remaining: 0,
+            position: KeystreamPosition::default(),
+            message: Sha256::new(),
+            messages: Vec::with_capacity(header.packets.len()),
+            plaintext: RecordsHasher::default(),
+            total_bytes: 0,
+        };
+        verifier.enter();
+        verifier
+    }
+
+    /// Whether every record has been absorbed.
+    pub fn is_complete(&self) -> bool {
+        self.packet == self.header.packets.len()
+    }
+
+    /// Absorb the next `chunk` of ciphertext, which may start or end anywhere within a record.
+    ///
+    /// Fails with [`VerifyError::MalformedInput`] when the chunk is empty, larger than the
+    /// header's chunk size, or runs past the last record.
+    pub fn update(&mut self, chunk: &[u8]) -> Result<(), VerifyError> {
+        if chunk.is_empty() || chunk.len() > self.header.chunk_bytes as usize {
+            return Err(VerifyError::MalformedInput);
+        }
+
+        let header = self.header;
+        let mut chunk = chunk;
+        while !chunk.is_empty() {
+            let packet = header
+                .packets
+                .get(self.packet)
+                .ok_or(VerifyError::MalformedInput)?;
+            let record = &packet.records[self.record];
+            let take = (chunk.len() as u64).min(self.remaining) as usize;
+            let (ciphertext, rest) = chunk.split_at(take);
+
+            self.message.update(ciphertext);
+            let cipher = Aes128::new(&packet.aes_key.into());
+            let plaintext: Vec<u8> = ciphertext
+                .iter()
+                .map(|byte| byte ^ self.position.next(&cipher, record))
+                .collect();
+            self.plaintext.update(&plaintext);
+            self.remaining -= take as u64;
+            self.total_bytes += take as u64;
+            if self.remaining == 0 {
+                self.record += 1;
+                self.enter();
+            }
+            chunk = rest;
+        }
+        Ok(())
+    }
+
+    /// The digests of the absorbed records, to check the signatures against.
+    ///
+    /// Fails with [`VerifyError::MalformedInput`] when records are still missing bytes.
+    pub fn finish(self) -> Result<StreamedRecords, VerifyError> {
+        if !self.is_complete() {
+            return Err(VerifyError::MalformedInput);
+        }
+        Ok(StreamedRecords {
+            signed: self
+                .messages
+                .into_iter()
+                .zip(&self.header.packets)
+                .map(|(message, packet)| (message, packet.signature.clone()))
+                .collect(),
+            total_bytes: self.total_bytes,
+            records_digest: self.plaintext.finalize(),
+        })
+    }
+
+    /// Move to the next record with ciphertext still to come, hashing the nonce of every record
+    /// reached and finishing the message of every packet left on the way.
+    fn enter(&mut self) {
+        self.position = KeystreamPosition::default();
+        while let Some(packet) = self.header.packets.get(self.packet) {
+            match packet.records.get(self.record) {
+                Some(record) => {
+                    self.message.update(record.nonce);
+                    self.remaining = record.len();
+                    hash::update_length(&mut self.plaintext, self.remaining);
+                    if self.remaining > 0 {
+                        return;
+                    }
+                    self.record += 1;
+                }
+                None => {
+                    let message = std::mem::take(&mut self.message);
+                    self.messages.push(message.finalize().into());
+                    self.packet += 1;
+                    self.record = 0;
+                }
+            }
+        }
+    }
+}
+
+impl KeystreamPosition {
+    /// The keystream byte of the next disclosed byte of `record`, which has one.
+    ///
+    /// The counter of block `id` is `id + 2`, after the nonce, as in AES-GCM.
+    fn next(&mut self, cipher: &Aes128, record: &StreamRecord) -> u8 {
+        loop {
+            let run = record.blocks[self.run];
+            if self.block == run.count {
+                self.run += 1;
+                self.block = 0;
+            } else if self.byte == 16 {
+                self.block += 1;
+                self.byte = 0;
+                self.keystream = None;
+            } else if run.mask & (1 << self.byte) == 0 {
+                self.byte += 1;
+            } else {
+                let keystream = self.keystream.get_or_insert_with(|| {
+                    let id = run.first_id.wrapping_add(self.block);
+                    let mut block = [0u8; 16];
+                    block[..12].copy_from_slice(&record.nonce);
+                    block[12..].copy_from_slice(&id.wrapping_add(2).to_be_bytes());
+                    let mut block = block.into();
+                    cipher.encrypt_block(&mut block);
+                    block.into()
+                });
+                let byte = keystream[self.byte as usize];
+                self.byte += 1;
+                return byte;
+            }
+        }
+    }
+}
+
+/// The records of a streamed attestation, reduced to what is checked and committed of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StreamedRecords {
+    /// The signed message digest and the signature of every packet.
+    signed: Vec<([u8; 32], Vec<u8>)>,
+    total_bytes: u64,
+    records_digest: [u8; 32],
+}
+
+impl StreamedRecords {
+    /// The bytes all records hold together.
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    /// The [`RecordsHasher`] digest of the plaintext of the records, each preceded by its length,
+    /// as it would be committed had they been held.
+    pub fn records_digest(&self) -> [u8; 32] {
+        self.records_digest
+    }
+
+    /// Check the signature of every packet against `verifying_key`.
+    ///
+    /// The key is either a parsed [`VerifyingKey`](crate::key::VerifyingKey) or text, PEM or hex,
+    /// that fails to parse with [`VerifyError::KeyParseError`].
+    pub fn verify<K: AsVerifyingKey + ?Sized>(&self, verifying_key: &K) -> Result<(), VerifyError> {
+        debug_span!("verify", format = "stream", packets = self.signed.len());
+
+        let verifying_key = verifying_key.verifying_key()?;
+        for (message, signature) in &self.signed {
+            verifying_key
+                .verify_digest(message, signature)
+                .inspect_err(|_| {
+                    debug!("packet signature does not verify");
+                })?;
+        }
+        Ok(())
+    }
+}
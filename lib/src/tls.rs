@@ -0,0 +1,195 @@
+//! The TLS version and cipher suite of the attested session, as far as the attestation states
+//! them.
+//!
+//! An attestation may carry `tls_version` (e.g. `"1.2"`, `"TLS1.3"`) and `cipher_suite` (an IANA
+//! name such as `"TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256"`) at its top level or in every packet.
+//! Without a `cipher_suite`, the AEAD is recovered from the length of the packets' AES key.
+//! Anything the attestation does not state commits an `Unknown` tag, as do Merkle transcripts,
+//! which carry neither field nor key.
+//!
+//! The upstream containers drop both fields, so the session is read from the attestation file with
+//! its [metadata](crate::metadata), which the host passes to the program to commit.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The TLS protocol version of the session.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TlsVersion {
+    #[default]
+    Unknown,
+    Tls12,
+    Tls13,
+}
+
+impl TlsVersion {
+    /// The tag committed to the public values.
+    pub const fn tag(self) -> u8 {
+        match self {
+            TlsVersion::Unknown => 0,
+            TlsVersion::Tls12 => 1,
+            TlsVersion::Tls13 => 2,
+        }
+    }
+
+    /// The version for `tag`, if it is known.
+    pub const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(TlsVersion::Unknown),
+            1 => Some(TlsVersion::Tls12),
+            2 => Some(TlsVersion::Tls13),
+            _ => None,
+        }
+    }
+
+    fn parse(version: &str) -> Self {
+        let version = version.trim_start_matches("TLSv").trim_start_matches("TLS");
+        match version.trim() {
+            "1.2" => TlsVersion::Tls12,
+            "1.3" => TlsVersion::Tls13,
+            _ => TlsVersion::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for TlsVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TlsVersion::Unknown => f.write_str("unknown"),
+            TlsVersion::Tls12 => f.write_str("TLS 1.2"),
+            TlsVersion::Tls13 => f.write_str("TLS 1.3"),
+        }
+    }
+}
+
+/// The record protection of the session's cipher suite.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CipherSuite {
+    #[default]
+    Unknown,
+    Aes128Gcm,
+    Aes256Gcm,
+    ChaCha20Poly1305,
+}
+
+impl CipherSuite {
+    /// The tag committed to the public values.
+    pub const fn tag(self) -> u8 {
+        match self {
+            CipherSuite::Unknown => 0,
+            CipherSuite::Aes128Gcm => 1,
+            CipherSuite::Aes256Gcm => 2,
+            CipherSuite::ChaCha20Poly1305 => 3,
+        }
+    }
+
+    /// The cipher suite for `tag`, if it is known.
+    pub const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(CipherSuite::Unknown),
+            1 => Some(CipherSuite::Aes128Gcm),
+            2 => Some(CipherSuite::Aes256Gcm),
+            3 => Some(CipherSuite::ChaCha20Poly1305),
+            _ => None,
+        }
+    }
+
+    fn parse(name: &str) -> Self {
+        if name.contains("AES_128_GCM") {
+            CipherSuite::Aes128Gcm
+        } else if name.contains("AES_256_GCM") {
+            CipherSuite::Aes256Gcm
+        } else if name.contains("CHACHA20_POLY1305") {
+            CipherSuite::ChaCha20Poly1305
+        } else {
+            CipherSuite::Unknown
+        }
+    }
+
+    fn from_aes_key(key: &str) -> Self {
+        match hex::decode(key).map(|key| key.len()) {
+            Ok(16) => CipherSuite::Aes128Gcm,
+            Ok(32) => CipherSuite::Aes256Gcm,
+            _ => CipherSuite::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for CipherSuite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CipherSuite::Unknown => f.write_str("unknown"),
+            CipherSuite::Aes128Gcm => f.write_str("AES-128-GCM"),
+            CipherSuite::Aes256Gcm => f.write_str("AES-256-GCM"),
+            CipherSuite::ChaCha20Poly1305 => f.write_str("ChaCha20-Poly1305"),
+        }
+    }
+}
+
+/// The TLS session metadata committed by the program.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsSession {
+    /// A [`TlsVersion`] tag.
+    pub version: u8,
+    /// A [`CipherSuite`] tag.
+    pub cipher_suite: u8,
+}
+
+impl TlsSession {
+    /// The metadata `attestation` states; see the [module documentation](self).
+    ///
+    /// `attestation` is the file's JSON or CBOR value; an upstream container only states its AES
+    /// key. Packets that disagree on a field leave it unknown.
+    pub fn of<T: Serialize>(attestation: &T) -> Self {
+        let Ok(attestation) = serde_json::to_value(attestation) else {
+            return TlsSession::default();
+        };
+
+        let version = stated(&attestation, "tls_version")
+            .flatten()
+            .map(TlsVersion::parse)
+            .unwrap_or_default();
+        let cipher_suite = match stated(&attestation, "cipher_suite") {
+            Some(name) => name.map(CipherSuite::parse),
+            None => stated(&attestation, "aes_key")
+                .flatten()
+                .map(CipherSuite::from_aes_key),
+        }
+        .unwrap_or_default();
+
+        TlsSession {
+            version: version.tag(),
+            cipher_suite: cipher_suite.tag(),
+        }
+    }
+
+    pub fn version(&self) -> Option<TlsVersion> {
+        TlsVersion::from_tag(self.version)
+    }
+
+    pub fn cipher_suite(&self) -> Option<CipherSuite> {
+        CipherSuite::from_tag(self.cipher_suite)
+    }
+}
+
+/// The string `field` of `attestation`, or of its packets.
+///
+/// `None` when neither states it, and `Some(None)` when it is not a string or the packets that
+/// state it disagree.
+fn stated<'a>(attestation: &'a Value, field: &str) -> Option<Option<&'a str>> {
+    if let Some(value) = attestation.get(field) {
+        return Some(value.as_str());
+    }
+
+    let values: Vec<Option<&str>> = attestation
+        .get("packets")?
+        .as_array()?
+        .iter()
+        .filter_map(|packet| packet.get(field))
+        .map(Value::as_str)
+        .collect();
+    let first = *values.first()?;
+    Some(first.filter(|_| values.iter().all(|value| *value == first)))
+}
@@ -0,0 +1,21 @@
+//! Tracing hooks for host-side debugging.
+//!
+//! With the `tracing` feature the macros below forward to the `tracing` crate, so spans and events
+//! surface through whatever subscriber the host installed. Without it they expand to nothing, which
+//! keeps them out of the zkVM program entirely.
+
+/// Enter a debug-level span until the end of the enclosing block.
+macro_rules! debug_span {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($($arg)*).entered();
+    };
+}
+
+/// Emit a debug-level event.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
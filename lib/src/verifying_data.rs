@@ -0,0 +1,293 @@
+//! The fields of a `VerifyingDataOpt` that verification needs, checked present before verifying.
+//!
+//! Upstream, a packet or record that lacks a field, or states it empty, only shows up as a
+//! signature that does not verify. [`VerifyingData`] holds exactly what `verify` reads, so
+//! converting into it names the first field that is missing. The attestation is read from its
+//! serialized form, the way the [TLS session](crate::tls) is, so the program can convert without
+//! the upstream crate; with the `upstream` feature, `VerifyingDataOpt` converts with `TryFrom`
+//! and back with `From`.
+//!
+//! [`VerifyingData::verify`] checks the scheme upstream checks: each packet is signed with ECDSA
+//! over secp256k1 and SHA-256 of every record's nonce followed by its ciphertext, and each
+//! record's plaintext is its ciphertext decrypted with the keystream bytes its blocks disclose;
+//! see [`stream`](crate::stream).
+
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::ct::CtDigest;
+use crate::hash::RecordHasher;
+use crate::key::AsVerifyingKey;
+use crate::stream::{BlockRun, StreamHeader, StreamPacket, StreamRecord, StreamVerifier};
+use crate::VerifyError;
+
+/// An attestation with every field `verify` needs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyingData {
+    pub packets: Vec<Packet>,
+}
+
+/// A signed packet of records.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Packet {
+    pub aes_key: [u8; 16],
+    /// The 64-byte `r || s` signature over the packet's nonces and ciphertext.
+    pub signature: [u8; 64],
+    pub records: Vec<PacketRecord>,
+}
+
+/// A record with its ciphertext, the keystream blocks it was encrypted with, and its plaintext.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PacketRecord {
+    pub nonce: [u8; 12],
+    pub ciphertext: Vec<u8>,
+    pub blocks: Vec<BlockRun>,
+    pub plaintext: Vec<u8>,
+}
+
+/// The first field of an attestation that is absent, empty, or not in the form `verify` reads.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingField {
+    pub packet: usize,
+    /// The record within the packet, when the field is a record's.
+    pub record: Option<usize>,
+    pub field: &'static str,
+}
+
+impl fmt::Display for MissingField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.record {
+            Some(record) => write!(
+                f,
+                "record {} of packet {} has no valid `{}`",
+                record, self.packet, self.field
+            ),
+            None => write!(f, "packet {} has no valid `{}`", self.packet, self.field),
+        }
+    }
+}
+
+impl std::error::Error for MissingField {}
+
+impl VerifyingData {
+    /// The fields of a serialized `VerifyingDataOpt`, or the first one it lacks.
+    ///
+    /// Hex fields must decode to the length `verify` reads, every record's ciphertext must be as
+    /// long as its blocks disclose, and every packet must state one plaintext per record, as long
+    /// as its ciphertext.
+    pub fn of<T: Serialize>(attestation: &T) -> Result<Self, MissingField> {
+        let missing = |packet, record, field| MissingField {
+            packet,
+            record,
+            field,
+        };
+        let attestation = serde_json::to_value(attestation).unwrap_or(Value::Null);
+        let packets = attestation["packets"]
+            .as_array()
+            .filter(|packets| !packets.is_empty())
+            .ok_or(missing(0, None, "packets"))?;
+
+        let mut verifying_data = VerifyingData {
+            packets: Vec::with_capacity(packets.len()),
+        };
+        for (index, packet) in packets.iter().enumerate() {
+            let missing = |field| missing(index, None, field);
+            let aes_key = hex_array(&packet["aes_key"]).ok_or(missing("aes_key"))?;
+            let signature =
+                hex_array(&packet["ecdsa_signature"]).ok_or(missing("ecdsa_signature"))?;
+            let records = packet["records"].as_array().ok_or(missing("records"))?;
+            let messages = packet["record_messages"]
+                .as_array()
+                .filter(|messages| messages.len() == records.len())
+                .ok_or(missing("record_messages"))?;
+
+            let records = records
+                .iter()
+                .zip(messages)
+                .enumerate()
+                .map(|(record_index, (record, message))| {
+                    let missing = |field| MissingField {
+                        packet: index,
+                        record: Some(record_index),
+                        field,
+                    };
+                    let record = PacketRecord {
+                        nonce: hex_array(&record["nonce"]).ok_or(missing("nonce"))?,
+                        ciphertext: hex_vec(&record["ciphertext"]).ok_or(missing("ciphertext"))?,
+                        blocks: block_runs(&record["blocks"]).ok_or(missing("blocks"))?,
+                        plaintext: hex_vec(message).ok_or(missing("record_messages"))?,
+                    };
+                    let disclosed: u64 = record.blocks.iter().map(BlockRun::disclosed_bytes).sum();
+                    if disclosed != record.ciphertext.len() as u64 {
+                        return Err(missing("blocks"));
+                    }
+                    if record.plaintext.len() != record.ciphertext.len() {
+                        return Err(missing("record_messages"));
+                    }
+                    Ok(record)
+                })
+                .collect::<Result<_, _>>()?;
+            verifying_data.packets.push(Packet {
+                aes_key,
+                signature,
+                records,
+            });
+        }
+        Ok(verifying_data)
+    }
+
+    /// Every record of every packet, in transcript order.
+    pub fn records(&self) -> impl Iterator<Item = &PacketRecord> {
+        self.packets.iter().flat_map(|packet| &packet.records)
+    }
+
+    /// The attestation without its ciphertext and plaintext, to stream the ciphertext after it in
+    /// chunks of at most `chunk_bytes`.
+    pub fn stream_header(&self, chunk_bytes: u32) -> StreamHeader {
+        StreamHeader {
+            chunk_bytes,
+            packets: self
+                .packets
+                .iter()
+                .map(|packet| StreamPacket {
+                    aes_key: packet.aes_key,
+                    signature: packet.signature.to_vec(),
+                    records: packet
+                        .records
+                        .iter()
+                        .map(|record| StreamRecord {
+                            nonce: record.nonce,
+                            blocks: record.blocks.clone(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Check every packet signature against `verifying_key`, and every plaintext against the
+    /// ciphertext it decrypts from.
+    ///
+    /// Fails with [`VerifyError::BadSignature`] when either does not hold, and with
+    /// [`VerifyError::KeyParseError`] when the key is text that does not parse.
+    pub fn verify<K: AsVerifyingKey + ?Sized>(&self, verifying_key: &K) -> Result<(), VerifyError> {
+        let header = self.stream_header(u32::MAX);
+        let mut verifier = StreamVerifier::new(&header);
+        for record in self
+            .records()
+            .filter(|record| !record.ciphertext.is_empty())
+        {
+            verifier.update(&record.ciphertext)?;
+        }
+        let streamed = verifier.finish()?;
+        streamed.verify(verifying_key)?;
+
+        let plaintext = RecordHasher::default()
+            .hash_parts(self.records().map(|record| record.plaintext.as_slice()));
+        if !CtDigest(plaintext).ct_eq(&CtDigest(streamed.records_digest())) {
+            debug!("plaintext does not match the decrypted ciphertext");
+            return Err(VerifyError::BadSignature);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "upstream")]
+impl TryFrom<zktls_att_verification::verification_data::VerifyingDataOpt> for VerifyingData {
+    type Error = MissingField;
+
+    fn try_from(
+        data: zktls_att_verification::verification_data::VerifyingDataOpt,
+    ) -> Result<Self, Self::Error> {
+        VerifyingData::of(&data)
+    }
+}
+
+#[cfg(feature = "upstream")]
+impl From<VerifyingData> for zktls_att_verification::verification_data::VerifyingDataOpt {
+    /// The attestation with each run of blocks listed block by block again, as upstream lists them.
+    fn from(data: VerifyingData) -> Self {
+        let packets: Vec<Value> = data
+            .packets
+            .iter()
+            .map(|packet| {
+                let records: Vec<Value> = packet
+                    .records
+                    .iter()
+                    .map(|record| {
+                        let blocks: Vec<Value> = record
+                            .blocks
+                            .iter()
+                            .flat_map(|run| {
+                                let mask: Vec<u8> = (0..16)
+                                    .map(|index| ((run.mask >> index) & 1) as u8)
+                                    .collect();
+                                (0..run.count).map(move |offset| {
+                                    serde_json::json!({
+                                        "id": run.first_id + offset,
+                                        "mask": mask.clone(),
+                                    })
+                                })
+                            })
+                            .collect();
+                        serde_json::json!({
+                            "ciphertext": hex::encode(&record.ciphertext),
+                            "nonce": hex::encode(record.nonce),
+                            "blocks": blocks,
+                        })
+                    })
+                    .collect();
+                let messages: Vec<String> = packet
+                    .records
+                    .iter()
+                    .map(|record| hex::encode(&record.plaintext))
+                    .collect();
+                serde_json::json!({
+                    "aes_key": hex::encode(packet.aes_key),
+                    "record_messages": messages,
+                    "ecdsa_signature": hex::encode(packet.signature),
+                    "records": records,
+                })
+            })
+            .collect();
+
+        serde_json::from_value(serde_json::json!({ "packets": packets }))
+            .expect("every field upstream reads is set")
+    }
+}
+
+fn hex_vec(value: &Value) -> Option<Vec<u8>> {
+    hex::decode(value.as_str()?).ok()
+}
+
+fn hex_array<const N: usize>(value: &Value) -> Option<[u8; N]> {
+    hex_vec(value)?.try_into().ok()
+}
+
+/// The blocks of a record, each listed with its id and a mask of 16 bytes, merged into runs of
+/// consecutive blocks with the same mask.
+fn block_runs(blocks: &Value) -> Option<Vec<BlockRun>> {
+    let mut runs: Vec<BlockRun> = Vec::new();
+    for block in blocks.as_array()? {
+        let id = u32::try_from(block["id"].as_u64()?).ok()?;
+        let mask = block["mask"].as_array().filter(|mask| mask.len() == 16)?;
+        let mask = mask
+            .iter()
+            .enumerate()
+            .filter(|(_, bit)| bit.as_u64() != Some(0))
+            .fold(0u16, |mask, (index, _)| mask | 1 << index);
+        match runs.last_mut() {
+            Some(run) if run.mask == mask && run.first_id.checked_add(run.count) == Some(id) => {
+                run.count += 1;
+            }
+            _ => runs.push(BlockRun {
+                first_id: id,
+                count: 1,
+                mask,
+            }),
+        }
+    }
+    Some(runs)
+}
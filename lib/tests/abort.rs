@@ -0,0 +1,30 @@
+use zktls_lib::abort::{decode_abort, AbortCode, ABORT_TAG, STATUS_OK};
+use zktls_lib::{PublicValues, VerifyError};
+
+#[test]
+fn codes_are_stable_and_round_trip() {
+    let codes: Vec<u32> = (0..=37)
+        .filter_map(AbortCode::from_code)
+        .map(AbortCode::code)
+        .collect();
+
+    assert_eq!(codes, (1..=36).collect::<Vec<_>>());
+    assert_eq!(AbortCode::from_code(STATUS_OK), None);
+    assert_eq!(AbortCode::SizeLimitExceeded.code(), 5);
+    assert_eq!(
+        AbortCode::from(&VerifyError::BrokenChain { page: 2 }),
+        AbortCode::BrokenChain
+    );
+}
+
+#[test]
+fn abort_commitments_never_decode_as_public_values() {
+    let commitment = AbortCode::SizeLimitExceeded.commitment();
+
+    assert_eq!(&commitment[..4], &ABORT_TAG);
+    assert_eq!(decode_abort(&commitment), Some(5));
+    assert_eq!(decode_abort(&commitment[..7]), None);
+    assert_eq!(decode_abort(&bincode::serialize(&5u32).unwrap()), None);
+    assert!(bincode::deserialize::<PublicValues>(&commitment).is_err());
+    assert_ne!(u32::from_le_bytes(ABORT_TAG), STATUS_OK);
+}
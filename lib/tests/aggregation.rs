@@ -0,0 +1,14 @@
+use zktls_lib::aggregation::ChildCommitment;
+
+#[test]
+fn commitment_binds_the_public_values() {
+    let commitment = ChildCommitment::new([7; 8], b"public values");
+
+    assert_eq!(commitment.vkey_digest, [7; 8]);
+    assert!(commitment.commits(b"public values"));
+    assert!(!commitment.commits(b"public valuez"));
+    assert_eq!(
+        hex::encode(ChildCommitment::new([0; 8], b"").public_values_digest),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+}
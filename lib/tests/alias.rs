@@ -0,0 +1,36 @@
+use serde_json::Value;
+use zktls_lib::alias::canonicalize;
+
+mod common;
+
+use common::fixture_json;
+
+#[test]
+fn aliases_are_renamed_to_canonical_names() {
+    for (aliased, canonical) in [
+        ("bench16_aliased.json", "bench16.json"),
+        ("tls_session_aliased.json", "tls_session.json"),
+    ] {
+        let mut attestation = fixture_json(aliased);
+        assert!(canonicalize(&mut attestation), "{}", aliased);
+        assert_eq!(attestation, fixture_json(canonical), "{}", aliased);
+    }
+}
+
+#[test]
+fn canonical_attestation_is_left_as_is() {
+    let mut attestation = fixture_json("tls_session.json");
+    assert!(!canonicalize(&mut attestation));
+    assert_eq!(attestation, fixture_json("tls_session.json"));
+    assert!(!canonicalize(&mut Value::Null));
+}
+
+#[test]
+fn canonical_field_wins_over_its_alias() {
+    let mut attestation = fixture_json("bench16.json");
+    attestation["packets"][0]["aesKey"] = "00".into();
+    let expected = attestation.clone();
+
+    assert!(!canonicalize(&mut attestation));
+    assert_eq!(attestation, expected);
+}
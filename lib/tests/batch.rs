@@ -0,0 +1,33 @@
+use zktls_lib::batch::{aggregate_digest, BatchOutcome, FAILED_DIGEST};
+use zktls_lib::hash::{HashAlgorithm, RecordHasher};
+
+fn keccak(data: &[u8]) -> [u8; 32] {
+    RecordHasher::new(HashAlgorithm::Keccak256).hash_bytes(data)
+}
+
+#[test]
+fn aggregate_is_keccak_of_concatenated_digests() {
+    let (first, second) = ([1; 32], [2; 32]);
+    let outcome = BatchOutcome::new(true, &[(true, first), (false, second), (true, second)]);
+
+    let mut concatenated = Vec::new();
+    for digest in [first, FAILED_DIGEST, second] {
+        concatenated.extend_from_slice(&digest);
+    }
+    assert_eq!(outcome.len, 3);
+    assert_eq!(outcome.aggregate_digest, keccak(&concatenated));
+    assert_ne!(
+        aggregate_digest(&[first, second]),
+        aggregate_digest(&[second, first])
+    );
+}
+
+#[test]
+fn batch_of_one_is_distinct_from_its_records_digest() {
+    let records_digest = [7; 32];
+    let outcome = BatchOutcome::new(false, &[(true, records_digest)]);
+
+    assert_eq!(outcome.len, 1);
+    assert_eq!(outcome.aggregate_digest, keccak(&records_digest));
+    assert_ne!(outcome.aggregate_digest, records_digest);
+}
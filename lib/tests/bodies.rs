@@ -0,0 +1,33 @@
+use zktls_lib::public_values::bodies_digest;
+use zktls_lib::{Record, RecordDigests, VerifyError};
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn chunked_body_digest_matches_plain_body() {
+    let chunked = fixture_records("chunked_response.json");
+    let plain = fixture_records("balance_above.json");
+
+    assert_ne!(
+        RecordDigests::new(&chunked).response_digest,
+        RecordDigests::new(&plain).response_digest
+    );
+    assert_eq!(bodies_digest(&chunked), bodies_digest(&plain));
+}
+
+#[test]
+fn truncated_chunk_is_malformed() {
+    let mut records = fixture_records("chunked_response.json");
+    let truncated = records[1].bytes.len() - 10;
+    records[1] = Record {
+        bytes: records[1].bytes[..truncated].to_vec(),
+        redacted: Vec::new(),
+    };
+
+    assert_eq!(
+        bodies_digest(&records),
+        Err(VerifyError::MalformedResponse { record: 1 })
+    );
+}
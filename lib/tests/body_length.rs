@@ -0,0 +1,59 @@
+use zktls_lib::content_encoding::decode_bodies;
+use zktls_lib::http::check_body_lengths;
+use zktls_lib::{Record, VerifyError};
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn complete_bodies_report_their_length() {
+    assert_eq!(
+        check_body_lengths(&fixture_records("balance_above.json")),
+        Ok(Some(44))
+    );
+    assert_eq!(
+        check_body_lengths(&fixture_records("chunked_response.json")),
+        Ok(Some(44))
+    );
+    assert_eq!(
+        check_body_lengths(&fixture_records("bench16.json")),
+        Ok(None)
+    );
+}
+
+#[test]
+fn truncated_body_is_rejected() {
+    assert_eq!(
+        check_body_lengths(&fixture_records("truncated_body.json")),
+        Err(VerifyError::BodyLengthMismatch {
+            record: 1,
+            declared: 44,
+            actual: 34,
+        })
+    );
+}
+
+#[test]
+fn missing_terminator_is_rejected() {
+    assert_eq!(
+        check_body_lengths(&fixture_records("unterminated_chunked.json")),
+        Err(VerifyError::UnterminatedBody { record: 1 })
+    );
+}
+
+#[test]
+fn response_split_across_records_is_checked_whole() {
+    let mut records = fixture_records("balance_above.json");
+    let split = records[1].bytes.len() - 10;
+    let tail = records[1].bytes.split_off(split);
+    records.push(Record {
+        bytes: tail,
+        redacted: Vec::new(),
+    });
+    assert_eq!(check_body_lengths(&records), Ok(Some(44)));
+
+    let mut records = fixture_records("balance_above_gzip.json");
+    decode_bodies(&mut records, 1024).unwrap();
+    assert_eq!(check_body_lengths(&records), Ok(Some(44)));
+}
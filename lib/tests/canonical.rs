@@ -0,0 +1,45 @@
+use serde_json::Value;
+use zktls_lib::canonical::{canonical_digest, canonical_json};
+
+mod common;
+
+use common::fixture_json;
+
+/// Rebuild every object in `value` with its keys inserted in reverse order.
+fn reverse_keys(value: Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.into_iter().map(reverse_keys).collect()),
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.reverse();
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, item)| (key, reverse_keys(item)))
+                    .collect(),
+            )
+        }
+        scalar => scalar,
+    }
+}
+
+#[test]
+fn digest_ignores_key_order() {
+    let data = fixture_json("bench16.json");
+
+    assert_eq!(
+        canonical_digest(&data),
+        canonical_digest(&reverse_keys(data.clone()))
+    );
+}
+
+#[test]
+fn keys_are_sorted_without_whitespace() {
+    let value: Value =
+        serde_json::from_str(r#"{ "b": [1, {"d": 2, "c": "x"}], "a": null }"#).unwrap();
+
+    assert_eq!(
+        canonical_json(&value),
+        br#"{"a":null,"b":[1,{"c":"x","d":2}]}"#
+    );
+}
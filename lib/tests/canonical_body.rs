@@ -0,0 +1,41 @@
+use zktls_lib::canonical::body_digests;
+use zktls_lib::{Record, VerifyError};
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn equal_json_bodies_share_a_canonical_digest() {
+    let compact = body_digests(&fixture_records("canonical_compact.json")).unwrap();
+    let pretty = body_digests(&fixture_records("canonical_pretty.json")).unwrap();
+
+    assert_ne!(compact.raw_digest, pretty.raw_digest);
+    assert_eq!(compact.canonical_digest, pretty.canonical_digest);
+}
+
+#[test]
+fn chunked_body_digests_match_the_plain_body() {
+    assert_eq!(
+        body_digests(&fixture_records("chunked_response.json")),
+        body_digests(&fixture_records("balance_above.json"))
+    );
+}
+
+#[test]
+fn non_json_body_is_rejected() {
+    let mut records = fixture_records("balance_above.json");
+    records[1] = Record {
+        bytes: b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok".to_vec(),
+        redacted: Vec::new(),
+    };
+
+    assert_eq!(
+        body_digests(&records),
+        Err(VerifyError::BodyNotJson { record: 1 })
+    );
+    assert_eq!(
+        body_digests(&fixture_records("bench16.json")),
+        Err(VerifyError::MissingResponse)
+    );
+}
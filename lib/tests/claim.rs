@@ -0,0 +1,68 @@
+use alloy_primitives::Address;
+use alloy_sol_types::SolValue;
+use zktls_lib::claim::{Claim, CommitMode};
+use zktls_lib::key::VerifyingKey;
+use zktls_lib::{RecordDigests, VerifyError};
+
+mod common;
+
+use common::fixture_records;
+
+fn bench_key() -> VerifyingKey {
+    let path = format!(
+        "{}/../fixtures/zktls/verifying_k256.key",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    VerifyingKey::parse(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn claim_round_trips_through_abi_encoding() {
+    let records = fixture_records("balance_above.json");
+    let records_digest = RecordDigests::new(&records).records_digest;
+    let claim = Claim::new(
+        &bench_key(),
+        &records,
+        "$.balance".to_string(),
+        1_700_000_000,
+        records_digest,
+    )
+    .unwrap();
+
+    let decoded = Claim::abi_decode(&claim.abi_encode()).unwrap();
+
+    assert_eq!(decoded, claim);
+    assert_eq!(decoded.domain, "api.example.com");
+    assert_eq!(decoded.path, "$.balance");
+    assert_eq!(decoded.value, "1000000.01");
+    assert_eq!(decoded.timestamp, 1_700_000_000);
+    assert_eq!(decoded.recordsDigest, records_digest);
+}
+
+#[test]
+fn attestor_is_the_address_of_the_verifying_key() {
+    let key = bench_key();
+    let point = key.as_k256().unwrap().to_encoded_point(false);
+
+    assert_eq!(
+        Address::from(key.address().unwrap()),
+        Address::from_raw_public_key(&point.as_bytes()[1..])
+    );
+}
+
+#[test]
+fn missing_path_is_not_claimed() {
+    let records = fixture_records("balance_above.json");
+
+    assert_eq!(
+        Claim::new(&bench_key(), &records, "$.missing".to_string(), 0, [0; 32]),
+        Err(VerifyError::PathNotFound {
+            path: "$.missing".to_string()
+        })
+    );
+    assert_eq!(
+        CommitMode::from_tag(CommitMode::Claim.tag()),
+        Some(CommitMode::Claim)
+    );
+    assert_eq!("full".parse(), Ok(CommitMode::Full));
+}
@@ -0,0 +1,25 @@
+// Each test crate uses only some of these helpers.
+#![allow(dead_code)]
+
+use zktls_lib::Record;
+
+/// A fixture under `fixtures/zktls/data`, parsed as JSON.
+pub fn fixture_json(name: &str) -> serde_json::Value {
+    let path = format!(
+        "{}/../fixtures/zktls/data/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// The records of a fixture under `fixtures/zktls/data`, decoded from `record_messages`.
+pub fn fixture_records(name: &str) -> Vec<Record> {
+    fixture_json(name)["packets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|packet| packet["record_messages"].as_array().unwrap())
+        .map(|record| Record::from_hex(record.as_str().unwrap()).unwrap())
+        .collect()
+}
@@ -0,0 +1,66 @@
+use zktls_lib::content_encoding::{decode_bodies, ContentEncoding};
+use zktls_lib::json::response_value;
+use zktls_lib::VerifyError;
+
+mod common;
+
+use common::fixture_records;
+
+const MAX_BYTES: usize = 1024 * 1024;
+
+#[test]
+fn gzip_and_deflate_bodies_decode_to_the_plain_response() {
+    let plain = fixture_records("balance_above.json");
+
+    for (fixture, encoding) in [
+        ("balance_above_gzip.json", ContentEncoding::Gzip),
+        ("balance_above_deflate.json", ContentEncoding::Deflate),
+    ] {
+        let mut records = fixture_records(fixture);
+        let decoded = decode_bodies(&mut records, MAX_BYTES).unwrap();
+
+        assert_eq!(records, plain, "{}", fixture);
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].record, 1);
+        assert_eq!(decoded[0].encoding, encoding.tag());
+        assert_ne!(decoded[0].encoded_digest, decoded[0].plaintext_digest);
+        assert_eq!(
+            response_value(&records, "$.balance").unwrap(),
+            "\"1000000.01\""
+        );
+    }
+}
+
+#[test]
+fn identity_responses_are_left_alone() {
+    let mut records = fixture_records("balance_above.json");
+
+    assert_eq!(decode_bodies(&mut records, MAX_BYTES).unwrap(), []);
+    assert_eq!(records, fixture_records("balance_above.json"));
+}
+
+#[test]
+fn zip_bomb_is_rejected_at_the_cap() {
+    let mut records = fixture_records("gzip_bomb.json");
+
+    assert_eq!(
+        decode_bodies(&mut records, MAX_BYTES),
+        Err(VerifyError::DecodedTooLarge {
+            record: 1,
+            max_bytes: MAX_BYTES,
+        })
+    );
+    assert!(decode_bodies(&mut records, 8 << 20).is_ok());
+}
+
+#[test]
+fn corrupt_body_is_rejected() {
+    let mut records = fixture_records("balance_above_gzip.json");
+    let last = records[1].bytes.len() - 5;
+    records[1].bytes[last] ^= 0xff;
+
+    assert_eq!(
+        decode_bodies(&mut records, MAX_BYTES),
+        Err(VerifyError::BadContentEncoding { record: 1 })
+    );
+}
@@ -0,0 +1,51 @@
+use zktls_lib::http::{check_content_type, Mime};
+use zktls_lib::VerifyError;
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn charset_parameter_is_parsed() {
+    let records = fixture_records("json_charset.json");
+
+    assert_eq!(records[0].content_type(), None);
+    assert_eq!(
+        records[1].content_type(),
+        Some(Mime {
+            essence: "application/json".to_string(),
+            params: vec![("charset".to_string(), "utf-8".to_string())],
+        })
+    );
+}
+
+#[test]
+fn parameters_are_ignored_unless_expected() {
+    let records = fixture_records("json_charset.json");
+
+    assert_eq!(check_content_type(&records, "application/json"), Ok(()));
+    assert_eq!(
+        check_content_type(&records, "Application/JSON; charset=UTF-8"),
+        Ok(())
+    );
+    assert_eq!(
+        check_content_type(&records, "application/json; charset=iso-8859-1"),
+        Err(VerifyError::ContentTypeMismatch {
+            expected: "application/json; charset=iso-8859-1".to_string(),
+            found: Some("application/json; charset=utf-8".to_string()),
+        })
+    );
+}
+
+#[test]
+fn other_media_type_is_rejected() {
+    let records = fixture_records("balance_above.json");
+
+    assert_eq!(
+        check_content_type(&records, "text/html"),
+        Err(VerifyError::ContentTypeMismatch {
+            expected: "text/html".to_string(),
+            found: Some("application/json".to_string()),
+        })
+    );
+}
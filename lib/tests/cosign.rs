@@ -0,0 +1,87 @@
+use zktls_lib::cosign::{verify_cosigned, CoSignature};
+use zktls_lib::hash::RecordHasher;
+use zktls_lib::key::VerifyingKey;
+use zktls_lib::verifying_data::VerifyingData;
+use zktls_lib::VerifyError;
+
+mod common;
+
+use common::fixture_records;
+
+fn read(path: &str) -> String {
+    std::fs::read_to_string(format!(
+        "{}/../fixtures/zktls/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        path
+    ))
+    .unwrap()
+}
+
+fn cosigned() -> (VerifyingData, VerifyingKey) {
+    let data: serde_json::Value = serde_json::from_str(&read("data/cosigned.json")).unwrap();
+    let verifying_key = VerifyingKey::from_hex(&read("generated_k256.key")).unwrap();
+    (VerifyingData::of(&data).unwrap(), verifying_key)
+}
+
+fn cosignatures(name: &str) -> Vec<CoSignature> {
+    serde_json::from_str(&read(&format!("data/{}", name))).unwrap()
+}
+
+#[test]
+fn every_key_is_committed_in_order() {
+    let (data, verifying_key) = cosigned();
+    let cosignatures = cosignatures("cosigned.cosignatures.json");
+
+    let cosigned = verify_cosigned(&data, &verifying_key, &cosignatures).unwrap();
+    assert_eq!(
+        cosigned.key_digests,
+        [
+            verifying_key.digest(),
+            cosignatures[0].verifying_key.digest()
+        ]
+    );
+    assert_ne!(cosigned.key_digests[0], cosigned.key_digests[1]);
+    assert_eq!(
+        cosigned.records_digest,
+        RecordHasher::default().hash_records(&fixture_records("cosigned.json"))
+    );
+}
+
+#[test]
+fn wrong_second_signature_fails() {
+    let (data, verifying_key) = cosigned();
+
+    assert_eq!(
+        verify_cosigned(
+            &data,
+            &verifying_key,
+            &cosignatures("cosigned_bad.cosignatures.json")
+        ),
+        Err(VerifyError::BadSignature)
+    );
+}
+
+#[test]
+fn cosigner_must_sign_every_packet() {
+    let (data, verifying_key) = cosigned();
+    let mut cosignatures = cosignatures("cosigned.cosignatures.json");
+    cosignatures[0].signatures.clear();
+
+    assert_eq!(
+        verify_cosigned(&data, &verifying_key, &cosignatures),
+        Err(VerifyError::BadSignature)
+    );
+}
+
+#[test]
+fn primary_key_must_verify_too() {
+    let (data, _) = cosigned();
+    let cosignatures = cosignatures("cosigned.cosignatures.json");
+    // The co-signer's key does not verify the attestation's own signature.
+    let cosigner = cosignatures[0].verifying_key;
+
+    assert_eq!(
+        verify_cosigned(&data, &cosigner, &cosignatures),
+        Err(VerifyError::BadSignature)
+    );
+}
@@ -0,0 +1,28 @@
+use zktls_lib::cross_check::CrossCheck;
+
+mod common;
+
+use common::fixture_records;
+
+fn cross_check() -> CrossCheck {
+    CrossCheck {
+        path: "$.price".to_string(),
+        other_path: "$.data.amount".to_string(),
+    }
+}
+
+#[test]
+fn matching_values_agree() {
+    let records = fixture_records("price_exchange_a.json");
+    let other_records = fixture_records("price_exchange_b.json");
+
+    assert_eq!(cross_check().evaluate(&records, &other_records), Ok(true));
+}
+
+#[test]
+fn differing_values_disagree() {
+    let records = fixture_records("price_exchange_a.json");
+    let other_records = fixture_records("price_exchange_b_stale.json");
+
+    assert_eq!(cross_check().evaluate(&records, &other_records), Ok(false));
+}
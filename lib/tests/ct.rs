@@ -0,0 +1,35 @@
+use std::marker::PhantomData;
+
+use zktls_lib::ct::CtDigest;
+
+/// `IMPLS_PARTIAL_EQ` is the inherent constant when `T: PartialEq`, and falls back to the
+/// trait's otherwise.
+struct Probe<T>(PhantomData<T>);
+
+trait NoPartialEq {
+    const IMPLS_PARTIAL_EQ: bool = false;
+}
+
+impl<T> NoPartialEq for Probe<T> {}
+
+impl<T: PartialEq> Probe<T> {
+    #[allow(dead_code)]
+    const IMPLS_PARTIAL_EQ: bool = true;
+}
+
+#[test]
+fn digest_cannot_be_compared_with_eq() {
+    const { assert!(Probe::<[u8; 32]>::IMPLS_PARTIAL_EQ) };
+    const { assert!(!Probe::<CtDigest>::IMPLS_PARTIAL_EQ) };
+}
+
+#[test]
+fn constant_time_comparison_matches_bytes() {
+    let digest = CtDigest([7; 32]);
+    let mut last_byte_differs = [7; 32];
+    last_byte_differs[31] = 8;
+
+    assert!(digest.ct_eq(&CtDigest([7; 32])));
+    assert!(!digest.ct_eq(&CtDigest(last_byte_differs)));
+    assert!(!digest.ct_eq(&[0; 32].into()));
+}
@@ -0,0 +1,66 @@
+use zktls_lib::date::{parse_http_date, response_date, NO_RESPONSE_DATE};
+use zktls_lib::VerifyError;
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn imf_fixdate_is_a_unix_timestamp() {
+    assert_eq!(
+        parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+        Ok(784111777)
+    );
+    assert_eq!(parse_http_date("Thu, 01 Jan 1970 00:00:00 GMT"), Ok(0));
+    assert_eq!(
+        parse_http_date("Thu, 29 Feb 2024 23:59:59 GMT"),
+        Ok(1709251199)
+    );
+    assert_eq!(
+        response_date(&fixture_records("chunked_response.json")),
+        Ok(1791288000)
+    );
+}
+
+#[test]
+fn missing_date_commits_the_sentinel() {
+    assert_eq!(
+        response_date(&fixture_records("balance_above.json")),
+        Ok(NO_RESPONSE_DATE)
+    );
+    assert_eq!(
+        response_date(&fixture_records("bench16.json")),
+        Ok(NO_RESPONSE_DATE)
+    );
+}
+
+#[test]
+fn malformed_date_commits_the_sentinel() {
+    for value in [
+        "",
+        "yesterday",
+        "Sun, 6 Nov 1994 08:49:37 GMT",
+        "Sun, 06 Nov 1994 08:49:37 UTC",
+        "Sun, 06 Nov 1994 24:00:00 GMT",
+        "Sun, 31 Nov 1994 08:49:37 GMT",
+        "Fri, 29 Feb 2023 08:49:37 GMT",
+        "Mon, 06 Nov 1994 08:49:37 GMT",
+        "Sun Nov  6 08:49:37 1994",
+    ] {
+        assert_eq!(parse_http_date(value), Ok(NO_RESPONSE_DATE), "{}", value);
+    }
+}
+
+#[test]
+fn legacy_and_leap_second_dates_are_rejected() {
+    assert_eq!(
+        response_date(&fixture_records("legacy_date.json")),
+        Err(VerifyError::AmbiguousDate {
+            value: "Sunday, 06-Nov-94 08:49:37 GMT".to_string()
+        })
+    );
+    assert!(matches!(
+        parse_http_date("Sat, 31 Dec 2016 23:59:60 GMT"),
+        Err(VerifyError::AmbiguousDate { .. })
+    ));
+}
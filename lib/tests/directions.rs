@@ -0,0 +1,32 @@
+use zktls_lib::http::Direction;
+use zktls_lib::public_values::{digest, UNKNOWN_DIRECTION_DIGEST};
+use zktls_lib::RecordDigests;
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn splits_digests_by_direction() {
+    let records = fixture_records("redacted_auth.json");
+    assert_eq!(Direction::of(&records[0].bytes), Direction::Request);
+    assert_eq!(Direction::of(&records[1].bytes), Direction::Response);
+
+    let digests = RecordDigests::new(&records);
+
+    assert_eq!(digests.request_digest, digest(records[..1].iter()));
+    assert_eq!(digests.response_digest, digest(records[1..].iter()));
+    assert_eq!(digests.records_digest, digest(records.iter()));
+    assert_ne!(digests.request_digest, digests.response_digest);
+}
+
+#[test]
+fn falls_back_to_combined_digest_without_directions() {
+    let records = fixture_records("bench16.json");
+
+    let digests = RecordDigests::new(&records);
+
+    assert_eq!(digests.request_digest, UNKNOWN_DIRECTION_DIGEST);
+    assert_eq!(digests.response_digest, UNKNOWN_DIRECTION_DIGEST);
+    assert_eq!(digests.records_digest, digest(records.iter()));
+}
@@ -0,0 +1,40 @@
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::{sol, SolStruct};
+use zktls_lib::eip712::{eip712_records_hash, Eip712Domain};
+
+mod common;
+
+use common::fixture_records;
+
+sol! {
+    struct ZkTlsRecords {
+        bytes[] records;
+    }
+}
+
+#[test]
+fn records_hash_matches_alloy() {
+    let records = fixture_records("balance_above.json");
+    let domain = Eip712Domain {
+        name: "ZkTlsConsumer".to_string(),
+        version: "1".to_string(),
+        chain_id: 11155111,
+        verifying_contract: [0x5f; 20],
+    };
+
+    let expected = ZkTlsRecords {
+        records: records
+            .iter()
+            .map(|record| Bytes::from(record.bytes.clone()))
+            .collect(),
+    }
+    .eip712_signing_hash(&alloy_sol_types::Eip712Domain::new(
+        Some(domain.name.clone().into()),
+        Some(domain.version.clone().into()),
+        Some(U256::from(domain.chain_id)),
+        Some(Address::from(domain.verifying_contract)),
+        None,
+    ));
+
+    assert_eq!(eip712_records_hash(&records, &domain), expected.0);
+}
@@ -0,0 +1,37 @@
+use zktls_lib::key::KeyFormat;
+use zktls_lib::VerifyError;
+
+#[test]
+fn messages_name_the_failure_and_where_to_look() {
+    let message = VerifyError::BadSignature.to_string();
+    assert!(message.starts_with("invalid signature: the k256 ECDSA signature did not verify"));
+    assert!(message.contains("\"Verifying Keys\" in the README"));
+
+    assert_eq!(
+        VerifyError::KeyParseError {
+            format: KeyFormat::Pem
+        }
+        .to_string(),
+        "the verifying key is not a secp256k1, P-256 or Ed25519 key in PEM format. Check the key \
+         file's encoding (see \"Verifying Keys\" in the README)"
+    );
+}
+
+#[test]
+fn messages_carry_the_offending_values() {
+    let message = VerifyError::BodyLengthMismatch {
+        record: 1,
+        declared: 120,
+        actual: 110,
+    }
+    .to_string();
+    assert!(message.contains("record 1"));
+    assert!(message.contains("Content-Length of 120 bytes but carries 110"));
+
+    let message = VerifyError::ContentTypeMismatch {
+        expected: "application/json".to_string(),
+        found: None,
+    }
+    .to_string();
+    assert!(message.contains("declares no content type, expected `application/json`"));
+}
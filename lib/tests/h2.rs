@@ -0,0 +1,192 @@
+use zktls_lib::h2::{message, FRAME_HEADER_LEN};
+use zktls_lib::hpack::Decoder;
+use zktls_lib::http::HttpVersion;
+use zktls_lib::{Record, VerifyError};
+
+/// A request and its response over HTTP/2. The response splits its header block across a
+/// `CONTINUATION` frame and pads its body.
+fn http2_records() -> (Record, Record) {
+    let records: Vec<String> = serde_json::from_str(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../fixtures/zktls/http2_records.json"
+    )))
+    .unwrap();
+    (
+        Record::from_hex(&records[0]).unwrap(),
+        Record::from_hex(&records[1]).unwrap(),
+    )
+}
+
+fn record(bytes: &[u8]) -> Record {
+    Record {
+        bytes: bytes.to_vec(),
+        redacted: Vec::new(),
+    }
+}
+
+fn frame(frame_type: u8, flags: u8, stream_id: u32, payload: &[u8]) -> Vec<u8> {
+    let mut frame = (payload.len() as u32).to_be_bytes()[1..].to_vec();
+    frame.extend_from_slice(&[frame_type, flags]);
+    frame.extend_from_slice(&stream_id.to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+#[test]
+fn version_is_detected_from_the_framing() {
+    let (request, response) = http2_records();
+
+    assert_eq!(request.http_version(), HttpVersion::Http2);
+    assert_eq!(response.http_version(), HttpVersion::Http2);
+    assert_eq!(
+        record(b"HTTP/1.1 200 OK\r\n\r\n").http_version(),
+        HttpVersion::Http1
+    );
+    assert_eq!(
+        record(b"GET / HTTP/1.1\r\n\r\n").http_version(),
+        HttpVersion::Http1
+    );
+    assert_eq!(
+        record(b"{\"balance\":1}").http_version(),
+        HttpVersion::Http1
+    );
+}
+
+#[test]
+fn http2_response_status_and_headers_are_extracted() {
+    let (_, response) = http2_records();
+
+    assert_eq!(response.http_status(), Ok(Some(200)));
+    assert_eq!(
+        response.header("Content-Type"),
+        Ok(Some("application/json".to_string()))
+    );
+    assert_eq!(
+        response.header("date"),
+        Ok(Some("Sun, 06 Nov 1994 08:49:37 GMT".to_string()))
+    );
+    assert_eq!(
+        response.header("x-request-id"),
+        Ok(Some("abc123".to_string()))
+    );
+    assert_eq!(response.header("etag"), Ok(None));
+
+    let message = message(&response.bytes).unwrap().unwrap();
+    assert_eq!(message.body, br#"{"balance":"1000"}"#);
+    assert_eq!(message.field("content-length"), Some("18"));
+    assert!(message.complete);
+}
+
+#[test]
+fn http2_request_headers_are_extracted() {
+    let (request, _) = http2_records();
+
+    assert_eq!(request.http_status(), Ok(None));
+    assert_eq!(request.header(":method"), Ok(Some("GET".to_string())));
+    assert_eq!(
+        request.header(":authority"),
+        Ok(Some("api.example.com".to_string()))
+    );
+    assert_eq!(
+        request.header(":path"),
+        Ok(Some("/api/balance".to_string()))
+    );
+    assert_eq!(
+        request.header("user-agent"),
+        Ok(Some("zktls-test".to_string()))
+    );
+}
+
+#[test]
+fn http1_status_and_headers_are_extracted() {
+    let response = record(b"HTTP/1.1 404 Not Found\r\nContent-Type: text/plain\r\n\r\nmissing");
+
+    assert_eq!(response.http_status(), Ok(Some(404)));
+    assert_eq!(
+        response.header("content-type"),
+        Ok(Some("text/plain".to_string()))
+    );
+    assert_eq!(record(b"GET / HTTP/1.1\r\n\r\n").http_status(), Ok(None));
+}
+
+/// The Huffman-coded requests of RFC 7541 Appendix C.4.
+#[test]
+fn rfc_7541_huffman_examples_decode() {
+    let mut decoder = Decoder::new();
+    let first = hex::decode("828684418cf1e3c2e5f23a6ba0ab90f4ff").unwrap();
+
+    assert_eq!(
+        decoder.decode(&first).unwrap(),
+        [
+            (":method", "GET"),
+            (":scheme", "http"),
+            (":path", "/"),
+            (":authority", "www.example.com"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+    );
+
+    // The second request refers to the `:authority` the first added to the dynamic table.
+    let second = hex::decode("828684be5886a8eb10649cbf").unwrap();
+    let decoded = decoder.decode(&second);
+    if cfg!(feature = "hpack-dynamic") {
+        let decoded = decoded.unwrap();
+        assert_eq!(
+            decoded[3],
+            (":authority".to_string(), "www.example.com".to_string())
+        );
+        assert_eq!(
+            decoded[4],
+            ("cache-control".to_string(), "no-cache".to_string())
+        );
+    } else {
+        assert_eq!(decoded, Err(VerifyError::HpackDynamicTable { index: 62 }));
+    }
+}
+
+#[test]
+fn invalid_huffman_padding_is_rejected() {
+    // `www.example.com` with its final padding bit cleared.
+    let block = hex::decode("418cf1e3c2e5f23a6ba0ab90f4fe").unwrap();
+
+    assert_eq!(
+        Decoder::new().decode(&block),
+        Err(VerifyError::MalformedFrame)
+    );
+}
+
+#[test]
+fn unsupported_frame_types_are_rejected() {
+    let (_, response) = http2_records();
+
+    // A PUSH_PROMISE after the response.
+    let mut pushed = response.bytes.clone();
+    pushed.extend(frame(0x5, 0x4, 1, &[0, 0, 0, 2, 0x82]));
+    assert_eq!(
+        record(&pushed).header("content-type"),
+        Err(VerifyError::UnsupportedFrame { frame_type: 0x5 })
+    );
+
+    // An extension frame, such as ORIGIN, at the start of the record.
+    let mut extended = frame(0xc, 0, 0, b"");
+    extended.extend_from_slice(&response.bytes);
+    assert_eq!(
+        message(&extended),
+        Err(VerifyError::UnsupportedFrame { frame_type: 0xc })
+    );
+}
+
+#[test]
+fn truncated_frame_is_rejected() {
+    let (_, response) = http2_records();
+    let truncated = &response.bytes[..response.bytes.len() - 1];
+
+    assert_eq!(
+        record(truncated).http_status(),
+        Err(VerifyError::MalformedFrame)
+    );
+    assert_eq!(
+        message(&response.bytes[..FRAME_HEADER_LEN - 1]),
+        Err(VerifyError::MalformedFrame)
+    );
+}
@@ -0,0 +1,97 @@
+use zktls_lib::hash::{HashAlgorithm, Hasher, Keccak256, RecordHasher, RecordsHasher, Sha256};
+use zktls_lib::public_values::{digest, digest_with};
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn keccak256_of_bench16() {
+    let records = fixture_records("bench16.json");
+
+    assert_eq!(
+        hex::encode(digest_with::<Keccak256>(records.iter())),
+        "6997f06ec354233a88d4772d5560789931b56c395fd265f3cf7ecdf9a2c476a1"
+    );
+    assert_eq!(
+        hex::encode(Keccak256::default().finalize()),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+}
+
+#[test]
+fn sha256_of_bench16() {
+    let records = fixture_records("bench16.json");
+
+    assert_eq!(
+        hex::encode(digest_with::<Sha256>(records.iter())),
+        "a0a9b8042637d64562a82f55445232c146084e1e3ee599253010b2aafe324e63"
+    );
+}
+
+#[test]
+fn records_digest_uses_the_selected_hash() {
+    let records = fixture_records("bench16.json");
+
+    assert_eq!(
+        digest(records.iter()),
+        digest_with::<RecordsHasher>(records.iter())
+    );
+}
+
+#[test]
+fn record_hasher_matches_each_algorithm() {
+    let records = fixture_records("bench16.json");
+    let hash = |algorithm| hex::encode(RecordHasher::new(algorithm).hash_records(&records));
+
+    assert_eq!(
+        hash(HashAlgorithm::Keccak256),
+        "6997f06ec354233a88d4772d5560789931b56c395fd265f3cf7ecdf9a2c476a1"
+    );
+    assert_eq!(
+        hash(HashAlgorithm::Sha256),
+        "a0a9b8042637d64562a82f55445232c146084e1e3ee599253010b2aafe324e63"
+    );
+    assert_eq!(
+        hash(HashAlgorithm::Blake3),
+        "9d1ad1d0d06d85947b3afa8eb5fd6a7c213d2058615ffd5815bfbb7b3b39dd1d"
+    );
+    assert_eq!(
+        RecordHasher::default().hash_records(&records),
+        digest(records.iter())
+    );
+}
+
+#[test]
+fn record_boundaries_change_the_digest() {
+    let hasher = RecordHasher::default();
+    let digest = hasher.hash_parts([b"ab".as_slice(), b"c"]);
+
+    assert_ne!(digest, hasher.hash_parts([b"a".as_slice(), b"bc"]));
+    assert_ne!(digest, hasher.hash_parts([b"abc".as_slice()]));
+    assert_ne!(digest, hasher.hash_parts([b"ab".as_slice(), b"c", b""]));
+    assert_eq!(
+        hasher.hash_parts([b"abc".as_slice()]),
+        hasher.hash_bytes(&[&3u64.to_be_bytes()[..], b"abc"].concat())
+    );
+}
+
+#[cfg(feature = "poseidon")]
+#[test]
+fn poseidon_does_not_depend_on_how_input_is_split() {
+    use zktls_lib::hash::Poseidon;
+
+    let records = fixture_records("bench256.json");
+    let whole = digest_with::<Poseidon>(records.iter());
+
+    let mut hasher = Poseidon::default();
+    for record in &records {
+        hasher.update(&(record.bytes.len() as u64).to_be_bytes());
+        for piece in record.bytes.chunks(7) {
+            hasher.update(piece);
+        }
+    }
+
+    assert_eq!(hasher.finalize(), whole);
+    assert_ne!(whole, digest_with::<Poseidon>(records[1..].iter()));
+}
@@ -0,0 +1,91 @@
+use zktls_lib::input::{decode, Preamble, PREAMBLE_MAGIC};
+use zktls_lib::record::Redaction;
+use zktls_lib::VerifyError;
+
+fn redactions() -> Vec<Redaction> {
+    vec![
+        Redaction {
+            record: 0,
+            start: 66,
+            end: 84,
+        },
+        Redaction {
+            record: 1,
+            start: 0,
+            end: 4,
+        },
+    ]
+}
+
+#[test]
+fn decodes_what_was_encoded() {
+    let bytes = bincode::serialize(&redactions()).unwrap();
+
+    assert_eq!(decode::<Vec<Redaction>>(&bytes), Ok(redactions()));
+}
+
+#[test]
+fn truncated_input_is_an_error() {
+    let bytes = bincode::serialize(&redactions()).unwrap();
+
+    for len in 0..bytes.len() {
+        assert_eq!(
+            decode::<Vec<Redaction>>(&bytes[..len]),
+            Err(VerifyError::MalformedInput)
+        );
+    }
+}
+
+#[test]
+fn trailing_bytes_are_an_error() {
+    let mut bytes = bincode::serialize(&redactions()).unwrap();
+    bytes.push(0);
+
+    assert_eq!(
+        decode::<Vec<Redaction>>(&bytes),
+        Err(VerifyError::MalformedInput)
+    );
+}
+
+#[test]
+fn preamble_round_trips() {
+    let preamble = Preamble::new(true, b"key", b"attestation");
+    let bytes = preamble.to_bytes();
+
+    assert_eq!(bytes[..4], PREAMBLE_MAGIC);
+    assert_eq!(Preamble::from_bytes(&bytes), Ok(preamble));
+    assert_eq!(
+        Preamble::from_bytes(&bytes[..Preamble::LEN - 1]),
+        Err(VerifyError::BadPreamble)
+    );
+    // A status flag that is neither set nor unset.
+    let mut flag = bytes;
+    flag[4] = 2;
+    assert_eq!(Preamble::from_bytes(&flag), Err(VerifyError::BadPreamble));
+    // The lone status flag older hosts wrote first.
+    assert_eq!(Preamble::from_bytes(&[1]), Err(VerifyError::BadPreamble));
+}
+
+#[test]
+fn preamble_checks_lengths_then_checksum() {
+    // The CRC-32 check value.
+    assert_eq!(
+        Preamble::new(false, b"1234", b"56789").checksum,
+        0xcbf4_3926
+    );
+
+    let preamble = Preamble::new(false, b"key", b"attestation");
+    assert_eq!(preamble.check(b"key", b"attestation"), Ok(()));
+    assert_eq!(
+        preamble.check(b"key", b"attestat"),
+        Err(VerifyError::BadPreamble)
+    );
+    assert_eq!(
+        preamble.check(b"attestation", b"key"),
+        Err(VerifyError::BadPreamble)
+    );
+    assert_eq!(
+        preamble.check(b"kez", b"attestation"),
+        Err(VerifyError::BadPreamble)
+    );
+}
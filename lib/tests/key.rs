@@ -0,0 +1,168 @@
+use zktls_lib::key::{Curve, KeyFormat, VerifyingKey};
+use zktls_lib::VerifyError;
+
+fn fixture(name: &str) -> Vec<u8> {
+    std::fs::read(format!(
+        "{}/../fixtures/zktls/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    ))
+    .unwrap()
+}
+
+#[test]
+fn every_encoding_parses_to_the_same_point() {
+    let hex = String::from_utf8(fixture("verifying_k256.key")).unwrap();
+    let pem = String::from_utf8(fixture("verifying_k256.pem")).unwrap();
+    let key = VerifyingKey::from_hex(&hex).unwrap();
+
+    assert_eq!(VerifyingKey::from_pem(&pem), Ok(key));
+    assert_eq!(
+        VerifyingKey::from_der(&fixture("verifying_k256.der")),
+        Ok(key)
+    );
+    assert_eq!(
+        VerifyingKey::from_sec1_bytes(&fixture("verifying_k256.sec1")),
+        Ok(key)
+    );
+    assert_eq!(pem.parse(), Ok(key));
+    assert_eq!(key.to_hex(), hex.trim());
+}
+
+#[test]
+fn parse_error_names_the_attempted_format() {
+    let pem = String::from_utf8(fixture("verifying_k256.pem")).unwrap();
+
+    assert_eq!(
+        VerifyingKey::from_der(pem.as_bytes()),
+        Err(VerifyError::KeyParseError {
+            format: KeyFormat::Der
+        })
+    );
+    assert_eq!(
+        "0352e3fd".parse::<VerifyingKey>(),
+        Err(VerifyError::KeyParseError {
+            format: KeyFormat::Hex
+        })
+    );
+}
+
+#[test]
+fn serializes_as_compressed_hex() {
+    let pem = String::from_utf8(fixture("verifying_k256.pem")).unwrap();
+    let hex = String::from_utf8(fixture("verifying_k256.key")).unwrap();
+    let key = VerifyingKey::try_from(pem.as_str()).unwrap();
+
+    assert_eq!(key.to_string(), hex.trim());
+    assert_eq!(
+        bincode::serialize(&key).unwrap(),
+        bincode::serialize(hex.trim()).unwrap()
+    );
+    assert_eq!(
+        bincode::deserialize::<VerifyingKey>(&bincode::serialize(hex.trim()).unwrap()).unwrap(),
+        key
+    );
+    assert!(
+        bincode::deserialize::<VerifyingKey>(&bincode::serialize("0352e3fd").unwrap()).is_err()
+    );
+}
+
+#[test]
+fn keys_on_other_curves_parse_from_their_encodings() {
+    use k256::pkcs8::{EncodePublicKey, LineEnding};
+
+    let p256 = *p256::ecdsa::SigningKey::from_slice(&[7; 32])
+        .unwrap()
+        .verifying_key();
+    let ed25519 = ed25519_dalek::SigningKey::from_bytes(&[7; 32]).verifying_key();
+
+    let key = VerifyingKey::P256(p256);
+    let der = p256.to_public_key_der().unwrap();
+    assert_eq!(VerifyingKey::from_der(der.as_bytes()), Ok(key));
+    assert_eq!(
+        VerifyingKey::from_pem(&p256.to_public_key_pem(LineEnding::LF).unwrap()),
+        Ok(key)
+    );
+    assert_eq!(key.curve(), Curve::P256);
+    assert!(key.to_hex().starts_with("p256:"));
+    assert_eq!(VerifyingKey::from_hex(&key.to_hex()), Ok(key));
+    assert_eq!(
+        VerifyingKey::from_curve_bytes(Curve::P256, &key.to_bytes()),
+        Ok(key)
+    );
+
+    let key = VerifyingKey::Ed25519(ed25519);
+    assert_eq!(
+        VerifyingKey::from_pem(&ed25519.to_public_key_pem(LineEnding::LF).unwrap()),
+        Ok(key)
+    );
+    assert_eq!(
+        VerifyingKey::from_ed25519_bytes(ed25519.as_bytes()),
+        Ok(key)
+    );
+    assert_eq!(
+        key.to_hex(),
+        format!("ed25519:{}", hex::encode(ed25519.as_bytes()))
+    );
+    assert_eq!(
+        bincode::deserialize::<VerifyingKey>(&bincode::serialize(&key).unwrap()).unwrap(),
+        key
+    );
+    assert_eq!(key.address(), None);
+}
+
+#[test]
+fn digests_differ_across_curves_for_the_same_bytes() {
+    let hex = String::from_utf8(fixture("verifying_k256.key")).unwrap();
+    let k256 = VerifyingKey::from_hex(&hex).unwrap();
+
+    // The same compressed point read as P-256, when it is one, must not name the same key.
+    let p256 = (1..=255u8)
+        .find_map(|x| {
+            let mut bytes = [x; 33];
+            bytes[0] = 2;
+            VerifyingKey::from_p256_sec1_bytes(&bytes)
+                .ok()
+                .zip(VerifyingKey::from_sec1_bytes(&bytes).ok())
+        })
+        .unwrap();
+    assert_eq!(p256.0.to_bytes(), p256.1.to_bytes());
+    assert_ne!(p256.0.digest(), p256.1.digest());
+    // Keys on secp256k1 keep the digest of their compressed SEC1 encoding.
+    let mut keccak = tiny_keccak::Keccak::v256();
+    tiny_keccak::Hasher::update(&mut keccak, &k256.to_bytes());
+    let mut digest = [0; 32];
+    tiny_keccak::Hasher::finalize(keccak, &mut digest);
+    assert_eq!(k256.digest(), digest);
+}
+
+#[test]
+fn signatures_verify_on_each_curve() {
+    use ed25519_dalek::Signer as _;
+    use k256::ecdsa::signature::hazmat::PrehashSigner;
+
+    let digest = [9; 32];
+
+    let signing_key = k256::ecdsa::SigningKey::from_slice(&[7; 32]).unwrap();
+    let signature: k256::ecdsa::Signature = signing_key.sign_prehash(&digest).unwrap();
+    let key = VerifyingKey::K256(*signing_key.verifying_key());
+    assert_eq!(key.verify_digest(&digest, &signature.to_bytes()), Ok(()));
+
+    let signing_key = p256::ecdsa::SigningKey::from_slice(&[7; 32]).unwrap();
+    let signature: p256::ecdsa::Signature = signing_key.sign_prehash(&digest).unwrap();
+    let key = VerifyingKey::P256(*signing_key.verifying_key());
+    assert_eq!(key.verify_digest(&digest, &signature.to_bytes()), Ok(()));
+    assert_eq!(
+        key.verify_digest(&[8; 32], &signature.to_bytes()),
+        Err(VerifyError::BadSignature)
+    );
+
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+    let signature = signing_key.sign(&digest);
+    let key = VerifyingKey::Ed25519(signing_key.verifying_key());
+    assert_eq!(key.verify_digest(&digest, &signature.to_bytes()), Ok(()));
+    assert_eq!(
+        key.verify_digest(&[8; 32], &signature.to_bytes()),
+        Err(VerifyError::BadSignature)
+    );
+}
@@ -0,0 +1,76 @@
+use zktls_lib::key::VerifyingKey;
+use zktls_lib::merkle::MerkleTranscript;
+use zktls_lib::VerifyError;
+
+fn fixture(name: &str) -> (String, MerkleTranscript) {
+    let read = |path: &str| {
+        std::fs::read_to_string(format!(
+            "{}/../fixtures/zktls/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            path
+        ))
+        .unwrap()
+    };
+    let transcript = serde_json::from_str(&read(&format!("data/{}", name))).unwrap();
+
+    (read("merkle_verifying_k256.key"), transcript)
+}
+
+#[test]
+fn disclosed_chunks_verify_against_signed_root() {
+    let (verifying_key, transcript) = fixture("merkle_transcript.json");
+
+    assert_eq!(transcript.verify(&verifying_key), Ok(()));
+    assert_eq!(transcript.chunk_count(), 8);
+    let indices: Vec<u32> = transcript
+        .disclosed_chunks()
+        .iter()
+        .map(|chunk| chunk.index)
+        .collect();
+    assert_eq!(indices, [0, 7]);
+}
+
+#[test]
+fn tampered_chunk_is_rejected() {
+    let (verifying_key, transcript) = fixture("merkle_transcript_tampered.json");
+
+    assert_eq!(
+        transcript.verify(&verifying_key),
+        Err(VerifyError::BadInclusionProof { index: 7 })
+    );
+}
+
+#[test]
+fn tampered_proof_is_rejected() {
+    let (verifying_key, transcript) = fixture("merkle_transcript.json");
+    let mut chunks = transcript.disclosed_chunks().to_vec();
+    chunks[0].proof[1][0] ^= 1;
+    let tampered = MerkleTranscript::new(
+        transcript.root(),
+        transcript.chunk_count(),
+        transcript.signature().to_vec(),
+        chunks,
+    );
+
+    assert_eq!(
+        tampered.verify(&verifying_key),
+        Err(VerifyError::BadInclusionProof { index: 0 })
+    );
+}
+
+#[test]
+fn typed_key_verifies_like_its_hex() {
+    let (verifying_key, transcript) = fixture("merkle_transcript.json");
+    let verifying_key: VerifyingKey = verifying_key.parse().unwrap();
+
+    assert_eq!(transcript.verify(&verifying_key), Ok(()));
+}
+
+#[test]
+fn iter_records_yields_every_record() {
+    let (_, transcript) = fixture("merkle_transcript.json");
+    let records = transcript.iter_records();
+
+    assert_eq!(records.clone().count(), transcript.record_count());
+    assert_eq!(records.collect::<Vec<_>>(), transcript.get_records());
+}
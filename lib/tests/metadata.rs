@@ -0,0 +1,50 @@
+use zktls_lib::abort::AbortCode;
+use zktls_lib::metadata::{AttestationMetadata, ATTESTATION_VERSION, SUPPORTED_VERSIONS};
+use zktls_lib::VerifyError;
+
+mod common;
+
+use common::fixture_json;
+
+#[test]
+fn unversioned_attestation_is_the_current_version() {
+    let metadata = AttestationMetadata::of(&fixture_json("bench16.json")).unwrap();
+
+    assert_eq!(metadata.version(), ATTESTATION_VERSION);
+    assert_eq!(metadata.attestor_id(), None);
+    assert_eq!(metadata.check(), Ok(()));
+}
+
+#[test]
+fn unsupported_version_is_rejected() {
+    let metadata = AttestationMetadata::of(&fixture_json("unsupported_version.json")).unwrap();
+    let err = metadata.check().unwrap_err();
+
+    assert_eq!(metadata.version(), 2);
+    assert_eq!(metadata.attestor_id(), Some("attestor-eu-1"));
+    assert_eq!(
+        err,
+        VerifyError::UnsupportedVersion {
+            got: 2,
+            supported: SUPPORTED_VERSIONS.to_vec(),
+        }
+    );
+    assert_eq!(AbortCode::from(&err), AbortCode::UnsupportedVersion);
+}
+
+#[test]
+fn version_that_is_not_a_u16_is_malformed() {
+    let mut attestation = fixture_json("bench16.json");
+    attestation["version"] = 65536.into();
+
+    assert_eq!(
+        AttestationMetadata::of(&attestation),
+        Err(VerifyError::MalformedInput)
+    );
+
+    attestation["version"] = "1".into();
+    assert_eq!(
+        AttestationMetadata::of(&attestation),
+        Err(VerifyError::MalformedInput)
+    );
+}
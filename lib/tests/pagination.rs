@@ -0,0 +1,38 @@
+use zktls_lib::pagination::Linkage;
+use zktls_lib::{Record, VerifyError};
+
+mod common;
+
+use common::fixture_records;
+
+fn linkage() -> Linkage {
+    Linkage {
+        next_cursor_path: "$.next_cursor".to_string(),
+        cursor_param: "cursor".to_string(),
+    }
+}
+
+fn page(number: u32) -> Vec<Record> {
+    fixture_records(&format!("transactions_page_{}.json", number))
+}
+
+#[test]
+fn pages_in_order_form_a_chain() {
+    assert_eq!(linkage().check(&[page(1), page(2), page(3)]), Ok(()));
+}
+
+#[test]
+fn skipped_page_breaks_the_chain() {
+    assert_eq!(
+        linkage().check(&[page(1), page(3)]),
+        Err(VerifyError::BrokenChain { page: 1 })
+    );
+}
+
+#[test]
+fn reordered_pages_break_the_chain() {
+    assert_eq!(
+        linkage().check(&[page(1), page(2), page(1)]),
+        Err(VerifyError::BrokenChain { page: 2 })
+    );
+}
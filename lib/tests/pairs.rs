@@ -0,0 +1,57 @@
+use zktls_lib::pair::pairs;
+use zktls_lib::{Record, VerifyError};
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn pairs_each_request_with_its_response() {
+    let records = fixture_records("three_pairs.json");
+
+    let pairs = pairs(&records).unwrap();
+
+    assert_eq!(pairs.len(), 3);
+    assert!(pairs[1].request.bytes.starts_with(b"POST /v1/transfers "));
+    assert!(pairs[1].response.bytes.starts_with(b"HTTP/1.1 201 Created"));
+    assert_eq!(pairs[2].response, records[5]);
+}
+
+#[test]
+fn joins_messages_spanning_several_records() {
+    let mut records = fixture_records("three_pairs.json");
+    let split = records[1].bytes.len() - 10;
+    let tail = Record {
+        bytes: records[1].bytes.split_off(split),
+        redacted: Vec::new(),
+    };
+    records.insert(2, tail);
+
+    let pairs = pairs(&records).unwrap();
+
+    assert_eq!(pairs.len(), 3);
+    assert_eq!(pairs[0].response, fixture_records("three_pairs.json")[1]);
+}
+
+#[test]
+fn rejects_a_request_without_response() {
+    let mut records = fixture_records("three_pairs.json");
+    records.remove(3);
+
+    assert_eq!(pairs(&records), Err(VerifyError::OrphanRecord { index: 2 }));
+
+    let mut records = fixture_records("three_pairs.json");
+    records.pop();
+    assert_eq!(pairs(&records), Err(VerifyError::OrphanRecord { index: 4 }));
+}
+
+#[test]
+fn rejects_a_body_in_answer_to_head() {
+    let mut records = fixture_records("three_pairs.json");
+    records[0].bytes.splice(..3, *b"HEAD");
+
+    assert_eq!(
+        pairs(&records),
+        Err(VerifyError::MismatchedResponse { record: 1 })
+    );
+}
@@ -0,0 +1,62 @@
+use zktls_lib::predicate::{Comparator, Predicate};
+use zktls_lib::VerifyError;
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn parses_scaled_thresholds() {
+    let predicate: Predicate = "$.balance >= 1000000".parse().unwrap();
+    assert_eq!(predicate.comparator, Comparator::GreaterOrEqual);
+    assert_eq!((predicate.threshold, predicate.scale), (1_000_000, 0));
+
+    let predicate: Predicate = "$.rates[0] <= -0.25".parse().unwrap();
+    assert_eq!((predicate.threshold, predicate.scale), (-25, 2));
+    assert_eq!(predicate.to_string(), "$.rates[0] <= -0.25");
+
+    assert!("$.balance > 1".parse::<Predicate>().is_err());
+    assert!("balance >= 1".parse::<Predicate>().is_err());
+    assert!("$.balance >= 1e6".parse::<Predicate>().is_err());
+}
+
+#[test]
+fn evaluates_fixtures_straddling_the_threshold() {
+    let predicate: Predicate = "$.balance >= 1000000".parse().unwrap();
+
+    assert_eq!(
+        predicate.evaluate(&fixture_records("balance_above.json")),
+        Ok(true)
+    );
+    assert_eq!(
+        predicate.evaluate(&fixture_records("balance_below.json")),
+        Ok(false)
+    );
+
+    let predicate: Predicate = "$.balance == 999999.990".parse().unwrap();
+    assert_eq!(
+        predicate.evaluate(&fixture_records("balance_below.json")),
+        Ok(true)
+    );
+}
+
+#[test]
+fn rejects_non_numeric_values() {
+    let records = fixture_records("balance_above.json");
+
+    let predicate: Predicate = "$.account >= 1".parse().unwrap();
+    assert_eq!(
+        predicate.evaluate(&records),
+        Err(VerifyError::NotANumber {
+            path: "$.account".to_string()
+        })
+    );
+
+    let predicate: Predicate = "$.missing >= 1".parse().unwrap();
+    assert_eq!(
+        predicate.evaluate(&records),
+        Err(VerifyError::PathNotFound {
+            path: "$.missing".to_string()
+        })
+    );
+}
@@ -0,0 +1,93 @@
+use zktls_lib::hash::{HashAlgorithm, RecordHasher};
+use zktls_lib::preview::{preview_records, MAX_PREVIEW_BYTES};
+use zktls_lib::Record;
+
+mod common;
+
+use common::fixture_records;
+
+fn records(texts: &[&str]) -> Vec<Record> {
+    texts
+        .iter()
+        .map(|text| Record::from_hex(&hex::encode(text)).unwrap())
+        .collect()
+}
+
+#[test]
+fn previews_the_start_of_the_concatenated_records() {
+    let records = records(&["GET / HTTP/1.1\r\n\r\n", "HTTP/1.1 200 OK\r\n\r\n"]);
+
+    let preview = preview_records(&records, 24);
+    assert_eq!(preview.bytes, b"GET / HTTP/1.1\r\n\r\nHTTP/1");
+    assert_eq!(preview.total_bytes, 37);
+    assert!(!preview.is_complete());
+    assert_eq!(
+        preview.digest,
+        RecordHasher::new(HashAlgorithm::Keccak256).hash_parts([
+            b"GET / HTTP/1.1\r\n\r\n".as_slice(),
+            b"HTTP/1.1 200 OK\r\n\r\n"
+        ])
+    );
+}
+
+#[test]
+fn short_records_are_previewed_whole() {
+    let records = records(&["ok"]);
+
+    let preview = preview_records(&records, 16);
+    assert_eq!(preview.bytes, b"ok");
+    assert!(preview.is_complete());
+}
+
+#[test]
+fn preview_is_capped() {
+    let records = fixture_records("bench1024.json");
+
+    let preview = preview_records(&records, u32::MAX);
+    assert_eq!(preview.bytes.len(), MAX_PREVIEW_BYTES as usize);
+    assert_eq!(
+        preview.bytes,
+        records[0].bytes[..MAX_PREVIEW_BYTES as usize]
+    );
+}
+
+#[test]
+fn split_character_is_left_out() {
+    // "é" is two bytes, "€" three and "😀" four.
+    for (text, max_bytes, expected) in [
+        ("caf\u{e9}", 4, "caf"),
+        ("caf\u{e9}", 5, "caf\u{e9}"),
+        ("1\u{20ac}", 2, "1"),
+        ("1\u{20ac}", 3, "1"),
+        ("1\u{20ac}", 4, "1\u{20ac}"),
+        ("a\u{1f600}", 4, "a"),
+        ("a\u{1f600}", 5, "a\u{1f600}"),
+    ] {
+        let preview = preview_records(&records(&[text]), max_bytes);
+        assert_eq!(
+            preview.bytes,
+            expected.as_bytes(),
+            "{:?} at {}",
+            text,
+            max_bytes
+        );
+    }
+}
+
+#[test]
+fn character_split_across_records_is_left_out() {
+    let mut records = records(&["caf", ""]);
+    records[0].bytes.push(0xc3);
+    records[1].bytes.push(0xa9);
+
+    assert_eq!(preview_records(&records, 4).bytes, b"caf");
+}
+
+#[test]
+fn binary_bytes_are_previewed_as_they_are() {
+    let records = vec![Record::from_hex("00ff80c3c3").unwrap()];
+
+    // A lead byte followed by another lead byte is not a character the limit split.
+    assert_eq!(preview_records(&records, 4).bytes, [0x00, 0xff, 0x80, 0xc3]);
+    assert_eq!(preview_records(&records, 3).bytes, [0x00, 0xff, 0x80]);
+}
@@ -0,0 +1,30 @@
+use zktls_lib::public_values::{public_values_digest, public_values_digest_bn254};
+use zktls_lib::PublicValues;
+
+/// The public values stream committed for `bench16.json`: the bincode encoding of one
+/// `PublicValues`.
+const STREAM: &[u8] = include_bytes!("../../fixtures/zktls/bench16_public_values.bin");
+
+/// SHA-256 of [`STREAM`], as `sha256sum` computes it.
+const STREAM_SHA256: &str = "304648d5a7192cba2b400b3cb9960583d17c9d894e096fe79e4a0f0a5fd40dd7";
+
+#[test]
+fn digest_is_the_sha256_of_the_stream() {
+    let public_values: PublicValues = bincode::deserialize(STREAM).unwrap();
+
+    assert_eq!(bincode::serialize(&public_values).unwrap(), STREAM);
+    assert_eq!(
+        hex::encode(public_values_digest(&public_values)),
+        STREAM_SHA256
+    );
+}
+
+#[test]
+fn bn254_digest_clears_the_top_three_bits() {
+    let public_values: PublicValues = bincode::deserialize(STREAM).unwrap();
+
+    assert_eq!(
+        hex::encode(public_values_digest_bn254(&public_values)),
+        "104648d5a7192cba2b400b3cb9960583d17c9d894e096fe79e4a0f0a5fd40dd7"
+    );
+}
@@ -0,0 +1,159 @@
+//! The bincode layout of [`PublicValues`], which decoders outside Rust rely on: any change to a
+//! field's type or position shows here.
+
+use zktls_lib::abort::STATUS_OK;
+use zktls_lib::date::NO_RESPONSE_DATE;
+use zktls_lib::public_values::{DigestMode, RecordDigests};
+use zktls_lib::sequence::UNKNOWN_SEQUENCE;
+use zktls_lib::tls::TlsSession;
+use zktls_lib::{PublicValues, Record};
+
+const KEY: &str = "0352e3fd73b10d77550dba5b8f327a2c9c8b4c980e460b19d96c58529473a60a53";
+
+fn public_values() -> PublicValues {
+    PublicValues {
+        status: STATUS_OK,
+        verifying_key: KEY.parse().unwrap(),
+        format_tag: 0,
+        attestation_digest: [0x01; 32],
+        verified: true,
+        records: vec![Record {
+            bytes: b"ok".to_vec(),
+            redacted: Vec::new(),
+        }],
+        context: None,
+        largest_record_bytes: 2,
+        digests: RecordDigests {
+            request_digest: [0xaa; 32],
+            response_digest: [0xbb; 32],
+            records_digest: [0xcc; 32],
+        },
+        predicate: None,
+        cross_check: None,
+        bodies_digest: None,
+        digest_mode: DigestMode::Concatenated.tag(),
+        record_digests: Vec::new(),
+        eip712_records_hash: None,
+        paginated: None,
+        first_sequence: UNKNOWN_SEQUENCE,
+        last_sequence: UNKNOWN_SEQUENCE,
+        batch: None,
+        records_encoding: 0,
+        decoded_bodies: Vec::new(),
+        body_length: None,
+        response_header: None,
+        body_digests: None,
+        text_validated: false,
+        request_template: None,
+        prefix_records: None,
+        sanitized: true,
+        response_date: NO_RESPONSE_DATE,
+        tls_session: TlsSession::default(),
+        attestation_version: 1,
+        record_commitment: 0,
+        record_payload: 0,
+        streamed_bytes: None,
+        ranges: Vec::new(),
+        cosigned: None,
+        extracted: Vec::new(),
+        preview: None,
+    }
+}
+
+/// The encoding of [`public_values`] up to and including `digests`. Integers are little-endian,
+/// a `Vec` or string is preceded by its length as a `u64`, and an `Option` by a 0 or 1 byte.
+fn head() -> String {
+    [
+        "00000000".to_string(),                  // status
+        "4200000000000000".to_string(),          // verifying_key length
+        hex::encode(KEY),                        // verifying_key
+        "00".to_string(),                        // format_tag
+        "01".repeat(32),                         // attestation_digest
+        "01".to_string(),                        // verified
+        "0100000000000000".to_string(),          // records length
+        "0200000000000000".to_string() + "6f6b", // record bytes
+        "0000000000000000".to_string(),          // record redactions
+        "00".to_string(),                        // context
+        "02000000".to_string(),                  // largest_record_bytes
+        "aa".repeat(32),                         // digests.request_digest
+        "bb".repeat(32),                         // digests.response_digest
+        "cc".repeat(32),                         // digests.records_digest
+    ]
+    .concat()
+}
+
+/// The encoding of [`public_values`] from `eip712_records_hash` on.
+fn tail() -> String {
+    [
+        "00",               // eip712_records_hash
+        "00",               // paginated
+        "ffffffffffffffff", // first_sequence
+        "ffffffffffffffff", // last_sequence
+        "00",               // batch
+        "00",               // records_encoding
+        "0000000000000000", // decoded_bodies
+        "00",               // body_length
+        "00",               // response_header
+        "00",               // body_digests
+        "00",               // text_validated
+        "00",               // request_template
+        "00",               // prefix_records
+        "01",               // sanitized
+        "0000000000000000", // response_date
+        "0000",             // tls_session
+        "0100",             // attestation_version
+        "00",               // record_commitment
+        "00",               // record_payload
+        "00",               // streamed_bytes
+        "0000000000000000", // ranges
+        "00",               // cosigned
+        "0000000000000000", // extracted
+        "00",               // preview
+    ]
+    .concat()
+}
+
+#[test]
+fn direction_digests_follow_the_records() {
+    let expected = [
+        head(),
+        "00".to_string(),               // predicate
+        "00".to_string(),               // cross_check
+        "00".to_string(),               // bodies_digest
+        "00".to_string(),               // digest_mode
+        "0000000000000000".to_string(), // record_digests
+        tail(),
+    ]
+    .concat();
+
+    assert_eq!(
+        hex::encode(bincode::serialize(&public_values()).unwrap()),
+        expected
+    );
+}
+
+#[test]
+fn per_record_digests_follow_the_mode_byte() {
+    let public_values = PublicValues {
+        digest_mode: DigestMode::PerRecord.tag(),
+        record_digests: vec![[0x11; 32], [0x22; 32]],
+        ..public_values()
+    };
+    let expected = [
+        head(),
+        "00".to_string(),               // predicate
+        "00".to_string(),               // cross_check
+        "00".to_string(),               // bodies_digest
+        "01".to_string(),               // digest_mode
+        "0200000000000000".to_string(), // record_digests length
+        "11".repeat(32),                // record_digests[0]
+        "22".repeat(32),                // record_digests[1]
+        tail(),
+    ]
+    .concat();
+
+    assert_eq!(
+        hex::encode(bincode::serialize(&public_values).unwrap()),
+        expected
+    );
+}
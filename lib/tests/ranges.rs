@@ -0,0 +1,128 @@
+use zktls_lib::hash::RecordHasher;
+use zktls_lib::record::{
+    commit_ranges, extract_ranges, ExtractedRange, RangeCommitment, RangeRequest,
+};
+use zktls_lib::VerifyError;
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn commits_each_range_in_order() {
+    let records = fixture_records("balance_above.json");
+    let ranges = [
+        RangeRequest {
+            record: 1,
+            start: 92,
+            end: 114,
+        },
+        RangeRequest {
+            record: 0,
+            start: 4,
+            end: 24,
+        },
+    ];
+
+    let digest = |bytes: &[u8]| RecordHasher::default().hash_parts([bytes]);
+    assert_eq!(
+        commit_ranges(&records, &ranges),
+        Ok(vec![
+            RangeCommitment {
+                record: 1,
+                start: 92,
+                end: 114,
+                digest: digest(br#""balance":"1000000.01""#),
+            },
+            RangeCommitment {
+                record: 0,
+                start: 4,
+                end: 24,
+                digest: digest(b"/v1/accounts/acc_123"),
+            },
+        ])
+    );
+    assert_eq!(
+        records[1].commit_range(92, 114),
+        digest(br#""balance":"1000000.01""#)
+    );
+}
+
+#[test]
+fn rejects_ranges_outside_their_record() {
+    let records = fixture_records("balance_above.json");
+    let check = |record, start, end| {
+        commit_ranges(&records, &[RangeRequest { record, start, end }]).map(|_| ())
+    };
+
+    assert_eq!(check(1, 0, 115), Ok(()));
+    assert_eq!(check(1, 115, 115), Ok(()));
+    assert_eq!(
+        check(1, 100, 116),
+        Err(VerifyError::RangeOutOfBounds {
+            record: 1,
+            start: 100,
+            end: 116
+        })
+    );
+    assert_eq!(
+        check(0, 10, 4),
+        Err(VerifyError::RangeOutOfBounds {
+            record: 0,
+            start: 10,
+            end: 4
+        })
+    );
+    assert_eq!(
+        check(2, 0, 1),
+        Err(VerifyError::RangeOutOfBounds {
+            record: 2,
+            start: 0,
+            end: 1
+        })
+    );
+}
+
+#[test]
+fn extracts_each_range_verbatim() {
+    let records = fixture_records("html_page.json");
+    let ranges = [
+        RangeRequest {
+            record: 1,
+            start: 1870,
+            end: 1908,
+        },
+        RangeRequest {
+            record: 0,
+            start: 0,
+            end: 3,
+        },
+    ];
+
+    let extracted = extract_ranges(&records, &ranges).unwrap();
+    assert_eq!(
+        extracted[0],
+        ExtractedRange {
+            record: 1,
+            start: 1870,
+            end: 1908,
+            bytes: br#"<span id="balance">1,024.50 USD</span>"#.to_vec(),
+        }
+    );
+    assert_eq!(extracted[1].bytes, b"GET");
+    assert_eq!(
+        extract_ranges(
+            &records,
+            &[RangeRequest {
+                record: 1,
+                start: 1900,
+                end: 1932
+            }]
+        ),
+        Err(VerifyError::RangeOutOfBounds {
+            record: 1,
+            start: 1900,
+            end: 1932
+        })
+    );
+}
@@ -0,0 +1,34 @@
+use zktls_lib::record::RecordsEncoding;
+use zktls_lib::{Record, VerifyError};
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn hex_records_decode_to_the_raw_fixture() {
+    let mut records = fixture_records("balance_above_hex.json");
+
+    RecordsEncoding::Hex.decode(&mut records).unwrap();
+
+    assert_eq!(records, fixture_records("balance_above.json"));
+}
+
+#[test]
+fn invalid_hex_names_the_record() {
+    let mut records = fixture_records("balance_above_hex.json");
+    records.push(Record {
+        bytes: b"not hex".to_vec(),
+        redacted: Vec::new(),
+    });
+
+    assert_eq!(
+        RecordsEncoding::Hex.decode(&mut records),
+        Err(VerifyError::InvalidRecordEncoding { record: 2 })
+    );
+    assert_eq!("hex".parse(), Ok(RecordsEncoding::Hex));
+    assert_eq!(
+        RecordsEncoding::from_tag(RecordsEncoding::Raw.tag()),
+        Some(RecordsEncoding::Raw)
+    );
+}
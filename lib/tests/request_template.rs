@@ -0,0 +1,74 @@
+use zktls_lib::request_template::{remove_requests, RequestTemplate, TemplateComponent};
+use zktls_lib::VerifyError;
+
+mod common;
+
+use common::fixture_records;
+
+fn template(name: &str) -> RequestTemplate {
+    let path = format!("{}/../fixtures/zktls/{}", env!("CARGO_MANIFEST_DIR"), name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn mismatch(template: &RequestTemplate) -> Option<TemplateComponent> {
+    match template.check(&fixture_records("account_request.json")) {
+        Ok(()) => None,
+        Err(VerifyError::RequestMismatch { component }) => Some(component),
+        Err(err) => panic!("unexpected error: {:?}", err),
+    }
+}
+
+#[test]
+fn matches_the_template_without_its_bearer_token() {
+    assert_eq!(mismatch(&template("request_template.json")), None);
+}
+
+#[test]
+fn names_the_component_that_differs() {
+    assert_eq!(
+        mismatch(&template("request_template_mismatch.json")),
+        Some(TemplateComponent::Query {
+            name: "currency".to_string()
+        })
+    );
+
+    let mut wrong_method = template("request_template.json");
+    wrong_method.method = "POST".to_string();
+    assert_eq!(mismatch(&wrong_method), Some(TemplateComponent::Method));
+
+    // A path wildcard matches within one segment only.
+    let mut wrong_path = template("request_template.json");
+    wrong_path.path = "/v2/*".to_string();
+    assert_eq!(mismatch(&wrong_path), Some(TemplateComponent::Path));
+
+    let mut unignored = template("request_template.json");
+    unignored
+        .ignore_headers
+        .retain(|name| name != "Authorization");
+    assert_eq!(
+        mismatch(&unignored),
+        Some(TemplateComponent::UnexpectedHeader {
+            name: "Authorization".to_string()
+        })
+    );
+}
+
+#[test]
+fn a_transcript_without_request_does_not_match() {
+    assert_eq!(
+        template("request_template.json").check(&fixture_records("bench16.json")),
+        Err(VerifyError::RequestMismatch {
+            component: TemplateComponent::Request
+        })
+    );
+}
+
+#[test]
+fn removes_the_request_records() {
+    let mut records = fixture_records("account_request.json");
+    let response = records[1].clone();
+
+    remove_requests(&mut records);
+
+    assert_eq!(records, vec![response]);
+}
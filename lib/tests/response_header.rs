@@ -0,0 +1,42 @@
+use zktls_lib::response_header::ResponseHeader;
+use zktls_lib::RecordDigests;
+
+mod common;
+
+use common::fixture_records;
+
+fn extract(fixture: &str, name: &str) -> ResponseHeader {
+    let records = fixture_records(fixture);
+    let records_digest = RecordDigests::new(&records).records_digest;
+    ResponseHeader::extract(&records, name.to_string(), records_digest)
+}
+
+#[test]
+fn header_is_found_case_insensitively() {
+    let header = extract("etag_response.json", "etag");
+
+    assert_eq!(header.name, "etag");
+    assert_eq!(header.value, "\"33a64df551425fcc\"");
+    assert_eq!(header.count, 1);
+    assert_eq!(
+        header.records_digest,
+        RecordDigests::new(&fixture_records("etag_response.json")).records_digest
+    );
+}
+
+#[test]
+fn repeated_header_commits_the_first_value_and_count() {
+    let header = extract("etag_response.json", "Set-Cookie");
+
+    assert_eq!(header.value, "session=abc; Path=/");
+    assert_eq!(header.count, 2);
+}
+
+#[test]
+fn missing_header_commits_an_empty_value() {
+    let header = extract("balance_above.json", "x-request-id");
+
+    assert_eq!(header.value, "");
+    assert_eq!(header.count, 0);
+    assert_eq!(extract("bench16.json", "etag").count, 0);
+}
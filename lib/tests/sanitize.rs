@@ -0,0 +1,55 @@
+use zktls_lib::record::{redact, ByteRange, Redaction};
+use zktls_lib::sanitize::sanitize;
+
+mod common;
+
+use common::fixture_records;
+
+const SECRETS: [&[u8]; 3] = [
+    b"sk_live_9QmX2vR7tK4pL8wZ",
+    b"session=3f1c9e7a5b2d8046",
+    b"ak_51Hd8eP0q3Vz",
+];
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[test]
+fn masks_every_credential_header() {
+    let mut records = fixture_records("sensitive_headers.json");
+    let response = records[1].clone();
+
+    sanitize(&mut records);
+
+    for secret in SECRETS {
+        assert!(!contains(&records[0].bytes, secret));
+    }
+    assert!(contains(&records[0].bytes, b"\r\nAuthorization: \0"));
+    assert!(contains(
+        &records[0].bytes,
+        b"\r\nAccept: application/json\r\n"
+    ));
+    assert_eq!(records[0].redacted.len(), 3);
+    assert_eq!(records[1], response);
+}
+
+#[test]
+fn merges_with_existing_redactions() {
+    let mut records = fixture_records("redacted_auth.json");
+    redact(
+        &mut records,
+        &[Redaction {
+            record: 0,
+            start: 66,
+            end: 84,
+        }],
+    )
+    .unwrap();
+
+    sanitize(&mut records);
+
+    assert_eq!(records[0].redacted, vec![ByteRange { start: 59, end: 84 }]);
+}
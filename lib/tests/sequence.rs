@@ -0,0 +1,84 @@
+use zktls_lib::sequence::{
+    check_sequence, check_session_sequence, sequence_numbers, UNKNOWN_SEQUENCE,
+};
+use zktls_lib::tls::TlsSession;
+use zktls_lib::VerifyError;
+
+mod common;
+
+use common::fixture_json;
+
+#[test]
+fn nonces_yield_increasing_sequence() {
+    let sequence = sequence_numbers(&fixture_json("bench16.json")).unwrap();
+
+    assert_eq!(
+        sequence,
+        [
+            0xeb4310788ee32225,
+            0xeb4310788ee32226,
+            0xeb4310788ee32227,
+            0xeb4310788ee32228
+        ]
+    );
+    assert_eq!(
+        check_sequence(Some(&sequence)),
+        Ok((0xeb4310788ee32225, 0xeb4310788ee32228))
+    );
+}
+
+#[test]
+fn reordered_transcript_is_rejected() {
+    let sequence = sequence_numbers(&fixture_json("bench16_reordered.json")).unwrap();
+
+    assert_eq!(
+        check_sequence(Some(&sequence)),
+        Err(VerifyError::OutOfOrder { record: 2 })
+    );
+}
+
+#[test]
+fn tls13_nonces_are_not_checked() {
+    let attestation = fixture_json("tls13_session.json");
+    let sequence = sequence_numbers(&attestation).unwrap();
+
+    assert!(check_sequence(Some(&sequence)).is_err());
+    assert_eq!(
+        check_session_sequence(&TlsSession::of(&attestation), Some(&sequence)),
+        Ok((UNKNOWN_SEQUENCE, UNKNOWN_SEQUENCE))
+    );
+}
+
+#[test]
+fn unstated_version_is_not_checked() {
+    let attestation = fixture_json("bench16.json");
+    let sequence = sequence_numbers(&attestation).unwrap();
+
+    assert_eq!(
+        check_session_sequence(&TlsSession::of(&attestation), Some(&sequence)),
+        Ok((UNKNOWN_SEQUENCE, UNKNOWN_SEQUENCE))
+    );
+}
+
+#[test]
+fn reordered_tls12_session_is_rejected() {
+    let attestation = fixture_json("bench16_reordered.json");
+    let sequence = sequence_numbers(&attestation).unwrap();
+
+    assert_eq!(
+        check_session_sequence(&TlsSession::of(&attestation), Some(&sequence)),
+        Err(VerifyError::OutOfOrder { record: 2 })
+    );
+}
+
+#[test]
+fn missing_sequence_commits_sentinel() {
+    assert_eq!(
+        sequence_numbers(&fixture_json("merkle_transcript.json")),
+        None
+    );
+    assert_eq!(
+        check_sequence(None),
+        Ok((UNKNOWN_SEQUENCE, UNKNOWN_SEQUENCE))
+    );
+}
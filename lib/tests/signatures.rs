@@ -0,0 +1,56 @@
+use zktls_lib::signatures::{check_signature_count, signature_count};
+use zktls_lib::VerifyError;
+
+mod common;
+
+use common::fixture_json;
+
+#[test]
+fn one_signature_per_packet_passes() {
+    for name in ["bench16.json", "bench256.json", "balance_above.json"] {
+        let attestation = fixture_json(name);
+        assert_eq!(signature_count(&attestation), 1, "{}", name);
+        assert_eq!(check_signature_count(&attestation), Ok(()), "{}", name);
+    }
+}
+
+#[test]
+fn missing_signature_is_rejected() {
+    let attestation = fixture_json("bench16_missing_signature.json");
+
+    assert_eq!(signature_count(&attestation), 0);
+    assert_eq!(
+        check_signature_count(&attestation),
+        Err(VerifyError::SignatureCountMismatch {
+            records: 4,
+            signatures: 0,
+        })
+    );
+}
+
+#[test]
+fn duplicated_signature_is_rejected() {
+    let attestation = fixture_json("bench16_extra_signature.json");
+
+    assert_eq!(signature_count(&attestation), 2);
+    assert_eq!(
+        check_signature_count(&attestation),
+        Err(VerifyError::SignatureCountMismatch {
+            records: 4,
+            signatures: 2,
+        })
+    );
+}
+
+#[test]
+fn truncated_signature_counts_as_none() {
+    let mut attestation = fixture_json("bench16.json");
+    let signature = attestation["packets"][0]["ecdsa_signature"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    attestation["packets"][0]["ecdsa_signature"] = signature[..126].into();
+
+    assert_eq!(signature_count(&attestation), 0);
+    assert!(check_signature_count(&attestation).is_err());
+}
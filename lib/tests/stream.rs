@@ -0,0 +1,180 @@
+mod common;
+
+use common::fixture_records;
+use zktls_lib::hash::RecordHasher;
+use zktls_lib::stream::{BlockRun, StreamHeader, StreamPacket, StreamRecord, StreamVerifier};
+use zktls_lib::VerifyError;
+
+fn read(path: &str) -> String {
+    std::fs::read_to_string(format!(
+        "{}/../fixtures/zktls/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        path
+    ))
+    .unwrap()
+}
+
+fn hex_array<const N: usize>(value: &serde_json::Value) -> [u8; N] {
+    hex::decode(value.as_str().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+/// A bench fixture split into a stream header and the ciphertext of its records, with runs of
+/// blocks built the way a host builds them.
+fn streamed(name: &str, chunk_bytes: u32) -> (StreamHeader, Vec<u8>) {
+    let fixture: serde_json::Value =
+        serde_json::from_str(&read(&format!("data/{}", name))).unwrap();
+    let mut ciphertext = Vec::new();
+    let packets = fixture["packets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|packet| StreamPacket {
+            aes_key: hex_array(&packet["aes_key"]),
+            signature: hex::decode(packet["ecdsa_signature"].as_str().unwrap()).unwrap(),
+            records: packet["records"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|record| {
+                    ciphertext.extend(hex::decode(record["ciphertext"].as_str().unwrap()).unwrap());
+                    let mut blocks: Vec<BlockRun> = Vec::new();
+                    for block in record["blocks"].as_array().unwrap() {
+                        let id = block["id"].as_u64().unwrap() as u32;
+                        let mask = block["mask"]
+                            .as_array()
+                            .unwrap()
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, bit)| bit.as_u64() != Some(0))
+                            .fold(0u16, |mask, (index, _)| mask | 1 << index);
+                        match blocks.last_mut() {
+                            Some(run) if run.mask == mask && run.first_id + run.count == id => {
+                                run.count += 1;
+                            }
+                            _ => blocks.push(BlockRun {
+                                first_id: id,
+                                count: 1,
+                                mask,
+                            }),
+                        }
+                    }
+                    StreamRecord {
+                        nonce: hex_array(&record["nonce"]),
+                        blocks,
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    (
+        StreamHeader {
+            chunk_bytes,
+            packets,
+        },
+        ciphertext,
+    )
+}
+
+#[test]
+fn streamed_fixture_verifies_and_digests_its_plaintext() {
+    let (header, ciphertext) = streamed("bench256.json", 64);
+    assert_eq!(header.total_bytes(), ciphertext.len() as u64);
+
+    let mut verifier = StreamVerifier::new(&header);
+    for chunk in ciphertext.chunks(64) {
+        assert!(!verifier.is_complete());
+        verifier.update(chunk).unwrap();
+    }
+    assert!(verifier.is_complete());
+    let records = verifier.finish().unwrap();
+
+    assert_eq!(records.verify(read("verifying_k256.key").trim()), Ok(()));
+    assert_eq!(records.total_bytes(), 256);
+    assert_eq!(
+        records.records_digest(),
+        RecordHasher::default().hash_records(&fixture_records("bench256.json"))
+    );
+}
+
+#[test]
+fn chunk_boundaries_do_not_change_the_digests() {
+    let (header, ciphertext) = streamed("bench16.json", 16);
+    let digest = |chunk_bytes: usize| {
+        let mut verifier = StreamVerifier::new(&header);
+        for chunk in ciphertext.chunks(chunk_bytes) {
+            verifier.update(chunk).unwrap();
+        }
+        verifier.finish().unwrap()
+    };
+
+    // The records are 2, 5, 6 and 3 bytes, so these split them at every offset.
+    assert_eq!(digest(1), digest(16));
+    assert_eq!(digest(7), digest(16));
+    assert_eq!(
+        digest(16).records_digest(),
+        RecordHasher::default().hash_records(&fixture_records("bench16.json"))
+    );
+}
+
+#[test]
+fn tampered_ciphertext_does_not_verify() {
+    let (header, mut ciphertext) = streamed("bench16.json", 16);
+    ciphertext[3] ^= 1;
+
+    let mut verifier = StreamVerifier::new(&header);
+    verifier.update(&ciphertext).unwrap();
+
+    assert_eq!(
+        verifier
+            .finish()
+            .unwrap()
+            .verify(read("verifying_k256.key").trim()),
+        Err(VerifyError::BadSignature)
+    );
+}
+
+#[test]
+fn wrong_key_does_not_verify() {
+    let (header, ciphertext) = streamed("bench16.json", 16);
+    let mut verifier = StreamVerifier::new(&header);
+    verifier.update(&ciphertext).unwrap();
+
+    assert_eq!(
+        verifier
+            .finish()
+            .unwrap()
+            .verify(read("merkle_verifying_k256.key").trim()),
+        Err(VerifyError::BadSignature)
+    );
+}
+
+#[test]
+fn chunks_must_match_the_header() {
+    let (header, ciphertext) = streamed("bench16.json", 8);
+
+    // Larger than the header's chunk size.
+    assert_eq!(
+        StreamVerifier::new(&header).update(&ciphertext),
+        Err(VerifyError::MalformedInput)
+    );
+    assert_eq!(
+        StreamVerifier::new(&header).update(&[]),
+        Err(VerifyError::MalformedInput)
+    );
+
+    // Past the last record.
+    let mut verifier = StreamVerifier::new(&header);
+    for chunk in ciphertext.chunks(8) {
+        verifier.update(chunk).unwrap();
+    }
+    assert_eq!(verifier.update(&[0]), Err(VerifyError::MalformedInput));
+
+    // Short of the last record.
+    let mut verifier = StreamVerifier::new(&header);
+    verifier.update(&ciphertext[..8]).unwrap();
+    assert_eq!(verifier.finish().err(), Some(VerifyError::MalformedInput));
+}
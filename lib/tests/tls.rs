@@ -0,0 +1,39 @@
+use serde_json::Value;
+use zktls_lib::tls::{CipherSuite, TlsSession, TlsVersion};
+
+mod common;
+
+use common::fixture_json;
+
+#[test]
+fn stated_metadata_is_committed() {
+    let session = TlsSession::of(&fixture_json("tls_session.json"));
+
+    assert_eq!(session.version(), Some(TlsVersion::Tls12));
+    assert_eq!(session.cipher_suite(), Some(CipherSuite::Aes128Gcm));
+    assert_eq!(
+        (session.version, session.cipher_suite),
+        (TlsVersion::Tls12.tag(), CipherSuite::Aes128Gcm.tag())
+    );
+}
+
+#[test]
+fn cipher_suite_falls_back_to_the_key_length() {
+    let session = TlsSession::of(&fixture_json("bench16.json"));
+
+    assert_eq!(session.version(), Some(TlsVersion::Unknown));
+    assert_eq!(session.cipher_suite(), Some(CipherSuite::Aes128Gcm));
+}
+
+#[test]
+fn disagreeing_packets_leave_the_field_unknown() {
+    let mut attestation = fixture_json("tls_session.json");
+    let packet = attestation["packets"][0].clone();
+    attestation["packets"].as_array_mut().unwrap().push(packet);
+    attestation["packets"][1]["cipher_suite"] = "TLS_CHACHA20_POLY1305_SHA256".into();
+
+    let session = TlsSession::of(&attestation);
+
+    assert_eq!(session.cipher_suite(), Some(CipherSuite::Unknown));
+    assert_eq!(TlsSession::of(&Value::Null), TlsSession::default());
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use zktls_lib::merkle::MerkleTranscript;
+use zktls_lib::VerifyError;
+
+/// Records the name of every span opened and the message of every event, with the span it was
+/// emitted in.
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<String>>>);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Capture {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _: &Id, _: Context<'_, S>) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("span {}", attrs.metadata().name()));
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        struct Message(String);
+        impl Visit for Message {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.0 = format!("{:?}", value);
+                }
+            }
+        }
+
+        let mut message = Message(String::new());
+        event.record(&mut message);
+        let span = ctx.event_span(event).map_or("", |span| span.name());
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("event {}: {}", span, message.0));
+    }
+}
+
+#[test]
+fn failing_inclusion_check_is_traced() {
+    let read = |path: &str| {
+        std::fs::read_to_string(format!(
+            "{}/../fixtures/zktls/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            path
+        ))
+        .unwrap()
+    };
+    let transcript: MerkleTranscript =
+        serde_json::from_str(&read("data/merkle_transcript_tampered.json")).unwrap();
+    let verifying_key = read("merkle_verifying_k256.key");
+
+    let capture = Capture::default();
+    let subscriber = tracing_subscriber::registry().with(capture.clone());
+    let result =
+        tracing::subscriber::with_default(subscriber, || transcript.verify(&verifying_key));
+
+    assert_eq!(result, Err(VerifyError::BadInclusionProof { index: 7 }));
+    assert_eq!(
+        *capture.0.lock().unwrap(),
+        [
+            "span verify",
+            "span key_parse",
+            "span signature_check",
+            "span inclusion_check",
+            "span inclusion_check",
+            "event inclusion_check: chunk is not included under the root",
+        ]
+    );
+}
@@ -0,0 +1,35 @@
+use zktls_lib::record::{check_utf8, redact, Redaction};
+use zktls_lib::VerifyError;
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn reports_the_first_invalid_sequence() {
+    assert_eq!(
+        check_utf8(&fixture_records("invalid_utf8.json")),
+        Err(VerifyError::InvalidUtf8 {
+            record: 1,
+            offset: 83
+        })
+    );
+    assert_eq!(check_utf8(&fixture_records("balance_above.json")), Ok(()));
+}
+
+#[test]
+fn checks_the_records_after_redaction() {
+    let mut records = fixture_records("invalid_utf8.json");
+    let body = records[1].bytes.len() as u32 - 38;
+    redact(
+        &mut records,
+        &[Redaction {
+            record: 1,
+            start: body + 9,
+            end: body + 21,
+        }],
+    )
+    .unwrap();
+
+    assert_eq!(check_utf8(&records), Ok(()));
+}
@@ -0,0 +1,50 @@
+use zktls_lib::{VerifyConfig, VerifyError};
+
+mod common;
+
+use common::fixture_records;
+
+#[test]
+fn records_at_the_limits_pass() {
+    let records = fixture_records("bench16.json");
+    let config = VerifyConfig {
+        max_records: records.len(),
+        max_total_bytes: 16,
+    };
+
+    assert_eq!(config.check(&records), Ok(()));
+}
+
+#[test]
+fn one_record_over_the_limit_fails() {
+    let records = fixture_records("bench16.json");
+    let config = VerifyConfig {
+        max_records: records.len() - 1,
+        max_total_bytes: 16,
+    };
+
+    assert_eq!(
+        config.check(&records),
+        Err(VerifyError::TooLarge {
+            records: 4,
+            total_bytes: 16
+        })
+    );
+}
+
+#[test]
+fn one_byte_over_the_limit_fails() {
+    let records = fixture_records("bench16.json");
+    let config = VerifyConfig {
+        max_records: records.len(),
+        max_total_bytes: 15,
+    };
+
+    assert_eq!(
+        config.check(&records),
+        Err(VerifyError::TooLarge {
+            records: 4,
+            total_bytes: 16
+        })
+    );
+}
@@ -0,0 +1,107 @@
+use zktls_lib::verifying_data::{MissingField, VerifyingData};
+use zktls_lib::VerifyError;
+
+fn read(path: &str) -> String {
+    std::fs::read_to_string(format!(
+        "{}/../fixtures/zktls/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        path
+    ))
+    .unwrap()
+}
+
+fn bench16() -> serde_json::Value {
+    serde_json::from_str(&read("data/bench16.json")).unwrap()
+}
+
+#[test]
+fn complete_attestation_converts_and_verifies() {
+    let data = VerifyingData::of(&bench16()).unwrap();
+
+    assert_eq!(data.packets.len(), 1);
+    assert_eq!(data.records().count(), 4);
+    assert_eq!(
+        data.stream_header(16).total_bytes(),
+        data.records()
+            .map(|record| record.plaintext.len() as u64)
+            .sum::<u64>()
+    );
+    assert_eq!(data.verify(read("verifying_k256.key").trim()), Ok(()));
+    assert_eq!(
+        data.verify(read("merkle_verifying_k256.key").trim()),
+        Err(VerifyError::BadSignature)
+    );
+}
+
+#[test]
+fn missing_packet_field_is_named() {
+    let mut fixture = bench16();
+    fixture["packets"][0]
+        .as_object_mut()
+        .unwrap()
+        .remove("ecdsa_signature");
+
+    let missing = VerifyingData::of(&fixture).unwrap_err();
+    assert_eq!(
+        missing,
+        MissingField {
+            packet: 0,
+            record: None,
+            field: "ecdsa_signature",
+        }
+    );
+    assert_eq!(
+        missing.to_string(),
+        "packet 0 has no valid `ecdsa_signature`"
+    );
+}
+
+#[test]
+fn missing_record_field_is_named() {
+    let mut fixture = bench16();
+    fixture["packets"][0]["records"][2]["nonce"] = "".into();
+
+    assert_eq!(
+        VerifyingData::of(&fixture),
+        Err(MissingField {
+            packet: 0,
+            record: Some(2),
+            field: "nonce",
+        })
+    );
+}
+
+#[test]
+fn plaintext_must_match_the_ciphertext() {
+    let mut fixture = bench16();
+    let messages = fixture["packets"][0]["record_messages"]
+        .as_array_mut()
+        .unwrap();
+    messages.pop();
+    assert_eq!(
+        VerifyingData::of(&fixture).unwrap_err().field,
+        "record_messages"
+    );
+
+    let mut fixture = bench16();
+    let message = &mut fixture["packets"][0]["record_messages"][0];
+    let mut plaintext = hex::decode(message.as_str().unwrap()).unwrap();
+    plaintext[0] ^= 1;
+    *message = hex::encode(plaintext).into();
+
+    let data = VerifyingData::of(&fixture).unwrap();
+    assert_eq!(
+        data.verify(read("verifying_k256.key").trim()),
+        Err(VerifyError::BadSignature)
+    );
+}
+
+#[test]
+fn no_packets_is_missing_packets() {
+    assert_eq!(
+        VerifyingData::of(&serde_json::json!({ "packets": [] }))
+            .unwrap_err()
+            .field,
+        "packets"
+    );
+}
@@ -6,15 +6,695 @@
 // Under the hood, we wrap your main function with some extra code so that it behaves properly
 // inside the zkVM.
 #![no_main]
-use zktls_att_verification::verification_data::VerifyingDataOpt;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use alloy_sol_types::SolValue;
+use zktls_att_verification::verification_data::{VerifyingData, VerifyingDataOpt};
+use zktls_lib::abort::{AbortCode, STATUS_OK};
+use zktls_lib::batch::BatchOutcome;
+use zktls_lib::canonical::{body_digests, canonical_digest};
+use zktls_lib::claim::{Claim, CommitMode};
+use zktls_lib::content_encoding::decode_bodies;
+use zktls_lib::context::AppContext;
+use zktls_lib::cosign::{verify_cosigned, CoSignature};
+use zktls_lib::cross_check::{CrossCheck, CrossCheckOutcome};
+use zktls_lib::date::{response_date, NO_RESPONSE_DATE};
+use zktls_lib::eip712::{eip712_records_hash, Eip712Domain};
+use zktls_lib::hash::RecordHasher;
+use zktls_lib::http::check_body_lengths;
+use zktls_lib::input::{decode, Preamble};
+use zktls_lib::key::VerifyingKey;
+use zktls_lib::merkle::MerkleTranscript;
+use zktls_lib::metadata::AttestationMetadata;
+use zktls_lib::pagination::{Linkage, PaginatedSession};
+use zktls_lib::predicate::{Predicate, PredicateOutcome};
+use zktls_lib::preview::preview_records;
+use zktls_lib::public_values::{
+    bodies_digest, digest, record_digests, DigestMode, RecordCommitment,
+};
+use zktls_lib::record::{
+    check_utf8, commit_ranges, extract_ranges, redact, RangeRequest, RecordPayload,
+    RecordsEncoding, Redaction,
+};
+use zktls_lib::request_template::{remove_requests, RequestTemplate, RequestTemplateOutcome};
+use zktls_lib::response_header::ResponseHeader;
+use zktls_lib::sanitize::sanitize;
+use zktls_lib::sequence::{check_session_sequence, sequence_numbers};
+use zktls_lib::signatures::check_signature_count;
+use zktls_lib::stream::{StreamHeader, StreamVerifier, StreamedRecords};
+use zktls_lib::{DataFormat, PublicValues, Record, RecordDigests, VerifyConfig, VerifyError};
 sp1_zkvm::entrypoint!(main);
 
+/// Whether the host asked for aborts to be committed rather than panicked.
+static REPORT_STATUS: AtomicBool = AtomicBool::new(false);
+
+/// Abort with `code`, the single exit of every failure path.
+///
+/// With status reporting, the [`AbortCode::commitment`] is the only public value and the program
+/// halts cleanly, so an execution can hand the code back to the host. A proof of that execution
+/// can exist, but its public values never decode as `PublicValues` or a claim. Otherwise the
+/// program panics, and no proof of a rejected input exists.
+fn abort(code: AbortCode, detail: impl Debug) -> ! {
+    if REPORT_STATUS.load(Ordering::Relaxed) {
+        sp1_zkvm::io::commit_slice(&code.commitment());
+        sp1_zkvm::syscalls::syscall_halt(0);
+    }
+    panic!("abort {}: {}: {:?}", code.code(), code, detail)
+}
+
+/// The attestation read from stdin, in whichever container the host selected.
+enum Attestation {
+    Opt(VerifyingDataOpt),
+    Full(VerifyingData),
+    Merkle(MerkleTranscript),
+    Stream(StreamHeader),
+}
+
+impl Attestation {
+    /// Decode the attestation item `bytes`, aborting rather than panicking if it is malformed.
+    fn decode(format: DataFormat, bytes: &[u8]) -> Self {
+        let attestation = match format {
+            DataFormat::Opt => decode(bytes).map(Attestation::Opt),
+            DataFormat::Full => decode(bytes).map(Attestation::Full),
+            DataFormat::Merkle => decode(bytes).map(Attestation::Merkle),
+            DataFormat::Stream => decode(bytes).map(Attestation::Stream),
+        };
+        attestation.unwrap_or_else(|err| abort((&err).into(), err))
+    }
+
+    fn get_records(&self) -> Vec<Record> {
+        let records = match self {
+            Attestation::Opt(data) => data.get_records(),
+            Attestation::Full(data) => data.get_records(),
+            Attestation::Merkle(data) => return data.get_records(),
+            // The ciphertext of a stream is read after every other input, and never held.
+            Attestation::Stream(_) => return Vec::new(),
+        };
+
+        records
+            .iter()
+            .map(|record| {
+                Record::from_hex(record).unwrap_or_else(|_| {
+                    let err = VerifyError::MalformedInput;
+                    abort((&err).into(), err)
+                })
+            })
+            .collect()
+    }
+
+    fn canonical_digest(&self) -> [u8; 32] {
+        match self {
+            Attestation::Opt(data) => canonical_digest(data),
+            Attestation::Full(data) => canonical_digest(data),
+            Attestation::Merkle(data) => canonical_digest(data),
+            Attestation::Stream(header) => canonical_digest(header),
+        }
+    }
+
+    /// The record sequence numbers, which Merkle transcripts and streams do not carry.
+    fn sequence_numbers(&self) -> Option<Vec<u64>> {
+        match self {
+            Attestation::Opt(data) => sequence_numbers(data),
+            Attestation::Full(data) => sequence_numbers(data),
+            Attestation::Merkle(_) | Attestation::Stream(_) => None,
+        }
+    }
+
+    fn verify(&self, verifying_key: &VerifyingKey) -> bool {
+        match self {
+            Attestation::Opt(data) => data.verify(&verifying_key.to_hex()).is_ok(),
+            Attestation::Full(data) => data.verify(&verifying_key.to_hex()).is_ok(),
+            // Only the disclosed chunks and their inclusion proofs are hashed, not the transcript.
+            Attestation::Merkle(data) => data.verify(verifying_key).is_ok(),
+            // Only the first attestation can be streamed, and it is verified from its
+            // `StreamedRecords` once they are read.
+            Attestation::Stream(_) => false,
+        }
+    }
+}
+
+/// The verifying key item and the tagged attestation item that follows it, undecoded.
+struct AttestationItems {
+    key: Vec<u8>,
+    format_tag: u8,
+    data: Vec<u8>,
+}
+
+impl AttestationItems {
+    /// Read the items, those `preamble` declares for the first attestation. An attestation item
+    /// of another length than declared is rejected from the length of the next input alone,
+    /// before its bytes are read.
+    fn read(preamble: Option<&Preamble>) -> Self {
+        let key = sp1_zkvm::io::read_vec();
+        let format_tag = sp1_zkvm::io::read();
+        let data_bytes = sp1_zkvm::syscalls::syscall_hint_len();
+        if let Some(preamble) =
+            preamble.filter(|preamble| data_bytes != preamble.data_bytes as usize)
+        {
+            abort(
+                AbortCode::BadPreamble,
+                format!(
+                    "attestation item of {} bytes, preamble declares {}",
+                    data_bytes, preamble.data_bytes
+                ),
+            );
+        }
+        AttestationItems {
+            key,
+            format_tag,
+            data: sp1_zkvm::io::read_vec(),
+        }
+    }
+
+    /// Decode the verifying key and the attestation.
+    fn decode(self) -> (VerifyingKey, u8, Attestation) {
+        let format = DataFormat::from_tag(self.format_tag)
+            .unwrap_or_else(|| abort(AbortCode::UnknownFormat, self.format_tag));
+        let verifying_key: String =
+            decode(&self.key).unwrap_or_else(|err| abort((&err).into(), err));
+        let verifying_key = VerifyingKey::try_from(verifying_key.as_str())
+            .unwrap_or_else(|err| abort((&err).into(), verifying_key));
+
+        let attestation = Attestation::decode(format, &self.data);
+        // A signature that is missing or repeated could otherwise verify fewer packets than the
+        // attestation holds.
+        if let Attestation::Opt(data) = &attestation {
+            if let Err(err) = check_signature_count(data) {
+                abort((&err).into(), err);
+            }
+        }
+
+        (verifying_key, self.format_tag, attestation)
+    }
+}
+
+/// Read a verifying key and the tagged attestation that follows it.
+fn read_attestation() -> (VerifyingKey, u8, Attestation) {
+    AttestationItems::read(None).decode()
+}
+
+/// Read an attestation after the first, which cannot be streamed, and bound its records.
+fn read_other_attestation(
+    config: &VerifyConfig,
+    max_record_bytes: u32,
+) -> (VerifyingKey, Attestation, Vec<Record>) {
+    let (verifying_key, _, attestation) = read_attestation();
+    if let Attestation::Stream(_) = attestation {
+        let err = VerifyError::MalformedInput;
+        abort((&err).into(), "only the first attestation can be streamed");
+    }
+    let records = attestation.get_records();
+    check_config(&records, config);
+    check_record_sizes(&records, max_record_bytes);
+    (verifying_key, attestation, records)
+}
+
+/// Read the chunks of a streamed attestation, which follow every other input, absorbing each
+/// before the next is read.
+fn read_stream(header: &StreamHeader) -> StreamedRecords {
+    let mut verifier = StreamVerifier::new(header);
+    while !verifier.is_complete() {
+        let chunk = sp1_zkvm::io::read_vec();
+        if let Err(err) = verifier.update(&chunk) {
+            abort((&err).into(), err);
+        }
+    }
+    verifier
+        .finish()
+        .unwrap_or_else(|err| abort((&err).into(), err))
+}
+
+/// Abort if any record exceeds `max_record_bytes`, otherwise return the largest record size.
+fn check_record_sizes(records: &[Record], max_record_bytes: u32) -> u32 {
+    let mut largest_record_bytes = 0;
+    for (index, record) in records.iter().enumerate() {
+        let record_bytes = record.bytes.len() as u32;
+        if record_bytes > max_record_bytes {
+            abort(
+                AbortCode::SizeLimitExceeded,
+                format!(
+                    "record {} is {} bytes, exceeding max_record_bytes {}",
+                    index, record_bytes, max_record_bytes
+                ),
+            );
+        }
+        largest_record_bytes = largest_record_bytes.max(record_bytes);
+    }
+    largest_record_bytes
+}
+
+/// Abort if a streamed attestation declares no records, or more records or bytes than `config`
+/// or `max_record_bytes` allow, otherwise return the largest record size.
+fn check_stream(header: &StreamHeader, config: &VerifyConfig, max_record_bytes: u32) -> u32 {
+    if header.record_count() == 0 {
+        abort(AbortCode::EmptyRecords, "no records");
+    }
+    let total_bytes = usize::try_from(header.total_bytes()).unwrap_or(usize::MAX);
+    if let Err(err) = config.check_totals(header.record_count(), total_bytes) {
+        abort((&err).into(), err);
+    }
+    let largest_record_bytes = header.largest_record_bytes();
+    if largest_record_bytes > u64::from(max_record_bytes) {
+        abort(
+            AbortCode::SizeLimitExceeded,
+            format!(
+                "a streamed record is {} bytes, exceeding max_record_bytes {}",
+                largest_record_bytes, max_record_bytes
+            ),
+        );
+    }
+    largest_record_bytes as u32
+}
+
+/// Abort if the attestation holds no records, or more records or bytes than `config` allows.
+fn check_config(records: &[Record], config: &VerifyConfig) {
+    if records.is_empty() {
+        abort(AbortCode::EmptyRecords, "no records");
+    }
+    if let Err(err) = config.check(records) {
+        abort((&err).into(), err);
+    }
+}
+
 pub fn main() {
-    let verifying_key: String = sp1_zkvm::io::read();
-    let verifying_data: VerifyingDataOpt = sp1_zkvm::io::read();
+    // Without a preamble, the host's status flag is unknown, so this abort panics without
+    // consulting it.
+    let preamble = Preamble::from_bytes(&sp1_zkvm::io::read_vec()).unwrap_or_else(|err| {
+        let code = AbortCode::from(&err);
+        panic!("abort {}: {}: no preamble", code.code(), code)
+    });
+    REPORT_STATUS.store(preamble.report_status, Ordering::Relaxed);
+    let record_commitment_tag: u8 = sp1_zkvm::io::read();
+    let record_commitment = RecordCommitment::from_tag(record_commitment_tag)
+        .unwrap_or_else(|| abort(AbortCode::UnknownRecordCommitment, record_commitment_tag));
+
+    // The key and attestation are checked against the preamble before either is decoded.
+    let items = AttestationItems::read(Some(&preamble));
+    if let Err(err) = preamble.check(&items.key, &items.data) {
+        abort((&err).into(), err);
+    }
+    // The container drops the metadata, so the host writes it after the attestation. A version
+    // this program does not understand is rejected before anything is parsed from it.
+    let metadata: AttestationMetadata = sp1_zkvm::io::read();
+    if let Err(err) = metadata.check() {
+        abort((&err).into(), err);
+    }
+    let (verifying_key, format_tag, verifying_data) = items.decode();
+    let max_record_bytes: u32 = sp1_zkvm::io::read();
+    let config: VerifyConfig = sp1_zkvm::io::read();
+    let redactions: Vec<Redaction> = sp1_zkvm::io::read();
+    let predicate: Option<Predicate> = sp1_zkvm::io::read();
+    let bodies_only: bool = sp1_zkvm::io::read();
+    let digest_mode_tag: u8 = sp1_zkvm::io::read();
+    let digest_mode = DigestMode::from_tag(digest_mode_tag)
+        .unwrap_or_else(|| abort(AbortCode::UnknownDigestMode, digest_mode_tag));
+    let eip712_domain: Option<Eip712Domain> = sp1_zkvm::io::read();
+    let records_encoding_tag: u8 = sp1_zkvm::io::read();
+    let records_encoding = RecordsEncoding::from_tag(records_encoding_tag)
+        .unwrap_or_else(|| abort(AbortCode::UnknownRecordsEncoding, records_encoding_tag));
+    let record_payload_tag: u8 = sp1_zkvm::io::read();
+    let record_payload = RecordPayload::from_tag(record_payload_tag)
+        .unwrap_or_else(|| abort(AbortCode::UnknownRecordPayload, record_payload_tag));
+    let max_decoded_bytes: u32 = sp1_zkvm::io::read();
+    let header: Option<String> = sp1_zkvm::io::read();
+    let canonical_body: bool = sp1_zkvm::io::read();
+    let text_records: bool = sp1_zkvm::io::read();
+    let request_template: Option<RequestTemplate> = sp1_zkvm::io::read();
+    let prefix_records: Option<u32> = sp1_zkvm::io::read();
+    let sanitized: bool = sp1_zkvm::io::read();
+    let cross_check: Option<CrossCheck> = sp1_zkvm::io::read();
+
+    // Bound every record before doing any signature work; a stream is bounded by what its header
+    // declares, and its chunks are checked against the header as they are read.
+    let mut records = verifying_data.get_records();
+    let largest_record_bytes = match &verifying_data {
+        Attestation::Stream(header) => check_stream(header, &config, max_record_bytes),
+        _ => {
+            check_config(&records, &config);
+            check_record_sizes(&records, max_record_bytes)
+        }
+    };
+    if let Some(prefix_records) = prefix_records.filter(|&n| n as usize != records.len()) {
+        abort(
+            AbortCode::PrefixMismatch,
+            format!(
+                "prefix of {} records, attestation holds {}",
+                prefix_records,
+                records.len()
+            ),
+        );
+    }
+
+    // A cross-check is followed by the second attestation, which may come from a different
+    // attestor.
+    let mut other = cross_check.map(|cross_check| {
+        let (other_verifying_key, other_data, other_records) =
+            read_other_attestation(&config, max_record_bytes);
+        (cross_check, other_verifying_key, other_data, other_records)
+    });
+
+    // A linkage is followed by the pages after the first, each with its own verifying key.
+    let linkage: Option<Linkage> = sp1_zkvm::io::read();
+    let mut pages = linkage.map(|linkage| {
+        let count: u32 = sp1_zkvm::io::read();
+        let pages: Vec<_> = (0..count)
+            .map(|_| read_other_attestation(&config, max_record_bytes))
+            .collect();
+        (linkage, pages)
+    });
+
+    // A batch mode is followed by the attestations after the first, each with its own verifying
+    // key.
+    let lenient: Option<bool> = sp1_zkvm::io::read();
+    let mut batch = lenient.map(|lenient| {
+        let count: u32 = sp1_zkvm::io::read();
+        let items: Vec<_> = (0..count)
+            .map(|_| read_other_attestation(&config, max_record_bytes))
+            .collect();
+        (lenient, items)
+    });
+
+    // The claim mode is followed by the path of the value to claim.
+    let commit_mode_tag: u8 = sp1_zkvm::io::read();
+    let claim_path = match CommitMode::from_tag(commit_mode_tag) {
+        Some(CommitMode::Full) => None,
+        Some(CommitMode::Claim) => Some(sp1_zkvm::io::read::<String>()),
+        None => abort(AbortCode::UnknownCommitMode, commit_mode_tag),
+    };
+    let expected_digest: Option<[u8; 32]> = sp1_zkvm::io::read();
+    let ranges: Vec<RangeRequest> = sp1_zkvm::io::read();
+    let context: Option<AppContext> = sp1_zkvm::io::read();
+    let cosignatures: Vec<CoSignature> = sp1_zkvm::io::read();
+    let extract: Vec<RangeRequest> = sp1_zkvm::io::read();
+    let preview_bytes: Option<u32> = sp1_zkvm::io::read();
+
+    // The ciphertext of a streamed attestation follows every other input. It is hashed chunk by
+    // chunk as it is read, so only one chunk is ever held, and nothing is committed of it but its
+    // length and digest.
+    let streamed = match &verifying_data {
+        Attestation::Stream(header) => Some(read_stream(header)),
+        _ => None,
+    };
+    let verified = match &streamed {
+        Some(streamed) => streamed.verify(&verifying_key).is_ok(),
+        None => verifying_data.verify(&verifying_key),
+    };
+    // A claim, predicate, request template, cross-check, paginated session or batch is derived
+    // from the attestation, so none is committed unless it verified; only the full public values
+    // commit a failed verification, as their `verified` flag.
+    let derived = claim_path.is_some()
+        || predicate.is_some()
+        || request_template.is_some()
+        || other.is_some()
+        || pages.is_some()
+        || batch.is_some();
+    if derived && !verified {
+        abort(
+            AbortCode::SignatureFailure,
+            "the attestation did not verify, so nothing can be derived from it",
+        );
+    }
+    // Every co-signer must have signed the same packets; one that did not fails the proof.
+    let cosigned = (!cosignatures.is_empty()).then(|| {
+        let Attestation::Opt(data) = &verifying_data else {
+            abort(
+                AbortCode::MalformedInput,
+                "only VerifyingDataOpt attestations are co-signed",
+            );
+        };
+        let data = zktls_lib::verifying_data::VerifyingData::of(data)
+            .unwrap_or_else(|err| abort(AbortCode::MalformedInput, err));
+        verify_cosigned(&data, &verifying_key, &cosignatures)
+            .unwrap_or_else(|err| abort((&err).into(), err))
+    });
+    // The records as attested, before they are decoded, redacted or sanitized, must be the ones
+    // the host expects; only a mismatch shows, and nothing of them need be committed.
+    if let Some(expected_digest) = expected_digest {
+        let records_digest = match &streamed {
+            Some(streamed) => streamed.records_digest(),
+            None => digest(records.iter()),
+        };
+        if records_digest != expected_digest {
+            abort(
+                AbortCode::DigestMismatch,
+                format!(
+                    "records digest {:?}, expected {:?}",
+                    records_digest, expected_digest
+                ),
+            );
+        }
+    }
+    // Like binary records, a stream holds no records to read as HTTP.
+    let http = record_payload == RecordPayload::Http && streamed.is_none();
+
+    // Signatures cover the records as encoded, and response bodies as compressed; everything
+    // checked and committed from here on is over the decoded bytes. A truncated response is
+    // rejected rather than proven as if it were complete. Binary records are not HTTP, so they
+    // are committed as they decode, however much they look like a response.
+    let decode = |records: &mut [Record]| {
+        if let Err(err) = records_encoding.decode(records) {
+            abort((&err).into(), err);
+        }
+        if !http {
+            return (Vec::new(), None);
+        }
+        let decoded_bodies = decode_bodies(records, max_decoded_bytes as usize)
+            .unwrap_or_else(|err| abort((&err).into(), err));
+        let body_length =
+            check_body_lengths(records).unwrap_or_else(|err| abort((&err).into(), err));
+        (decoded_bodies, body_length)
+    };
+    let (decoded_bodies, body_length) = decode(&mut records);
+    if let Some((_, _, _, other_records)) = &mut other {
+        decode(other_records);
+    }
+    for (_, _, page_records) in pages.iter_mut().flat_map(|(_, pages)| pages) {
+        decode(page_records);
+    }
+    for (_, _, item_records) in batch.iter_mut().flat_map(|(_, items)| items) {
+        decode(item_records);
+    }
+
+    // Verify every attestation of a batch independently; strict mode accepts no failure.
+    let batch = batch.map(|(lenient, items)| {
+        let results: Vec<_> = items
+            .iter()
+            .map(|(item_verifying_key, item_data, item_records)| {
+                (
+                    item_data.verify(item_verifying_key),
+                    RecordHasher::default().hash_records(item_records),
+                )
+            })
+            .collect();
+        let failed = std::iter::once(verified)
+            .chain(results.iter().map(|(ok, _)| *ok))
+            .position(|ok| !ok);
+        if let (false, Some(index)) = (lenient, failed) {
+            abort(
+                AbortCode::SignatureFailure,
+                format!("batch attestation {} did not verify", index),
+            );
+        }
+        (lenient, results)
+    });
+    let attestation_digest = verifying_data.canonical_digest();
+    let tls_session = metadata.tls_session();
+    let attestation_version = metadata.version();
+
+    // A reordered TLS 1.2 transcript is rejected; any other commits a sentinel.
+    let (first_sequence, last_sequence) =
+        check_session_sequence(&tls_session, verifying_data.sequence_numbers().as_deref())
+            .unwrap_or_else(|err| abort((&err).into(), err));
+
+    // Evaluate the predicate over the unredacted response; only the outcome is committed.
+    let predicate = predicate.map(|predicate| {
+        let result = predicate
+            .evaluate(&records)
+            .unwrap_or_else(|err| abort((&err).into(), err));
+        PredicateOutcome { predicate, result }
+    });
+
+    // Match the unredacted request; only the template and the outcome are committed.
+    let request_template = request_template.map(|template| {
+        if let Err(err) = template.check(&records) {
+            abort((&err).into(), err);
+        }
+        RequestTemplateOutcome {
+            template,
+            matched: true,
+        }
+    });
+
+    let cross_check = other.map(
+        |(cross_check, other_verifying_key, other_data, other_records)| {
+            let equal = cross_check
+                .evaluate(&records, &other_records)
+                .unwrap_or_else(|err| abort((&err).into(), err));
+            CrossCheckOutcome {
+                other_verified: other_data.verify(&other_verifying_key),
+                other_records_digest: RecordHasher::default().hash_records(&other_records),
+                other_verifying_key,
+                path: cross_check.path,
+                other_path: cross_check.other_path,
+                equal,
+            }
+        },
+    );
+
+    // Check the chain over the unredacted pages.
+    let paginated = pages.map(|(linkage, pages)| {
+        let pages_verified = pages
+            .iter()
+            .all(|(page_verifying_key, page_data, _)| page_data.verify(page_verifying_key));
+        let mut chain = vec![records.clone()];
+        chain.extend(pages.into_iter().map(|(_, _, page_records)| page_records));
+        if let Err(err) = linkage.check(&chain) {
+            abort((&err).into(), err);
+        }
+        let last_response_digest =
+            (chain.len() > 1).then(|| RecordDigests::new(&chain[chain.len() - 1]).response_digest);
+        (
+            linkage,
+            chain.len() as u32,
+            pages_verified,
+            last_response_digest,
+        )
+    });
+
+    // The signature covers the full plaintext, so redact only once it has been checked.
+    if let Err(err) = redact(&mut records, &redactions) {
+        abort((&err).into(), err);
+    }
+    // Credentials are masked in HTTP request headers, which binary records do not have.
+    let sanitized = sanitized && http;
+    if sanitized {
+        sanitize(&mut records);
+    }
+
+    let digests = match (&streamed, record_payload) {
+        (Some(streamed), _) => RecordDigests::streamed(streamed.records_digest()),
+        (None, RecordPayload::Http) => RecordDigests::new(&records),
+        (None, RecordPayload::Binary) => RecordDigests::opaque(&records),
+    };
+    let paginated =
+        paginated.map(
+            |(linkage, pages, pages_verified, last_response_digest)| PaginatedSession {
+                linkage,
+                pages,
+                pages_verified,
+                first_request_digest: digests.request_digest,
+                last_response_digest: last_response_digest.unwrap_or(digests.response_digest),
+            },
+        );
+    let batch = batch.map(|(lenient, results)| {
+        let mut all = vec![(verified, digests.records_digest)];
+        all.extend(results);
+        BatchOutcome::new(lenient, &all)
+    });
+    let bodies_digest = bodies_only
+        .then(|| bodies_digest(&records).unwrap_or_else(|err| abort((&err).into(), err)));
+    let body_digests = canonical_body
+        .then(|| body_digests(&records).unwrap_or_else(|err| abort((&err).into(), err)));
+    let record_digests = match digest_mode {
+        DigestMode::PerRecord => record_digests(&records),
+        DigestMode::Concatenated => Vec::new(),
+    };
+    let response_header =
+        header.map(|name| ResponseHeader::extract(&records, name, digests.records_digest));
+    let response_date = if http {
+        response_date(&records).unwrap_or_else(|err| abort((&err).into(), err))
+    } else {
+        NO_RESPONSE_DATE
+    };
+    let eip712_records_hash = eip712_domain
+        .as_ref()
+        .map(|domain| eip712_records_hash(&records, domain));
+    // Ranges are committed and extracted over the redacted records, so none reveals a secret.
+    let ranges = commit_ranges(&records, &ranges).unwrap_or_else(|err| abort((&err).into(), err));
+    let extracted =
+        extract_ranges(&records, &extract).unwrap_or_else(|err| abort((&err).into(), err));
 
-    let _ = verifying_data.verify(&verifying_key).is_ok();
+    // A claim is only ever made for an attestation that verified, so it commits no flag.
+    if let Some(path) = claim_path {
+        let claim = Claim::new(
+            &verifying_key,
+            &records,
+            path,
+            response_date,
+            digests.records_digest,
+        )
+        .unwrap_or_else(|err| abort((&err).into(), err));
+        sp1_zkvm::io::commit_slice(&claim.abi_encode());
+        return;
+    }
+    if predicate.is_some() {
+        // Committing the records would reveal the value the predicate keeps private.
+        records.clear();
+    }
+    if request_template.is_some() {
+        // The request carries the credentials the template lets the host leave unmatched.
+        remove_requests(&mut records);
+    }
+    // The preview is of what would otherwise be committed, so it reveals nothing the predicate
+    // or request template withholds.
+    let preview = preview_bytes.map(|max_bytes| preview_records(&records, max_bytes));
+    if record_commitment != RecordCommitment::Raw || !extracted.is_empty() || preview.is_some() {
+        // The digests, computed above, stand in for the records, and only the extracted bytes
+        // or preview of them are revealed.
+        records.clear();
+    }
+    // Records committed as text must render as exactly what was proven; redaction can cut a
+    // character in half, so this runs on the committed bytes.
+    let text_validated = text_records
+        && predicate.is_none()
+        && record_commitment == RecordCommitment::Raw
+        && extracted.is_empty()
+        && preview.is_none();
+    if text_validated {
+        if let Err(err) = check_utf8(&records) {
+            abort((&err).into(), err);
+        }
+    }
 
-    sp1_zkvm::io::commit(&verifying_key);
-    sp1_zkvm::io::commit(&verifying_data.get_records());
+    sp1_zkvm::io::commit(&PublicValues {
+        status: STATUS_OK,
+        verifying_key,
+        format_tag,
+        attestation_digest,
+        verified,
+        records,
+        context,
+        largest_record_bytes,
+        digests,
+        predicate,
+        cross_check,
+        bodies_digest,
+        digest_mode: digest_mode_tag,
+        record_digests,
+        eip712_records_hash,
+        paginated,
+        first_sequence,
+        last_sequence,
+        batch,
+        records_encoding: records_encoding_tag,
+        decoded_bodies,
+        body_length,
+        response_header,
+        body_digests,
+        text_validated,
+        request_template,
+        prefix_records,
+        sanitized,
+        response_date,
+        tls_session,
+        attestation_version,
+        record_commitment: record_commitment_tag,
+        record_payload: record_payload_tag,
+        streamed_bytes: streamed.as_ref().map(StreamedRecords::total_bytes),
+        ranges,
+        cosigned,
+        extracted,
+        preview,
+    });
 }
@@ -1,5 +1,22 @@
-use sp1_build::build_program_with_args;
+use sp1_build::{build_program_with_args, BuildArgs};
 
 fn main() {
-    build_program_with_args("../program", Default::default())
+    // Build the program with the same records hash the host computes digests with.
+    let features = ["sha256", "poseidon"]
+        .into_iter()
+        .filter(|feature| {
+            std::env::var_os(format!("CARGO_FEATURE_{}", feature.to_uppercase())).is_some()
+        })
+        .map(String::from)
+        .collect();
+
+    build_program_with_args(
+        "../program",
+        BuildArgs {
+            features,
+            ..Default::default()
+        },
+    );
+    // Only verifies proofs, so it commits the same values whichever records hash is selected.
+    build_program_with_args("../aggregation", BuildArgs::default());
 }
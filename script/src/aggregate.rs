@@ -0,0 +1,96 @@
+use std::fmt;
+
+use sp1_sdk::{
+    include_elf, HashableKey, SP1Proof, SP1ProofWithPublicValues, SP1Stdin, SP1VerifyingKey,
+};
+use zktls_lib::aggregation::ChildCommitment;
+
+/// The program that verifies compressed proofs of other programs inside its own; see
+/// [`zktls_lib::aggregation`].
+pub const AGGREGATION_ELF: &[u8] = include_elf!("zktls-aggregation");
+
+/// Compressed proofs, of the zktls program or any other, to verify together in one proof of
+/// [`AGGREGATION_ELF`].
+#[derive(Default)]
+pub struct ChildProofs {
+    proofs: Vec<(SP1ProofWithPublicValues, SP1VerifyingKey)>,
+}
+
+/// Why a proof cannot be aggregated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AggregationError {
+    /// The proof is not compressed; only compressed proofs are verified inside another program.
+    NotCompressed,
+}
+
+impl fmt::Display for AggregationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregationError::NotCompressed => {
+                f.write_str("only compressed proofs can be aggregated; prove with `.compressed()`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AggregationError {}
+
+impl ChildProofs {
+    pub fn new() -> Self {
+        ChildProofs::default()
+    }
+
+    /// Add `proof` of the program with verifying key `vk`, which must be compressed.
+    pub fn push(
+        &mut self,
+        proof: SP1ProofWithPublicValues,
+        vk: &SP1VerifyingKey,
+    ) -> Result<(), AggregationError> {
+        if !matches!(proof.proof, SP1Proof::Compressed(_)) {
+            return Err(AggregationError::NotCompressed);
+        }
+        self.proofs.push((proof, vk.clone()));
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.proofs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proofs.is_empty()
+    }
+
+    /// The public values every proof commits, in the order they were added.
+    pub fn public_values(&self) -> impl Iterator<Item = &[u8]> {
+        self.proofs
+            .iter()
+            .map(|(proof, _)| proof.public_values.as_slice())
+    }
+
+    /// What the aggregation program commits of every proof, in the order they were added.
+    pub fn commitments(&self) -> Vec<ChildCommitment> {
+        self.proofs
+            .iter()
+            .map(|(proof, vk)| ChildCommitment::new(vk.hash_u32(), proof.public_values.as_slice()))
+            .collect()
+    }
+
+    /// The input of [`AGGREGATION_ELF`]: the commitments, then every proof as a deferred proof.
+    pub fn stdin(&self) -> SP1Stdin {
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&self.commitments());
+        for (proof, vk) in &self.proofs {
+            let SP1Proof::Compressed(proof) = &proof.proof else {
+                unreachable!("only compressed proofs are added")
+            };
+            stdin.write_proof(*proof.clone(), vk.vk.clone());
+        }
+        stdin
+    }
+}
+
+/// Read the commitments an aggregation proof commits.
+pub fn read_aggregation(proof: &SP1ProofWithPublicValues) -> Vec<ChildCommitment> {
+    proof.public_values.clone().read()
+}
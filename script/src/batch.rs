@@ -0,0 +1,32 @@
+//! Native batch verification for server-side pipelines, spread over tokio's blocking thread pool.
+
+use std::sync::Arc;
+
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+
+use crate::VerificationReport;
+
+/// Verify every attestation in `items` natively on tokio's blocking pool, returning the reports
+/// in the order of `items`.
+///
+/// Verification itself stays synchronous; this only runs many of them at once.
+pub async fn verify_many_async(
+    items: Vec<VerifyingDataOpt>,
+    verifying_key: &str,
+) -> Vec<VerificationReport> {
+    let verifying_key: Arc<str> = Arc::from(verifying_key);
+
+    let tasks: Vec<_> = items
+        .into_iter()
+        .map(|data| {
+            let verifying_key = Arc::clone(&verifying_key);
+            tokio::task::spawn_blocking(move || VerificationReport::native(&data, &verifying_key))
+        })
+        .collect();
+
+    let mut reports = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        reports.push(task.await.expect("verification task panicked"));
+    }
+    reports
+}
@@ -0,0 +1,84 @@
+//! Proves several attestations separately, then aggregates the proofs into one.
+//!
+//! Each attestation is proven with a compressed proof of the zktls program, which the
+//! aggregation program verifies inside its own proof. Proofs of other programs can be added to
+//! the same [`ChildProofs`] with their own verifying keys.
+//!
+//! You can run this script using the following command:
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin aggregate -- --zktls-length 16 --zktls-length 256
+//! ```
+
+use std::sync::Arc;
+
+use clap::Parser;
+use sp1_sdk::{HashableKey, ProverClient, SP1Stdin};
+use zktls_lib::PublicValues;
+use zktls_script::{
+    load_bench, read_aggregation, setup_cached, setup_logger, ChildProofs, LogFormat, ZkTlsInput,
+    ZkTlsVerifier, AGGREGATION_ELF,
+};
+
+/// The arguments for the aggregate command.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct AggregateArgs {
+    /// The bench attestation to prove, once per occurrence.
+    #[arg(long, value_name = "LENGTH", default_values_t = [16, 16])]
+    zktls_length: Vec<u32>,
+    #[arg(long, value_enum, default_value = "text")]
+    log_format: LogFormat,
+    #[arg(long)]
+    no_cache: bool,
+}
+
+fn main() {
+    let args = AggregateArgs::parse();
+    setup_logger(args.log_format, false);
+
+    let client = Arc::new(ProverClient::from_env());
+    let (verifier, pk) = ZkTlsVerifier::setup(client.clone(), args.no_cache);
+
+    let mut children = ChildProofs::new();
+    for length in &args.zktls_length {
+        let (verifying_key, verifying_data) =
+            load_bench(*length, None, None, None).unwrap_or_else(|err| {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            });
+        let stdin: SP1Stdin = ZkTlsInput::new(verifying_key, verifying_data).into();
+        let proof = client
+            .prove(&pk, &stdin)
+            .compressed()
+            .run()
+            .expect("failed to generate proof");
+        verifier.verify(&proof).expect("failed to verify proof");
+
+        let public_values: PublicValues = proof.public_values.clone().read();
+        println!(
+            "Proved the length-{} attestation, verified: {}",
+            length, public_values.verified
+        );
+        children
+            .push(proof, verifier.vk())
+            .expect("the proof is compressed");
+    }
+
+    let (aggregation_pk, aggregation_vk) = setup_cached(&client, AGGREGATION_ELF, args.no_cache);
+    let proof = client
+        .prove(&aggregation_pk, &children.stdin())
+        .run()
+        .expect("failed to generate the aggregation proof");
+    client
+        .verify(&proof, &aggregation_vk)
+        .expect("failed to verify the aggregation proof");
+
+    let commitments = read_aggregation(&proof);
+    assert_eq!(commitments, children.commitments());
+    println!(
+        "Aggregated {} proofs of program {}",
+        commitments.len(),
+        verifier.vk().bytes32()
+    );
+    println!("Aggregation verifying key: {}", aggregation_vk.bytes32());
+}
@@ -9,35 +9,58 @@
 //! ```shell
 //! RUST_LOG=info cargo run --release --bin evm -- --system plonk
 //! ```
+//! The proof system can also be set with `ZKTLS_PROOF_SYSTEM=plonk`. To check the input before
+//! a long proving run, without setting up the program,
+//! ```shell
+//! cargo run --release --bin evm -- --dry-run
+//! ```
 
 // use alloy_sol_types::{sol, SolType};
 
-use clap::{Parser, ValueEnum};
-use serde::{Deserialize, Serialize};
-use sp1_sdk::{
-    include_elf, HashableKey, ProverClient, SP1ProofWithPublicValues, SP1Stdin, SP1VerifyingKey,
+use clap::Parser;
+use sp1_sdk::ProverClient;
+use std::sync::Arc;
+use std::time::Duration;
+use zktls_script::{
+    check_cycle_budget, fixture_path, prove_with_timeout, retry_with_backoff, setup_logger,
+    InputArgs, LogFormat, ProofEncoding, ProofSystem, SP1ZktlsProofFixture, VerificationReport,
+    ZkTlsProof, ZkTlsVerifier, ZKTLS_ELF,
 };
-use std::path::PathBuf;
-use zktls_att_verification::verification_data::VerifyingDataOpt;
-
-/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
-pub const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
 
 /// The arguments for the EVM command.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct EVMArgs {
-    #[arg(long, value_enum, default_value = "groth16")]
+    #[arg(long, value_enum, env = "ZKTLS_PROOF_SYSTEM", default_value_t = ProofSystem::Groth16)]
     system: ProofSystem,
-    #[arg(long, default_value = "16")]
-    zktls_length: u32,
-}
-
-/// Enum representing the available proof systems
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
-enum ProofSystem {
-    Plonk,
-    Groth16,
+    #[command(flatten)]
+    input: InputArgs,
+    /// Give up on a proof attempt that takes longer than this.
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<u64>,
+    /// Retry a proof that fails transiently up to this many times, backing off between attempts.
+    #[arg(long, value_name = "N", env = "ZKTLS_MAX_RETRIES", default_value_t = 0)]
+    max_retries: u32,
+    /// Execute first and refuse to prove if the program takes more cycles than this.
+    #[arg(long, value_name = "N", env = "ZKTLS_MAX_CYCLES")]
+    max_cycles: Option<u64>,
+    /// Prove without checking the cycle budget.
+    #[arg(long)]
+    skip_cycle_check: bool,
+    #[arg(long, value_enum, default_value = "text")]
+    log_format: LogFormat,
+    #[arg(long)]
+    no_cache: bool,
+    /// How the proof bytes are written in the fixture.
+    #[arg(long, value_enum, default_value_t = ProofEncoding::Hex)]
+    proof_encoding: ProofEncoding,
+    /// Validate the input, verify natively and print the estimated cycles, without setting up or
+    /// proving the program.
+    #[arg(long)]
+    dry_run: bool,
+    /// Print the dry run's report as JSON, the only output on stdout.
+    #[arg(long, requires = "dry_run")]
+    json_report: bool,
 }
 
 // sol! {
@@ -48,135 +71,135 @@ enum ProofSystem {
 //     }
 // }
 
-/// A fixture that can be used to test the verification of SP1 zkVM proofs inside Solidity.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct SP1ZktlsProofFixture {
-    // zktls_verification_key: String,
-    // records: String,
-    vkey: String,
-    proof: String,
-}
-
-fn load(length: u32, stdin: &mut SP1Stdin) {
-    match length {
-        16 => {
-            let verifying_key =
-                std::fs::read_to_string("fixtures/zktls/verifying_k256.key").unwrap();
-
-            stdin.write(&verifying_key);
-
-            let verifying_data =
-                std::fs::read_to_string("fixtures/zktls/data/bench16.json").unwrap();
-
-            let verifying_data: VerifyingDataOpt = serde_json::from_str(&verifying_data).unwrap();
-
-            stdin.write(&verifying_data);
-        }
-        256 => {
-            let verifying_key =
-                std::fs::read_to_string("fixtures/zktls/verifying_k256.key").unwrap();
-
-            stdin.write(&verifying_key);
-
-            let verifying_data =
-                std::fs::read_to_string("fixtures/zktls/data/bench256.json").unwrap();
-
-            let verifying_data: VerifyingDataOpt = serde_json::from_str(&verifying_data).unwrap();
-
-            stdin.write(&verifying_data);
-        }
-        1024 => {
-            let verifying_key =
-                std::fs::read_to_string("fixtures/zktls/verifying_k256.key").unwrap();
-
-            stdin.write(&verifying_key);
-
-            let verifying_data =
-                std::fs::read_to_string("fixtures/zktls/data/bench1024.json").unwrap();
-
-            let verifying_data: VerifyingDataOpt = serde_json::from_str(&verifying_data).unwrap();
-
-            stdin.write(&verifying_data);
-        }
-        2048 => {
-            let verifying_key =
-                std::fs::read_to_string("fixtures/zktls/verifying_k256.key").unwrap();
-
-            stdin.write(&verifying_key);
-
-            let verifying_data =
-                std::fs::read_to_string("fixtures/zktls/data/bench2048.json").unwrap();
-
-            let verifying_data: VerifyingDataOpt = serde_json::from_str(&verifying_data).unwrap();
-
-            stdin.write(&verifying_data);
-        }
-        _ => {
-            eprintln!("Unsupported length: {}", length);
-            std::process::exit(1);
-        }
-    }
-}
 fn main() {
-    // Setup the logger.
-    sp1_sdk::utils::setup_logger();
-
     // Parse the command line arguments.
     let args = EVMArgs::parse();
 
+    // Setup the logger. The JSON report owns stdout, so logs go to stderr instead.
+    setup_logger(args.log_format, args.json_report);
+
+    if args.dry_run {
+        std::process::exit(dry_run(&args));
+    }
+
     // Setup the prover client.
-    let client = ProverClient::from_env();
+    let client = Arc::new(ProverClient::from_env());
 
     // Setup the program.
-    let (pk, vk) = client.setup(ZKTLS_ELF);
+    let (verifier, pk) = ZkTlsVerifier::setup(Arc::clone(&client), args.no_cache);
 
     // Setup the inputs.
-    let mut stdin = SP1Stdin::new();
-    load(args.zktls_length, &mut stdin);
-
-    println!("zktls verification length: {}", args.zktls_length);
-    println!("Proof System: {:?}", args.system);
-
-    // Generate the proof based on the selected proof system.
-    let proof = match args.system {
-        ProofSystem::Plonk => client.prove(&pk, &stdin).plonk().run(),
-        ProofSystem::Groth16 => client.prove(&pk, &stdin).groth16().run(),
+    let stdin = args.input.try_stdin().unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        if let Some(hint) = err.hint() {
+            eprintln!("Hint: {}", hint);
+        }
+        std::process::exit(1);
+    });
+
+    println!("zktls verification length: {}", args.input.zktls_length);
+    println!("Proof System: {}", args.system);
+
+    // Check the cycle budget before paying for a proof.
+    if let (Some(max_cycles), false) = (args.max_cycles, args.skip_cycle_check) {
+        match check_cycle_budget(&client, ZKTLS_ELF, &stdin, max_cycles) {
+            Ok(cycles) => println!("Number of cycles: {} of {}", cycles, max_cycles),
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                std::process::exit(1);
+            }
+        }
     }
-    .expect("failed to generate proof");
-
-    create_proof_fixture(&proof, &vk, args.system);
-}
-
-/// Create a fixture for the given proof.
-fn create_proof_fixture(
-    proof: &SP1ProofWithPublicValues,
-    vk: &SP1VerifyingKey,
-    system: ProofSystem,
-) {
-    // Deserialize the public values.
-    let _bytes = proof.public_values.as_slice();
-
-    // let PublicZkTLSValuesStruct {
-    //     zktls_verification_key,
-    //     records,
-    // } = PublicZkTLSValuesStruct::abi_decode(bytes).unwrap();
 
+    // Generate the proof based on the selected proof system.
+    // Each attempt has the whole timeout to itself, so a retry is not cut short by the attempts
+    // before it.
+    let (pk, stdin) = (Arc::new(pk), Arc::new(stdin));
+    let system = args.system;
+    let timeout = args.timeout.map(Duration::from_secs);
+    let proof = retry_with_backoff(args.max_retries, || {
+        let (prover, pk, stdin) = (Arc::clone(&client), Arc::clone(&pk), Arc::clone(&stdin));
+        prove_with_timeout(
+            move || match system {
+                ProofSystem::Plonk => prover.prove(&pk, &stdin).plonk().run(),
+                ProofSystem::Groth16 => prover.prove(&pk, &stdin).groth16().run(),
+            },
+            timeout,
+        )
+    })
+    .unwrap_or_else(|err| {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    });
+
+    let proof = ZkTlsProof::from(proof);
     let fixture = SP1ZktlsProofFixture {
-        // zktls_verification_key: zktls_verification_key.to_string(),
-        // records: records.to_string(),
-        vkey: vk.bytes32().to_string(),
-        proof: format!("0x{}", hex::encode(proof.bytes())),
+        proof: args.proof_encoding.encode(&proof.0.bytes()),
+        proof_encoding: args.proof_encoding,
+        ..SP1ZktlsProofFixture::from((&proof, verifier.vk()))
     };
+    print_fixture(&fixture);
+    save_fixture(&fixture, args.system);
+}
 
-    // println!("Zktls Verification Key: {}", fixture.zktls_verification_key);
-
-    // The public values are the values which are publicly committed to by the zkVM.
-    //
-    // If you need to expose the inputs or outputs of your program, you should commit them in
-    // the public values.
+/// Validate the input and verify it natively, printing the report, and return the exit code.
+fn dry_run(args: &EVMArgs) -> i32 {
+    let report = match args.input.dry_run() {
+        Ok(report) => report,
+        Err(err) if args.json_report => VerificationReport::failed(args.input.zktls_length, err),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            if let Some(hint) = err.hint() {
+                eprintln!("Hint: {}", hint);
+            }
+            return 1;
+        }
+    };
+    if args.json_report {
+        println!("{}", serde_json::to_string(&report).unwrap());
+    } else {
+        println!("Proof System: {}", args.system);
+        println!("{}", report);
+    }
+    if report.verified {
+        0
+    } else {
+        1
+    }
+}
 
-    // println!("Public Records: {}", fixture.records);
+/// Print what `fixture` records of the proof.
+fn print_fixture(fixture: &SP1ZktlsProofFixture) {
+    if let Some(claim) = &fixture.claim {
+        println!("Claim Attestor: {}", claim.attestor);
+        println!("Claim Domain: {}", claim.domain);
+        println!("Claim {}: {}", claim.path, claim.value);
+        println!("Claim Timestamp: {}", claim.timestamp);
+        println!("Records Digest: {}", claim.records_digest);
+    } else {
+        // The public values are the values which are publicly committed to by the zkVM.
+        //
+        // If you need to expose the inputs or outputs of your program, you should commit them in
+        // the public values.
+        println!("Request Digest: {}", fixture.request_digest);
+        println!("Response Digest: {}", fixture.response_digest);
+        println!("Records Digest: {}", fixture.records_digest);
+        if let Some(bodies_digest) = &fixture.bodies_digest {
+            println!("Bodies Digest: {}", bodies_digest);
+        }
+        if let Some(eip712_records_hash) = &fixture.eip712_records_hash {
+            println!("EIP-712 Records Hash: {}", eip712_records_hash);
+        }
+        for (index, digest) in fixture.record_digests.iter().enumerate() {
+            println!("Record {} Digest: {}", index, digest);
+        }
+        println!("TLS Version: {}", fixture.tls_version);
+        println!("Cipher Suite: {}", fixture.cipher_suite);
+        if let (Some(chain_id), Some(app_id)) = (fixture.chain_id, &fixture.app_id) {
+            println!("Chain Id: {}", chain_id);
+            println!("App Id: {}", app_id);
+        }
+    }
 
     // The verification key is used to verify that the proof corresponds to the execution of the
     // program on the given input.
@@ -187,13 +210,12 @@ fn create_proof_fixture(
     // The proof proves to the verifier that the program was executed with some inputs that led to
     // the give public values.
     println!("Proof Bytes: {}", fixture.proof);
+}
 
-    // Save the fixture to a file.
-    let fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../contracts/src/fixtures");
-    std::fs::create_dir_all(&fixture_path).expect("failed to create fixture path");
-    std::fs::write(
-        fixture_path.join(format!("{:?}-fixture.json", system).to_lowercase()),
-        serde_json::to_string_pretty(&fixture).unwrap(),
-    )
-    .expect("failed to write fixture");
+/// Save `fixture` where the contract tests for `system` read it.
+fn save_fixture(fixture: &SP1ZktlsProofFixture, system: ProofSystem) {
+    let fixture_path = fixture_path(system);
+    std::fs::create_dir_all(fixture_path.parent().unwrap()).expect("failed to create fixture path");
+    std::fs::write(fixture_path, serde_json::to_string_pretty(fixture).unwrap())
+        .expect("failed to write fixture");
 }
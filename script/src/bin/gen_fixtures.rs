@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use zktls_script::{
+    attest_records, attest_tls13_records, cosign_attestation, generate_attestation, load_records,
+    GENERATED_KEY_PATH,
+};
+
+/// Generate a signed bench attestation, identical on every run.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct GenArgs {
+    /// The number of records to generate.
+    #[arg(long, required_unless_present = "records")]
+    count: Option<usize>,
+
+    /// The size of each record.
+    #[arg(long, default_value_t = 16)]
+    record_bytes: usize,
+
+    /// JSON file of records to attest instead of generated ones, with `base64` or `hex` entries
+    /// for binary records.
+    #[arg(long, value_name = "PATH", conflicts_with = "count", requires = "out")]
+    records: Option<PathBuf>,
+
+    /// Derive the record nonces as TLS 1.3 does, XORing the sequence number into a fixed IV,
+    /// instead of as TLS 1.2 explicit nonces.
+    #[arg(long, requires = "records")]
+    tls13: bool,
+
+    /// Where to write the attestation, `fixtures/zktls/data/bench<COUNT>.json` by default.
+    #[arg(long, value_name = "PATH")]
+    out: Option<PathBuf>,
+
+    /// Also sign the attestation under this many further keys, written as co-signatures next to
+    /// it with a `.cosignatures.json` extension.
+    #[arg(long, default_value_t = 0)]
+    cosigners: u64,
+
+    /// Replace an existing attestation. The bench fixtures checked in before this generator are
+    /// signed under `verifying_k256.key`, not the generator's key.
+    #[arg(long)]
+    force: bool,
+}
+
+fn main() {
+    let args = GenArgs::parse();
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+    let out = match (args.out, args.count) {
+        (Some(out), _) => out,
+        (None, Some(count)) => root.join(format!("fixtures/zktls/data/bench{}.json", count)),
+        (None, None) => unreachable!("clap requires --out with --records"),
+    };
+    if out.exists() && !args.force {
+        eprintln!(
+            "Error: {} already exists; pass --force to replace it",
+            out.display()
+        );
+        std::process::exit(1);
+    }
+
+    let (verifying_key, verifying_data) = match (&args.records, args.count) {
+        (Some(records), _) => match load_records(records) {
+            Ok(records) if args.tls13 => attest_tls13_records(&records),
+            Ok(records) => attest_records(&records),
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                std::process::exit(1);
+            }
+        },
+        (None, Some(count)) => generate_attestation(count, args.record_bytes),
+        (None, None) => unreachable!("clap requires --count without --records"),
+    };
+    let count = verifying_data.get_records().len();
+    let json = serde_json::to_string(&verifying_data).unwrap();
+    std::fs::write(&out, json + "\n").expect("failed to write attestation");
+    if args.cosigners > 0 {
+        let cosignatures = cosign_attestation(&verifying_data, args.cosigners);
+        let cosignatures_path = out.with_extension("cosignatures.json");
+        let json = serde_json::to_string_pretty(&cosignatures).unwrap();
+        std::fs::write(&cosignatures_path, json + "\n").expect("failed to write co-signatures");
+        println!(
+            "Wrote {} co-signatures to {}",
+            cosignatures.len(),
+            cosignatures_path.display()
+        );
+    }
+    let key_path = root.join(GENERATED_KEY_PATH);
+    std::fs::write(&key_path, verifying_key).expect("failed to write verifying key");
+
+    println!("Wrote {} records to {}", count, out.display());
+    println!("Verifying key: {}", key_path.display());
+}
@@ -9,14 +9,31 @@
 //! ```shell
 //! RUST_LOG=info cargo run --release -- --prove
 //! ```
+//! or, to save a flame graph of the execution (with `--features profiling`),
+//! ```shell
+//! cargo run --release --features profiling -- --execute --profile trace.json
+//! ```
+//! or, to verify natively without the zkVM,
+//! ```shell
+//! cargo run --release -- --simulate
+//! ```
+//! or, to also validate the input and estimate the cycles before a long proving run,
+//! ```shell
+//! cargo run --release -- --dry-run
+//! ```
 
 // use alloy_sol_types::SolType;
-use clap::Parser;
-use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
-use zktls_att_verification::verification_data::VerifyingDataOpt;
-
-/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
-pub const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+use clap::{Parser, ValueEnum};
+use sp1_sdk::{EnvProver, ProverClient};
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use zktls_script::{
+    check_cycle_budget, profile, prove_with_timeout, read_claim, read_public_values,
+    retry_with_backoff, setup_logger, watch, InputArgs, InputError, LogFormat, VerificationReport,
+    ZkTlsVerifier, ZKTLS_ELF,
+};
 
 /// The arguments for the command.
 #[derive(Parser, Debug)]
@@ -28,115 +45,415 @@ struct Args {
     #[arg(long)]
     prove: bool,
 
-    #[arg(long, default_value = "16")]
-    zktls_length: u32,
-}
-
-fn load(length: u32, stdin: &mut SP1Stdin) {
-    match length {
-        16 => {
-            let verifying_key =
-                std::fs::read_to_string("fixtures/zktls/verifying_k256.key").unwrap();
-
-            stdin.write(&verifying_key);
+    /// The kind of proof `--prove` generates.
+    #[arg(long, value_enum, default_value = "core")]
+    prove_mode: ProveMode,
 
-            let verifying_data =
-                std::fs::read_to_string("fixtures/zktls/data/bench16.json").unwrap();
+    #[arg(long)]
+    simulate: bool,
 
-            let verifying_data: VerifyingDataOpt = serde_json::from_str(&verifying_data).unwrap();
+    /// Validate the input, verify natively and print the estimated cycles, without setting up,
+    /// executing or proving the program.
+    #[arg(long)]
+    dry_run: bool,
 
-            stdin.write(&verifying_data);
-        }
-        256 => {
-            let verifying_key =
-                std::fs::read_to_string("fixtures/zktls/verifying_k256.key").unwrap();
+    #[command(flatten)]
+    input: InputArgs,
 
-            stdin.write(&verifying_key);
+    /// Give up on a proof attempt that takes longer than this.
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<u64>,
 
-            let verifying_data =
-                std::fs::read_to_string("fixtures/zktls/data/bench256.json").unwrap();
+    /// Retry a proof that fails transiently up to this many times, backing off between attempts.
+    #[arg(long, value_name = "N", env = "ZKTLS_MAX_RETRIES", default_value_t = 0)]
+    max_retries: u32,
 
-            let verifying_data: VerifyingDataOpt = serde_json::from_str(&verifying_data).unwrap();
+    /// Execute first and refuse to prove if the program takes more cycles than this.
+    #[arg(long, value_name = "N", env = "ZKTLS_MAX_CYCLES")]
+    max_cycles: Option<u64>,
 
-            stdin.write(&verifying_data);
-        }
-        1024 => {
-            let verifying_key =
-                std::fs::read_to_string("fixtures/zktls/verifying_k256.key").unwrap();
+    /// Prove without checking the cycle budget.
+    #[arg(long)]
+    skip_cycle_check: bool,
 
-            stdin.write(&verifying_key);
+    #[arg(long)]
+    json_report: bool,
 
-            let verifying_data =
-                std::fs::read_to_string("fixtures/zktls/data/bench1024.json").unwrap();
+    #[arg(long, value_enum, default_value = "text")]
+    log_format: LogFormat,
 
-            let verifying_data: VerifyingDataOpt = serde_json::from_str(&verifying_data).unwrap();
+    /// Print the committed digest of the attestation and check it against the input file.
+    #[arg(long)]
+    print_input_digest: bool,
 
-            stdin.write(&verifying_data);
-        }
-        2048 => {
-            let verifying_key =
-                std::fs::read_to_string("fixtures/zktls/verifying_k256.key").unwrap();
+    /// Recompute the proving and verifying keys instead of loading them from the setup cache.
+    #[arg(long)]
+    no_cache: bool,
 
-            stdin.write(&verifying_key);
+    /// Profile the execution and save the flame graph to this path.
+    #[arg(long, value_name = "OUTPUT_PATH", requires = "execute")]
+    profile: Option<PathBuf>,
 
-            let verifying_data =
-                std::fs::read_to_string("fixtures/zktls/data/bench2048.json").unwrap();
+    /// Fail the execution if the program takes more cycles than this.
+    #[arg(long, value_name = "N", requires = "execute")]
+    limit_cycles: Option<u64>,
 
-            let verifying_data: VerifyingDataOpt = serde_json::from_str(&verifying_data).unwrap();
+    /// Run again whenever the key or attestation files change.
+    #[arg(long)]
+    watch: bool,
+}
 
-            stdin.write(&verifying_data);
-        }
-        _ => {
-            eprintln!("Unsupported length: {}", length);
-            std::process::exit(1);
-        }
-    }
+/// The proof `--prove` generates, trading proving time for proof size.
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+enum ProveMode {
+    /// A STARK per shard, the fastest to generate.
+    Core,
+    /// A single STARK recursively compressed from the shards, of constant size.
+    Compressed,
 }
 
 fn main() {
-    // Setup the logger.
-    sp1_sdk::utils::setup_logger();
+    // Parse the command line arguments, some of which may be set in `.env`.
     dotenv::dotenv().ok();
-
-    // Parse the command line arguments.
     let args = Args::parse();
 
-    if args.execute == args.prove {
-        eprintln!("Error: You must specify either --execute or --prove");
+    // Setup the logger. The JSON report owns stdout, so logs go to stderr instead.
+    setup_logger(args.log_format, args.json_report);
+
+    if [args.execute, args.prove, args.simulate, args.dry_run]
+        .iter()
+        .filter(|&&mode| mode)
+        .count()
+        != 1
+    {
+        eprintln!("Error: You must specify one of --execute, --prove, --simulate or --dry-run");
         std::process::exit(1);
     }
 
-    // Setup the prover client.
-    let client = ProverClient::from_env();
+    // Setup the prover client, which simulation and dry runs do without.
+    let client = (!args.simulate && !args.dry_run).then(|| Arc::new(ProverClient::from_env()));
+
+    if !args.watch {
+        std::process::exit(run(&args, client.as_ref()));
+    }
+
+    let paths = args.input.paths();
+    let run_and_report = || {
+        // A failing run is reported, and watching goes on until the files are fixed.
+        match std::panic::catch_unwind(AssertUnwindSafe(|| run(&args, client.as_ref()))) {
+            Ok(0) => eprintln!("Run succeeded."),
+            Ok(code) => eprintln!("Run failed with exit code {}.", code),
+            Err(_) => eprintln!("Run panicked."),
+        }
+        eprintln!("Watching {} files for changes...", paths.len());
+    };
+
+    run_and_report();
+    if let Err(err) = watch(&paths, run_and_report) {
+        eprintln!("Error: failed to watch input files: {}", err);
+        std::process::exit(1);
+    }
+}
 
-    // Setup the inputs.
-    let mut stdin = SP1Stdin::new();
-    load(args.zktls_length, &mut stdin);
+/// Run the selected mode once, returning the exit code.
+fn run(args: &Args, client: Option<&Arc<EnvProver>>) -> i32 {
+    if args.dry_run {
+        return dry_run(args);
+    }
+    if args.simulate {
+        // Run the verification natively, without any prover infrastructure.
+        let (verifying_key, verifying_data) = match args.input.load() {
+            Ok(input) => input,
+            Err(err) => return input_error(err.into()),
+        };
+        let result = verifying_data.verify_with_config(&verifying_key, &args.input.verify_config());
+        match &result {
+            Ok(()) => eprintln!("Verification succeeded."),
+            Err(err) => eprintln!("Verification failed: {}.", err),
+        }
+        if let Ok(records) = verifying_data.get_records() {
+            println!("{}", serde_json::to_string_pretty(&records).unwrap());
+        }
+        return if result.is_ok() { 0 } else { 1 };
+    }
+    let client = client.expect("a prover client is set up unless simulating");
+
+    // Setup the inputs. An execution reports why the program aborted, if it does.
+    let stdin = args
+        .input
+        .builder()
+        .and_then(|builder| builder.report_status(args.execute).build());
+    let stdin = match stdin {
+        Ok(stdin) => stdin,
+        Err(err) => return input_error(err),
+    };
     // stdin.write(&args.n);
 
-    println!("zktls verification length: {}", args.zktls_length);
+    if args.execute && args.json_report {
+        if args.input.claim.is_some() {
+            eprintln!("Error: --json-report reports the full public values, which --claim omits");
+            return 1;
+        }
+        let executed = match profiled(args, || client.execute(ZKTLS_ELF, &stdin).run()) {
+            Ok(executed) => executed,
+            Err(code) => return code,
+        };
+        let report = match executed {
+            Ok((public_values, report)) => match read_public_values(&public_values) {
+                Ok(public_values) => VerificationReport::new(
+                    args.input.zktls_length,
+                    report.total_instruction_count(),
+                    &public_values,
+                ),
+                Err(aborted) => VerificationReport::failed(args.input.zktls_length, aborted),
+            },
+            Err(err) => VerificationReport::failed(args.input.zktls_length, err),
+        }
+        .with_cycle_limit(args.limit_cycles);
+        if let Some(cycles) = report.cycles {
+            // The JSON report owns stdout, so the overrun is also explained on stderr.
+            within_cycle_limit(args, cycles);
+        }
+
+        println!("{}", serde_json::to_string(&report).unwrap());
+        return if report.passed() { 0 } else { 1 };
+    }
+
+    println!("zktls verification length: {}", args.input.zktls_length);
 
     if args.execute {
         // Execute the program
-        let (_, report) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+        let executed = match profiled(args, || client.execute(ZKTLS_ELF, &stdin).run()) {
+            Ok(executed) => executed,
+            Err(code) => return code,
+        };
+        let (public_values, report) = match executed {
+            Ok(executed) => executed,
+            Err(err) => {
+                eprintln!("Error: failed to execute the program: {}", err);
+                return 1;
+            }
+        };
+        if args.input.claim.is_some() {
+            let claim = match read_claim(&public_values) {
+                Ok(claim) => claim,
+                Err(aborted) => {
+                    eprintln!("Error: {}", aborted);
+                    return 1;
+                }
+            };
+            println!("Program executed successfully.");
+            println!("Number of cycles: {}", report.total_instruction_count());
+            if !within_cycle_limit(args, report.total_instruction_count()) {
+                return 1;
+            }
+            println!("Claim attestor: {}", claim.attestor);
+            println!("Claim domain: {}", claim.domain);
+            println!("Claim {}: {}", claim.path, claim.value);
+            println!("Claim timestamp: {}", claim.timestamp);
+            println!("Claim records digest: {}", claim.recordsDigest);
+            return 0;
+        }
+        let public_values = match read_public_values(&public_values) {
+            Ok(public_values) => public_values,
+            Err(aborted) => {
+                eprintln!("Error: {}", aborted);
+                return 1;
+            }
+        };
         println!("Program executed successfully.");
+        println!("Attestation verified: {}", public_values.verified);
 
         // Record the number of cycles executed.
         println!("Number of cycles: {}", report.total_instruction_count());
+        if !within_cycle_limit(args, report.total_instruction_count()) {
+            return 1;
+        }
+
+        if let Some(preview) = &public_values.preview {
+            println!(
+                "Preview ({} of {} bytes): {:?}",
+                preview.bytes.len(),
+                preview.total_bytes,
+                String::from_utf8_lossy(&preview.bytes)
+            );
+            println!("Records keccak256: 0x{}", hex::encode(preview.digest));
+        }
+        if let Some(header) = &public_values.response_header {
+            println!(
+                "Header {}: {:?} ({} occurrences)",
+                header.name, header.value, header.count
+            );
+        }
+
+        let tls_session = public_values.tls_session;
+        if let (Some(version), Some(cipher_suite)) =
+            (tls_session.version(), tls_session.cipher_suite())
+        {
+            println!("TLS session: {}, {}", version, cipher_suite);
+        }
+        println!("Attestation version: {}", public_values.attestation_version);
+
+        if let Some(response_date) = public_values.response_date() {
+            println!("Response date: {}", response_date);
+        }
+
+        if let Some(outcome) = &public_values.request_template {
+            println!(
+                "Request matches {} {}: {}",
+                outcome.template.method, outcome.template.path, outcome.matched
+            );
+        }
+
+        if let Some(batch) = &public_values.batch {
+            // Check the program aggregated the batch we gave it.
+            let input = args
+                .input
+                .builder()
+                .and_then(|builder| builder.build_input());
+            let expected = match input {
+                Ok(input) => input.batch_aggregate_digest(),
+                Err(err) => return input_error(err),
+            };
+            println!(
+                "Batch: {} of {} verified, aggregate digest 0x{}",
+                batch.verified_count(),
+                batch.len,
+                hex::encode(batch.aggregate_digest)
+            );
+            if expected != Some(batch.aggregate_digest) {
+                eprintln!(
+                    "Error: expected aggregate digest {}",
+                    expected.map_or("none".to_string(), |digest| format!(
+                        "0x{}",
+                        hex::encode(digest)
+                    ))
+                );
+                return 1;
+            }
+        }
+
+        if args.print_input_digest {
+            // Check the program committed the digest of the attestation we gave it.
+            let (_, verifying_data) = match args.input.load() {
+                Ok(input) => input,
+                Err(err) => return input_error(err.into()),
+            };
+            let expected = verifying_data.canonical_digest();
+            let committed = public_values.attestation_digest;
+            println!("Input digest: 0x{}", hex::encode(committed));
+            if committed != expected {
+                eprintln!("Error: expected input digest 0x{}", hex::encode(expected));
+                return 1;
+            }
+        }
     } else {
+        // Check the cycle budget before paying for a proof.
+        if let (Some(max_cycles), false) = (args.max_cycles, args.skip_cycle_check) {
+            match check_cycle_budget(client, ZKTLS_ELF, &stdin, max_cycles) {
+                Ok(cycles) => println!("Number of cycles: {} of {}", cycles, max_cycles),
+                Err(err) => {
+                    eprintln!("Error: {}", err);
+                    return 1;
+                }
+            }
+        }
+
         // Setup the program for proving.
-        let (pk, vk) = client.setup(ZKTLS_ELF);
+        let (verifier, pk) = ZkTlsVerifier::setup(Arc::clone(client), args.no_cache);
 
         // Generate the proof
-        let proof = client
-            .prove(&pk, &stdin)
-            .run()
-            .expect("failed to generate proof");
+        // Each attempt has the whole timeout to itself, so a retry is not cut short by the
+        // attempts before it.
+        let (pk, stdin) = (Arc::new(pk), Arc::new(stdin));
+        let prove_mode = args.prove_mode;
+        let timeout = args.timeout.map(Duration::from_secs);
+        let proof = match retry_with_backoff(args.max_retries, || {
+            let (prover, pk, stdin) = (Arc::clone(client), Arc::clone(&pk), Arc::clone(&stdin));
+            prove_with_timeout(
+                move || match prove_mode {
+                    ProveMode::Core => prover.prove(&pk, &stdin).core().run(),
+                    ProveMode::Compressed => prover.prove(&pk, &stdin).compressed().run(),
+                },
+                timeout,
+            )
+        }) {
+            Ok(proof) => proof,
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                return 1;
+            }
+        };
 
         println!("Successfully generated proof!");
+        let proof_size = bincode::serialized_size(&proof).expect("proof is serializable");
+        println!("Proof size: {} bytes", proof_size);
 
         // Verify the proof.
-        client.verify(&proof, &vk).expect("failed to verify proof");
+        verifier.verify(&proof).expect("failed to verify proof");
         println!("Successfully verified proof!");
     }
+
+    0
+}
+
+/// Validate the input and verify it natively, printing the report, and return the exit code.
+fn dry_run(args: &Args) -> i32 {
+    let report = match args.input.dry_run() {
+        Ok(report) => report,
+        Err(err) if args.json_report => VerificationReport::failed(args.input.zktls_length, err),
+        Err(err) => return input_error(err),
+    };
+    if args.json_report {
+        println!("{}", serde_json::to_string(&report).unwrap());
+    } else {
+        println!("{}", report);
+    }
+    if report.verified {
+        0
+    } else {
+        1
+    }
+}
+
+/// Run `execute`, profiling it when `--profile` is given, or return the exit code if the profile
+/// cannot be saved.
+fn profiled<T>(args: &Args, execute: impl FnOnce() -> T) -> Result<T, i32> {
+    let Some(output) = &args.profile else {
+        return Ok(execute());
+    };
+    match profile(output, execute) {
+        Ok(executed) => {
+            // The JSON report owns stdout.
+            eprintln!("Profile written to {}", output.display());
+            Ok(executed)
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            Err(1)
+        }
+    }
+}
+
+/// Check the cycles an execution took against `--limit-cycles`, printing both when over it.
+fn within_cycle_limit(args: &Args, cycles: u64) -> bool {
+    match args.limit_cycles {
+        Some(limit) if cycles > limit => {
+            eprintln!(
+                "Error: executed {} cycles, more than the limit of {}",
+                cycles, limit
+            );
+            false
+        }
+        _ => true,
+    }
+}
+
+/// Report an input that failed to load or validate, returning the exit code.
+fn input_error(err: InputError) -> i32 {
+    eprintln!("Error: {}", err);
+    if let Some(hint) = err.hint() {
+        eprintln!("Hint: {}", hint);
+    }
+    1
 }
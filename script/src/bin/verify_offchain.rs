@@ -0,0 +1,57 @@
+//! Verifies a Groth16 or PLONK fixture written by `evm` in plain Rust, without a contract.
+//!
+//! You can run this script using the following command:
+//! ```shell
+//! cargo run --release --bin verify_offchain -- --system groth16
+//! ```
+//! or, for a fixture elsewhere,
+//! ```shell
+//! cargo run --release --bin verify_offchain -- --system plonk --fixture path/to/fixture.json
+//! ```
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use sp1_verifier::{Groth16Verifier, PlonkVerifier, GROTH16_VK_BYTES, PLONK_VK_BYTES};
+use zktls_script::{fixture_path, ProofSystem, SP1ZktlsProofFixture};
+
+/// The arguments for the off-chain verify command.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct VerifyArgs {
+    #[arg(long, value_enum, env = "ZKTLS_PROOF_SYSTEM", default_value_t = ProofSystem::Groth16)]
+    system: ProofSystem,
+    /// The fixture to verify, by default the one `evm` writes for `--system`.
+    #[arg(long, value_name = "PATH")]
+    fixture: Option<PathBuf>,
+}
+
+fn main() {
+    let args = VerifyArgs::parse();
+
+    let path = args.fixture.unwrap_or_else(|| fixture_path(args.system));
+    match verify(&path, args.system) {
+        Ok(()) => println!("Proof verified: {}", path.display()),
+        Err(err) => {
+            eprintln!("Proof failed to verify: {}: {}", path.display(), err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Verify the `system` proof in the fixture at `path` against its vkey and public values.
+fn verify(path: &PathBuf, system: ProofSystem) -> Result<(), Box<dyn std::error::Error>> {
+    let fixture: SP1ZktlsProofFixture = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    let proof = fixture.proof_bytes()?;
+    let public_values = fixture.public_values_bytes()?;
+
+    match system {
+        ProofSystem::Groth16 => {
+            Groth16Verifier::verify(&proof, &public_values, &fixture.vkey, &GROTH16_VK_BYTES)?
+        }
+        ProofSystem::Plonk => {
+            PlonkVerifier::verify(&proof, &public_values, &fixture.vkey, &PLONK_VK_BYTES)?
+        }
+    }
+    Ok(())
+}
@@ -1,10 +1,40 @@
-use sp1_sdk::{include_elf, HashableKey, Prover, ProverClient};
+use std::sync::Arc;
 
-/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
-pub const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+use clap::{Parser, ValueEnum};
+use sp1_sdk::{HashableKey, ProverClient};
+use zktls_script::ZkTlsVerifier;
+
+/// The arguments for the vkey command.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct VkeyArgs {
+    #[arg(long, value_enum, default_value = "bytes32")]
+    format: VkeyFormat,
+}
+
+/// How to print the verifying key.
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+enum VkeyFormat {
+    /// The 0x-prefixed hash the on-chain verifier checks proofs against.
+    Bytes32,
+    /// The 0x-prefixed bincode encoding of the whole verifying key.
+    Hex,
+    /// `{ "bytes32": "0x..", "hash": [..] }`, with the hash as eight u32 words.
+    Json,
+}
 
 fn main() {
-    let prover = ProverClient::builder().cpu().build();
-    let (_, vk) = prover.setup(ZKTLS_ELF);
-    println!("{}", vk.bytes32());
+    let args = VkeyArgs::parse();
+
+    let (verifier, _) = ZkTlsVerifier::setup(Arc::new(ProverClient::from_env()), false);
+    let vk = verifier.vk();
+
+    match args.format {
+        VkeyFormat::Bytes32 => println!("{}", vk.bytes32()),
+        VkeyFormat::Hex => println!("0x{}", hex::encode(bincode::serialize(&vk).unwrap())),
+        VkeyFormat::Json => println!(
+            "{}",
+            serde_json::json!({ "bytes32": vk.bytes32(), "hash": vk.hash_u32() })
+        ),
+    }
 }
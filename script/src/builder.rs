@@ -0,0 +1,611 @@
+use std::fmt;
+
+use sp1_sdk::SP1Stdin;
+use zktls_lib::context::AppContext;
+use zktls_lib::cosign::CoSignature;
+use zktls_lib::cross_check::CrossCheck;
+use zktls_lib::eip712::Eip712Domain;
+use zktls_lib::metadata::AttestationMetadata;
+use zktls_lib::pagination::Linkage;
+use zktls_lib::predicate::Predicate;
+use zktls_lib::public_values::{DigestMode, RecordCommitment};
+use zktls_lib::record::{RangeRequest, RecordPayload, RecordsEncoding, Redaction};
+use zktls_lib::request_template::RequestTemplate;
+use zktls_lib::{is_verifying_key, DataFormat, VerifyConfig};
+
+use crate::input::{
+    Attestation, LoadError, ZkTlsInput, DEFAULT_MAX_DECODED_BYTES, DEFAULT_MAX_RECORD_BYTES,
+};
+use crate::split::{SplitError, SplitVerifyingData};
+
+/// The largest serialized program input the builder accepts by default.
+pub const DEFAULT_MAX_INPUT_BYTES: usize = 64 * 1024 * 1024;
+
+/// Configuration errors caught before the input reaches a prover.
+#[derive(Debug)]
+pub enum InputError {
+    /// An input file could not be loaded.
+    Load(LoadError),
+    /// No verifying key was given.
+    MissingVerifyingKey,
+    /// No attestation was given.
+    MissingData,
+    /// A verifying key is not the hex of a key on a supported curve.
+    InvalidVerifyingKey(String),
+    /// An attestation holds more records than the configured maximum.
+    TooManyRecords { records: usize, max_records: usize },
+    /// The serialized input is larger than the configured maximum.
+    TooLarge { bytes: usize, max_bytes: usize },
+    /// The prefix to prove does not end on a packet boundary within the attestation.
+    Prefix(SplitError),
+    /// Only `VerifyingDataOpt` attestations can be cut to a prefix.
+    PrefixUnsupported(DataFormat),
+    /// Only `VerifyingDataOpt` attestations can be co-signed.
+    CoSignaturesUnsupported(DataFormat),
+    /// An option that reads the records as HTTP messages was combined with binary records.
+    BinaryRecords { option: &'static str },
+    /// An option that needs the records in the program's memory was combined with a streamed
+    /// attestation.
+    StreamedRecords { option: &'static str },
+    /// An option committed in the full public values was combined with a claim, which is
+    /// committed in their place.
+    ClaimOmits { option: &'static str },
+    /// A cross-check was asked for without exactly two attestations and two extraction paths.
+    CrossCheckPaths {
+        data_paths: usize,
+        extract_paths: usize,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Load(err) => write!(f, "{}", err),
+            InputError::MissingVerifyingKey => f.write_str("no verifying key was given"),
+            InputError::MissingData => f.write_str("no attestation was given"),
+            InputError::InvalidVerifyingKey(key) => {
+                write!(f, "`{}` is not a verifying key", key)
+            }
+            InputError::TooManyRecords {
+                records,
+                max_records,
+            } => write!(
+                f,
+                "attestation holds {} records, more than the maximum of {}",
+                records, max_records
+            ),
+            InputError::TooLarge { bytes, max_bytes } => write!(
+                f,
+                "program input is {} bytes, more than the maximum of {}",
+                bytes, max_bytes
+            ),
+            InputError::Prefix(err) => write!(f, "{}", err),
+            InputError::PrefixUnsupported(format) => {
+                write!(f, "cannot prove a prefix of a `{}` attestation", format)
+            }
+            InputError::CoSignaturesUnsupported(format) => {
+                write!(
+                    f,
+                    "cannot verify co-signatures of a `{}` attestation",
+                    format
+                )
+            }
+            InputError::BinaryRecords { option } => write!(
+                f,
+                "`{}` reads the records as HTTP, but they are binary",
+                option
+            ),
+            InputError::StreamedRecords { option } => write!(
+                f,
+                "`{}` needs the records, but the attestation is streamed",
+                option
+            ),
+            InputError::ClaimOmits { option } => write!(
+                f,
+                "`{}` is committed in the public values, but a claim is committed in their place",
+                option
+            ),
+            InputError::CrossCheckPaths {
+                data_paths,
+                extract_paths,
+            } => write!(
+                f,
+                "a cross-check needs two --data-path and two --extract-path, got {} and {}",
+                data_paths, extract_paths
+            ),
+        }
+    }
+}
+
+impl InputError {
+    /// A hint for fixing the error, if there is one beyond its message.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            InputError::Load(err) => err.hint(),
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Load(err) => Some(err),
+            InputError::Prefix(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<LoadError> for InputError {
+    fn from(err: LoadError) -> Self {
+        InputError::Load(err)
+    }
+}
+
+/// Builds a validated [`SP1Stdin`] for the program.
+///
+/// ```ignore
+/// let stdin = ZkTlsInput::builder()
+///     .verifying_key(verifying_key)
+///     .data(verifying_data)
+///     .max_records(16)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZkTlsInputBuilder {
+    report_status: bool,
+    verifying_key: Option<String>,
+    verifying_data: Option<Attestation>,
+    metadata: AttestationMetadata,
+    max_record_bytes: u32,
+    config: VerifyConfig,
+    redactions: Vec<Redaction>,
+    predicate: Option<Predicate>,
+    bodies_only: bool,
+    digest_mode: DigestMode,
+    record_commitment: RecordCommitment,
+    eip712_domain: Option<Eip712Domain>,
+    records_encoding: RecordsEncoding,
+    record_payload: RecordPayload,
+    max_decoded_bytes: u32,
+    header: Option<String>,
+    canonical_body: bool,
+    text_records: bool,
+    request_template: Option<RequestTemplate>,
+    prefix: Option<u32>,
+    sanitize: bool,
+    cross_check: Option<(CrossCheck, String, Attestation)>,
+    pages: Option<(Linkage, Vec<(String, Attestation)>)>,
+    batch: Option<(bool, Vec<(String, Attestation)>)>,
+    claim_path: Option<String>,
+    expected_digest: Option<[u8; 32]>,
+    ranges: Vec<RangeRequest>,
+    context: Option<AppContext>,
+    cosignatures: Vec<CoSignature>,
+    extract: Vec<RangeRequest>,
+    preview: Option<u32>,
+    max_input_bytes: usize,
+}
+
+impl ZkTlsInput {
+    pub fn builder() -> ZkTlsInputBuilder {
+        ZkTlsInputBuilder::default()
+    }
+}
+
+impl Default for ZkTlsInputBuilder {
+    fn default() -> Self {
+        ZkTlsInputBuilder {
+            report_status: false,
+            verifying_key: None,
+            verifying_data: None,
+            metadata: AttestationMetadata::default(),
+            max_record_bytes: DEFAULT_MAX_RECORD_BYTES,
+            config: VerifyConfig::default(),
+            redactions: Vec::new(),
+            predicate: None,
+            bodies_only: false,
+            digest_mode: DigestMode::default(),
+            record_commitment: RecordCommitment::default(),
+            eip712_domain: None,
+            records_encoding: RecordsEncoding::default(),
+            record_payload: RecordPayload::default(),
+            max_decoded_bytes: DEFAULT_MAX_DECODED_BYTES,
+            header: None,
+            canonical_body: false,
+            text_records: false,
+            request_template: None,
+            prefix: None,
+            sanitize: true,
+            cross_check: None,
+            pages: None,
+            batch: None,
+            claim_path: None,
+            expected_digest: None,
+            ranges: Vec::new(),
+            context: None,
+            cosignatures: Vec::new(),
+            extract: Vec::new(),
+            preview: None,
+            max_input_bytes: DEFAULT_MAX_INPUT_BYTES,
+        }
+    }
+}
+
+impl ZkTlsInputBuilder {
+    /// Have a failed execution commit its abort code instead of panicking.
+    pub fn report_status(mut self, report_status: bool) -> Self {
+        self.report_status = report_status;
+        self
+    }
+
+    pub fn verifying_key(mut self, verifying_key: impl Into<String>) -> Self {
+        self.verifying_key = Some(verifying_key.into());
+        self
+    }
+
+    pub fn data(mut self, verifying_data: impl Into<Attestation>) -> Self {
+        self.verifying_data = Some(verifying_data.into());
+        self
+    }
+
+    /// The format version and attestor the attestation's file states, which the program checks
+    /// and commits; see [`load_metadata`](crate::load_metadata).
+    pub fn metadata(mut self, metadata: AttestationMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn max_record_bytes(mut self, max_record_bytes: u32) -> Self {
+        self.max_record_bytes = max_record_bytes;
+        self
+    }
+
+    /// Limit the records of each attestation, both here and in the program.
+    pub fn max_records(mut self, max_records: usize) -> Self {
+        self.config.max_records = max_records;
+        self
+    }
+
+    pub fn max_total_bytes(mut self, max_total_bytes: usize) -> Self {
+        self.config.max_total_bytes = max_total_bytes;
+        self
+    }
+
+    /// Limit the size of the serialized input.
+    pub fn max_input_bytes(mut self, max_input_bytes: usize) -> Self {
+        self.max_input_bytes = max_input_bytes;
+        self
+    }
+
+    pub fn redactions(mut self, redactions: Vec<Redaction>) -> Self {
+        self.redactions = redactions;
+        self
+    }
+
+    pub fn predicate(mut self, predicate: Option<Predicate>) -> Self {
+        self.predicate = predicate;
+        self
+    }
+
+    pub fn bodies_only(mut self, bodies_only: bool) -> Self {
+        self.bodies_only = bodies_only;
+        self
+    }
+
+    pub fn digest_mode(mut self, digest_mode: DigestMode) -> Self {
+        self.digest_mode = digest_mode;
+        self
+    }
+
+    /// Have the program commit only digests of the records instead of the records themselves.
+    pub fn record_commitment(mut self, record_commitment: RecordCommitment) -> Self {
+        self.record_commitment = record_commitment;
+        self
+    }
+
+    pub fn eip712_domain(mut self, eip712_domain: Option<Eip712Domain>) -> Self {
+        self.eip712_domain = eip712_domain;
+        self
+    }
+
+    /// Have the program decode the records of every attestation from `records_encoding` once
+    /// their signatures are checked.
+    pub fn records_encoding(mut self, records_encoding: RecordsEncoding) -> Self {
+        self.records_encoding = records_encoding;
+        self
+    }
+
+    /// Have the program treat the records as opaque bytes rather than HTTP messages, e.g. for
+    /// WebSocket frames. Options that read HTTP are then rejected when building.
+    pub fn record_payload(mut self, record_payload: RecordPayload) -> Self {
+        self.record_payload = record_payload;
+        self
+    }
+
+    /// Limit the size a compressed response body may decode to.
+    pub fn max_decoded_bytes(mut self, max_decoded_bytes: u32) -> Self {
+        self.max_decoded_bytes = max_decoded_bytes;
+        self
+    }
+
+    /// Commit the first value and the count of the response header `header`.
+    pub fn header(mut self, header: Option<String>) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Commit the raw and canonical JSON digests of the response body.
+    pub fn canonical_body(mut self, canonical_body: bool) -> Self {
+        self.canonical_body = canonical_body;
+        self
+    }
+
+    /// Check that the committed records are valid UTF-8, aborting at the first invalid sequence.
+    pub fn text_records(mut self, text_records: bool) -> Self {
+        self.text_records = text_records;
+        self
+    }
+
+    /// Match the request against `request_template`, committing the template instead of the
+    /// request records.
+    pub fn request_template(mut self, request_template: Option<RequestTemplate>) -> Self {
+        self.request_template = request_template;
+        self
+    }
+
+    /// Prove only the first `prefix` records of the attestation, committing their number.
+    pub fn prefix(mut self, prefix: Option<u32>) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Mask the credentials in the request records before committing them, which is the default.
+    pub fn sanitize(mut self, sanitize: bool) -> Self {
+        self.sanitize = sanitize;
+        self
+    }
+
+    /// Cross-check against `other_data`, signed under `other_key`.
+    pub fn cross_check(
+        mut self,
+        cross_check: CrossCheck,
+        other_key: impl Into<String>,
+        other_data: impl Into<Attestation>,
+    ) -> Self {
+        self.cross_check = Some((cross_check, other_key.into(), other_data.into()));
+        self
+    }
+
+    /// Prove the attestation as the first page of a paginated session continued by `pages`, each
+    /// with its verifying key.
+    pub fn pages(mut self, linkage: Linkage, pages: Vec<(String, Attestation)>) -> Self {
+        self.pages = Some((linkage, pages));
+        self
+    }
+
+    /// Verify `items`, each with its verifying key, in the same proof as the attestation. Unless
+    /// `lenient`, the program aborts if any of them fails to verify.
+    pub fn batch(mut self, lenient: bool, items: Vec<(String, Attestation)>) -> Self {
+        self.batch = Some((lenient, items));
+        self
+    }
+
+    /// Commit only a [`Claim`](zktls_lib::claim::Claim) of the value at `path` in the response.
+    pub fn claim(mut self, path: Option<String>) -> Self {
+        self.claim_path = path;
+        self
+    }
+
+    /// Abort unless the attested records hash to `digest`, as
+    /// [`digest`](zktls_lib::public_values::digest) computes it.
+    pub fn expected_digest(mut self, digest: Option<[u8; 32]>) -> Self {
+        self.expected_digest = digest;
+        self
+    }
+
+    /// Also commit the digest of each of `ranges` of the records as committed.
+    pub fn ranges(mut self, ranges: Vec<RangeRequest>) -> Self {
+        self.ranges = ranges;
+        self
+    }
+
+    /// Bind the proof to the deployment `context`, which the program commits as given.
+    pub fn context(mut self, context: Option<AppContext>) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Also verify the attestation under every co-signer's key, committing the keys.
+    pub fn cosignatures(mut self, cosignatures: Vec<CoSignature>) -> Self {
+        self.cosignatures = cosignatures;
+        self
+    }
+
+    /// Commit the bytes of each of `ranges` of the records as committed, leaving the records
+    /// themselves out.
+    pub fn extract(mut self, ranges: Vec<RangeRequest>) -> Self {
+        self.extract = ranges;
+        self
+    }
+
+    /// Commit the first `max_bytes` of the records as committed, and the keccak256 of all of
+    /// them, leaving the records themselves out. The program previews at most
+    /// [`MAX_PREVIEW_BYTES`](zktls_lib::preview::MAX_PREVIEW_BYTES) however many are asked for.
+    pub fn preview(mut self, max_bytes: Option<u32>) -> Self {
+        self.preview = max_bytes;
+        self
+    }
+
+    /// Validate the inputs and write them to stdin in the order the program reads them.
+    pub fn build(self) -> Result<SP1Stdin, InputError> {
+        let max_input_bytes = self.max_input_bytes;
+        let stdin: SP1Stdin = self.build_input()?.into();
+
+        let bytes = stdin.buffer.iter().map(Vec::len).sum();
+        if bytes > max_input_bytes {
+            return Err(InputError::TooLarge {
+                bytes,
+                max_bytes: max_input_bytes,
+            });
+        }
+
+        Ok(stdin)
+    }
+
+    /// Validate the inputs without writing them, to recompute what the program commits.
+    pub fn build_input(mut self) -> Result<ZkTlsInput, InputError> {
+        let verifying_key = self
+            .verifying_key
+            .take()
+            .ok_or(InputError::MissingVerifyingKey)?;
+        let mut verifying_data = self.verifying_data.take().ok_or(InputError::MissingData)?;
+        if let Some(n) = self.prefix {
+            let Attestation::Opt(data) = &verifying_data else {
+                return Err(InputError::PrefixUnsupported(verifying_data.format()));
+            };
+            verifying_data = Attestation::Opt(data.prefix(n as usize).map_err(InputError::Prefix)?);
+        }
+
+        if !self.cosignatures.is_empty() && !matches!(verifying_data, Attestation::Opt(_)) {
+            return Err(InputError::CoSignaturesUnsupported(verifying_data.format()));
+        }
+        if self.record_payload == RecordPayload::Binary {
+            if let Some(option) = self.http_option() {
+                return Err(InputError::BinaryRecords { option });
+            }
+        }
+        if let Attestation::Stream(_) = verifying_data {
+            if let Some(option) = self.records_option() {
+                return Err(InputError::StreamedRecords { option });
+            }
+        }
+        if self.claim_path.is_some() {
+            if let Some(option) = self.public_values_option() {
+                return Err(InputError::ClaimOmits { option });
+            }
+        }
+
+        check_attestation(&verifying_key, &verifying_data, &self.config)?;
+        if let Some((_, other_key, other_data)) = &self.cross_check {
+            check_attestation(other_key, other_data, &self.config)?;
+        }
+        for (page_key, page_data) in self.pages.iter().flat_map(|(_, pages)| pages) {
+            check_attestation(page_key, page_data, &self.config)?;
+        }
+        for (item_key, item_data) in self.batch.iter().flat_map(|(_, items)| items) {
+            check_attestation(item_key, item_data, &self.config)?;
+        }
+
+        Ok(ZkTlsInput {
+            report_status: self.report_status,
+            record_commitment: self.record_commitment,
+            verifying_key,
+            verifying_data,
+            metadata: self.metadata,
+            max_record_bytes: self.max_record_bytes,
+            config: self.config,
+            redactions: self.redactions,
+            predicate: self.predicate,
+            bodies_only: self.bodies_only,
+            digest_mode: self.digest_mode,
+            eip712_domain: self.eip712_domain,
+            records_encoding: self.records_encoding,
+            record_payload: self.record_payload,
+            max_decoded_bytes: self.max_decoded_bytes,
+            header: self.header,
+            canonical_body: self.canonical_body,
+            text_records: self.text_records,
+            request_template: self.request_template,
+            prefix_records: self.prefix,
+            sanitize: self.sanitize,
+            cross_check: self.cross_check,
+            pages: self.pages,
+            batch: self.batch,
+            claim_path: self.claim_path,
+            expected_digest: self.expected_digest,
+            ranges: self.ranges,
+            context: self.context,
+            cosignatures: self.cosignatures,
+            extract: self.extract,
+            preview: self.preview,
+        })
+    }
+
+    /// The first option set that reads the records as HTTP messages, if any. Sanitizing is on by
+    /// default, so the program skips it for binary records instead.
+    fn http_option(&self) -> Option<&'static str> {
+        [
+            ("predicate", self.predicate.is_some()),
+            ("bodies_only", self.bodies_only),
+            ("header", self.header.is_some()),
+            ("canonical_body", self.canonical_body),
+            ("text_records", self.text_records),
+            ("request_template", self.request_template.is_some()),
+            ("cross_check", self.cross_check.is_some()),
+            ("pages", self.pages.is_some()),
+            ("claim", self.claim_path.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(option, set)| set.then_some(option))
+    }
+
+    /// The first option set that is only committed in the full public values, if any. A claim
+    /// would silently drop it, and a context left out would let the claim be replayed.
+    fn public_values_option(&self) -> Option<&'static str> {
+        [
+            ("ranges", !self.ranges.is_empty()),
+            ("context", self.context.is_some()),
+            ("cosignatures", !self.cosignatures.is_empty()),
+            ("extract", !self.extract.is_empty()),
+            ("preview", self.preview.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(option, set)| set.then_some(option))
+    }
+
+    /// The first option set that needs the records themselves rather than their digest, if any.
+    /// A streamed attestation only ever has its digest computed.
+    fn records_option(&self) -> Option<&'static str> {
+        self.http_option().or_else(|| {
+            [
+                ("redactions", !self.redactions.is_empty()),
+                ("digest_mode", self.digest_mode == DigestMode::PerRecord),
+                ("eip712_domain", self.eip712_domain.is_some()),
+                (
+                    "records_encoding",
+                    self.records_encoding != RecordsEncoding::Raw,
+                ),
+                ("batch", self.batch.is_some()),
+                ("ranges", !self.ranges.is_empty()),
+                ("extract", !self.extract.is_empty()),
+                ("preview", self.preview.is_some()),
+            ]
+            .into_iter()
+            .find_map(|(option, set)| set.then_some(option))
+        })
+    }
+}
+
+fn check_attestation(
+    verifying_key: &str,
+    verifying_data: &Attestation,
+    config: &VerifyConfig,
+) -> Result<(), InputError> {
+    if !is_verifying_key(verifying_key) {
+        return Err(InputError::InvalidVerifyingKey(
+            verifying_key.trim().to_string(),
+        ));
+    }
+
+    let records = verifying_data.iter_records().count();
+    if records > config.max_records {
+        return Err(InputError::TooManyRecords {
+            records,
+            max_records: config.max_records,
+        });
+    }
+
+    Ok(())
+}
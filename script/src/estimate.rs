@@ -0,0 +1,45 @@
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+
+use crate::Attestation;
+
+/// The cycles the program spends regardless of the attestation: reading the inputs, parsing and
+/// checking the verifying key, and committing the public values.
+///
+/// A starting point until `fixtures/cycle_counts.json` records a baseline; recalibrate both
+/// constants from it with `UPDATE_CYCLE_COUNTS=1 cargo test --release --test cycle_counts`.
+pub const FIXED_OVERHEAD: u64 = 2_000_000;
+
+/// The cycles the program spends on each record: decrypting it, verifying the signature share
+/// that covers it, and hashing it into the digests.
+pub const CYCLES_PER_RECORD: u64 = 150_000;
+
+/// A cycle count estimated from the shape of an attestation, without executing the program.
+///
+/// The estimate is meant to pick a prover, e.g. the network instead of a local CPU prover for a
+/// large attestation, not to enforce a budget; `--max-cycles` executes the program for that.
+pub trait EstimateCycles {
+    /// The number of records in the attestation.
+    fn record_count(&self) -> usize;
+
+    /// `record_count() * CYCLES_PER_RECORD + FIXED_OVERHEAD`.
+    fn estimate_cycle_cost(&self) -> u64 {
+        (self.record_count() as u64)
+            .saturating_mul(CYCLES_PER_RECORD)
+            .saturating_add(FIXED_OVERHEAD)
+    }
+}
+
+impl EstimateCycles for VerifyingDataOpt {
+    fn record_count(&self) -> usize {
+        self.get_records().len()
+    }
+}
+
+impl EstimateCycles for Attestation {
+    fn record_count(&self) -> usize {
+        match self {
+            Attestation::Merkle(data) => data.record_count(),
+            _ => self.iter_records().count(),
+        }
+    }
+}
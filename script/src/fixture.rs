@@ -0,0 +1,347 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use alloy_sol_types::SolValue;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{HashableKey, SP1Proof, SP1VerifyingKey};
+use zktls_lib::abort::decode_abort;
+use zktls_lib::claim::Claim;
+use zktls_lib::key::VerifyingKey;
+use zktls_lib::{PublicValues, Record};
+
+use crate::ZkTlsProof;
+
+/// The proof systems whose proofs can be verified on the EVM.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum ProofSystem {
+    Plonk,
+    Groth16,
+}
+
+impl ProofSystem {
+    /// The system `proof` can be verified with on the EVM, or `None` for a core or compressed
+    /// proof, which can only be verified natively.
+    pub fn of(proof: &SP1Proof) -> Option<Self> {
+        match proof {
+            SP1Proof::Plonk(_) => Some(ProofSystem::Plonk),
+            SP1Proof::Groth16(_) => Some(ProofSystem::Groth16),
+            SP1Proof::Core(_) | SP1Proof::Compressed(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ProofSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofSystem::Plonk => f.write_str("plonk"),
+            ProofSystem::Groth16 => f.write_str("groth16"),
+        }
+    }
+}
+
+impl FromStr for ProofSystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plonk" => Ok(ProofSystem::Plonk),
+            "groth16" => Ok(ProofSystem::Groth16),
+            _ => Err(format!(
+                "unknown proof system `{}`, expected `plonk` or `groth16`",
+                s
+            )),
+        }
+    }
+}
+
+/// Where `evm` writes the fixture for `system`, under `contracts/src/fixtures`.
+pub fn fixture_path(system: ProofSystem) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../contracts/src/fixtures")
+        .join(format!("{}-fixture.json", system))
+}
+
+/// A fixture that can be used to test the verification of SP1 zkVM proofs inside Solidity.
+///
+/// Every hash and byte string is `0x`-prefixed hex, except the proof, which is written in its
+/// `proof_encoding`. A proof in claim mode commits no request or response digest, so those are
+/// left out and the decoded `claim` is recorded instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SP1ZktlsProofFixture {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_digest: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub response_digest: String,
+    pub records_digest: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bodies_digest: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub record_digests: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eip712_records_hash: Option<String>,
+    /// The public values buffer the proof commits to, empty in fixtures written before it was
+    /// recorded.
+    #[serde(default)]
+    pub public_values: String,
+    /// The committed [`TlsVersion`](zktls_lib::tls::TlsVersion) tag, 0 (unknown) in fixtures
+    /// written before it was recorded.
+    #[serde(default)]
+    pub tls_version: u8,
+    /// The committed [`CipherSuite`](zktls_lib::tls::CipherSuite) tag, 0 (unknown) in fixtures
+    /// written before it was recorded.
+    #[serde(default)]
+    pub cipher_suite: u8,
+    /// The chain id the proof is bound to, if the host committed a context.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u64>,
+    /// The application id the proof is bound to, if the host committed a context.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<String>,
+    /// The committed preview of the records, if the host asked for one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview: Option<String>,
+    /// The keccak256 of all the records the preview is the start of.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_digest: Option<String>,
+    /// The claim the proof commits in claim mode, decoded from `public_values`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claim: Option<ClaimFixture>,
+    pub vkey: String,
+    pub proof: ProofBytes,
+    /// How `proof` is encoded, omitted for hex, which fixtures written before it was recorded
+    /// use.
+    #[serde(default, skip_serializing_if = "ProofEncoding::is_hex")]
+    pub proof_encoding: ProofEncoding,
+}
+
+/// A decoded [`Claim`], with the address and digest as `0x`-prefixed hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimFixture {
+    pub attestor: String,
+    pub domain: String,
+    pub path: String,
+    pub value: String,
+    pub timestamp: u64,
+    pub records_digest: String,
+}
+
+impl From<&Claim> for ClaimFixture {
+    fn from(claim: &Claim) -> Self {
+        ClaimFixture {
+            attestor: format!("0x{}", hex::encode(claim.attestor)),
+            domain: claim.domain.clone(),
+            path: claim.path.clone(),
+            value: claim.value.clone(),
+            timestamp: claim.timestamp,
+            records_digest: format!("0x{}", hex::encode(claim.recordsDigest)),
+        }
+    }
+}
+
+/// How the proof bytes are written in a fixture, selected with `--proof-encoding`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProofEncoding {
+    /// A `0x`-prefixed hex string.
+    #[default]
+    Hex,
+    /// A standard, padded base64 string.
+    Base64,
+    /// An array of byte values.
+    Raw,
+}
+
+impl ProofEncoding {
+    fn is_hex(&self) -> bool {
+        *self == ProofEncoding::Hex
+    }
+
+    /// Encode `proof` for a fixture.
+    pub fn encode(self, proof: &[u8]) -> ProofBytes {
+        match self {
+            ProofEncoding::Hex => ProofBytes::Encoded(format!("0x{}", hex::encode(proof))),
+            ProofEncoding::Base64 => ProofBytes::Encoded(BASE64.encode(proof)),
+            ProofEncoding::Raw => ProofBytes::Raw(proof.to_vec()),
+        }
+    }
+}
+
+/// The proof bytes of a fixture, as a string in a text [`ProofEncoding`] or as raw bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ProofBytes {
+    Encoded(String),
+    Raw(Vec<u8>),
+}
+
+impl fmt::Display for ProofBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofBytes::Encoded(encoded) => f.write_str(encoded),
+            ProofBytes::Raw(bytes) => write!(f, "{:?}", bytes),
+        }
+    }
+}
+
+/// Why the public values of a [`SP1ZktlsProofFixture`] or a [`ZkTlsProof`](crate::ZkTlsProof)
+/// did not decode.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// `publicValues`, or a hex `proof`, is not `0x`-prefixed hex.
+    Hex(hex::FromHexError),
+    /// A base64 `proof` is not base64.
+    Base64(base64::DecodeError),
+    /// `proof` is a string when `proofEncoding` is `raw`, or an array when it is not.
+    ProofEncoding(ProofEncoding),
+    /// The buffer is not bincode-encoded [`PublicValues`].
+    Bincode(bincode::Error),
+    /// The program aborted with this code instead of committing its public values.
+    Aborted(u32),
+    /// The buffer is not an ABI-encoded [`Claim`].
+    Abi(alloy_sol_types::Error),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Hex(err) => write!(f, "public values are not hex: {}", err),
+            DecodeError::Base64(err) => write!(f, "proof is not base64: {}", err),
+            DecodeError::ProofEncoding(encoding) => {
+                write!(f, "proof is not in its declared {:?} encoding", encoding)
+            }
+            DecodeError::Bincode(err) => write!(f, "public values do not decode: {}", err),
+            DecodeError::Aborted(code) => write!(f, "program aborted with code {}", code),
+            DecodeError::Abi(err) => write!(f, "public values are not a claim: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Decode the claim committed in claim mode, failing with the code of a run that aborted.
+pub(crate) fn decode_claim(bytes: &[u8]) -> Result<Claim, DecodeError> {
+    if let Some(code) = decode_abort(bytes) {
+        return Err(DecodeError::Aborted(code));
+    }
+    Claim::abi_decode(bytes).map_err(DecodeError::Abi)
+}
+
+/// Decode a public values buffer the same way `SP1PublicValues::read` does, failing with the code
+/// of a run that aborted.
+pub(crate) fn decode_public_values(bytes: &[u8]) -> Result<PublicValues, DecodeError> {
+    if let Some(code) = decode_abort(bytes) {
+        return Err(DecodeError::Aborted(code));
+    }
+    bincode::deserialize(bytes).map_err(DecodeError::Bincode)
+}
+
+impl SP1ZktlsProofFixture {
+    /// The public values buffer the proof commits to.
+    pub fn public_values_bytes(&self) -> Result<Vec<u8>, DecodeError> {
+        hex::decode(self.public_values.trim_start_matches("0x")).map_err(DecodeError::Hex)
+    }
+
+    /// Decode the committed public values, the same way `SP1PublicValues::read` does.
+    pub fn public_values(&self) -> Result<PublicValues, DecodeError> {
+        decode_public_values(&self.public_values_bytes()?)
+    }
+
+    /// Decode the claim committed in claim mode, as a contract does with `abi.decode`.
+    pub fn claim(&self) -> Result<Claim, DecodeError> {
+        decode_claim(&self.public_values_bytes()?)
+    }
+
+    /// The proof bytes, decoded from `proof_encoding`.
+    pub fn proof_bytes(&self) -> Result<Vec<u8>, DecodeError> {
+        match (&self.proof, self.proof_encoding) {
+            (ProofBytes::Encoded(proof), ProofEncoding::Hex) => {
+                hex::decode(proof.trim_start_matches("0x")).map_err(DecodeError::Hex)
+            }
+            (ProofBytes::Encoded(proof), ProofEncoding::Base64) => {
+                BASE64.decode(proof).map_err(DecodeError::Base64)
+            }
+            (ProofBytes::Raw(proof), ProofEncoding::Raw) => Ok(proof.clone()),
+            (_, encoding) => Err(DecodeError::ProofEncoding(encoding)),
+        }
+    }
+
+    /// The verifying key and the records the proof commits to, without re-running the zkVM.
+    pub fn public_values_as_records(&self) -> Result<(VerifyingKey, Vec<Record>), DecodeError> {
+        let public_values = self.public_values()?;
+        Ok((public_values.verifying_key, public_values.records))
+    }
+}
+
+impl From<(&ZkTlsProof, &SP1VerifyingKey)> for SP1ZktlsProofFixture {
+    /// The fixture for `proof` of the program with verifying key `vk`, with the proof in hex.
+    ///
+    /// A proof whose public values are an ABI-encoded claim records the decoded `claim`; any
+    /// other records the digests and TLS session it commits. A proof of a run that aborted
+    /// records only its public values.
+    fn from((proof, vk): (&ZkTlsProof, &SP1VerifyingKey)) -> Self {
+        let hex = |bytes: &[u8]| format!("0x{}", hex::encode(bytes));
+        let fixture = SP1ZktlsProofFixture {
+            request_digest: String::new(),
+            response_digest: String::new(),
+            records_digest: String::new(),
+            bodies_digest: None,
+            record_digests: Vec::new(),
+            eip712_records_hash: None,
+            public_values: hex(proof.0.public_values.as_slice()),
+            tls_version: 0,
+            cipher_suite: 0,
+            chain_id: None,
+            app_id: None,
+            preview: None,
+            preview_digest: None,
+            claim: None,
+            vkey: vk.bytes32().to_string(),
+            proof: ProofEncoding::Hex.encode(&proof.0.bytes()),
+            proof_encoding: ProofEncoding::Hex,
+        };
+
+        if let Ok(claim) = proof.claim() {
+            let claim = ClaimFixture::from(&claim);
+            return SP1ZktlsProofFixture {
+                records_digest: claim.records_digest.clone(),
+                claim: Some(claim),
+                ..fixture
+            };
+        }
+        let Ok(public_values) = proof.public_values() else {
+            return fixture;
+        };
+        let digests = public_values.digests;
+        SP1ZktlsProofFixture {
+            request_digest: hex(&digests.request_digest),
+            response_digest: hex(&digests.response_digest),
+            records_digest: hex(&digests.records_digest),
+            bodies_digest: public_values.bodies_digest.map(|digest| hex(&digest)),
+            record_digests: public_values
+                .record_digests
+                .iter()
+                .map(|digest| hex(digest.as_slice()))
+                .collect(),
+            eip712_records_hash: public_values.eip712_records_hash.map(|hash| hex(&hash)),
+            tls_version: public_values.tls_session.version,
+            cipher_suite: public_values.tls_session.cipher_suite,
+            chain_id: public_values.context.map(|context| context.chain_id),
+            app_id: public_values.context.map(|context| hex(&context.app_id)),
+            preview: public_values
+                .preview
+                .as_ref()
+                .map(|preview| hex(&preview.bytes)),
+            preview_digest: public_values
+                .preview
+                .as_ref()
+                .map(|preview| hex(&preview.digest)),
+            ..fixture
+        }
+    }
+}
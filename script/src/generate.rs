@@ -0,0 +1,179 @@
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::Aes128;
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::{Signature, SigningKey};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::cosign::CoSignature;
+use zktls_lib::key::VerifyingKey;
+use zktls_lib::verifying_data::VerifyingData;
+
+/// The seed every generated attestation derives its key, nonces and records from, so that
+/// regenerating a fixture reproduces it byte for byte.
+pub const GENERATOR_SEED: u64 = 0x7a6b_746c_735f_6b31;
+
+/// Where `gen_fixtures` writes the verifying key of the generated attestations.
+pub const GENERATED_KEY_PATH: &str = "fixtures/zktls/generated_k256.key";
+
+/// Generate a one-packet `VerifyingDataOpt` of `count` records of `record_bytes` ASCII digits,
+/// returning the hex verifying key it is signed under alongside it.
+///
+/// Attestors encrypt each record with AES-128 in counter mode under the record's 12-byte nonce,
+/// with the counter of 16-byte block `id` starting at `id + 2` as in AES-GCM, and disclose the
+/// plaintext together with the blocks it came from. The packet signature is ECDSA over secp256k1
+/// and SHA-256 of every record's nonce followed by its ciphertext, in order.
+pub fn generate_attestation(count: usize, record_bytes: usize) -> (String, VerifyingDataOpt) {
+    let mut rng = ChaCha20Rng::seed_from_u64(GENERATOR_SEED);
+    let session = Session::new(&mut rng);
+    let messages: Vec<Vec<u8>> = (0..count)
+        .map(|_| {
+            (0..record_bytes)
+                .map(|_| rng.gen_range(b'0'..=b'9'))
+                .collect()
+        })
+        .collect();
+
+    session.attest(&messages, false)
+}
+
+/// Attest `messages` as the records of a one-packet `VerifyingDataOpt`, signed under the same key
+/// as [`generate_attestation`], e.g. to build a verifiable HTTP transcript for a test.
+pub fn attest_records<M: AsRef<[u8]>>(messages: &[M]) -> (String, VerifyingDataOpt) {
+    let mut rng = ChaCha20Rng::seed_from_u64(GENERATOR_SEED);
+    Session::new(&mut rng).attest(messages, false)
+}
+
+/// Attest `messages` as [`attest_records`] does, but with the per-record nonces of TLS 1.3: a fixed
+/// 12-byte IV XORed with the record's sequence number, which need not increase from one record to
+/// the next.
+pub fn attest_tls13_records<M: AsRef<[u8]>>(messages: &[M]) -> (String, VerifyingDataOpt) {
+    let mut rng = ChaCha20Rng::seed_from_u64(GENERATOR_SEED);
+    Session::new(&mut rng).attest(messages, true)
+}
+
+/// Sign every packet of `data` under each of `cosigners` further keys, derived from the generator
+/// seed like the attestation's own, so a fixture can be verified under several attestors.
+///
+/// Panics if `data` lacks a field its packets are signed over.
+pub fn cosign_attestation(data: &VerifyingDataOpt, cosigners: u64) -> Vec<CoSignature> {
+    let data = VerifyingData::try_from(data.clone()).expect("attestation is complete");
+    (1..=cosigners)
+        .map(|cosigner| {
+            let mut rng = ChaCha20Rng::seed_from_u64(GENERATOR_SEED.wrapping_add(cosigner));
+            let signing_key = SigningKey::random(&mut rng);
+            let signatures = data
+                .packets
+                .iter()
+                .map(|packet| {
+                    let signed: Vec<u8> = packet
+                        .records
+                        .iter()
+                        .flat_map(|record| record.nonce.iter().chain(&record.ciphertext))
+                        .copied()
+                        .collect();
+                    let signature: Signature = signing_key.sign(&signed);
+                    hex::encode(signature.to_bytes())
+                })
+                .collect();
+            CoSignature {
+                verifying_key: VerifyingKey::from_sec1_bytes(
+                    signing_key
+                        .verifying_key()
+                        .to_encoded_point(true)
+                        .as_bytes(),
+                )
+                .expect("a signing key's point is on the curve"),
+                signatures,
+            }
+        })
+        .collect()
+}
+
+/// The keys and nonces of a generated session.
+struct Session {
+    signing_key: SigningKey,
+    aes_key: [u8; 16],
+    salt: [u8; 4],
+    first_sequence: u64,
+}
+
+impl Session {
+    fn new(rng: &mut ChaCha20Rng) -> Self {
+        Session {
+            signing_key: SigningKey::random(&mut *rng),
+            aes_key: rng.gen(),
+            salt: rng.gen(),
+            first_sequence: rng.gen(),
+        }
+    }
+
+    /// The nonce of the record at `index`: the salt followed by the explicit nonce, the sequence
+    /// number, as in TLS 1.2, or with `tls13` the salt and first sequence number taken as the IV
+    /// and the sequence number from zero XORed into it.
+    fn nonce(&self, index: u64, tls13: bool) -> [u8; 12] {
+        let mut nonce = [0u8; 12];
+        nonce[..4].copy_from_slice(&self.salt);
+        let explicit = if tls13 {
+            self.first_sequence ^ index
+        } else {
+            self.first_sequence.wrapping_add(index)
+        };
+        nonce[4..].copy_from_slice(&explicit.to_be_bytes());
+        nonce
+    }
+
+    fn attest<M: AsRef<[u8]>>(&self, messages: &[M], tls13: bool) -> (String, VerifyingDataOpt) {
+        let cipher = Aes128::new(&self.aes_key.into());
+
+        let mut record_messages = Vec::with_capacity(messages.len());
+        let mut records = Vec::with_capacity(messages.len());
+        let mut signed = Vec::new();
+        for (index, message) in messages.iter().enumerate() {
+            let message = message.as_ref();
+            let nonce = self.nonce(index as u64, tls13);
+
+            let mut ciphertext = Vec::with_capacity(message.len());
+            let mut blocks = Vec::new();
+            for (id, chunk) in message.chunks(16).enumerate() {
+                let mut keystream = [0u8; 16];
+                keystream[..12].copy_from_slice(&nonce);
+                keystream[12..].copy_from_slice(&(id as u32 + 2).to_be_bytes());
+                let mut keystream = keystream.into();
+                cipher.encrypt_block(&mut keystream);
+                ciphertext.extend(
+                    chunk
+                        .iter()
+                        .zip(keystream.iter())
+                        .map(|(byte, key)| byte ^ key),
+                );
+
+                let mask: Vec<u8> = (0..16)
+                    .map(|offset| u8::from(offset < chunk.len()))
+                    .collect();
+                blocks.push(serde_json::json!({ "id": id, "mask": mask }));
+            }
+
+            signed.extend_from_slice(&nonce);
+            signed.extend_from_slice(&ciphertext);
+            record_messages.push(hex::encode(message));
+            records.push(serde_json::json!({
+                "ciphertext": hex::encode(&ciphertext),
+                "nonce": hex::encode(nonce),
+                "blocks": blocks,
+            }));
+        }
+
+        let signature: Signature = self.signing_key.sign(&signed);
+        let packet = serde_json::json!({
+            "aes_key": hex::encode(self.aes_key),
+            "record_messages": record_messages,
+            "ecdsa_signature": hex::encode(signature.to_bytes()),
+            "records": records,
+        });
+        let verifying_key = hex::encode(self.signing_key.verifying_key().to_encoded_point(true));
+
+        let data = serde_json::from_value(serde_json::json!({ "packets": [packet] })).unwrap();
+        (verifying_key, data)
+    }
+}
@@ -0,0 +1,1266 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use serde::de::Error as _;
+use serde::Deserialize;
+use sp1_sdk::SP1Stdin;
+use zktls_att_verification::verification_data::{VerifyingData, VerifyingDataOpt};
+use zktls_lib::alias::canonicalize;
+use zktls_lib::batch::{aggregate_digest, FAILED_DIGEST};
+use zktls_lib::canonical::canonical_digest;
+use zktls_lib::claim::CommitMode;
+use zktls_lib::content_encoding::decode_bodies;
+use zktls_lib::context::AppContext;
+use zktls_lib::cosign::CoSignature;
+use zktls_lib::cross_check::CrossCheck;
+use zktls_lib::eip712::{eip712_records_hash, Eip712Domain};
+use zktls_lib::hash::RecordHasher;
+use zktls_lib::http::check_content_type;
+use zktls_lib::input::Preamble;
+use zktls_lib::key::{AsVerifyingKey, VerifyingKey};
+use zktls_lib::merkle::MerkleTranscript;
+use zktls_lib::metadata::AttestationMetadata;
+use zktls_lib::pagination::Linkage;
+use zktls_lib::pair::{pairs, RecordPair};
+use zktls_lib::predicate::Predicate;
+use zktls_lib::preview::MAX_PREVIEW_BYTES;
+use zktls_lib::public_values::{DigestMode, RecordCommitment};
+use zktls_lib::record::{redact, RangeRequest, RecordPayload, RecordsEncoding, Redaction};
+use zktls_lib::request_template::RequestTemplate;
+use zktls_lib::sanitize::sanitize;
+use zktls_lib::signatures::{check_signature_count, signature_count};
+use zktls_lib::{DataFormat, Record, VerifyConfig, VerifyError};
+
+use crate::{
+    EstimateCycles, InputError, StreamedAttestation, VerificationReport, ZkTlsInputBuilder,
+};
+
+/// An attestation in whichever container it was serialized as.
+#[derive(Debug, Clone)]
+pub enum Attestation {
+    Opt(VerifyingDataOpt),
+    Full(VerifyingData),
+    Merkle(MerkleTranscript),
+    Stream(StreamedAttestation),
+}
+
+impl Attestation {
+    /// Parse an attestation from `json`, detecting the format when `format` is not given.
+    pub fn parse(json: &str, format: Option<DataFormat>) -> serde_json::Result<Self> {
+        Ok(match format.unwrap_or_else(|| detect_format(json)) {
+            DataFormat::Opt => Attestation::Opt(parse_opt(json)?),
+            DataFormat::Full => Attestation::Full(serde_json::from_str(json)?),
+            DataFormat::Merkle => Attestation::Merkle(serde_json::from_str(json)?),
+            DataFormat::Stream => Attestation::Stream(
+                StreamedAttestation::try_from(parse_opt(json)?)
+                    .map_err(serde_json::Error::custom)?,
+            ),
+        })
+    }
+
+    /// Decode an attestation from CBOR `bytes`, detecting the format when `format` is not given.
+    pub fn from_cbor(
+        bytes: &[u8],
+        format: Option<DataFormat>,
+    ) -> Result<Self, ciborium::de::Error<io::Error>> {
+        Ok(match format.unwrap_or_else(|| detect_cbor_format(bytes)) {
+            DataFormat::Opt => Attestation::Opt(ciborium::from_reader(bytes)?),
+            DataFormat::Full => Attestation::Full(ciborium::from_reader(bytes)?),
+            DataFormat::Merkle => Attestation::Merkle(ciborium::from_reader(bytes)?),
+            DataFormat::Stream => Attestation::Stream(
+                StreamedAttestation::try_from(ciborium::from_reader::<VerifyingDataOpt, _>(bytes)?)
+                    .map_err(ciborium::de::Error::custom)?,
+            ),
+        })
+    }
+
+    pub fn format(&self) -> DataFormat {
+        match self {
+            Attestation::Opt(_) => DataFormat::Opt,
+            Attestation::Full(_) => DataFormat::Full,
+            Attestation::Merkle(_) => DataFormat::Merkle,
+            Attestation::Stream(_) => DataFormat::Stream,
+        }
+    }
+
+    /// Verify the attestation natively, outside the zkVM, against a parsed [`VerifyingKey`] or a
+    /// PEM or hex key.
+    #[tracing::instrument(
+        name = "verify_attestation",
+        level = "debug",
+        skip_all,
+        fields(format = %self.format(), record_count = self.record_count())
+    )]
+    pub fn verify<K: AsVerifyingKey + ?Sized>(&self, verifying_key: &K) -> bool {
+        let verifying_key = match verifying_key.verifying_key() {
+            Ok(verifying_key) => verifying_key,
+            Err(err) => {
+                tracing::debug!("verifying key does not parse: {}", err);
+                return false;
+            }
+        };
+        if let Err(err) = self.check_signature_count() {
+            tracing::debug!("{}", err);
+            return false;
+        }
+        let verified = match self {
+            Attestation::Opt(data) => data.verify(&verifying_key.to_hex()).is_ok(),
+            Attestation::Full(data) => data.verify(&verifying_key.to_hex()).is_ok(),
+            Attestation::Merkle(data) => data.verify(&verifying_key).is_ok(),
+            Attestation::Stream(data) => data.stream().verify(&verifying_key).is_ok(),
+        };
+        if !verified {
+            tracing::debug!("attestation does not verify");
+        }
+        verified
+    }
+
+    /// The number of packet signatures a `VerifyingDataOpt` carries, which is one per packet when
+    /// it is well formed; see [`signatures`](zktls_lib::signatures). Other formats are not
+    /// counted.
+    pub fn signature_count(&self) -> Option<usize> {
+        match self {
+            Attestation::Opt(data) => Some(signature_count(data)),
+            _ => None,
+        }
+    }
+
+    /// Check that a `VerifyingDataOpt` carries exactly one signature per packet, as the program
+    /// does before verifying it.
+    pub fn check_signature_count(&self) -> Result<(), VerifyError> {
+        match self {
+            Attestation::Opt(data) => check_signature_count(data),
+            _ => Ok(()),
+        }
+    }
+
+    /// Verify the attestation natively, rejecting it with [`VerifyError::SignatureCountMismatch`]
+    /// or [`VerifyError::TooLarge`] before any signature work if it is not signed once per packet
+    /// or exceeds `config`.
+    ///
+    /// The container holds no format version; [`load_bench`] checks it on the file.
+    pub fn verify_with_config(
+        &self,
+        verifying_key: &str,
+        config: &VerifyConfig,
+    ) -> Result<(), VerifyError> {
+        self.check_signature_count()?;
+        config.check(&self.records()?)?;
+
+        if self.verify(verifying_key) {
+            Ok(())
+        } else {
+            Err(VerifyError::BadSignature)
+        }
+    }
+
+    /// Verify the attestation natively, then check that its response declares the `expected`
+    /// content type.
+    ///
+    /// Parameters such as `charset` are compared only when `expected` includes them, so
+    /// `application/json` accepts `application/json; charset=utf-8`.
+    pub fn verify_for_content_type(
+        &self,
+        verifying_key: &str,
+        expected: &str,
+    ) -> Result<(), VerifyError> {
+        if !self.verify(verifying_key) {
+            return Err(VerifyError::BadSignature);
+        }
+
+        check_content_type(&self.records()?, expected)
+    }
+
+    /// Verify the attestation natively, then pair every request in it with its response.
+    ///
+    /// Fails with [`VerifyError::OrphanRecord`] when a request has no response or a response no
+    /// request, and with [`VerifyError::MismatchedResponse`] when a response cannot answer its
+    /// request; see [`pairs`].
+    pub fn verify_pairs(&self, verifying_key: &str) -> Result<Vec<RecordPair>, VerifyError> {
+        if !self.verify(verifying_key) {
+            return Err(VerifyError::BadSignature);
+        }
+        self.pairs()
+    }
+
+    /// Every request in the attestation paired with its response, without checking the signature.
+    pub fn pairs(&self) -> Result<Vec<RecordPair>, VerifyError> {
+        pairs(&self.records()?)
+    }
+
+    /// The hex-encoded plaintext of every record.
+    ///
+    /// Fails as [`Attestation::iter_records`] does.
+    // The count is recorded once the records are decoded.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(format = %self.format(), record_count = tracing::field::Empty)
+    )]
+    pub fn get_records(&self) -> Result<Vec<String>, VerifyError> {
+        let records = self
+            .iter_records()
+            .map(|record| record.map(|record| hex::encode(record.bytes)))
+            .collect::<Result<Vec<_>, _>>()?;
+        tracing::Span::current().record("record_count", records.len());
+        Ok(records)
+    }
+
+    /// The plaintext of every record, decoded as it is reached.
+    ///
+    /// The upstream containers decrypt all their records at once, so only a Merkle transcript
+    /// builds its records lazily; either way the iterator is cheap to clone. A record an upstream
+    /// container does not give as hex is yielded as [`VerifyError::InvalidRecordEncoding`].
+    pub fn iter_records(&self) -> impl Iterator<Item = Result<Record, VerifyError>> + Clone + '_ {
+        let (hex, merkle) = match self {
+            Attestation::Opt(data) => (data.get_records(), None),
+            Attestation::Full(data) => (data.get_records(), None),
+            Attestation::Stream(data) => (data.data().get_records(), None),
+            Attestation::Merkle(data) => (Vec::new(), Some(data.iter_records())),
+        };
+        hex.into_iter()
+            .enumerate()
+            .map(|(index, record)| {
+                Record::from_hex(&record).map_err(|_| VerifyError::InvalidRecordEncoding {
+                    record: index as u32,
+                })
+            })
+            .chain(merkle.into_iter().flatten().map(Ok))
+    }
+
+    /// The plaintext of every record, collected; see [`Attestation::iter_records`].
+    pub fn records(&self) -> Result<Vec<Record>, VerifyError> {
+        self.iter_records().collect()
+    }
+
+    /// The EIP-712 hash of the records under `domain`, as the program commits it without
+    /// redactions.
+    pub fn eip712_records_hash(&self, domain: &Eip712Domain) -> Result<[u8; 32], VerifyError> {
+        Ok(eip712_records_hash(&self.records()?, domain))
+    }
+
+    /// SHA-256 over the canonical JSON encoding of the attestation, as the program commits it.
+    pub fn canonical_digest(&self) -> [u8; 32] {
+        match self {
+            Attestation::Opt(data) => canonical_digest(data),
+            Attestation::Full(data) => canonical_digest(data),
+            Attestation::Merkle(data) => canonical_digest(data),
+            Attestation::Stream(data) => canonical_digest(data.header()),
+        }
+    }
+
+    /// Write the format tag followed by the attestation, which for a stream is only its header;
+    /// see [`Attestation::write_chunks`].
+    pub fn write(&self, stdin: &mut SP1Stdin) {
+        stdin.write(&self.format().tag());
+        stdin.write_vec(self.to_bytes());
+    }
+
+    /// The attestation item [`Attestation::write`] puts after the format tag, bincode-encoded as
+    /// the program reads it.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Attestation::Opt(data) => bincode::serialize(data),
+            Attestation::Full(data) => bincode::serialize(data),
+            Attestation::Merkle(data) => bincode::serialize(data),
+            Attestation::Stream(data) => bincode::serialize(data.header()),
+        }
+        .expect("attestations are serializable")
+    }
+
+    /// Write the ciphertext of a streamed attestation in chunks, after every other input; other
+    /// attestations have been written whole.
+    pub fn write_chunks(&self, stdin: &mut SP1Stdin) {
+        if let Attestation::Stream(data) = self {
+            data.write_chunks(stdin);
+        }
+    }
+}
+
+impl From<VerifyingDataOpt> for Attestation {
+    fn from(data: VerifyingDataOpt) -> Self {
+        Attestation::Opt(data)
+    }
+}
+
+impl From<VerifyingData> for Attestation {
+    fn from(data: VerifyingData) -> Self {
+        Attestation::Full(data)
+    }
+}
+
+impl From<MerkleTranscript> for Attestation {
+    fn from(data: MerkleTranscript) -> Self {
+        Attestation::Merkle(data)
+    }
+}
+
+impl From<StreamedAttestation> for Attestation {
+    fn from(data: StreamedAttestation) -> Self {
+        Attestation::Stream(data)
+    }
+}
+
+/// Which input file failed to load.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InputFile {
+    VerifyingKey,
+    Attestation,
+    Redactions,
+    Eip712Domain,
+    RequestTemplate,
+    Records,
+    CoSignatures,
+}
+
+impl fmt::Display for InputFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputFile::VerifyingKey => f.write_str("verifying key"),
+            InputFile::Attestation => f.write_str("attestation"),
+            InputFile::Redactions => f.write_str("redactions"),
+            InputFile::Eip712Domain => f.write_str("EIP-712 domain"),
+            InputFile::RequestTemplate => f.write_str("request template"),
+            InputFile::Records => f.write_str("records"),
+            InputFile::CoSignatures => f.write_str("co-signatures"),
+        }
+    }
+}
+
+/// Errors reading the program inputs from disk.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The `file` at `path` could not be read.
+    Read {
+        file: InputFile,
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// The `file` at `path` was read but does not parse.
+    Parse {
+        file: InputFile,
+        path: PathBuf,
+        message: String,
+    },
+    /// There is no bench attestation of this length.
+    UnsupportedLength(u32),
+}
+
+impl LoadError {
+    pub(crate) fn read(file: InputFile, path: &Path) -> impl FnOnce(io::Error) -> Self + '_ {
+        move |source| LoadError::Read {
+            file,
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    pub(crate) fn parse<E: fmt::Display>(
+        file: InputFile,
+        path: &Path,
+    ) -> impl FnOnce(E) -> Self + '_ {
+        move |err| LoadError::Parse {
+            file,
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        }
+    }
+
+    /// A hint for fixing the error, when it is likely a file missing from the bench fixtures.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            LoadError::Read {
+                file: InputFile::VerifyingKey | InputFile::Attestation,
+                source,
+                ..
+            } if source.kind() == io::ErrorKind::NotFound => Some(
+                "the bench key and attestations are read from `fixtures/zktls/` relative to the \
+                 working directory; run from the repository root, or pass --key-path and \
+                 --data-path",
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Read { file, path, source } => {
+                write!(f, "cannot read {} `{}`: {}", file, path.display(), source)
+            }
+            LoadError::Parse {
+                file,
+                path,
+                message,
+            } => write!(f, "cannot parse {} `{}`: {}", file, path.display(), message),
+            LoadError::UnsupportedLength(length) => write!(
+                f,
+                "no bench attestation of length {}, expected 16, 256, 1024 or 2048",
+                length
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Read { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// The verifying key used when no `--key-path` is given.
+pub const DEFAULT_KEY_PATH: &str = "fixtures/zktls/verifying_k256.key";
+
+/// The largest record the program accepts when no `--max-record-bytes` is given.
+pub const DEFAULT_MAX_RECORD_BYTES: u32 = 16 * 1024;
+
+/// The most bytes a compressed response body may decode to when no `--max-decoded-bytes` is given.
+pub const DEFAULT_MAX_DECODED_BYTES: u32 = 1024 * 1024;
+
+/// Every input the program reads.
+///
+/// Converting into [`SP1Stdin`] writes the inputs in the order the program reads them, so callers
+/// never have to repeat that order themselves:
+///
+/// ```ignore
+/// let stdin: SP1Stdin = ZkTlsInput::new(verifying_key, verifying_data).into();
+/// ```
+///
+/// [`ZkTlsInput::builder`] additionally validates the inputs before writing them.
+#[derive(Debug, Clone)]
+pub struct ZkTlsInput {
+    /// Commit the [`AbortCode`](zktls_lib::abort::AbortCode) of a failed run instead of
+    /// panicking, so an execution reports why it failed. Proving leaves it unset.
+    pub report_status: bool,
+    /// Whether the records themselves are committed, or only digests of them.
+    pub record_commitment: RecordCommitment,
+    pub verifying_key: String,
+    pub verifying_data: Attestation,
+    /// The format version and attestor `verifying_data` states in its file, which its container
+    /// drops; see [`load_metadata`].
+    pub metadata: AttestationMetadata,
+    pub max_record_bytes: u32,
+    pub config: VerifyConfig,
+    pub redactions: Vec<Redaction>,
+    pub predicate: Option<Predicate>,
+    pub bodies_only: bool,
+    pub digest_mode: DigestMode,
+    pub eip712_domain: Option<Eip712Domain>,
+    /// How the records of every attestation encode their plaintext.
+    pub records_encoding: RecordsEncoding,
+    /// Whether the records hold HTTP messages or opaque bytes the program never parses.
+    pub record_payload: RecordPayload,
+    /// The most bytes a compressed response body may decode to.
+    pub max_decoded_bytes: u32,
+    /// The name of a response header to commit the value of.
+    pub header: Option<String>,
+    /// Commit the raw and canonical JSON digests of the response body.
+    pub canonical_body: bool,
+    /// Check that the committed records are valid UTF-8, so they can be rendered as text.
+    pub text_records: bool,
+    /// The template the request must match; the request records are then not committed.
+    pub request_template: Option<RequestTemplate>,
+    /// The number of records in `verifying_data` when it is the prefix of a longer session.
+    pub prefix_records: Option<u32>,
+    /// Mask the credentials in the request records before committing them.
+    pub sanitize: bool,
+    /// The cross-check with the verifying key and attestation it compares against.
+    pub cross_check: Option<(CrossCheck, String, Attestation)>,
+    /// The linkage and the verifying key and attestation of every page after the first.
+    pub pages: Option<(Linkage, Vec<(String, Attestation)>)>,
+    /// Whether the batch is lenient, and the verifying key and attestation of every batch member
+    /// after the first.
+    pub batch: Option<(bool, Vec<(String, Attestation)>)>,
+    /// The path of the value to commit as a [`Claim`](zktls_lib::claim::Claim) in place of the
+    /// full public values.
+    pub claim_path: Option<String>,
+    /// The digest the attested records must hash to, before they are decoded or redacted; see
+    /// [`digest`](zktls_lib::public_values::digest).
+    pub expected_digest: Option<[u8; 32]>,
+    /// The byte ranges of the committed records to commit a digest of.
+    pub ranges: Vec<RangeRequest>,
+    /// The deployment to bind the proof to, committed as given.
+    pub context: Option<AppContext>,
+    /// The signatures of other attestors over the same packets, which must all verify.
+    pub cosignatures: Vec<CoSignature>,
+    /// The byte ranges of the committed records to commit verbatim, in place of the records.
+    pub extract: Vec<RangeRequest>,
+    /// How many bytes of the committed records to preview, in place of the records.
+    pub preview: Option<u32>,
+}
+
+impl ZkTlsInput {
+    /// Prove `verifying_data` under `verifying_key` with the default limits and nothing else
+    /// requested.
+    pub fn new(verifying_key: String, verifying_data: Attestation) -> Self {
+        ZkTlsInput {
+            report_status: false,
+            record_commitment: RecordCommitment::default(),
+            verifying_key,
+            verifying_data,
+            metadata: AttestationMetadata::default(),
+            max_record_bytes: DEFAULT_MAX_RECORD_BYTES,
+            config: VerifyConfig::default(),
+            redactions: Vec::new(),
+            predicate: None,
+            bodies_only: false,
+            digest_mode: DigestMode::default(),
+            eip712_domain: None,
+            records_encoding: RecordsEncoding::default(),
+            record_payload: RecordPayload::default(),
+            max_decoded_bytes: DEFAULT_MAX_DECODED_BYTES,
+            header: None,
+            canonical_body: false,
+            text_records: false,
+            request_template: None,
+            prefix_records: None,
+            sanitize: true,
+            cross_check: None,
+            pages: None,
+            batch: None,
+            claim_path: None,
+            expected_digest: None,
+            ranges: Vec::new(),
+            context: None,
+            cosignatures: Vec::new(),
+            extract: Vec::new(),
+            preview: None,
+        }
+    }
+
+    /// The aggregate digest the program commits for the batch, recomputed natively from the
+    /// attestations, or `None` without a batch or when the program would abort on the records.
+    pub fn batch_aggregate_digest(&self) -> Option<[u8; 32]> {
+        let (_, items) = self.batch.as_ref()?;
+        let decoded = |data: &Attestation| -> Option<Vec<Record>> {
+            let mut records = data.records().ok()?;
+            self.records_encoding.decode(&mut records).ok()?;
+            if self.record_payload == RecordPayload::Http {
+                decode_bodies(&mut records, self.max_decoded_bytes as usize).ok()?;
+            }
+            Some(records)
+        };
+        let digest = |verifying_key: &String, data: &Attestation, records: &[Record]| {
+            if data.verify(verifying_key) {
+                RecordHasher::default().hash_records(records)
+            } else {
+                FAILED_DIGEST
+            }
+        };
+
+        // The first attestation is committed as redacted and sanitized, the others as decoded.
+        let mut records = decoded(&self.verifying_data)?;
+        redact(&mut records, &self.redactions).ok()?;
+        if self.sanitize && self.record_payload == RecordPayload::Http {
+            sanitize(&mut records);
+        }
+        let mut records_digests = vec![digest(&self.verifying_key, &self.verifying_data, &records)];
+        for (item_key, item_data) in items {
+            records_digests.push(digest(item_key, item_data, &decoded(item_data)?));
+        }
+        Some(aggregate_digest(&records_digests))
+    }
+}
+
+impl From<(String, Attestation)> for ZkTlsInput {
+    fn from((verifying_key, verifying_data): (String, Attestation)) -> Self {
+        ZkTlsInput::new(verifying_key, verifying_data)
+    }
+}
+
+impl From<(String, VerifyingDataOpt)> for ZkTlsInput {
+    fn from((verifying_key, verifying_data): (String, VerifyingDataOpt)) -> Self {
+        ZkTlsInput::new(verifying_key, Attestation::Opt(verifying_data))
+    }
+}
+
+impl From<ZkTlsInput> for SP1Stdin {
+    fn from(input: ZkTlsInput) -> Self {
+        let mut stdin = SP1Stdin::new();
+
+        // The preamble declares the key and attestation items, so they are encoded before it.
+        let key = bincode::serialize(&input.verifying_key).expect("strings are serializable");
+        let data = input.verifying_data.to_bytes();
+        let preamble = Preamble::new(input.report_status, &key, &data);
+        stdin.write_slice(&preamble.to_bytes());
+        stdin.write(&input.record_commitment.tag());
+        stdin.write_vec(key);
+        stdin.write(&input.verifying_data.format().tag());
+        stdin.write_vec(data);
+        stdin.write(&input.metadata);
+
+        stdin.write(&input.max_record_bytes);
+        stdin.write(&input.config);
+        stdin.write(&input.redactions);
+        stdin.write(&input.predicate);
+        stdin.write(&input.bodies_only);
+        stdin.write(&input.digest_mode.tag());
+        stdin.write(&input.eip712_domain);
+        stdin.write(&input.records_encoding.tag());
+        stdin.write(&input.record_payload.tag());
+        stdin.write(&input.max_decoded_bytes);
+        stdin.write(&input.header);
+        stdin.write(&input.canonical_body);
+        stdin.write(&input.text_records);
+        stdin.write(&input.request_template);
+        stdin.write(&input.prefix_records);
+        stdin.write(&input.sanitize);
+
+        let cross_check = input.cross_check.as_ref();
+        stdin.write(&cross_check.map(|(cross_check, _, _)| cross_check));
+        if let Some((_, other_key, other_data)) = cross_check {
+            stdin.write(other_key);
+            other_data.write(&mut stdin);
+        }
+
+        let pages = input.pages.as_ref();
+        stdin.write(&pages.map(|(linkage, _)| linkage));
+        if let Some((_, pages)) = pages {
+            stdin.write(&(pages.len() as u32));
+            for (page_key, page_data) in pages {
+                stdin.write(page_key);
+                page_data.write(&mut stdin);
+            }
+        }
+
+        let batch = input.batch.as_ref();
+        stdin.write(&batch.map(|(lenient, _)| *lenient));
+        if let Some((_, items)) = batch {
+            stdin.write(&(items.len() as u32));
+            for (item_key, item_data) in items {
+                stdin.write(item_key);
+                item_data.write(&mut stdin);
+            }
+        }
+
+        let claim_path = input.claim_path.as_ref();
+        let commit_mode = claim_path.map_or(CommitMode::Full, |_| CommitMode::Claim);
+        stdin.write(&commit_mode.tag());
+        if let Some(path) = claim_path {
+            stdin.write(path);
+        }
+        stdin.write(&input.expected_digest);
+        stdin.write(&input.ranges);
+        stdin.write(&input.context);
+        stdin.write(&input.cosignatures);
+        stdin.write(&input.extract);
+        stdin.write(&input.preview);
+
+        // Only the first attestation can be streamed.
+        input.verifying_data.write_chunks(&mut stdin);
+
+        stdin
+    }
+}
+
+/// Arguments selecting the attestation and the other program inputs, shared by the binaries.
+#[derive(clap::Args, Debug, Clone)]
+pub struct InputArgs {
+    #[arg(long, default_value = "16")]
+    pub zktls_length: u32,
+
+    #[arg(long, default_value_t = DEFAULT_MAX_RECORD_BYTES)]
+    pub max_record_bytes: u32,
+
+    /// The most records an attestation may hold.
+    #[arg(long, default_value_t = VerifyConfig::DEFAULT.max_records)]
+    pub max_records: usize,
+
+    /// The most bytes the records of an attestation may hold together.
+    #[arg(long, default_value_t = VerifyConfig::DEFAULT.max_total_bytes)]
+    pub max_bytes: usize,
+
+    /// `stream` sends a `VerifyingDataOpt` in chunks the program verifies as it reads them, for
+    /// records too large to hold in its memory at once.
+    #[arg(long, value_name = "opt|full|merkle|stream")]
+    pub format: Option<DataFormat>,
+
+    #[arg(long, value_name = "PATH")]
+    pub redactions: Option<PathBuf>,
+
+    /// Verifying key for each `--data-path`, defaulting to the bench key.
+    #[arg(long, value_name = "PATH")]
+    pub key_path: Vec<PathBuf>,
+
+    /// Attestation to prove instead of the bench fixture. A second one is cross-checked against
+    /// the first.
+    #[arg(long, value_name = "PATH")]
+    pub data_path: Vec<PathBuf>,
+
+    #[arg(long, value_name = "'$.PATH >= THRESHOLD'")]
+    pub predicate: Option<Predicate>,
+
+    /// Also commit a digest of the response bodies alone, without their headers.
+    #[arg(long)]
+    pub bodies_only: bool,
+
+    /// `per-record` also commits one digest per record, so a contract can check a single record
+    /// later.
+    #[arg(long, value_name = "concatenated|per-record", default_value_t = DigestMode::default())]
+    pub digest_mode: DigestMode,
+
+    /// `digest` commits only digests of the records, leaving the records out. Add
+    /// `--digest-mode per-record` to commit one digest per record.
+    #[arg(long, value_name = "raw|digest", default_value = "raw")]
+    pub commit_mode: RecordCommitment,
+
+    /// JSON file with the EIP-712 domain to also commit the records' typed-data hash under.
+    #[arg(long, value_name = "PATH")]
+    pub eip712_domain: Option<PathBuf>,
+
+    /// `hex` when the attested records hold hex text, decoded in the program after verification.
+    #[arg(long, value_name = "raw|hex", default_value_t = RecordsEncoding::default())]
+    pub records_encoding: RecordsEncoding,
+
+    /// `binary` when the records are not HTTP, e.g. WebSocket frames, so the program commits them
+    /// without parsing them.
+    #[arg(long, value_name = "http|binary", default_value_t = RecordPayload::default())]
+    pub record_payload: RecordPayload,
+
+    /// The most bytes a gzip or deflate response body may decode to.
+    #[arg(long, default_value_t = DEFAULT_MAX_DECODED_BYTES)]
+    pub max_decoded_bytes: u32,
+
+    /// Response header to commit the first value and count of, e.g. `etag`.
+    #[arg(long, value_name = "NAME")]
+    pub header: Option<String>,
+
+    /// Also commit a SHA-256 digest of the response body re-serialized as canonical JSON. A body
+    /// that is not JSON aborts the program.
+    #[arg(long)]
+    pub canonical_body: bool,
+
+    /// Commit the records as text: the program aborts if a committed record is not valid UTF-8,
+    /// and commits that it checked.
+    #[arg(long)]
+    pub text_records: bool,
+
+    /// JSON template the attested request must match, committed in place of the request records.
+    #[arg(long, value_name = "PATH")]
+    pub request_template: Option<PathBuf>,
+
+    /// Prove only the first N records of the attestation, which must end on a packet boundary,
+    /// and commit N.
+    #[arg(long, value_name = "N")]
+    pub prefix: Option<u32>,
+
+    /// Commit the Authorization, Cookie and X-Api-Key request headers as attested, rather than
+    /// masking their values.
+    #[arg(long)]
+    pub keep_credentials: bool,
+
+    /// Path of the value to compare in each cross-checked attestation.
+    #[arg(long, value_name = "$.PATH")]
+    pub extract_path: Vec<String>,
+
+    /// The next page of a paginated query, in order after the `--data-path` page.
+    #[arg(long, value_name = "PATH")]
+    pub page: Vec<PathBuf>,
+
+    /// Path of the next page's cursor in each page's response.
+    #[arg(long, value_name = "$.PATH", default_value = "$.next_cursor")]
+    pub next_cursor_path: String,
+
+    /// Query parameter carrying the cursor in each page's request.
+    #[arg(long, value_name = "NAME", default_value = "cursor")]
+    pub cursor_param: String,
+
+    /// Another attestation to verify in the same proof as the `--data-path` one.
+    #[arg(long, value_name = "PATH")]
+    pub batch: Vec<PathBuf>,
+
+    /// Commit which batch attestations verified instead of aborting when any fails.
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// Commit only an ABI-encoded claim of the value at this path in the response, with the
+    /// attestor, domain, date and records digest.
+    #[arg(long, value_name = "$.PATH")]
+    pub claim: Option<String>,
+
+    /// Abort unless the attested records hash to this digest, so the proof shows the attestor
+    /// signed exactly the bytes expected.
+    #[arg(long, value_name = "0xDIGEST", value_parser = parse_digest)]
+    pub expected_digest: Option<[u8; 32]>,
+
+    /// Also commit a digest of bytes START..END of record RECORD, as committed, so a contract can
+    /// check that slice alone. May be repeated.
+    #[arg(long, value_name = "RECORD:START:END", value_parser = parse_range)]
+    pub commit_range: Vec<RangeRequest>,
+
+    /// Commit this chain id, so a verifier contract on another chain can reject the proof.
+    #[arg(long)]
+    pub chain_id: Option<u64>,
+
+    /// Commit this application id with the chain id, zero when it is not given.
+    #[arg(long, value_name = "0xID", value_parser = parse_digest, requires = "chain_id")]
+    pub app_id: Option<[u8; 32]>,
+
+    /// JSON file of other attestors' signatures over the attestation's packets, all of which must
+    /// verify; the keys are committed.
+    #[arg(long, value_name = "PATH")]
+    pub cosignatures: Option<PathBuf>,
+
+    /// Commit bytes START..END of record RECORD, as committed, verbatim with the records digest,
+    /// and leave the records out. May be repeated.
+    #[arg(long, value_name = "RECORD:START:END", value_parser = parse_range)]
+    pub extract_range: Vec<RangeRequest>,
+
+    /// Commit the first N bytes of the records, as committed, with the keccak256 of all of them,
+    /// and leave the records out.
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(..=MAX_PREVIEW_BYTES as i64)
+    )]
+    pub preview_bytes: Option<u32>,
+}
+
+/// A byte range of a record as `RECORD:START:END`.
+fn parse_range(range: &str) -> Result<RangeRequest, String> {
+    let parts = range
+        .split(':')
+        .map(str::parse)
+        .collect::<Result<Vec<u32>, _>>()
+        .map_err(|err| err.to_string())?;
+    match parts[..] {
+        [record, start, end] => Ok(RangeRequest { record, start, end }),
+        _ => Err("expected RECORD:START:END".to_string()),
+    }
+}
+
+/// 32 bytes in hex, with or without a `0x` prefix.
+fn parse_digest(digest: &str) -> Result<[u8; 32], hex::FromHexError> {
+    let digits = digest.strip_prefix("0x").unwrap_or(digest);
+    <[u8; 32] as hex::FromHex>::from_hex(digits)
+}
+
+impl InputArgs {
+    /// Load the verifying key and the attestation to prove.
+    pub fn load(&self) -> Result<(String, Attestation), LoadError> {
+        let key_path = self.key_path.first().map(PathBuf::as_path);
+        let data_path = self.data_path.first().map(PathBuf::as_path);
+
+        load_bench(self.zktls_length, key_path, data_path, self.format)
+    }
+
+    /// The cross-check requested by a second `--data-path`, if any.
+    ///
+    /// Fails with [`InputError::CrossCheckPaths`] unless there are two `--data-path` and two
+    /// `--extract-path`, or a single `--data-path` and no `--extract-path`.
+    pub fn cross_check(&self) -> Result<Option<CrossCheck>, InputError> {
+        match (self.data_path.len(), &self.extract_path[..]) {
+            (0 | 1, []) => Ok(None),
+            (2, [path, other_path]) => Ok(Some(CrossCheck {
+                path: path.clone(),
+                other_path: other_path.clone(),
+            })),
+            _ => Err(InputError::CrossCheckPaths {
+                data_paths: self.data_path.len(),
+                extract_paths: self.extract_path.len(),
+            }),
+        }
+    }
+
+    /// The deployment given by `--chain-id` and `--app-id`, if any.
+    pub fn context(&self) -> Option<AppContext> {
+        self.chain_id.map(|chain_id| AppContext {
+            chain_id,
+            app_id: self.app_id.unwrap_or_default(),
+        })
+    }
+
+    /// The size limits checked before verifying each attestation.
+    pub fn verify_config(&self) -> VerifyConfig {
+        VerifyConfig {
+            max_records: self.max_records,
+            max_total_bytes: self.max_bytes,
+        }
+    }
+
+    /// Every file the program input is read from.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths = if self.key_path.is_empty() {
+            vec![PathBuf::from(DEFAULT_KEY_PATH)]
+        } else {
+            self.key_path.clone()
+        };
+        if self.data_path.is_empty() {
+            paths.push(bench_data_path(self.zktls_length));
+        }
+        paths.extend(self.data_path.iter().cloned());
+        paths.extend(self.page.iter().cloned());
+        paths.extend(self.batch.iter().cloned());
+        paths.extend(self.redactions.iter().cloned());
+        paths.extend(self.eip712_domain.iter().cloned());
+        paths.extend(self.request_template.iter().cloned());
+        paths.extend(self.cosignatures.iter().cloned());
+        paths
+    }
+
+    /// Build the program input.
+    pub fn try_stdin(&self) -> Result<SP1Stdin, InputError> {
+        self.builder()?.build()
+    }
+
+    /// A builder for the program input, to adjust before building.
+    pub fn builder(&self) -> Result<ZkTlsInputBuilder, InputError> {
+        let (verifying_key, verifying_data) = self.load()?;
+        let data_path = match self.data_path.first() {
+            Some(path) => path.clone(),
+            None => bench_data_path(self.zktls_length),
+        };
+
+        let mut builder = ZkTlsInput::builder()
+            .verifying_key(verifying_key)
+            .data(verifying_data)
+            .metadata(load_metadata(&data_path)?)
+            .max_record_bytes(self.max_record_bytes)
+            .max_records(self.max_records)
+            .max_total_bytes(self.max_bytes)
+            .redactions(load_redactions(self.redactions.as_deref())?)
+            .predicate(self.predicate.clone())
+            .bodies_only(self.bodies_only)
+            .digest_mode(self.digest_mode)
+            .record_commitment(self.commit_mode)
+            .eip712_domain(
+                self.eip712_domain
+                    .as_deref()
+                    .map(load_eip712_domain)
+                    .transpose()?,
+            )
+            .records_encoding(self.records_encoding)
+            .record_payload(self.record_payload)
+            .max_decoded_bytes(self.max_decoded_bytes)
+            .header(self.header.clone())
+            .canonical_body(self.canonical_body)
+            .text_records(self.text_records)
+            .request_template(
+                self.request_template
+                    .as_deref()
+                    .map(load_request_template)
+                    .transpose()?,
+            )
+            .prefix(self.prefix)
+            .sanitize(!self.keep_credentials)
+            .claim(self.claim.clone())
+            .expected_digest(self.expected_digest)
+            .ranges(self.commit_range.clone())
+            .context(self.context())
+            .cosignatures(load_cosignatures(self.cosignatures.as_deref())?)
+            .extract(self.extract_range.clone())
+            .preview(self.preview_bytes);
+
+        if let Some(cross_check) = self.cross_check()? {
+            let key_path = self.key_path.get(1).or(self.key_path.first());
+            let (other_key, other_data) = load_bench(
+                self.zktls_length,
+                key_path.map(PathBuf::as_path),
+                Some(&self.data_path[1]),
+                self.format,
+            )?;
+            builder = builder.cross_check(cross_check, other_key, other_data);
+        }
+
+        if !self.page.is_empty() {
+            let key_path = self.key_path.first().map(PathBuf::as_path);
+            let pages = self
+                .page
+                .iter()
+                .map(|page| load_bench(self.zktls_length, key_path, Some(page), self.format))
+                .collect::<Result<_, _>>()?;
+            let linkage = Linkage {
+                next_cursor_path: self.next_cursor_path.clone(),
+                cursor_param: self.cursor_param.clone(),
+            };
+            builder = builder.pages(linkage, pages);
+        }
+
+        if !self.batch.is_empty() {
+            let key_path = self.key_path.first().map(PathBuf::as_path);
+            let items = self
+                .batch
+                .par_iter()
+                .map(|item| load_bench(self.zktls_length, key_path, Some(item), self.format))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .collect::<Result<_, _>>()?;
+            builder = builder.batch(self.lenient, items);
+        }
+
+        Ok(builder)
+    }
+
+    /// Load and validate the program input, then verify the attestation natively, without
+    /// setting up, executing or proving the program.
+    ///
+    /// Fails if an input does not load or validate; an attestation that does not verify is
+    /// reported instead.
+    pub fn dry_run(&self) -> Result<VerificationReport, InputError> {
+        let builder = self.builder()?;
+        // Building checks the serialized size, which the input alone does not.
+        builder.clone().build()?;
+        let input = builder.build_input()?;
+
+        Ok(VerificationReport::dry_run(
+            self.zktls_length,
+            &input.verifying_data,
+            &input.verifying_key,
+            &input.config,
+        ))
+    }
+}
+
+/// Load the verifying key at `key_path` and the attestation at `data_path`, falling back to the
+/// bench key and the bench attestation for `length`.
+///
+/// Attestations in files with a `.cbor` extension are decoded as CBOR, all others as JSON. When
+/// `format` is not given it is detected from the attestation.
+pub fn load_bench(
+    length: u32,
+    key_path: Option<&Path>,
+    data_path: Option<&Path>,
+    format: Option<DataFormat>,
+) -> Result<(String, Attestation), LoadError> {
+    let data_path = match data_path {
+        Some(path) => path.to_path_buf(),
+        None => {
+            if !matches!(length, 16 | 256 | 1024 | 2048) {
+                return Err(LoadError::UnsupportedLength(length));
+            }
+            bench_data_path(length)
+        }
+    };
+
+    let verifying_key = load_verifying_key(key_path.unwrap_or(Path::new(DEFAULT_KEY_PATH)))?;
+    let verifying_data = load_attestation(&data_path, format)?;
+
+    Ok((verifying_key, verifying_data))
+}
+
+/// Load the `VerifyingDataOpt` attestations at `paths`, parsing them in parallel on all available
+/// cores, each paired with the bench verifying key.
+///
+/// The attestations are returned in the order of `paths`. If any fails to load, the error of the
+/// first such path is returned.
+pub fn load_fixtures_parallel(
+    paths: &[PathBuf],
+) -> Result<Vec<(String, VerifyingDataOpt)>, LoadError> {
+    let verifying_key = load_verifying_key(Path::new(DEFAULT_KEY_PATH))?;
+
+    paths
+        .par_iter()
+        .map(
+            |path| match load_attestation(path, Some(DataFormat::Opt))? {
+                Attestation::Opt(data) => Ok((verifying_key.clone(), data)),
+                _ => unreachable!("attestation was parsed as VerifyingDataOpt"),
+            },
+        )
+        .collect::<Vec<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Load the attestation at `path`, as CBOR if it has a `.cbor` extension and as JSON otherwise.
+///
+/// An attestation in a format version the verifier does not support is rejected before it is
+/// parsed; the program checks the version again, from the [`load_metadata`] written beside it.
+#[tracing::instrument(level = "debug", skip_all, fields(path = %path.display()))]
+fn load_attestation(path: &Path, format: Option<DataFormat>) -> Result<Attestation, LoadError> {
+    let file = InputFile::Attestation;
+    let (bytes, metadata) = read_metadata(path)?;
+    metadata.check().map_err(LoadError::parse(file, path))?;
+    if path.extension().is_some_and(|ext| ext == "cbor") {
+        Attestation::from_cbor(&bytes, format).map_err(LoadError::parse(file, path))
+    } else {
+        let json = std::str::from_utf8(&bytes).map_err(LoadError::parse(file, path))?;
+        Attestation::parse(json, format).map_err(LoadError::parse(file, path))
+    }
+}
+
+/// Load the format version and attestor the attestation at `path` states, which its container
+/// drops when it is parsed; see [`metadata`](zktls_lib::metadata).
+///
+/// Fails like [`load_bench`] when the file does not read or parse, but not on an unsupported
+/// version, which the program rejects.
+pub fn load_metadata(path: &Path) -> Result<AttestationMetadata, LoadError> {
+    read_metadata(path).map(|(_, metadata)| metadata)
+}
+
+/// The bytes of the attestation file at `path` and the metadata it states.
+fn read_metadata(path: &Path) -> Result<(Vec<u8>, AttestationMetadata), LoadError> {
+    let file = InputFile::Attestation;
+    let bytes = std::fs::read(path).map_err(LoadError::read(file, path))?;
+    let metadata = if path.extension().is_some_and(|ext| ext == "cbor") {
+        let value: ciborium::Value =
+            ciborium::from_reader(bytes.as_slice()).map_err(LoadError::parse(file, path))?;
+        AttestationMetadata::of(&value)
+    } else {
+        let mut value: serde_json::Value =
+            serde_json::from_slice(&bytes).map_err(LoadError::parse(file, path))?;
+        canonicalize(&mut value);
+        AttestationMetadata::of(&value)
+    };
+    let metadata = metadata.map_err(LoadError::parse(file, path))?;
+    Ok((bytes, metadata))
+}
+
+/// Load the verifying key at `path` as the hex the program expects.
+///
+/// Files with a `.der` extension hold a DER `SubjectPublicKeyInfo` and files with a `.sec1`
+/// extension a raw SEC1 point; all others hold a PEM block or hex. Text that does not parse is
+/// passed on as is, for input validation to reject.
+pub fn load_verifying_key(path: &Path) -> Result<String, LoadError> {
+    let file = InputFile::VerifyingKey;
+    let bytes = std::fs::read(path).map_err(LoadError::read(file, path))?;
+    let key = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("der") => VerifyingKey::from_der(&bytes),
+        Some("sec1") => VerifyingKey::from_sec1_bytes(&bytes),
+        _ => {
+            let text = String::from_utf8(bytes).map_err(LoadError::parse(file, path))?;
+            return Ok(VerifyingKey::parse(&text).map_or(text, |key| key.to_hex()));
+        }
+    };
+
+    key.map(|key| key.to_hex()).map_err(|err| LoadError::Parse {
+        file,
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    })
+}
+
+fn bench_data_path(length: u32) -> PathBuf {
+    PathBuf::from(format!("fixtures/zktls/data/bench{}.json", length))
+}
+
+/// Write the format tag followed by the attestation parsed from `json`, or fail without writing
+/// anything if `json` does not hold an attestation.
+pub fn write_verifying_data(
+    json: &str,
+    format: Option<DataFormat>,
+    stdin: &mut SP1Stdin,
+) -> serde_json::Result<()> {
+    Attestation::parse(json, format)?.write(stdin);
+    Ok(())
+}
+
+/// Load the redactions to apply before committing records, or none without a `path`.
+///
+/// The file holds a JSON array of `{ "record": .., "start": .., "end": .. }` objects.
+pub fn load_redactions(path: Option<&Path>) -> Result<Vec<Redaction>, LoadError> {
+    let Some(path) = path else {
+        return Ok(Vec::new());
+    };
+
+    let file = InputFile::Redactions;
+    let redactions = std::fs::read_to_string(path).map_err(LoadError::read(file, path))?;
+    serde_json::from_str(&redactions).map_err(LoadError::parse(file, path))
+}
+
+/// Load the co-signatures at `path`, a JSON array of objects with a hex `verifying_key` and the hex
+/// `signatures` of every packet, or none without a path.
+pub fn load_cosignatures(path: Option<&Path>) -> Result<Vec<CoSignature>, LoadError> {
+    let Some(path) = path else {
+        return Ok(Vec::new());
+    };
+
+    let file = InputFile::CoSignatures;
+    let cosignatures = std::fs::read_to_string(path).map_err(LoadError::read(file, path))?;
+    serde_json::from_str(&cosignatures).map_err(LoadError::parse(file, path))
+}
+
+/// Load the EIP-712 domain at `path`, a JSON object with `name`, `version`, `chain_id` and a
+/// `0x`-prefixed `verifying_contract`.
+pub fn load_eip712_domain(path: &Path) -> Result<Eip712Domain, LoadError> {
+    let file = InputFile::Eip712Domain;
+    let domain = std::fs::read_to_string(path).map_err(LoadError::read(file, path))?;
+    serde_json::from_str(&domain).map_err(LoadError::parse(file, path))
+}
+
+/// Read a [`RequestTemplate`] from the JSON file at `path`.
+pub fn load_request_template(path: &Path) -> Result<RequestTemplate, LoadError> {
+    let file = InputFile::RequestTemplate;
+    let template = std::fs::read_to_string(path).map_err(LoadError::read(file, path))?;
+    serde_json::from_str(&template).map_err(LoadError::parse(file, path))
+}
+
+/// One record of a records file.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RecordBytes {
+    Text(String),
+    Base64 { base64: String },
+    Hex { hex: String },
+}
+
+impl RecordBytes {
+    fn decode(self) -> Result<Vec<u8>, String> {
+        match self {
+            RecordBytes::Text(text) => Ok(text.into_bytes()),
+            RecordBytes::Base64 { base64 } => BASE64.decode(base64).map_err(|err| err.to_string()),
+            RecordBytes::Hex { hex } => hex::decode(hex).map_err(|err| err.to_string()),
+        }
+    }
+}
+
+/// Read the plaintext records in the JSON file at `path`, e.g. to attest with
+/// [`attest_records`](crate::attest_records).
+///
+/// The file holds a JSON array with one entry per record: a string for a record of UTF-8 text, or
+/// `{ "base64": .. }` or `{ "hex": .. }` for arbitrary bytes such as WebSocket frames.
+pub fn load_records(path: &Path) -> Result<Vec<Vec<u8>>, LoadError> {
+    let file = InputFile::Records;
+    let records = std::fs::read_to_string(path).map_err(LoadError::read(file, path))?;
+    let records: Vec<RecordBytes> =
+        serde_json::from_str(&records).map_err(LoadError::parse(file, path))?;
+    records
+        .into_iter()
+        .map(RecordBytes::decode)
+        .collect::<Result<_, _>>()
+        .map_err(|message| LoadError::Parse {
+            file,
+            path: path.to_path_buf(),
+            message,
+        })
+}
+
+/// Detect which container `json` holds, preferring `VerifyingDataOpt`.
+pub fn detect_format(json: &str) -> DataFormat {
+    if parse_opt(json).is_ok() {
+        DataFormat::Opt
+    } else if serde_json::from_str::<MerkleTranscript>(json).is_ok() {
+        DataFormat::Merkle
+    } else {
+        DataFormat::Full
+    }
+}
+
+/// Parse a `VerifyingDataOpt` from `json`, accepting the field aliases other attestors emit; see
+/// [`zktls_lib::alias`].
+///
+/// The aliases are renamed before parsing rather than on failure, as the container may fill in a
+/// field it finds missing.
+pub(crate) fn parse_opt(json: &str) -> serde_json::Result<VerifyingDataOpt> {
+    let mut value: serde_json::Value = serde_json::from_str(json)?;
+    canonicalize(&mut value);
+    serde_json::from_value(value)
+}
+
+/// Detect which container the CBOR `bytes` hold, preferring `VerifyingDataOpt`.
+pub fn detect_cbor_format(bytes: &[u8]) -> DataFormat {
+    if ciborium::from_reader::<VerifyingDataOpt, _>(bytes).is_ok() {
+        DataFormat::Opt
+    } else if ciborium::from_reader::<MerkleTranscript, _>(bytes).is_ok() {
+        DataFormat::Merkle
+    } else {
+        DataFormat::Full
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use zktls_att_verification::verification_data::{VerifyingData, VerifyingDataOpt};
+use zktls_lib::metadata::AttestationMetadata;
+
+use crate::{InputFile, LoadError};
+
+/// Errors that can occur while writing an attestation to a file.
+#[derive(Debug)]
+pub enum WriteError {
+    /// The attestation does not serialize to JSON.
+    Serialize(serde_json::Error),
+    /// The JSON could not be written to `path`.
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteError::Serialize(err) => write!(f, "cannot serialize the attestation: {}", err),
+            WriteError::Io { path, source } => {
+                write!(f, "cannot write `{}`: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WriteError::Serialize(err) => Some(err),
+            WriteError::Io { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Reading and checkpointing an attestation as a JSON file.
+pub trait JsonFile: Sized {
+    /// Read the attestation in the JSON file at `path`, rejecting it if it states a format
+    /// version the verifier does not support.
+    fn from_json_file(path: &Path) -> Result<Self, LoadError>;
+
+    /// Write the attestation to `path` as pretty-printed JSON.
+    ///
+    /// The JSON is written to a temporary file next to `path`, then renamed over it, so an
+    /// interrupted write leaves either the previous file or the new one, never a truncated one.
+    fn to_json_file(&self, path: &Path) -> Result<(), WriteError>;
+}
+
+impl JsonFile for VerifyingDataOpt {
+    fn from_json_file(path: &Path) -> Result<Self, LoadError> {
+        read_json(path)
+    }
+
+    fn to_json_file(&self, path: &Path) -> Result<(), WriteError> {
+        write_json(self, path)
+    }
+}
+
+impl JsonFile for VerifyingData {
+    fn from_json_file(path: &Path) -> Result<Self, LoadError> {
+        read_json(path)
+    }
+
+    fn to_json_file(&self, path: &Path) -> Result<(), WriteError> {
+        write_json(self, path)
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, LoadError> {
+    let file = InputFile::Attestation;
+    let json = std::fs::read_to_string(path).map_err(LoadError::read(file, path))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&json).map_err(LoadError::parse(file, path))?;
+    // The version is checked before the container can drop it.
+    AttestationMetadata::of(&value)
+        .and_then(|metadata| metadata.check())
+        .map_err(LoadError::parse(file, path))?;
+    serde_json::from_value(value).map_err(LoadError::parse(file, path))
+}
+
+fn write_json<T: Serialize>(value: &T, path: &Path) -> Result<(), WriteError> {
+    let mut json = serde_json::to_vec_pretty(value).map_err(WriteError::Serialize)?;
+    json.push(b'\n');
+
+    let partial = path.with_extension(format!("{}.partial", std::process::id()));
+    let written = std::fs::File::create(&partial)
+        .and_then(|mut file| {
+            file.write_all(&json)?;
+            // Flush to disk before the rename makes the file visible under `path`.
+            file.sync_all()
+        })
+        .and_then(|()| std::fs::rename(&partial, path));
+    written.map_err(|source| {
+        let _ = std::fs::remove_file(&partial);
+        WriteError::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    })
+}
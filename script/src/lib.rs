@@ -0,0 +1,63 @@
+//! Helpers shared by the zktls script binaries.
+
+mod aggregate;
+#[cfg(feature = "tokio")]
+mod batch;
+mod builder;
+mod estimate;
+mod fixture;
+mod generate;
+mod input;
+mod json_file;
+mod logging;
+mod profile;
+mod proof;
+mod prove;
+#[cfg(feature = "remote")]
+mod remote;
+mod report;
+mod session;
+mod setup;
+mod split;
+mod stream;
+mod verifier;
+mod verify_bytes;
+mod watch;
+
+pub use aggregate::{read_aggregation, AggregationError, ChildProofs, AGGREGATION_ELF};
+#[cfg(feature = "tokio")]
+pub use batch::verify_many_async;
+pub use builder::{InputError, ZkTlsInputBuilder, DEFAULT_MAX_INPUT_BYTES};
+pub use estimate::{EstimateCycles, CYCLES_PER_RECORD, FIXED_OVERHEAD};
+pub use fixture::{
+    fixture_path, ClaimFixture, DecodeError, ProofBytes, ProofEncoding, ProofSystem,
+    SP1ZktlsProofFixture,
+};
+pub use generate::{
+    attest_records, attest_tls13_records, cosign_attestation, generate_attestation,
+    GENERATED_KEY_PATH, GENERATOR_SEED,
+};
+pub use input::{
+    detect_cbor_format, detect_format, load_bench, load_cosignatures, load_eip712_domain,
+    load_fixtures_parallel, load_metadata, load_records, load_redactions, load_request_template,
+    load_verifying_key, write_verifying_data, Attestation, InputArgs, InputFile, LoadError,
+    ZkTlsInput, DEFAULT_KEY_PATH, DEFAULT_MAX_DECODED_BYTES, DEFAULT_MAX_RECORD_BYTES,
+};
+pub use json_file::{JsonFile, WriteError};
+pub use logging::{setup_logger, LogFormat};
+pub use profile::{profile, ProfileError, TRACE_FILE_ENV};
+pub use proof::ZkTlsProof;
+pub use prove::{
+    backoff_delay, check_cycle_budget, is_transient, prove_with_timeout, retry_with_backoff,
+    ProveError, INITIAL_BACKOFF, MAX_BACKOFF,
+};
+#[cfg(feature = "remote")]
+pub use remote::{FetchError, FromUrl, DEFAULT_FETCH_TIMEOUT};
+pub use report::{read_claim, read_public_values, verify_many, Aborted, VerificationReport};
+pub use session::{new_session, SessionStdin};
+pub use setup::{setup_cached, SETUP_CACHE_ENV};
+pub use split::{PrefixError, SplitError, SplitVerifyingData};
+pub use stream::StreamedAttestation;
+pub use verifier::{ZkTlsVerifier, ZKTLS_ELF};
+pub use verify_bytes::VerifyBytes;
+pub use watch::{watch, WATCH_DEBOUNCE};
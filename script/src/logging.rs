@@ -0,0 +1,43 @@
+use clap::ValueEnum;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+/// The format logs are emitted in.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines, as printed by `sp1_sdk::utils::setup_logger`.
+    #[default]
+    Text,
+    /// One JSON object per event, for log aggregation stacks such as ELK or Loki.
+    Json,
+}
+
+/// Install the global logger.
+///
+/// Text logs go through the SP1 logger unless `stderr_only` is set, in which case every format
+/// writes to stderr to keep stdout free for machine-readable output. `RUST_LOG` controls the
+/// level and defaults to `info`; at `debug` the verification steps of `zktls-lib` are logged as
+/// spans with their timings.
+pub fn setup_logger(format: LogFormat, stderr_only: bool) {
+    let filter = || EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    match (format, stderr_only) {
+        (LogFormat::Text, false) => sp1_sdk::utils::setup_logger(),
+        (LogFormat::Text, true) => tracing_subscriber::fmt()
+            .with_env_filter(filter())
+            .with_span_events(FmtSpan::CLOSE)
+            .with_writer(std::io::stderr)
+            .init(),
+        (LogFormat::Json, false) => tracing_subscriber::fmt()
+            .json()
+            .with_env_filter(filter())
+            .with_span_events(FmtSpan::CLOSE)
+            .init(),
+        (LogFormat::Json, true) => tracing_subscriber::fmt()
+            .json()
+            .with_env_filter(filter())
+            .with_span_events(FmtSpan::CLOSE)
+            .with_writer(std::io::stderr)
+            .init(),
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The environment variable SP1's executor writes its trace to when it is set.
+pub const TRACE_FILE_ENV: &str = "TRACE_FILE";
+
+/// Errors that can occur while profiling an execution.
+#[derive(Debug)]
+pub enum ProfileError {
+    /// The execution wrote no trace, because the SDK was built without profiling support.
+    NoTrace,
+    /// The trace was written but could not be moved to `path`.
+    Move { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::NoTrace => f.write_str(
+                "the execution wrote no trace; build the script with `--features profiling`",
+            ),
+            ProfileError::Move { path, source } => {
+                write!(
+                    f,
+                    "cannot write the profile to `{}`: {}",
+                    path.display(),
+                    source
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProfileError::Move { source, .. } => Some(source),
+            ProfileError::NoTrace => None,
+        }
+    }
+}
+
+/// Run `execute` with SP1's profiler on, then move the flame graph it writes to `output`.
+///
+/// The trace is written to a scratch file in the system temp directory, so a failed execution
+/// never leaves a partial profile at `output`. The trace is in the Gecko format the Firefox
+/// Profiler opens, and the executor has finished writing it once `execute` returns.
+pub fn profile<T>(output: &Path, execute: impl FnOnce() -> T) -> Result<T, ProfileError> {
+    let trace = std::env::temp_dir().join(format!("zktls-trace-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&trace);
+
+    std::env::set_var(TRACE_FILE_ENV, &trace);
+    let result = execute();
+    std::env::remove_var(TRACE_FILE_ENV);
+
+    if !trace.exists() {
+        return Err(ProfileError::NoTrace);
+    }
+    let moved = std::fs::rename(&trace, output).or_else(|_| {
+        // The temp directory may be on another filesystem than `output`.
+        std::fs::copy(&trace, output)?;
+        std::fs::remove_file(&trace)
+    });
+    moved.map_err(|source| ProfileError::Move {
+        path: output.to_path_buf(),
+        source,
+    })?;
+
+    Ok(result)
+}
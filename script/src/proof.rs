@@ -0,0 +1,52 @@
+use sp1_sdk::SP1ProofWithPublicValues;
+use zktls_lib::claim::Claim;
+use zktls_lib::key::VerifyingKey;
+use zktls_lib::{PublicValues, Record};
+
+use crate::fixture::{decode_claim, decode_public_values};
+use crate::{DecodeError, ProofSystem};
+
+/// A proof of the zktls program, with accessors for what it commits.
+///
+/// The public values are decoded on every call; read them once with
+/// [`public_values`](Self::public_values) to access several fields.
+#[derive(Clone)]
+pub struct ZkTlsProof(pub SP1ProofWithPublicValues);
+
+impl ZkTlsProof {
+    /// The verifying key of the attestor whose attestation the proof verifies.
+    pub fn verifying_key(&self) -> Result<VerifyingKey, DecodeError> {
+        Ok(self.public_values()?.verifying_key)
+    }
+
+    /// The records the proof commits to, after redaction.
+    pub fn records(&self) -> Result<Vec<Record>, DecodeError> {
+        Ok(self.public_values()?.records)
+    }
+
+    /// Every value the program committed, failing for a proof in claim mode, which commits a
+    /// [`Claim`](zktls_lib::claim::Claim) in their place.
+    pub fn public_values(&self) -> Result<PublicValues, DecodeError> {
+        decode_public_values(self.0.public_values.as_slice())
+    }
+
+    /// The claim a proof in claim mode commits in place of the public values.
+    pub fn claim(&self) -> Result<Claim, DecodeError> {
+        decode_claim(self.0.public_values.as_slice())
+    }
+
+    /// The system the proof can be verified with on the EVM; see [`ProofSystem::of`].
+    pub fn proof_system(&self) -> Option<ProofSystem> {
+        ProofSystem::of(&self.0.proof)
+    }
+
+    pub fn into_inner(self) -> SP1ProofWithPublicValues {
+        self.0
+    }
+}
+
+impl From<SP1ProofWithPublicValues> for ZkTlsProof {
+    fn from(proof: SP1ProofWithPublicValues) -> Self {
+        ZkTlsProof(proof)
+    }
+}
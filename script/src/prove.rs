@@ -0,0 +1,173 @@
+use std::fmt;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use sp1_sdk::{EnvProver, SP1Stdin};
+
+/// The delay before the first retry of a transient prover failure.
+pub const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The longest delay between two retries.
+pub const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Fragments of the errors a prover returns when it may succeed if asked again: the network
+/// dropped, the prover network was overloaded or rate limited, or a request timed out.
+const TRANSIENT_ERRORS: &[&str] = &[
+    "timed out",
+    "timeout",
+    "connection",
+    "temporarily",
+    "unavailable",
+    "rate limit",
+    "too many requests",
+    "bad gateway",
+    "gateway timeout",
+    "broken pipe",
+];
+
+/// Errors that can occur while generating a proof.
+#[derive(Debug)]
+pub enum ProveError {
+    /// The prover did not return a proof before the deadline.
+    Timeout(Duration),
+    /// The prover itself failed.
+    Prover(String),
+    /// Executing the program to count its cycles failed.
+    Execution(String),
+    /// The program takes more cycles than the budget allows.
+    CycleBudgetExceeded { cycles: u64, max_cycles: u64 },
+}
+
+impl fmt::Display for ProveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProveError::Timeout(timeout) => {
+                write!(f, "proof generation timed out after {}s", timeout.as_secs())
+            }
+            ProveError::Prover(err) => write!(f, "failed to generate proof: {}", err),
+            ProveError::Execution(err) => write!(f, "failed to execute the program: {}", err),
+            ProveError::CycleBudgetExceeded { cycles, max_cycles } => write!(
+                f,
+                "program takes {} cycles, more than the budget of {}",
+                cycles, max_cycles
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProveError {}
+
+/// Run `prove`, giving up once `timeout` has elapsed.
+///
+/// The proof is generated on a worker thread so that a hung prover cannot block the caller. A
+/// prover cannot be cancelled, so on timeout the worker is only abandoned: it is detached and
+/// keeps proving until it finishes or the process exits, and its result is dropped. A retry after
+/// a timeout therefore runs alongside the abandoned attempt. Without a timeout `prove` runs on the
+/// current thread.
+pub fn prove_with_timeout<T, F, E>(prove: F, timeout: Option<Duration>) -> Result<T, ProveError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, E> + Send + 'static,
+    E: fmt::Display,
+{
+    let Some(timeout) = timeout else {
+        return prove().map_err(|err| ProveError::Prover(err.to_string()));
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let result = prove().map_err(|err| ProveError::Prover(err.to_string()));
+        // The receiver is gone if we already timed out, in which case the proof is discarded.
+        let _ = sender.send(result);
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(ProveError::Timeout(timeout)),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(ProveError::Prover(
+            "prover thread exited without a result".to_string(),
+        )),
+    }
+}
+
+/// Execute `elf` on `stdin` and fail unless it takes at most `max_cycles` cycles, returning the
+/// cycle count.
+///
+/// Execution is much cheaper than proving, so running it first keeps an oversized input from ever
+/// reaching a paid prover.
+pub fn check_cycle_budget(
+    client: &EnvProver,
+    elf: &[u8],
+    stdin: &SP1Stdin,
+    max_cycles: u64,
+) -> Result<u64, ProveError> {
+    let (_, report) = client
+        .execute(elf, stdin)
+        .run()
+        .map_err(|err| ProveError::Execution(err.to_string()))?;
+
+    let cycles = report.total_instruction_count();
+    if cycles > max_cycles {
+        return Err(ProveError::CycleBudgetExceeded { cycles, max_cycles });
+    }
+    Ok(cycles)
+}
+
+/// Whether the prover error `err` is worth retrying.
+///
+/// Anything not recognised as transient is taken to be permanent, e.g. an input the program
+/// rejects or a proving key for another program, since retrying those only delays the error.
+pub fn is_transient(err: &str) -> bool {
+    let err = err.to_lowercase();
+    TRANSIENT_ERRORS
+        .iter()
+        .any(|transient| err.contains(transient))
+}
+
+/// The delay before retry number `retry`, counting from zero: [`INITIAL_BACKOFF`] doubled on
+/// every retry, up to [`MAX_BACKOFF`].
+pub fn backoff_delay(retry: u32) -> Duration {
+    INITIAL_BACKOFF
+        .saturating_mul(2u32.saturating_pow(retry))
+        .min(MAX_BACKOFF)
+}
+
+/// Run `attempt`, running it again up to `max_retries` times after a transient failure, waiting
+/// [`backoff_delay`] before each retry.
+///
+/// A permanent failure is returned at once, and the last failure once the retries are used up.
+pub fn retry_with_backoff<T, E>(
+    max_retries: u32,
+    mut attempt: impl FnMut() -> Result<T, E>,
+) -> Result<T, E>
+where
+    E: fmt::Display,
+{
+    let mut retry = 0;
+    loop {
+        let err = match attempt() {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        let message = format!("{:#}", err);
+        if !is_transient(&message) {
+            return Err(err);
+        }
+        if retry == max_retries {
+            if max_retries > 0 {
+                tracing::warn!("giving up after {} attempts: {}", retry + 1, message);
+            }
+            return Err(err);
+        }
+
+        let delay = backoff_delay(retry);
+        tracing::warn!(
+            "prover failed transiently, retrying in {}s: {}",
+            delay.as_secs(),
+            message
+        );
+        thread::sleep(delay);
+        retry += 1;
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+use std::time::Duration;
+
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::StatusCode;
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::metadata::AttestationMetadata;
+use zktls_lib::VerifyError;
+
+use crate::input::parse_opt;
+
+/// How long [`FromUrl::from_url`] waits for the whole response.
+pub const DEFAULT_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Errors that can occur while fetching an attestation over HTTP.
+#[derive(Debug)]
+pub enum FetchError {
+    /// The request could not be sent, or the response not read, within the timeout.
+    Request(reqwest::Error),
+    /// The server answered with a status other than success.
+    Status(StatusCode),
+    /// The response is not `application/json`; holds its `Content-Type`, if it had one.
+    ContentType(Option<String>),
+    /// The body does not hold an attestation.
+    Parse(serde_json::Error),
+    /// The attestation states a format version the verifier does not support.
+    Version(VerifyError),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Request(err) => write!(f, "cannot fetch the attestation: {}", err),
+            FetchError::Status(status) => write!(f, "the server answered {}", status),
+            FetchError::ContentType(Some(content_type)) => write!(
+                f,
+                "expected an `application/json` response, got `{}`",
+                content_type
+            ),
+            FetchError::ContentType(None) => {
+                f.write_str("expected an `application/json` response, got no content type")
+            }
+            FetchError::Parse(err) => write!(f, "the response is not an attestation: {}", err),
+            FetchError::Version(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FetchError::Request(err) => Some(err),
+            FetchError::Parse(err) => Some(err),
+            FetchError::Version(err) => Some(err),
+            FetchError::Status(_) | FetchError::ContentType(_) => None,
+        }
+    }
+}
+
+/// Fetching an attestation from a server, for attestation data that does not live on disk.
+pub trait FromUrl: Sized {
+    /// GET the attestation at `url`, giving up after [`DEFAULT_FETCH_TIMEOUT`].
+    fn from_url(url: &str) -> Result<Self, FetchError> {
+        Self::from_url_with_timeout(url, DEFAULT_FETCH_TIMEOUT)
+    }
+
+    /// GET the attestation at `url`, giving up after `timeout`.
+    ///
+    /// The response must succeed and be `application/json`; its body is read like an attestation
+    /// file, so a format version the verifier does not support is rejected.
+    fn from_url_with_timeout(url: &str, timeout: Duration) -> Result<Self, FetchError>;
+}
+
+impl FromUrl for VerifyingDataOpt {
+    fn from_url_with_timeout(url: &str, timeout: Duration) -> Result<Self, FetchError> {
+        let body = fetch_json(url, timeout)?;
+        let value: serde_json::Value = serde_json::from_str(&body).map_err(FetchError::Parse)?;
+        AttestationMetadata::of(&value)
+            .and_then(|metadata| metadata.check())
+            .map_err(FetchError::Version)?;
+        parse_opt(&body).map_err(FetchError::Parse)
+    }
+}
+
+fn fetch_json(url: &str, timeout: Duration) -> Result<String, FetchError> {
+    let client = Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(FetchError::Request)?;
+    let response = client.get(url).send().map_err(FetchError::Request)?;
+    if !response.status().is_success() {
+        return Err(FetchError::Status(response.status()));
+    }
+
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned());
+    // Parameters such as `; charset=utf-8` follow the media type.
+    let is_json = content_type.as_deref().is_some_and(|content_type| {
+        content_type
+            .split(';')
+            .next()
+            .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case("application/json"))
+    });
+    if !is_json {
+        return Err(FetchError::ContentType(content_type));
+    }
+
+    response.text().map_err(FetchError::Request)
+}
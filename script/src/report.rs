@@ -0,0 +1,189 @@
+use std::fmt;
+
+use alloy_sol_types::SolValue;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::SP1PublicValues;
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::abort::{decode_abort, AbortCode};
+use zktls_lib::claim::Claim;
+use zktls_lib::{PublicValues, VerifyConfig};
+
+use crate::{Attestation, EstimateCycles};
+
+/// The abort code committed by an execution with status reporting that failed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Aborted(pub u32);
+
+impl Aborted {
+    /// The reason for the abort, unless the program is newer than this script.
+    pub fn reason(self) -> Option<AbortCode> {
+        AbortCode::from_code(self.0)
+    }
+}
+
+impl fmt::Display for Aborted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.reason() {
+            Some(reason) => write!(f, "program aborted with code {}: {}", self.0, reason),
+            None => write!(f, "program aborted with unknown code {}", self.0),
+        }
+    }
+}
+
+impl std::error::Error for Aborted {}
+
+/// Read the public values of an execution, or the abort code it committed instead.
+pub fn read_public_values(public_values: &SP1PublicValues) -> Result<PublicValues, Aborted> {
+    match decode_abort(public_values.as_slice()) {
+        Some(code) => Err(Aborted(code)),
+        None => Ok(public_values.clone().read()),
+    }
+}
+
+/// Read the claim committed by an execution in claim mode, or the abort code it committed
+/// instead.
+///
+/// Panics if the public values are neither, i.e. the execution was not in claim mode.
+pub fn read_claim(public_values: &SP1PublicValues) -> Result<Claim, Aborted> {
+    let bytes = public_values.as_slice();
+    if let Some(code) = decode_abort(bytes) {
+        return Err(Aborted(code));
+    }
+    Ok(Claim::abi_decode(bytes).expect("public values are not an ABI-encoded claim"))
+}
+
+/// The machine-readable outcome of executing the program, printed by `--json-report`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationReport {
+    pub length: u32,
+    /// Cycles executed, absent when execution failed.
+    pub cycles: Option<u64>,
+    /// Cycles estimated from the attestation's shape, present only for a dry run.
+    pub estimated_cycles: Option<u64>,
+    pub verified: bool,
+    pub record_count: usize,
+    pub error: Option<String>,
+    /// The cycle budget of `--limit-cycles`, absent without one.
+    pub cycle_limit: Option<u64>,
+    /// Whether the cycles executed stayed within `cycle_limit`, absent unless both are known.
+    pub within_cycle_limit: Option<bool>,
+}
+
+impl VerificationReport {
+    /// Report a completed execution.
+    pub fn new(length: u32, cycles: u64, public_values: &PublicValues) -> Self {
+        VerificationReport {
+            length,
+            cycles: Some(cycles),
+            estimated_cycles: None,
+            verified: public_values.verified,
+            record_count: public_values.records.len(),
+            error: (!public_values.verified)
+                .then(|| "attestation signature did not verify".to_string()),
+            cycle_limit: None,
+            within_cycle_limit: None,
+        }
+    }
+
+    /// Report an execution that did not complete.
+    pub fn failed(length: u32, error: impl fmt::Display) -> Self {
+        VerificationReport {
+            length,
+            cycles: None,
+            estimated_cycles: None,
+            verified: false,
+            record_count: 0,
+            error: Some(error.to_string()),
+            cycle_limit: None,
+            within_cycle_limit: None,
+        }
+    }
+
+    /// Verify `data` natively, outside the zkVM, and report the outcome without a cycle count.
+    ///
+    /// The length is the total size of the records in bytes.
+    pub fn native(data: &VerifyingDataOpt, verifying_key: &str) -> Self {
+        let records = data.get_records();
+        let result = data.verify(verifying_key);
+        VerificationReport {
+            length: records.iter().map(|record| record.len() / 2).sum::<usize>() as u32,
+            cycles: None,
+            estimated_cycles: None,
+            verified: result.is_ok(),
+            record_count: records.len(),
+            error: result.err().map(|err| format!("{:?}", err)),
+            cycle_limit: None,
+            within_cycle_limit: None,
+        }
+    }
+
+    /// Verify `data` natively under `config`, as a dry run does before proving, and report the
+    /// outcome with the cycles executing it is estimated to take.
+    pub fn dry_run(
+        length: u32,
+        data: &Attestation,
+        verifying_key: &str,
+        config: &VerifyConfig,
+    ) -> Self {
+        let result = data.verify_with_config(verifying_key, config);
+        VerificationReport {
+            length,
+            cycles: None,
+            estimated_cycles: Some(data.estimate_cycle_cost()),
+            verified: result.is_ok(),
+            record_count: data.record_count(),
+            error: result.err().map(|err| err.to_string()),
+            cycle_limit: None,
+            within_cycle_limit: None,
+        }
+    }
+
+    /// Check the cycles executed against `cycle_limit`, if there is one.
+    ///
+    /// A report without a cycle count, i.e. of an execution that failed, records the limit but
+    /// cannot say whether it was kept.
+    pub fn with_cycle_limit(self, cycle_limit: Option<u64>) -> Self {
+        VerificationReport {
+            within_cycle_limit: cycle_limit
+                .zip(self.cycles)
+                .map(|(limit, cycles)| cycles <= limit),
+            cycle_limit,
+            ..self
+        }
+    }
+
+    /// Whether the attestation verified within the cycle limit, the condition for a zero exit
+    /// code.
+    pub fn passed(&self) -> bool {
+        self.verified && self.within_cycle_limit != Some(false)
+    }
+}
+
+/// The report as lines of text, for when it is not printed as JSON.
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "zktls verification length: {}", self.length)?;
+        writeln!(f, "Number of records: {}", self.record_count)?;
+        if let Some(cycles) = self.cycles {
+            writeln!(f, "Number of cycles: {}", cycles)?;
+        }
+        if let Some(estimated_cycles) = self.estimated_cycles {
+            writeln!(f, "Estimated cycles: {}", estimated_cycles)?;
+        }
+        if let Some(cycle_limit) = self.cycle_limit {
+            writeln!(f, "Cycle limit: {}", cycle_limit)?;
+        }
+        match &self.error {
+            None => write!(f, "Verification succeeded."),
+            Some(err) => write!(f, "Verification failed: {}.", err),
+        }
+    }
+}
+
+/// Verify every attestation in `items` natively, in order.
+pub fn verify_many(items: &[VerifyingDataOpt], verifying_key: &str) -> Vec<VerificationReport> {
+    items
+        .iter()
+        .map(|data| VerificationReport::native(data, verifying_key))
+        .collect()
+}
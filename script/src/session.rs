@@ -0,0 +1,40 @@
+use chrono::Utc;
+use sp1_sdk::SP1Stdin;
+use uuid::Uuid;
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::key::VerifyingKey;
+use zktls_lib::session::ZkTlsSession;
+
+/// Start a session for `verifying_data` under `verifying_key`, with a random id, created now.
+pub fn new_session(verifying_key: VerifyingKey, verifying_data: VerifyingDataOpt) -> ZkTlsSession {
+    ZkTlsSession {
+        verifying_key,
+        verifying_data,
+        session_id: Uuid::new_v4(),
+        created_at: Utc::now(),
+    }
+}
+
+/// Writing a [`ZkTlsSession`] to the program's stdin and reading it back.
+pub trait SessionStdin: Sized {
+    /// Append the session to `stdin` as a single item, which the program reads with
+    /// `sp1_zkvm::io::read::<ZkTlsSession>()`.
+    fn write_to_stdin(&self, stdin: &mut SP1Stdin);
+
+    /// Read the session from the next unread item of `stdin`, without consuming it.
+    fn from_stdin(stdin: &SP1Stdin) -> bincode::Result<Self>;
+}
+
+impl SessionStdin for ZkTlsSession {
+    fn write_to_stdin(&self, stdin: &mut SP1Stdin) {
+        stdin.write(self);
+    }
+
+    fn from_stdin(stdin: &SP1Stdin) -> bincode::Result<Self> {
+        let item = stdin
+            .buffer
+            .get(stdin.ptr)
+            .ok_or_else(|| bincode::ErrorKind::Custom("stdin has no unread item".to_string()))?;
+        bincode::deserialize(item)
+    }
+}
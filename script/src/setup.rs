@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+use sp1_sdk::{EnvProver, SP1ProvingKey, SP1VerifyingKey};
+
+/// Environment variable overriding the directory setup keys are cached in.
+pub const SETUP_CACHE_ENV: &str = "ZKTLS_SETUP_CACHE";
+
+/// Set up `elf`, reusing the proving and verifying keys cached by a previous run.
+///
+/// Keys are cached under `$ZKTLS_SETUP_CACHE`, or a directory in the system temp dir, in a file
+/// named after the hash of `elf`, so rebuilding the program invalidates them. A missing or corrupt
+/// cache entry is recomputed and rewritten; `no_cache` forces the same.
+pub fn setup_cached(
+    client: &EnvProver,
+    elf: &[u8],
+    no_cache: bool,
+) -> (SP1ProvingKey, SP1VerifyingKey) {
+    let path = cache_dir().join(format!("{}.bin", hex::encode(Sha256::digest(elf))));
+
+    if !no_cache {
+        if let Ok(bytes) = std::fs::read(&path) {
+            match bincode::deserialize(&bytes) {
+                Ok(keys) => {
+                    tracing::info!("loaded setup keys from {}", path.display());
+                    return keys;
+                }
+                Err(err) => {
+                    tracing::warn!("ignoring corrupt setup cache {}: {}", path.display(), err)
+                }
+            }
+        }
+    }
+
+    let keys = client.setup(elf);
+
+    // A cache that cannot be written only costs the next run a setup.
+    let written = std::fs::create_dir_all(cache_dir()).and_then(|()| {
+        let bytes = bincode::serialize(&keys).map_err(std::io::Error::other)?;
+        // Write through a temporary file so a concurrent run never reads a partial entry.
+        let partial = path.with_extension(format!("{}.partial", std::process::id()));
+        std::fs::write(&partial, bytes)?;
+        std::fs::rename(&partial, &path)
+    });
+    if let Err(err) = written {
+        tracing::warn!("failed to cache setup keys in {}: {}", path.display(), err);
+    }
+
+    keys
+}
+
+fn cache_dir() -> PathBuf {
+    std::env::var_os(SETUP_CACHE_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join("zktls-setup"))
+}
@@ -0,0 +1,265 @@
+use std::fmt;
+
+use serde_json::Value;
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::content_encoding::decode_bodies;
+use zktls_lib::public_values::RecordDigests;
+use zktls_lib::sanitize::sanitize;
+use zktls_lib::{Record, VerifyError};
+
+use crate::input::DEFAULT_MAX_DECODED_BYTES;
+
+/// Why [`SplitVerifyingData::split_at`] could not split an attestation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SplitError {
+    /// The split point is past the last record.
+    OutOfRange { n: usize, records: usize },
+    /// The split point falls inside a packet, whose signature covers all of its records. The
+    /// nearest valid split points are `packet_start` and `packet_end`.
+    InsidePacket {
+        n: usize,
+        packet_start: usize,
+        packet_end: usize,
+    },
+    /// The attestation does not serialize as packets, or the packets do not deserialize back into
+    /// an attestation; holds the serde error.
+    Malformed(String),
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SplitError::OutOfRange { n, records } => write!(
+                f,
+                "cannot split at record {} of an attestation holding {} records",
+                n, records
+            ),
+            SplitError::InsidePacket {
+                n,
+                packet_start,
+                packet_end,
+            } => write!(
+                f,
+                "record {} is inside a packet signed as a whole; split at {} or {} instead",
+                n, packet_start, packet_end
+            ),
+            SplitError::Malformed(err) => write!(f, "the attestation is malformed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SplitError {}
+
+/// Why [`SplitVerifyingData::verify_prefix`] rejected a prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrefixError {
+    /// The prefix does not end on a packet boundary within the attestation.
+    Split(SplitError),
+    /// The packets of the prefix do not verify under the verifying key.
+    BadSignature,
+    /// The records of the prefix cannot be committed, as a record that is not hex or a body that
+    /// does not decode.
+    Records(VerifyError),
+}
+
+impl fmt::Display for PrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrefixError::Split(err) => write!(f, "{}", err),
+            PrefixError::BadSignature => f.write_str("the prefix does not verify"),
+            PrefixError::Records(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for PrefixError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PrefixError::Split(err) => Some(err),
+            PrefixError::Records(err) => Some(err),
+            PrefixError::BadSignature => None,
+        }
+    }
+}
+
+impl From<SplitError> for PrefixError {
+    fn from(err: SplitError) -> Self {
+        PrefixError::Split(err)
+    }
+}
+
+/// Splitting an attestation into smaller attestations for continuation proofs, and merging them
+/// back.
+pub trait SplitVerifyingData: Sized {
+    /// Partition the records into attestations of at most `chunk_size` records each.
+    ///
+    /// The attestor signs each packet as a whole, so records are split at packet boundaries: a
+    /// packet holding more than `chunk_size` records becomes a chunk of its own. Every chunk
+    /// verifies under the same verifying key as the original attestation.
+    fn split(&self, chunk_size: usize) -> Result<Vec<Self>, SplitError>;
+
+    /// Split the records at index `n` into two attestations, for proving each half separately.
+    ///
+    /// Each half keeps the keys and signatures of its packets, so it verifies on its own under
+    /// the original verifying key. `n` must fall on a packet boundary; either half may be empty.
+    ///
+    /// The halves' digests recombine into those of the whole: with per-record digests, the
+    /// whole's list is the left half's followed by the right half's, and since the records digest
+    /// is keccak256 over the records in order, each preceded by its length, an aggregator
+    /// recomputes it from the records both halves commit.
+    fn split_at(&self, n: usize) -> Result<(Self, Self), SplitError>;
+
+    /// The attestation of the first `n` records alone, for proving a prefix of a session without
+    /// disclosing the rest.
+    ///
+    /// Unlike [`split_at`](SplitVerifyingData::split_at), the packets after the prefix are
+    /// dropped rather than collected into a second attestation. `n` must fall on a packet
+    /// boundary.
+    fn prefix(&self, n: usize) -> Result<Self, SplitError>;
+
+    /// Verify only the packets of the first `n` records under `verifying_key`, and return the
+    /// records digest over those records.
+    ///
+    /// This is the `records_digest` the program commits when proving the prefix from a
+    /// [`ZkTlsInput::new`](crate::ZkTlsInput::new): HTTP records, compressed bodies decoded and
+    /// credentials masked, nothing redacted. The digest mode and record commitment do not change
+    /// it. For `n` equal to the record count it is the records digest of the whole attestation.
+    fn verify_prefix(&self, verifying_key: &str, n: usize) -> Result<[u8; 32], PrefixError>;
+
+    /// Concatenate `chunks` back into one attestation.
+    fn merge(chunks: &[Self]) -> Result<Self, SplitError>;
+}
+
+impl SplitVerifyingData for VerifyingDataOpt {
+    fn split(&self, chunk_size: usize) -> Result<Vec<Self>, SplitError> {
+        assert!(chunk_size > 0, "chunk_size must be positive");
+
+        let mut chunks: Vec<Vec<Value>> = Vec::new();
+        let mut chunk_records = 0;
+        for packet in packets(self)? {
+            let records = packet["record_messages"].as_array().map_or(0, Vec::len);
+            match chunks.last_mut() {
+                Some(chunk) if chunk_records + records <= chunk_size => {
+                    chunk.push(packet);
+                    chunk_records += records;
+                }
+                _ => {
+                    chunks.push(vec![packet]);
+                    chunk_records = records;
+                }
+            }
+        }
+
+        chunks.into_iter().map(from_packets).collect()
+    }
+
+    fn split_at(&self, n: usize) -> Result<(Self, Self), SplitError> {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        let mut packet_start = 0;
+        for packet in packets(self)? {
+            let packet_end =
+                packet_start + packet["record_messages"].as_array().map_or(0, Vec::len);
+            if packet_end <= n {
+                left.push(packet);
+            } else if packet_start >= n {
+                right.push(packet);
+            } else {
+                return Err(SplitError::InsidePacket {
+                    n,
+                    packet_start,
+                    packet_end,
+                });
+            }
+            packet_start = packet_end;
+        }
+
+        if n > packet_start {
+            return Err(SplitError::OutOfRange {
+                n,
+                records: packet_start,
+            });
+        }
+        Ok((from_packets(left)?, from_packets(right)?))
+    }
+
+    fn prefix(&self, n: usize) -> Result<Self, SplitError> {
+        let mut prefix = Vec::new();
+        let mut packet_start = 0;
+        for packet in packets(self)? {
+            if packet_start >= n {
+                return from_packets(prefix);
+            }
+            let packet_end =
+                packet_start + packet["record_messages"].as_array().map_or(0, Vec::len);
+            if packet_end > n {
+                return Err(SplitError::InsidePacket {
+                    n,
+                    packet_start,
+                    packet_end,
+                });
+            }
+            prefix.push(packet);
+            packet_start = packet_end;
+        }
+
+        if n > packet_start {
+            return Err(SplitError::OutOfRange {
+                n,
+                records: packet_start,
+            });
+        }
+        from_packets(prefix)
+    }
+
+    fn verify_prefix(&self, verifying_key: &str, n: usize) -> Result<[u8; 32], PrefixError> {
+        let prefix = self.prefix(n)?;
+        if prefix.verify(verifying_key).is_err() {
+            return Err(PrefixError::BadSignature);
+        }
+
+        let mut records = prefix
+            .get_records()
+            .iter()
+            .enumerate()
+            .map(|(index, record)| {
+                Record::from_hex(record).map_err(|_| VerifyError::InvalidRecordEncoding {
+                    record: index as u32,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(PrefixError::Records)?;
+        decode_bodies(&mut records, DEFAULT_MAX_DECODED_BYTES as usize)
+            .map_err(PrefixError::Records)?;
+        sanitize(&mut records);
+        Ok(RecordDigests::new(&records).records_digest)
+    }
+
+    fn merge(chunks: &[Self]) -> Result<Self, SplitError> {
+        let mut merged = Vec::new();
+        for chunk in chunks {
+            merged.extend(packets(chunk)?);
+        }
+        from_packets(merged)
+    }
+}
+
+fn packets(data: &VerifyingDataOpt) -> Result<Vec<Value>, SplitError> {
+    let value = serde_json::to_value(data).map_err(|err| SplitError::Malformed(err.to_string()))?;
+    match value {
+        Value::Object(mut data) => match data.remove("packets") {
+            Some(Value::Array(packets)) => Ok(packets),
+            _ => Err(SplitError::Malformed(
+                "the attestation has no packets".to_string(),
+            )),
+        },
+        _ => Err(SplitError::Malformed(
+            "the attestation is not an object".to_string(),
+        )),
+    }
+}
+
+fn from_packets(packets: Vec<Value>) -> Result<VerifyingDataOpt, SplitError> {
+    serde_json::from_value(serde_json::json!({ "packets": packets }))
+        .map_err(|err| SplitError::Malformed(err.to_string()))
+}
@@ -0,0 +1,74 @@
+use sp1_sdk::SP1Stdin;
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::stream::{StreamHeader, StreamVerifier, StreamedRecords, STREAM_CHUNK_BYTES};
+use zktls_lib::verifying_data::{MissingField, VerifyingData};
+
+/// A `VerifyingDataOpt` to stream to the program: a [`StreamHeader`], then the ciphertext of its
+/// records in chunks, so the program never holds more than one chunk of it; see
+/// [`zktls_lib::stream`].
+#[derive(Debug, Clone)]
+pub struct StreamedAttestation {
+    data: VerifyingDataOpt,
+    header: StreamHeader,
+    ciphertext: Vec<u8>,
+}
+
+impl StreamedAttestation {
+    /// Split `data` into a header and its ciphertext, to be written in chunks of at most
+    /// `chunk_bytes`.
+    pub fn new(data: VerifyingDataOpt, chunk_bytes: u32) -> Result<Self, MissingField> {
+        let verifying_data = VerifyingData::of(&data)?;
+        Ok(StreamedAttestation {
+            header: verifying_data.stream_header(chunk_bytes),
+            ciphertext: verifying_data
+                .records()
+                .flat_map(|record| &record.ciphertext)
+                .copied()
+                .collect(),
+            data,
+        })
+    }
+
+    /// The attestation as it was loaded.
+    pub fn data(&self) -> &VerifyingDataOpt {
+        &self.data
+    }
+
+    pub fn header(&self) -> &StreamHeader {
+        &self.header
+    }
+
+    /// The ciphertext of every record in transcript order, in the chunks the program reads.
+    pub fn chunks(&self) -> impl Iterator<Item = &[u8]> {
+        self.ciphertext.chunks(self.header.chunk_bytes as usize)
+    }
+
+    /// Absorb every chunk natively, as the program does.
+    pub fn stream(&self) -> StreamedRecords {
+        let mut verifier = StreamVerifier::new(&self.header);
+        for chunk in self.chunks() {
+            verifier
+                .update(chunk)
+                .expect("chunks are cut from the header's records");
+        }
+        verifier
+            .finish()
+            .expect("chunks are cut from the header's records")
+    }
+
+    /// Write every chunk as its own stdin item, which the program reads after every other input.
+    pub fn write_chunks(&self, stdin: &mut SP1Stdin) {
+        for chunk in self.chunks() {
+            stdin.write_vec(chunk.to_vec());
+        }
+    }
+}
+
+impl TryFrom<VerifyingDataOpt> for StreamedAttestation {
+    type Error = MissingField;
+
+    /// Stream `data` in chunks of [`STREAM_CHUNK_BYTES`].
+    fn try_from(data: VerifyingDataOpt) -> Result<Self, Self::Error> {
+        StreamedAttestation::new(data, STREAM_CHUNK_BYTES)
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use sp1_sdk::{
+    include_elf, EnvProver, SP1ProofWithPublicValues, SP1ProvingKey, SP1VerificationError,
+    SP1VerifyingKey,
+};
+
+use crate::setup_cached;
+
+/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
+pub const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+/// The zktls program together with its verifying key, set up once and shared by everything that
+/// checks proofs of it.
+///
+/// The only way to build one is [`ZkTlsVerifier::setup`], which sets up [`ZKTLS_ELF`], so a proof
+/// it accepts is always a proof of this program.
+pub struct ZkTlsVerifier {
+    elf: &'static [u8],
+    vk: SP1VerifyingKey,
+    client: Arc<EnvProver>,
+}
+
+impl ZkTlsVerifier {
+    /// Set up [`ZKTLS_ELF`] with `client` through the setup cache, returning the verifier and
+    /// the proving key to prove with.
+    pub fn setup(client: Arc<EnvProver>, no_cache: bool) -> (Self, SP1ProvingKey) {
+        let (pk, vk) = setup_cached(&client, ZKTLS_ELF, no_cache);
+        let verifier = ZkTlsVerifier {
+            elf: ZKTLS_ELF,
+            vk,
+            client,
+        };
+        (verifier, pk)
+    }
+
+    /// The program the verifying key was set up from.
+    pub fn elf(&self) -> &'static [u8] {
+        self.elf
+    }
+
+    pub fn vk(&self) -> &SP1VerifyingKey {
+        &self.vk
+    }
+
+    /// Verify that `proof` is a proof of the program.
+    pub fn verify(&self, proof: &SP1ProofWithPublicValues) -> Result<(), SP1VerificationError> {
+        self.client.verify(proof, &self.vk)
+    }
+}
@@ -0,0 +1,27 @@
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::input::decode;
+use zktls_lib::{Record, VerifyError};
+
+/// Parsing and verifying an attestation in one step, from the bytes the program reads.
+pub trait VerifyBytes: Sized {
+    /// Decode `bytes` as the program decodes the attestation it reads from stdin, verify it under
+    /// `verifying_key`, and return its records.
+    ///
+    /// Fails with [`VerifyError::MalformedInput`] where the program would abort with
+    /// [`AbortCode::MalformedInput`](zktls_lib::abort::AbortCode::MalformedInput), and with
+    /// [`VerifyError::BadSignature`] when the attestation does not verify.
+    fn try_verify_bytes(bytes: &[u8], verifying_key: &str) -> Result<Vec<Record>, VerifyError>;
+}
+
+impl VerifyBytes for VerifyingDataOpt {
+    fn try_verify_bytes(bytes: &[u8], verifying_key: &str) -> Result<Vec<Record>, VerifyError> {
+        let data: VerifyingDataOpt = decode(bytes)?;
+        data.verify(verifying_key)
+            .map_err(|_| VerifyError::BadSignature)?;
+
+        data.get_records()
+            .iter()
+            .map(|record| Record::from_hex(record).map_err(|_| VerifyError::MalformedInput))
+            .collect()
+    }
+}
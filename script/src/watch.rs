@@ -0,0 +1,43 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify_debouncer_mini::notify::{self, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+
+/// How long changes must settle before a re-run, so the several events of an editor's atomic write
+/// trigger only one.
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Call `run` whenever any of `paths` changes, until watching fails.
+///
+/// The directories holding the paths are watched rather than the files themselves, so a file an
+/// editor replaces by renaming a temporary file over it is still seen.
+pub fn watch(paths: &[PathBuf], mut run: impl FnMut()) -> notify::Result<()> {
+    let paths = paths
+        .iter()
+        .map(std::path::absolute)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut dirs: Vec<&Path> = paths.iter().filter_map(|path| path.parent()).collect();
+    dirs.sort();
+    dirs.dedup();
+
+    let (sender, receiver) = mpsc::channel();
+    let mut debouncer = new_debouncer(WATCH_DEBOUNCE, sender)?;
+    for dir in dirs {
+        debouncer
+            .watcher()
+            .watch(dir, RecursiveMode::NonRecursive)?;
+    }
+
+    for events in receiver {
+        // A path that keeps changing is also reported while it changes; wait until it settles.
+        let changed = events?
+            .iter()
+            .any(|event| event.kind == DebouncedEventKind::Any && paths.contains(&event.path));
+        if changed {
+            run();
+        }
+    }
+    Ok(())
+}
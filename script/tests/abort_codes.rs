@@ -0,0 +1,228 @@
+use std::path::Path;
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::input::Preamble;
+use zktls_lib::record::{RecordsEncoding, Redaction};
+use zktls_lib::DataFormat;
+use zktls_script::{
+    load_metadata, load_request_template, read_public_values, Aborted, Attestation, ZkTlsInput,
+};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+fn input_for(data_file: &str) -> ZkTlsInput {
+    let verifying_key =
+        std::fs::read_to_string(format!("{}/verifying_k256.key", FIXTURES)).unwrap();
+    let data_path = format!("{}/data/{}", FIXTURES, data_file);
+    let verifying_data = std::fs::read_to_string(&data_path).unwrap();
+
+    ZkTlsInput {
+        report_status: true,
+        metadata: load_metadata(Path::new(&data_path)).unwrap(),
+        ..ZkTlsInput::new(
+            verifying_key,
+            Attestation::parse(&verifying_data, Some(DataFormat::Opt)).unwrap(),
+        )
+    }
+}
+
+/// Execute `input` and return the reason the program aborted.
+fn abort_reason(input: ZkTlsInput) -> Option<AbortCode> {
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, &input.into()).run().unwrap();
+
+    read_public_values(&public_values).unwrap_err().reason()
+}
+
+#[test]
+fn malformed_key_is_reported() {
+    let input = ZkTlsInput {
+        verifying_key: "not a key".to_string(),
+        ..input_for("bench16.json")
+    };
+
+    assert_eq!(abort_reason(input), Some(AbortCode::BadKeyEncoding));
+}
+
+#[test]
+fn oversized_record_is_reported() {
+    assert_eq!(
+        abort_reason(input_for("oversized_record.json")),
+        Some(AbortCode::SizeLimitExceeded)
+    );
+}
+
+#[test]
+fn out_of_bounds_redaction_is_reported() {
+    let input = ZkTlsInput {
+        redactions: vec![Redaction {
+            record: 0,
+            start: 0,
+            end: 1024,
+        }],
+        ..input_for("bench16.json")
+    };
+
+    assert_eq!(abort_reason(input), Some(AbortCode::InvalidRedaction));
+}
+
+#[test]
+fn prefix_mismatch_is_reported() {
+    let input = ZkTlsInput {
+        prefix_records: Some(2),
+        ..input_for("bench16.json")
+    };
+
+    assert_eq!(abort_reason(input), Some(AbortCode::PrefixMismatch));
+}
+
+#[test]
+fn reordered_transcript_is_reported() {
+    assert_eq!(
+        abort_reason(input_for("bench16_reordered.json")),
+        Some(AbortCode::OutOfOrder)
+    );
+}
+
+#[test]
+fn unsupported_version_is_reported() {
+    assert_eq!(
+        abort_reason(input_for("unsupported_version.json")),
+        Some(AbortCode::UnsupportedVersion)
+    );
+}
+
+#[test]
+fn invalid_hex_record_is_reported() {
+    let input = ZkTlsInput {
+        records_encoding: RecordsEncoding::Hex,
+        ..input_for("balance_above.json")
+    };
+
+    assert_eq!(abort_reason(input), Some(AbortCode::InvalidRecordEncoding));
+}
+
+#[test]
+fn predicate_over_an_unverified_attestation_is_reported() {
+    // `balance_above.json` is not signed under the bench key.
+    let input = ZkTlsInput {
+        predicate: Some("$.balance >= 1000000".parse().unwrap()),
+        ..input_for("balance_above.json")
+    };
+
+    assert_eq!(abort_reason(input), Some(AbortCode::SignatureFailure));
+}
+
+#[test]
+fn gzip_bomb_is_reported() {
+    assert_eq!(
+        abort_reason(input_for("gzip_bomb.json")),
+        Some(AbortCode::DecodedTooLarge)
+    );
+}
+
+#[test]
+fn truncated_body_is_reported() {
+    assert_eq!(
+        abort_reason(input_for("truncated_body.json")),
+        Some(AbortCode::BodyLengthMismatch)
+    );
+    assert_eq!(
+        abort_reason(input_for("unterminated_chunked.json")),
+        Some(AbortCode::UnterminatedBody)
+    );
+}
+
+#[test]
+fn non_json_body_is_reported() {
+    // Redacting the start of the body leaves zero bytes where the JSON began.
+    let input = ZkTlsInput {
+        canonical_body: true,
+        redactions: vec![Redaction {
+            record: 1,
+            start: 71,
+            end: 75,
+        }],
+        ..input_for("balance_above.json")
+    };
+
+    assert_eq!(abort_reason(input), Some(AbortCode::BodyNotJson));
+}
+
+#[test]
+fn invalid_utf8_is_reported_when_committing_text() {
+    let input = ZkTlsInput {
+        text_records: true,
+        ..input_for("invalid_utf8.json")
+    };
+
+    assert_eq!(abort_reason(input), Some(AbortCode::InvalidUtf8));
+}
+
+#[test]
+fn request_template_mismatch_is_reported() {
+    let input = ZkTlsInput {
+        request_template: Some(
+            load_request_template(Path::new(&format!(
+                "{}/request_template_mismatch.json",
+                FIXTURES
+            )))
+            .unwrap(),
+        ),
+        verifying_key: std::fs::read_to_string(format!("{}/generated_k256.key", FIXTURES)).unwrap(),
+        ..input_for("account_request.json")
+    };
+
+    assert_eq!(abort_reason(input), Some(AbortCode::RequestMismatch));
+}
+
+#[test]
+fn two_digit_year_date_is_reported() {
+    assert_eq!(
+        abort_reason(input_for("legacy_date.json")),
+        Some(AbortCode::AmbiguousDate)
+    );
+}
+
+#[test]
+fn truncated_attestation_is_reported() {
+    let mut stdin: SP1Stdin = input_for("bench16.json").into();
+    // The preamble, the record commitment, the key and the format tag come before the
+    // attestation. The preamble declares the truncated attestation, so it gets as far as decoding.
+    stdin.buffer[4] = std::fs::read(format!("{}/data/bench16_truncated.bin", FIXTURES)).unwrap();
+    stdin.buffer[0] = Preamble::new(true, &stdin.buffer[2], &stdin.buffer[4])
+        .to_bytes()
+        .to_vec();
+
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+
+    assert_eq!(
+        read_public_values(&public_values).unwrap_err().reason(),
+        Some(AbortCode::MalformedInput)
+    );
+}
+
+#[test]
+fn successful_execution_reports_no_abort() {
+    let client = ProverClient::builder().cpu().build();
+    let stdin = input_for("bench16.json").into();
+    let (public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+
+    assert_eq!(read_public_values(&public_values).unwrap().status, 0);
+}
+
+#[test]
+fn abort_codes_map_to_messages() {
+    assert_eq!(
+        Aborted(10).to_string(),
+        "program aborted with code 10: records are out of order"
+    );
+    assert_eq!(
+        Aborted(999).to_string(),
+        "program aborted with unknown code 999"
+    );
+}
@@ -0,0 +1,59 @@
+use sp1_sdk::{ProverClient, SP1Stdin};
+use zktls_script::{load_bench, AggregationError, ChildProofs, ZkTlsInput, ZKTLS_ELF};
+
+fn bench16() -> SP1Stdin {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(16, None, None, None).unwrap();
+    ZkTlsInput::new(verifying_key, verifying_data).into()
+}
+
+#[test]
+fn core_proofs_are_not_aggregated() {
+    let client = ProverClient::builder().mock().build();
+    let (pk, vk) = client.setup(ZKTLS_ELF);
+    let proof = client.prove(&pk, &bench16()).run().unwrap();
+
+    let mut children = ChildProofs::new();
+    assert_eq!(
+        children.push(proof, &vk),
+        Err(AggregationError::NotCompressed)
+    );
+    assert!(children.is_empty());
+}
+
+/// Two attestations proven separately, then aggregated. This takes a long while, so it only runs
+/// with `cargo test --release --features prove-tests --test aggregate`.
+#[cfg(feature = "prove-tests")]
+#[test]
+fn two_proofs_aggregate_into_one() {
+    use std::sync::Arc;
+
+    use sp1_sdk::HashableKey;
+    use zktls_lib::PublicValues;
+    use zktls_script::{read_aggregation, ZkTlsVerifier, AGGREGATION_ELF};
+
+    let client = Arc::new(ProverClient::from_env());
+    let (verifier, pk) = ZkTlsVerifier::setup(Arc::clone(&client), true);
+    let mut children = ChildProofs::new();
+    for _ in 0..2 {
+        let proof = client.prove(&pk, &bench16()).compressed().run().unwrap();
+        verifier.verify(&proof).unwrap();
+        children.push(proof, verifier.vk()).unwrap();
+    }
+
+    let (aggregation_pk, aggregation_vk) = client.setup(AGGREGATION_ELF);
+    let proof = client
+        .prove(&aggregation_pk, &children.stdin())
+        .run()
+        .unwrap();
+    client.verify(&proof, &aggregation_vk).unwrap();
+
+    let commitments = read_aggregation(&proof);
+    assert_eq!(commitments, children.commitments());
+    for (commitment, public_values) in commitments.iter().zip(children.public_values()) {
+        assert_eq!(commitment.vkey_digest, verifier.vk().hash_u32());
+        assert!(commitment.commits(public_values));
+        let public_values: PublicValues = bincode::deserialize(public_values).unwrap();
+        assert!(public_values.verified);
+    }
+}
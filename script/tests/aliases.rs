@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use zktls_lib::tls::TlsSession;
+use zktls_script::{load_bench, Attestation};
+
+fn load(data_path: &str) -> (String, Attestation) {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    load_bench(16, None, Some(Path::new(data_path)), None).unwrap()
+}
+
+#[test]
+fn aliased_attestation_parses_as_the_canonical_one() {
+    let (verifying_key, canonical) = load("fixtures/zktls/data/bench16.json");
+    let (_, aliased) = load("fixtures/zktls/data/bench16_aliased.json");
+    let (Attestation::Opt(canonical), Attestation::Opt(aliased)) = (canonical, aliased) else {
+        panic!("bench16 is not a VerifyingDataOpt");
+    };
+
+    assert!(aliased.verify(&verifying_key).is_ok());
+    assert_eq!(aliased.get_records(), canonical.get_records());
+    // Only the canonical names are written back.
+    assert_eq!(
+        serde_json::to_value(&aliased).unwrap(),
+        serde_json::to_value(&canonical).unwrap()
+    );
+}
+
+#[test]
+fn aliases_are_accepted_at_both_levels() {
+    let (verifying_key, aliased) = load("fixtures/zktls/data/tls_session_aliased.json");
+    let (_, canonical) = load("fixtures/zktls/data/tls_session.json");
+
+    let (Attestation::Opt(canonical), Attestation::Opt(aliased)) = (canonical, aliased) else {
+        panic!("tls_session is not a VerifyingDataOpt");
+    };
+
+    assert!(aliased.verify(&verifying_key).is_ok());
+    assert_eq!(aliased.get_records(), canonical.get_records());
+    assert_eq!(TlsSession::of(&aliased), TlsSession::of(&canonical));
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "tokio")]
+
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_script::{verify_many, verify_many_async};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+#[tokio::test(flavor = "multi_thread")]
+async fn async_reports_match_sequential_reports() {
+    let verifying_key =
+        std::fs::read_to_string(format!("{}/verifying_k256.key", FIXTURES)).unwrap();
+    let items: Vec<VerifyingDataOpt> = ["bench16", "bench256", "bench1024", "bench2048", "bench16"]
+        .iter()
+        .map(|name| {
+            let path = format!("{}/data/{}.json", FIXTURES, name);
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+        })
+        .collect();
+
+    let sequential = verify_many(&items, &verifying_key);
+    let concurrent = verify_many_async(items, &verifying_key).await;
+
+    assert_eq!(concurrent, sequential);
+}
@@ -0,0 +1,107 @@
+use sp1_sdk::{include_elf, ProverClient};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::batch::{aggregate_digest, FAILED_DIGEST};
+use zktls_lib::public_values::digest;
+use zktls_lib::Record;
+use zktls_script::{load_bench, read_public_values, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+/// Three bench attestations that verify and, third, one signed under another key.
+const MIXED: [&str; 4] = [
+    "bench16.json",
+    "bench256.json",
+    "balance_above.json",
+    "bench1024.json",
+];
+
+fn mixed_batch(lenient: bool) -> ZkTlsInput {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let mut attestations = MIXED.iter().map(|name| {
+        let path = format!("fixtures/zktls/data/{}", name);
+        load_bench(16, None, Some(path.as_ref()), None).unwrap()
+    });
+    let (verifying_key, verifying_data) = attestations.next().unwrap();
+
+    ZkTlsInput {
+        report_status: true,
+        batch: Some((lenient, attestations.collect())),
+        ..ZkTlsInput::new(verifying_key, verifying_data)
+    }
+}
+
+#[test]
+fn lenient_batch_commits_bitmap_of_verified_attestations() {
+    let input = mixed_batch(true);
+    let expected_digests: Vec<[u8; 32]> = std::iter::once(&input.verifying_data)
+        .chain(
+            input
+                .batch
+                .iter()
+                .flat_map(|(_, items)| items.iter().map(|(_, data)| data)),
+        )
+        .map(|data| {
+            let records: Vec<Record> = data.records().unwrap();
+            digest(records.iter())
+        })
+        .collect();
+    let expected_aggregate = input.batch_aggregate_digest();
+    let client = ProverClient::builder().cpu().build();
+
+    let (public_values, _) = client.execute(ZKTLS_ELF, &input.into()).run().unwrap();
+    let batch = read_public_values(&public_values).unwrap().batch.unwrap();
+
+    assert_eq!(batch.verified, [0b1011]);
+    assert_eq!(batch.verified_count(), 3);
+    assert!(!batch.is_verified(2));
+    assert_eq!(
+        batch.records_digests,
+        [
+            expected_digests[0],
+            expected_digests[1],
+            FAILED_DIGEST,
+            expected_digests[3],
+        ]
+    );
+    assert_eq!(batch.len, 4);
+    assert_eq!(
+        batch.aggregate_digest,
+        aggregate_digest(&batch.records_digests)
+    );
+    assert_eq!(Some(batch.aggregate_digest), expected_aggregate);
+}
+
+#[test]
+fn batch_of_one_commits_the_aggregate_of_its_digest() {
+    let mut input = mixed_batch(true);
+    input.batch = Some((true, Vec::new()));
+    let expected_aggregate = input.batch_aggregate_digest();
+    let client = ProverClient::builder().cpu().build();
+
+    let (public_values, _) = client.execute(ZKTLS_ELF, &input.into()).run().unwrap();
+    let public_values = read_public_values(&public_values).unwrap();
+    let batch = public_values.batch.unwrap();
+
+    assert_eq!(batch.len, 1);
+    assert_eq!(
+        batch.records_digests,
+        [public_values.digests.records_digest]
+    );
+    assert_ne!(batch.aggregate_digest, public_values.digests.records_digest);
+    assert_eq!(Some(batch.aggregate_digest), expected_aggregate);
+}
+
+#[test]
+fn strict_batch_aborts_on_any_failure() {
+    let client = ProverClient::builder().cpu().build();
+
+    let (public_values, _) = client
+        .execute(ZKTLS_ELF, &mixed_batch(false).into())
+        .run()
+        .unwrap();
+
+    assert_eq!(
+        read_public_values(&public_values).unwrap_err().reason(),
+        Some(AbortCode::SignatureFailure)
+    );
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::date::NO_RESPONSE_DATE;
+use zktls_lib::public_values::{record_digests, DigestMode, UNKNOWN_DIRECTION_DIGEST};
+use zktls_lib::record::RecordPayload;
+use zktls_lib::PublicValues;
+use zktls_script::{
+    attest_records, load_records, read_public_values, Aborted, InputError, ZkTlsInput,
+};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+/// A masked client text frame, then a server binary frame split across two records. The second
+/// record of the server frame starts with what reads like an HTTP response.
+fn websocket_records() -> Vec<Vec<u8>> {
+    load_records(Path::new(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../fixtures/zktls/websocket_records.json"
+    )))
+    .unwrap()
+}
+
+fn input(record_payload: RecordPayload) -> ZkTlsInput {
+    let (verifying_key, verifying_data) = attest_records(&websocket_records());
+    ZkTlsInput {
+        report_status: true,
+        record_payload,
+        ..ZkTlsInput::from((verifying_key, verifying_data))
+    }
+}
+
+fn execute(stdin: &SP1Stdin) -> Result<PublicValues, Aborted> {
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, stdin).run().unwrap();
+    read_public_values(&public_values)
+}
+
+#[test]
+fn base64_records_keep_every_byte() {
+    let records = websocket_records();
+
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0][..2], [0x81, 0x85]);
+    assert_eq!(
+        records[1],
+        [0x82, 0x33, 0x00, 0x01, 0xfe, 0xff, 0x80, 0xc3, 0x28, 0x00]
+    );
+    assert!(records[2].starts_with(b"HTTP/1.1 200 OK\r\n"));
+    assert!(records[2].ends_with(&[0x00, 0xff]));
+}
+
+#[test]
+fn binary_records_are_committed_unparsed() {
+    let public_values = execute(&input(RecordPayload::Binary).into()).unwrap();
+
+    assert!(public_values.verified);
+    assert_eq!(public_values.record_payload, RecordPayload::Binary.tag());
+    let committed: Vec<Vec<u8>> = public_values
+        .records
+        .iter()
+        .map(|record| record.bytes.clone())
+        .collect();
+    assert_eq!(committed, websocket_records());
+
+    // Only the digest over the raw bytes is meaningful; nothing is read as HTTP.
+    assert_eq!(
+        public_values.digests.request_digest,
+        UNKNOWN_DIRECTION_DIGEST
+    );
+    assert_eq!(
+        public_values.digests.response_digest,
+        UNKNOWN_DIRECTION_DIGEST
+    );
+    assert!(public_values.decoded_bodies.is_empty());
+    assert_eq!(public_values.body_length, None);
+    assert_eq!(public_values.response_date, NO_RESPONSE_DATE);
+    assert!(!public_values.sanitized);
+}
+
+#[test]
+fn binary_records_have_per_record_digests_over_raw_bytes() {
+    let input = ZkTlsInput {
+        digest_mode: DigestMode::PerRecord,
+        ..input(RecordPayload::Binary)
+    };
+    let public_values = execute(&input.into()).unwrap();
+
+    assert_eq!(
+        public_values.record_digests,
+        record_digests(&public_values.records)
+    );
+}
+
+#[test]
+fn http_payload_parses_the_binary_frame_as_a_response() {
+    // The continuation record declares a 1000-byte body and carries two bytes.
+    assert_eq!(
+        execute(&input(RecordPayload::Http).into())
+            .unwrap_err()
+            .reason(),
+        Some(AbortCode::BodyLengthMismatch)
+    );
+}
+
+#[test]
+fn http_options_are_rejected_for_binary_records() {
+    let (verifying_key, verifying_data) = attest_records(&websocket_records());
+    let built = ZkTlsInput::builder()
+        .verifying_key(verifying_key)
+        .data(verifying_data)
+        .record_payload(RecordPayload::Binary)
+        .text_records(true)
+        .build();
+
+    assert!(matches!(
+        built,
+        Err(InputError::BinaryRecords {
+            option: "text_records"
+        })
+    ));
+}
+
+#[test]
+fn unknown_payload_aborts() {
+    let mut stdin: SP1Stdin = input(RecordPayload::Binary).into();
+    // The payload tag directly follows the records encoding tag.
+    stdin.buffer[14] = bincode::serialize(&2u8).unwrap();
+
+    assert_eq!(
+        execute(&stdin).unwrap_err().reason(),
+        Some(AbortCode::UnknownRecordPayload)
+    );
+}
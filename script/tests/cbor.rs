@@ -0,0 +1,33 @@
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use std::path::Path;
+use zktls_lib::PublicValues;
+use zktls_script::{load_bench, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+fn public_values_for(data_file: &str) -> PublicValues {
+    let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+    let key_path = format!("{}/verifying_k256.key", fixtures);
+    let data_path = format!("{}/data/{}", fixtures, data_file);
+    let (verifying_key, verifying_data) = load_bench(
+        16,
+        Some(Path::new(&key_path)),
+        Some(Path::new(&data_path)),
+        None,
+    )
+    .unwrap();
+
+    let stdin: SP1Stdin = ZkTlsInput::new(verifying_key, verifying_data).into();
+
+    let client = ProverClient::builder().cpu().build();
+    let (mut public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+    public_values.read()
+}
+
+#[test]
+fn cbor_and_json_commit_the_same_values() {
+    assert_eq!(
+        public_values_for("bench16.cbor"),
+        public_values_for("bench16.json")
+    );
+}
@@ -0,0 +1,89 @@
+use sp1_sdk::{include_elf, ProverClient};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::hash::RecordHasher;
+use zktls_lib::key::VerifyingKey;
+use zktls_lib::{DataFormat, Record};
+use zktls_script::{
+    attest_records, read_claim, Attestation, ClaimFixture, SP1ZktlsProofFixture, ZkTlsInput,
+};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+const MESSAGES: [&[u8]; 2] = [
+    b"GET /v1/accounts/acc_123 HTTP/1.1\r\nHost: api.example.com\r\n\r\n",
+    b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nDate: Sun, 06 Nov 1994 08:49:37 GMT\r\nContent-Length: 44\r\n\r\n{\"account\":\"acc_123\",\"balance\":\"1000000.01\"}",
+];
+
+#[test]
+fn claim_matches_the_attested_inputs() {
+    let (verifying_key, verifying_data) = attest_records(&MESSAGES);
+    let input = ZkTlsInput {
+        claim_path: Some("$.balance".to_string()),
+        ..ZkTlsInput::new(verifying_key.clone(), Attestation::Opt(verifying_data))
+    };
+    let records: Vec<Record> = MESSAGES
+        .iter()
+        .map(|message| Record::from_hex(&hex::encode(message)).unwrap())
+        .collect();
+    let client = ProverClient::builder().cpu().build();
+
+    let (public_values, _) = client.execute(ZKTLS_ELF, &input.into()).run().unwrap();
+    let claim = read_claim(&public_values).unwrap();
+
+    let attestor = VerifyingKey::parse(&verifying_key)
+        .unwrap()
+        .address()
+        .unwrap();
+    assert_eq!(claim.attestor, attestor);
+    assert_eq!(claim.domain, "api.example.com");
+    assert_eq!(claim.path, "$.balance");
+    assert_eq!(claim.value, "1000000.01");
+    assert_eq!(claim.timestamp, 784_111_777);
+    assert_eq!(
+        claim.recordsDigest,
+        RecordHasher::default().hash_records(&records)
+    );
+
+    // A fixture records the claim a contract decodes from the same public values.
+    let fixture = SP1ZktlsProofFixture {
+        public_values: format!("0x{}", hex::encode(public_values.as_slice())),
+        claim: Some(ClaimFixture::from(&claim)),
+        ..serde_json::from_value(serde_json::json!({
+            "recordsDigest": "0x",
+            "vkey": "0x",
+            "proof": "0x",
+        }))
+        .unwrap()
+    };
+    assert_eq!(fixture.claim().unwrap(), claim);
+    assert_eq!(
+        fixture.claim.unwrap().attestor,
+        format!("0x{}", hex::encode(attestor))
+    );
+}
+
+#[test]
+fn unverified_attestation_is_not_claimed() {
+    let verifying_key =
+        std::fs::read_to_string(format!("{}/verifying_k256.key", FIXTURES)).unwrap();
+    let verifying_data =
+        std::fs::read_to_string(format!("{}/data/balance_above.json", FIXTURES)).unwrap();
+    let input = ZkTlsInput {
+        report_status: true,
+        claim_path: Some("$.balance".to_string()),
+        ..ZkTlsInput::new(
+            verifying_key,
+            Attestation::parse(&verifying_data, Some(DataFormat::Opt)).unwrap(),
+        )
+    };
+    let client = ProverClient::builder().cpu().build();
+
+    let (public_values, _) = client.execute(ZKTLS_ELF, &input.into()).run().unwrap();
+
+    assert_eq!(
+        read_claim(&public_values).unwrap_err().reason(),
+        Some(AbortCode::SignatureFailure)
+    );
+}
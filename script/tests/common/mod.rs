@@ -0,0 +1,9 @@
+/// A fixture under `fixtures/zktls/data`, parsed as JSON.
+pub fn fixture_json(name: &str) -> serde_json::Value {
+    let path = format!(
+        "{}/../fixtures/zktls/data/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
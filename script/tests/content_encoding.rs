@@ -0,0 +1,45 @@
+use sp1_sdk::{include_elf, ProverClient};
+use zktls_lib::content_encoding::ContentEncoding;
+use zktls_lib::{DataFormat, PublicValues};
+use zktls_script::{read_public_values, Attestation, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+fn execute(data_file: &str, predicate: Option<&str>) -> PublicValues {
+    let verifying_key =
+        std::fs::read_to_string(format!("{}/generated_k256.key", FIXTURES)).unwrap();
+    let verifying_data =
+        std::fs::read_to_string(format!("{}/data/{}", FIXTURES, data_file)).unwrap();
+    let input = ZkTlsInput {
+        predicate: predicate.map(|predicate| predicate.parse().unwrap()),
+        ..ZkTlsInput::new(
+            verifying_key,
+            Attestation::parse(&verifying_data, Some(DataFormat::Opt)).unwrap(),
+        )
+    };
+    let client = ProverClient::builder().cpu().build();
+
+    let (public_values, _) = client.execute(ZKTLS_ELF, &input.into()).run().unwrap();
+    read_public_values(&public_values).unwrap()
+}
+
+#[test]
+fn gzip_response_is_committed_as_plaintext() {
+    let plain = execute("balance_above.json", None);
+    let gzip = execute("balance_above_gzip.json", None);
+
+    assert_eq!(gzip.records, plain.records);
+    assert_eq!(gzip.digests, plain.digests);
+    assert_eq!(plain.decoded_bodies, []);
+    assert_eq!(gzip.decoded_bodies.len(), 1);
+    assert_eq!(gzip.decoded_bodies[0].encoding, ContentEncoding::Gzip.tag());
+}
+
+#[test]
+fn predicate_is_evaluated_over_the_decoded_body() {
+    let public_values = execute("balance_above_gzip.json", Some("$.balance >= 1000000"));
+
+    assert!(public_values.predicate.unwrap().result);
+}
@@ -0,0 +1,66 @@
+use sp1_sdk::{include_elf, ProverClient, SP1PublicValues, SP1Stdin};
+use zktls_lib::context::AppContext;
+use zktls_lib::PublicValues;
+use zktls_script::{load_bench, InputError, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+fn execute(context: Option<AppContext>) -> SP1PublicValues {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(16, None, None, None).unwrap();
+    let stdin: SP1Stdin = ZkTlsInput {
+        context,
+        ..ZkTlsInput::new(verifying_key, verifying_data)
+    }
+    .into();
+
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+    public_values
+}
+
+#[test]
+fn chain_id_is_bound_into_the_public_values() {
+    let mainnet = AppContext {
+        chain_id: 1,
+        app_id: [7; 32],
+    };
+    let optimism = AppContext {
+        chain_id: 10,
+        ..mainnet
+    };
+
+    let mut on_mainnet = execute(Some(mainnet));
+    let mut on_optimism = execute(Some(optimism));
+    assert_ne!(on_mainnet.as_slice(), on_optimism.as_slice());
+
+    let on_mainnet: PublicValues = on_mainnet.read();
+    let on_optimism: PublicValues = on_optimism.read();
+    assert_eq!(on_mainnet.context, Some(mainnet));
+    assert_eq!(on_optimism.context, Some(optimism));
+    // Nothing else the program commits depends on the context.
+    assert_eq!(
+        PublicValues {
+            context: None,
+            ..on_mainnet
+        },
+        execute(None).read::<PublicValues>()
+    );
+}
+
+#[test]
+fn context_is_not_combined_with_a_claim() {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(16, None, None, None).unwrap();
+    let result = ZkTlsInput::builder()
+        .verifying_key(verifying_key)
+        .data(verifying_data)
+        .claim(Some("$.balance".to_string()))
+        .context(Some(AppContext::default()))
+        .build();
+
+    assert!(matches!(
+        result,
+        Err(InputError::ClaimOmits { option: "context" })
+    ));
+}
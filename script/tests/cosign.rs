@@ -0,0 +1,99 @@
+use std::path::Path;
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::key::VerifyingKey;
+use zktls_lib::{DataFormat, PublicValues};
+use zktls_script::{
+    load_bench, load_cosignatures, read_public_values, Aborted, InputError, ZkTlsInput,
+    GENERATED_KEY_PATH,
+};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+fn input(cosignatures: &str) -> ZkTlsInput {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(
+        4,
+        Some(Path::new(GENERATED_KEY_PATH)),
+        Some(Path::new("fixtures/zktls/data/cosigned.json")),
+        None,
+    )
+    .unwrap();
+    let path = format!("fixtures/zktls/data/{}", cosignatures);
+    ZkTlsInput {
+        report_status: true,
+        cosignatures: load_cosignatures(Some(Path::new(&path))).unwrap(),
+        ..ZkTlsInput::new(verifying_key, verifying_data)
+    }
+}
+
+fn execute(input: ZkTlsInput) -> Result<PublicValues, Aborted> {
+    let stdin: SP1Stdin = input.into();
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+    read_public_values(&public_values)
+}
+
+#[test]
+fn dual_signed_attestation_commits_both_keys() {
+    let input = input("cosigned.cosignatures.json");
+    let verifying_key = VerifyingKey::from_hex(&input.verifying_key).unwrap();
+    let cosigner = input.cosignatures[0].verifying_key;
+    let public_values = execute(input).unwrap();
+
+    assert!(public_values.verified);
+    let cosigned = public_values.cosigned.unwrap();
+    assert_eq!(
+        cosigned.key_digests,
+        [verifying_key.digest(), cosigner.digest()]
+    );
+    assert_eq!(
+        cosigned.records_digest,
+        public_values.digests.records_digest
+    );
+}
+
+#[test]
+fn wrong_second_signature_aborts() {
+    assert_eq!(
+        execute(input("cosigned_bad.cosignatures.json"))
+            .unwrap_err()
+            .reason(),
+        Some(AbortCode::SignatureFailure)
+    );
+}
+
+#[test]
+fn without_cosignatures_nothing_is_committed() {
+    let input = ZkTlsInput {
+        cosignatures: Vec::new(),
+        ..input("cosigned.cosignatures.json")
+    };
+
+    assert_eq!(execute(input).unwrap().cosigned, None);
+}
+
+#[test]
+fn streamed_attestation_is_rejected() {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(
+        4,
+        Some(Path::new(GENERATED_KEY_PATH)),
+        Some(Path::new("fixtures/zktls/data/cosigned.json")),
+        Some(DataFormat::Stream),
+    )
+    .unwrap();
+    let cosignatures = input("cosigned.cosignatures.json").cosignatures;
+
+    let err = ZkTlsInput::builder()
+        .verifying_key(verifying_key)
+        .data(verifying_data)
+        .cosignatures(cosignatures)
+        .build()
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        InputError::CoSignaturesUnsupported(DataFormat::Stream)
+    ));
+}
@@ -0,0 +1,54 @@
+use clap::Parser;
+use zktls_script::{InputArgs, InputError};
+
+#[derive(Parser)]
+struct Args {
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+fn input_args(args: &[&str]) -> InputArgs {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    Args::parse_from(["zktls"].iter().chain(args)).input
+}
+
+#[test]
+fn unpaired_extract_path_is_an_error() {
+    let args = input_args(&[
+        "--data-path",
+        "fixtures/zktls/data/bench16.json",
+        "--extract-path",
+        "$.data",
+    ]);
+
+    assert!(matches!(
+        args.cross_check(),
+        Err(InputError::CrossCheckPaths {
+            data_paths: 1,
+            extract_paths: 1
+        })
+    ));
+    // Building returns the error rather than exiting, so a watcher or embedder keeps running.
+    assert!(matches!(
+        args.builder(),
+        Err(InputError::CrossCheckPaths { .. })
+    ));
+}
+
+#[test]
+fn two_attestations_are_cross_checked() {
+    let args = input_args(&[
+        "--data-path",
+        "fixtures/zktls/data/bench16.json",
+        "--data-path",
+        "fixtures/zktls/data/bench256.json",
+        "--extract-path",
+        "$.data",
+        "--extract-path",
+        "$.result",
+    ]);
+
+    let cross_check = args.cross_check().unwrap().unwrap();
+    assert_eq!(cross_check.path, "$.data");
+    assert_eq!(cross_check.other_path, "$.result");
+}
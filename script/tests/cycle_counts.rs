@@ -0,0 +1,78 @@
+//! Guards against cycle-count regressions by executing every bench fixture and comparing the
+//! instruction count against the known-good values in `fixtures/cycle_counts.json`.
+//!
+//! Counts only mean something from the real zkVM, and executing every fixture is slow, so the test
+//! is ignored by a plain `cargo test` and runs with `--ignored`, as CI does. Run it with
+//! `UPDATE_CYCLE_COUNTS=1` to record the current counts as the new baseline. A fixture without a
+//! recorded count fails the test until one is recorded.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_script::{load_bench, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures");
+
+/// How far a cycle count may drift from its baseline, in percent.
+const TOLERANCE_PERCENT: u64 = 5;
+
+#[test]
+#[ignore]
+fn cycle_counts_match_baseline() {
+    let baseline_path = format!("{}/cycle_counts.json", FIXTURES);
+    let mut baseline: BTreeMap<u32, Option<u64>> =
+        serde_json::from_str(&std::fs::read_to_string(&baseline_path).unwrap()).unwrap();
+    let update = std::env::var_os("UPDATE_CYCLE_COUNTS").is_some();
+
+    let client = ProverClient::builder().cpu().build();
+    let key_path = format!("{}/zktls/verifying_k256.key", FIXTURES);
+
+    let mut failures = Vec::new();
+    for (&length, expected) in baseline.iter_mut() {
+        let data_path = format!("{}/zktls/data/bench{}.json", FIXTURES, length);
+        let (verifying_key, verifying_data) = load_bench(
+            length,
+            Some(Path::new(&key_path)),
+            Some(Path::new(&data_path)),
+            None,
+        )
+        .unwrap();
+        let stdin: SP1Stdin = ZkTlsInput::new(verifying_key, verifying_data).into();
+
+        let (_, report) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+        let cycles = report.total_instruction_count();
+
+        match *expected {
+            _ if update => *expected = Some(cycles),
+            None => failures.push(format!(
+                "bench{}: {} cycles, no baseline recorded",
+                length, cycles
+            )),
+            Some(expected) => {
+                let tolerance = expected * TOLERANCE_PERCENT / 100;
+                if cycles.abs_diff(expected) > tolerance {
+                    failures.push(format!(
+                        "bench{}: {} cycles, expected {} ± {}",
+                        length, cycles, expected, tolerance
+                    ));
+                }
+            }
+        }
+    }
+
+    if update {
+        let baseline = serde_json::to_string_pretty(&baseline).unwrap();
+        std::fs::write(&baseline_path, baseline + "\n").unwrap();
+    }
+
+    assert!(
+        failures.is_empty(),
+        "cycle counts are unrecorded or drifted more than {}% from {}:\n{}",
+        TOLERANCE_PERCENT,
+        baseline_path,
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,53 @@
+use std::process::{Command, Output};
+
+use zktls_script::{VerificationReport, CYCLES_PER_RECORD, FIXED_OVERHEAD};
+
+fn dry_run(bin: &str, args: &[&str]) -> (Output, VerificationReport) {
+    let output = Command::new(bin)
+        .args(["--dry-run", "--json-report", "--zktls-length", "16"])
+        .args(args)
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+        .output()
+        .unwrap();
+    let report = serde_json::from_slice(&output.stdout).unwrap();
+    (output, report)
+}
+
+#[test]
+fn dry_run_verifies_and_estimates_without_executing() {
+    let (output, report) = dry_run(env!("CARGO_BIN_EXE_zktls"), &[]);
+
+    assert!(output.status.success());
+    assert!(report.verified);
+    assert_eq!(report.record_count, 4);
+    assert_eq!(report.cycles, None);
+    assert_eq!(
+        report.estimated_cycles,
+        Some(4 * CYCLES_PER_RECORD + FIXED_OVERHEAD)
+    );
+}
+
+#[test]
+fn dry_run_fails_on_the_wrong_key() {
+    let (output, report) = dry_run(
+        env!("CARGO_BIN_EXE_zktls"),
+        &["--key-path", "fixtures/zktls/merkle_verifying_k256.key"],
+    );
+
+    assert!(!output.status.success());
+    assert!(!report.verified);
+    assert!(report.error.is_some());
+}
+
+#[test]
+fn dry_run_reports_an_attestation_that_does_not_parse() {
+    let (output, report) = dry_run(
+        env!("CARGO_BIN_EXE_evm"),
+        &["--data-path", "fixtures/zktls/eip712_domain.json"],
+    );
+
+    assert!(!output.status.success());
+    assert!(report
+        .error
+        .is_some_and(|error| error.contains("eip712_domain.json")));
+}
@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use zktls_lib::DataFormat;
+use zktls_script::{load_bench, Attestation, EstimateCycles, CYCLES_PER_RECORD, FIXED_OVERHEAD};
+
+#[test]
+fn estimate_grows_with_record_count() {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (_, bench16) = load_bench(16, None, None, None).unwrap();
+    let (_, bench256) = load_bench(256, None, None, None).unwrap();
+
+    assert_eq!(bench16.record_count(), 4);
+    assert_eq!(
+        bench16.estimate_cycle_cost(),
+        4 * CYCLES_PER_RECORD + FIXED_OVERHEAD
+    );
+    assert!(bench16.estimate_cycle_cost() > bench256.estimate_cycle_cost());
+}
+
+/// The estimate stays within a factor of two of every recorded baseline.
+#[test]
+fn estimate_is_close_to_the_baseline() {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let baseline: BTreeMap<u32, Option<u64>> =
+        serde_json::from_str(&std::fs::read_to_string("fixtures/cycle_counts.json").unwrap())
+            .unwrap();
+
+    for (length, cycles) in baseline {
+        let Some(cycles) = cycles else { continue };
+        let (_, data) = load_bench(length, None, None, None).unwrap();
+        let estimate = data.estimate_cycle_cost();
+        assert!(
+            estimate / 2 <= cycles && cycles <= estimate * 2,
+            "bench{}: estimated {} cycles, executed {}",
+            length,
+            estimate,
+            cycles
+        );
+    }
+}
+
+#[test]
+fn iter_records_counts_like_record_count() {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (_, bench16) = load_bench(16, None, None, None).unwrap();
+    let merkle = std::fs::read_to_string("fixtures/zktls/data/merkle_transcript.json").unwrap();
+    let merkle = Attestation::parse(&merkle, Some(DataFormat::Merkle)).unwrap();
+
+    for attestation in [bench16, merkle] {
+        assert_eq!(
+            attestation.iter_records().count(),
+            attestation.record_count()
+        );
+        assert_eq!(
+            attestation
+                .iter_records()
+                .map(|record| hex::encode(record.unwrap().bytes))
+                .collect::<Vec<_>>(),
+            attestation.get_records().unwrap()
+        );
+    }
+}
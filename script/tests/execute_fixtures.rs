@@ -0,0 +1,63 @@
+//! Executes the program on each bench fixture and checks that it commits the fixture's records.
+//! Executing the larger fixtures is slow, so these only run with
+//! `cargo test --release --test execute_fixtures -- --include-ignored`.
+
+use std::path::Path;
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::{PublicValues, Record};
+use zktls_script::{load_bench, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+fn execute_bench(length: u32) {
+    let key_path = format!("{}/verifying_k256.key", FIXTURES);
+    let data_path = format!("{}/data/bench{}.json", FIXTURES, length);
+    let (verifying_key, verifying_data) = load_bench(
+        length,
+        Some(Path::new(&key_path)),
+        Some(Path::new(&data_path)),
+        None,
+    )
+    .unwrap();
+    let records: Vec<Record> = verifying_data.records().unwrap();
+    let stdin: SP1Stdin = ZkTlsInput::new(verifying_key.clone(), verifying_data).into();
+
+    let client = ProverClient::builder().cpu().build();
+    let (mut public_values, report) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+    assert!(report.total_instruction_count() > 0);
+
+    let public_values: PublicValues = public_values.read();
+    assert!(public_values.verified);
+    assert_eq!(
+        public_values.verifying_key.to_string(),
+        verifying_key.trim()
+    );
+    assert_eq!(public_values.records, records);
+}
+
+#[test]
+#[ignore]
+fn executes_bench16() {
+    execute_bench(16);
+}
+
+#[test]
+#[ignore]
+fn executes_bench256() {
+    execute_bench(256);
+}
+
+#[test]
+#[ignore]
+fn executes_bench1024() {
+    execute_bench(1024);
+}
+
+#[test]
+#[ignore]
+fn executes_bench2048() {
+    execute_bench(2048);
+}
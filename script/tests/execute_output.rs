@@ -0,0 +1,29 @@
+use std::process::{Command, Output};
+
+fn execute(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_zktls"))
+        .args(["--execute", "--zktls-length", "16"])
+        .args(args)
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+        .env("SP1_PROVER", "cpu")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn verified_attestation_is_reported() {
+    let output = execute(&[]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Attestation verified: true"), "{}", stdout);
+}
+
+#[test]
+fn unverified_attestation_is_reported() {
+    // `balance_above.json` is not signed under the bench key.
+    let output = execute(&["--data-path", "fixtures/zktls/data/balance_above.json"]);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Attestation verified: false"), "{}", stdout);
+}
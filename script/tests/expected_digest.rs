@@ -0,0 +1,52 @@
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::public_values::digest;
+use zktls_lib::{PublicValues, Record};
+use zktls_script::{load_bench, read_public_values, Aborted, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+/// The length-16 bench attestation, expected to hash to the digest `expected_digest` computes
+/// from its records.
+fn bench16(expected_digest: impl FnOnce(&[Record]) -> [u8; 32]) -> SP1Stdin {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(16, None, None, None).unwrap();
+    let records: Vec<Record> = verifying_data.records().unwrap();
+    ZkTlsInput {
+        report_status: true,
+        expected_digest: Some(expected_digest(&records)),
+        ..ZkTlsInput::new(verifying_key, verifying_data)
+    }
+    .into()
+}
+
+fn execute(stdin: &SP1Stdin) -> Result<PublicValues, Aborted> {
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, stdin).run().unwrap();
+    read_public_values(&public_values)
+}
+
+#[test]
+fn matching_digest_verifies() {
+    let public_values = execute(&bench16(|records| digest(records.iter()))).unwrap();
+
+    assert!(public_values.verified);
+    assert_eq!(
+        public_values.digests.records_digest,
+        digest(public_values.records.iter())
+    );
+}
+
+#[test]
+fn mismatching_digest_aborts() {
+    let stdin = bench16(|records| {
+        let mut records = records.to_vec();
+        records[0].bytes[0] ^= 1;
+        digest(records.iter())
+    });
+
+    assert_eq!(
+        execute(&stdin).unwrap_err().reason(),
+        Some(AbortCode::DigestMismatch)
+    );
+}
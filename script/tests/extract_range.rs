@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::public_values::digest;
+use zktls_lib::record::{ExtractedRange, RangeRequest};
+use zktls_lib::{PublicValues, Record};
+use zktls_script::{
+    load_bench, read_public_values, Aborted, Attestation, ZkTlsInput, GENERATED_KEY_PATH,
+};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+fn load() -> (String, Attestation) {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    load_bench(
+        16,
+        Some(Path::new(GENERATED_KEY_PATH)),
+        Some(Path::new("fixtures/zktls/data/html_page.json")),
+        None,
+    )
+    .unwrap()
+}
+
+fn execute(extract: Vec<RangeRequest>) -> Result<PublicValues, Aborted> {
+    let (verifying_key, verifying_data) = load();
+    let stdin: SP1Stdin = ZkTlsInput {
+        report_status: true,
+        extract,
+        ..ZkTlsInput::new(verifying_key, verifying_data)
+    }
+    .into();
+
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+    read_public_values(&public_values)
+}
+
+#[test]
+fn extracted_bytes_match_the_fixture() {
+    let (_, verifying_data) = load();
+    let records: Vec<Record> = verifying_data.records().unwrap();
+    let range = RangeRequest {
+        record: 1,
+        start: 1870,
+        end: 1908,
+    };
+    let public_values = execute(vec![range]).unwrap();
+
+    assert_eq!(
+        public_values.extracted,
+        [ExtractedRange {
+            record: 1,
+            start: 1870,
+            end: 1908,
+            bytes: records[1].bytes[1870..1908].to_vec(),
+        }]
+    );
+    assert_eq!(
+        public_values.extracted[0].bytes,
+        br#"<span id="balance">1,024.50 USD</span>"#
+    );
+    // Only the extracted bytes are revealed, next to the digest of the whole transcript.
+    assert!(public_values.records.is_empty());
+    assert_eq!(public_values.digests.records_digest, digest(records.iter()));
+}
+
+#[test]
+fn out_of_range_extraction_aborts() {
+    // The response record holds 1931 bytes.
+    let range = RangeRequest {
+        record: 1,
+        start: 1900,
+        end: 1932,
+    };
+
+    assert_eq!(
+        execute(vec![range]).unwrap_err().reason(),
+        Some(AbortCode::RangeOutOfBounds)
+    );
+}
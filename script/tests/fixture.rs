@@ -0,0 +1,100 @@
+use sp1_sdk::{include_elf, HashableKey, ProverClient};
+use zktls_lib::Record;
+use zktls_script::{
+    load_bench, DecodeError, ProofEncoding, SP1ZktlsProofFixture, ZkTlsInput, ZkTlsProof,
+};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+fn fixture_with(public_values: &[u8]) -> SP1ZktlsProofFixture {
+    let json = serde_json::json!({
+        "requestDigest": "0x",
+        "responseDigest": "0x",
+        "recordsDigest": "0x",
+        "publicValues": format!("0x{}", hex::encode(public_values)),
+        "vkey": "0x",
+        "proof": "0x",
+    });
+    serde_json::from_value(json).unwrap()
+}
+
+#[test]
+fn public_values_decode_to_key_and_records() {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(16, None, None, None).unwrap();
+    let records: Vec<Record> = verifying_data.records().unwrap();
+    let input = ZkTlsInput::new(verifying_key.clone(), verifying_data);
+    let client = ProverClient::builder().cpu().build();
+
+    let (public_values, _) = client.execute(ZKTLS_ELF, &input.into()).run().unwrap();
+    let fixture = fixture_with(public_values.as_slice());
+
+    assert_eq!(
+        fixture.public_values_as_records().unwrap(),
+        (verifying_key.parse().unwrap(), records)
+    );
+}
+
+#[test]
+fn truncated_public_values_are_rejected() {
+    let fixture = fixture_with(&[0, 0]);
+
+    assert!(matches!(
+        fixture.public_values_as_records(),
+        Err(DecodeError::Bincode(_))
+    ));
+}
+
+#[test]
+fn proof_round_trips_in_every_encoding() {
+    let proof = [0x11, 0x22, 0xfe, 0xff];
+
+    for encoding in [
+        ProofEncoding::Hex,
+        ProofEncoding::Base64,
+        ProofEncoding::Raw,
+    ] {
+        let fixture = SP1ZktlsProofFixture {
+            proof: encoding.encode(&proof),
+            proof_encoding: encoding,
+            ..fixture_with(&[])
+        };
+        let json = serde_json::to_string(&fixture).unwrap();
+        let fixture: SP1ZktlsProofFixture = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(fixture.proof_bytes().unwrap(), proof, "{:?}", encoding);
+    }
+
+    // Hex stays the default, and fixtures without `proofEncoding` decode as hex.
+    let json = serde_json::to_value(fixture_with(&[])).unwrap();
+    assert!(json.get("proofEncoding").is_none());
+    assert_eq!(json["proof"], "0x");
+}
+
+#[test]
+fn fixture_converts_from_a_proof() {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(16, None, None, None).unwrap();
+    let records: Vec<Record> = verifying_data.records().unwrap();
+    let input = ZkTlsInput::new(verifying_key.clone(), verifying_data);
+    let client = ProverClient::builder().mock().build();
+    let (pk, vk) = client.setup(ZKTLS_ELF);
+    let proof = ZkTlsProof::from(client.prove(&pk, &input.into()).groth16().run().unwrap());
+
+    let fixture = SP1ZktlsProofFixture::from((&proof, &vk));
+
+    assert_eq!(fixture.vkey, vk.bytes32());
+    assert_eq!(fixture.proof_bytes().unwrap(), proof.0.bytes());
+    assert_eq!(
+        fixture.public_values_as_records().unwrap(),
+        (verifying_key.parse().unwrap(), records)
+    );
+    assert_eq!(
+        fixture.records_digest,
+        format!(
+            "0x{}",
+            hex::encode(proof.public_values().unwrap().digests.records_digest)
+        )
+    );
+    assert!(fixture.claim.is_none());
+}
@@ -0,0 +1,27 @@
+use zktls_script::generate_attestation;
+
+#[test]
+fn generated_attestations_verify_natively() {
+    for (count, record_bytes) in [(1, 16), (16, 16), (4, 100)] {
+        let (verifying_key, verifying_data) = generate_attestation(count, record_bytes);
+
+        assert!(verifying_data.verify(&verifying_key).is_ok());
+        let records = verifying_data.get_records();
+        assert_eq!(records.len(), count);
+        assert!(records
+            .iter()
+            .all(|record| record.len() == 2 * record_bytes));
+    }
+}
+
+#[test]
+fn generation_is_reproducible() {
+    let (first_key, first_data) = generate_attestation(8, 32);
+    let (second_key, second_data) = generate_attestation(8, 32);
+
+    assert_eq!(first_key, second_key);
+    assert_eq!(
+        serde_json::to_string(&first_data).unwrap(),
+        serde_json::to_string(&second_data).unwrap()
+    );
+}
@@ -0,0 +1,67 @@
+use std::path::{Path, PathBuf};
+
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_script::{JsonFile, LoadError, WriteError};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("zktls-json-file-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn bench16() -> VerifyingDataOpt {
+    VerifyingDataOpt::from_json_file(Path::new(&format!("{}/data/bench16.json", FIXTURES))).unwrap()
+}
+
+#[test]
+fn written_attestation_reads_back_unchanged() {
+    let dir = scratch_dir("round-trip");
+    let path = dir.join("bench16.json");
+    let data = bench16();
+
+    data.to_json_file(&path).unwrap();
+    let read = VerifyingDataOpt::from_json_file(&path).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&read).unwrap(),
+        serde_json::to_value(&data).unwrap()
+    );
+    assert!(std::fs::read_to_string(&path).unwrap().contains("\n  "));
+    // Only the attestation is left behind.
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+}
+
+#[test]
+fn writing_replaces_an_existing_file() {
+    let dir = scratch_dir("replace");
+    let path = dir.join("bench16.json");
+    std::fs::write(&path, "stale").unwrap();
+
+    bench16().to_json_file(&path).unwrap();
+
+    assert!(VerifyingDataOpt::from_json_file(&path).is_ok());
+}
+
+#[test]
+fn failed_write_leaves_nothing_behind() {
+    let dir = scratch_dir("missing");
+    let path = dir.join("missing").join("bench16.json");
+
+    let err = bench16().to_json_file(&path).unwrap_err();
+
+    assert!(matches!(err, WriteError::Io { path: ref failed, .. } if *failed == path));
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+}
+
+#[test]
+fn unsupported_version_is_rejected_on_read() {
+    let path = format!("{}/data/unsupported_version.json", FIXTURES);
+
+    assert!(matches!(
+        VerifyingDataOpt::from_json_file(Path::new(&path)),
+        Err(LoadError::Parse { .. })
+    ));
+}
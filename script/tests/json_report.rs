@@ -0,0 +1,18 @@
+use std::process::Command;
+
+#[test]
+fn json_report_is_the_only_stdout_output() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zktls"))
+        .args(["--execute", "--json-report", "--zktls-length", "16"])
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+        .env("SP1_PROVER", "cpu")
+        .output()
+        .unwrap();
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(report["length"], 16);
+    assert!(report["cycles"].as_u64().is_some_and(|cycles| cycles > 0));
+    assert_eq!(report["record_count"], 4);
+    assert_eq!(report["verified"], output.status.success());
+}
@@ -0,0 +1,60 @@
+use std::process::{Command, Output};
+
+use zktls_script::VerificationReport;
+
+fn execute(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_zktls"))
+        .args(["--execute", "--zktls-length", "16"])
+        .args(args)
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+        .env("SP1_PROVER", "cpu")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn execution_over_the_limit_fails() {
+    let output = execute(&["--limit-cycles", "1"]);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("more than the limit of 1"), "{}", stderr);
+}
+
+#[test]
+fn execution_within_the_limit_succeeds() {
+    let output = execute(&["--limit-cycles", &u64::MAX.to_string()]);
+
+    assert!(output.status.success());
+}
+
+#[test]
+fn json_report_records_the_budget_result() {
+    let output = execute(&["--json-report", "--limit-cycles", "1"]);
+
+    let report: VerificationReport = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report.verified);
+    assert_eq!(report.cycle_limit, Some(1));
+    assert_eq!(report.within_cycle_limit, Some(false));
+    assert!(!report.passed());
+    assert!(!output.status.success());
+}
+
+#[test]
+fn without_a_limit_nothing_is_checked() {
+    let output = execute(&["--json-report"]);
+
+    let report: VerificationReport = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report.cycle_limit, None);
+    assert_eq!(report.within_cycle_limit, None);
+    assert_eq!(report.passed(), report.verified);
+}
+
+#[test]
+fn failed_execution_cannot_keep_the_limit() {
+    let report = VerificationReport::failed(16, "program aborted").with_cycle_limit(Some(1_000));
+
+    assert_eq!(report.cycle_limit, Some(1_000));
+    assert_eq!(report.within_cycle_limit, None);
+    assert!(!report.passed());
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use zktls_script::{load_bench, load_fixtures_parallel, Attestation, LoadError};
+
+fn bench_paths() -> Vec<PathBuf> {
+    [16, 256, 1024, 2048]
+        .iter()
+        .map(|length| PathBuf::from(format!("fixtures/zktls/data/bench{}.json", length)))
+        .collect()
+}
+
+#[test]
+fn parallel_load_matches_sequential_order() {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+
+    let loaded = load_fixtures_parallel(&bench_paths()).unwrap();
+
+    assert_eq!(loaded.len(), 4);
+    for ((verifying_key, data), length) in loaded.iter().zip([16, 256, 1024, 2048]) {
+        let (expected_key, expected) = load_bench(length, None, None, None).unwrap();
+        let Attestation::Opt(expected) = expected else {
+            panic!("bench{} is not a VerifyingDataOpt", length);
+        };
+        assert_eq!(verifying_key, &expected_key);
+        assert_eq!(data.get_records(), expected.get_records());
+    }
+}
+
+#[test]
+fn first_missing_fixture_is_reported() {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let mut paths = bench_paths();
+    paths.insert(1, PathBuf::from("fixtures/zktls/data/missing_a.json"));
+    paths.push(PathBuf::from("fixtures/zktls/data/missing_b.json"));
+
+    match load_fixtures_parallel(&paths) {
+        Err(LoadError::Read { path, .. }) => {
+            assert_eq!(path, PathBuf::from("fixtures/zktls/data/missing_a.json"))
+        }
+        other => panic!(
+            "expected a read error, got {:?}",
+            other.map(|items| items.len())
+        ),
+    }
+}
+
+#[test]
+fn unsupported_version_is_rejected_on_load() {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let path = PathBuf::from("fixtures/zktls/data/unsupported_version.json");
+
+    match load_bench(16, None, Some(&path), None) {
+        Err(LoadError::Parse { message, .. }) => {
+            assert!(message.contains("format version 2"), "{}", message)
+        }
+        other => panic!("expected a version error, got {:?}", other.map(|_| ())),
+    }
+}
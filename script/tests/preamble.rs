@@ -0,0 +1,94 @@
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::input::{Preamble, PREAMBLE_MAGIC};
+use zktls_script::{load_bench, read_public_values, Aborted, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+fn bench(records: u32) -> SP1Stdin {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(records, None, None, None).unwrap();
+    ZkTlsInput {
+        report_status: true,
+        ..ZkTlsInput::new(verifying_key, verifying_data)
+    }
+    .into()
+}
+
+/// Execute `stdin`, returning the outcome and the cycles it took.
+fn execute(stdin: &SP1Stdin) -> (Result<(), Aborted>, u64) {
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, report) = client.execute(ZKTLS_ELF, stdin).run().unwrap();
+    (
+        read_public_values(&public_values).map(|_| ()),
+        report.total_instruction_count(),
+    )
+}
+
+#[test]
+fn preamble_is_written_first() {
+    let stdin = bench(16);
+    let preamble = Preamble::from_bytes(&stdin.buffer[0]).unwrap();
+
+    assert_eq!(stdin.buffer[0][..4], PREAMBLE_MAGIC);
+    assert!(preamble.report_status);
+    assert_eq!(
+        preamble,
+        Preamble::new(true, &stdin.buffer[2], &stdin.buffer[4])
+    );
+}
+
+#[test]
+fn truncated_attestation_aborts_before_verifying() {
+    let stdin = bench(2048);
+    let (outcome, full_cycles) = execute(&stdin);
+    assert!(outcome.is_ok());
+
+    let mut truncated = stdin.clone();
+    let attestation = &mut truncated.buffer[4];
+    attestation.truncate(attestation.len() / 2);
+    let (outcome, cycles) = execute(&truncated);
+
+    assert_eq!(outcome.unwrap_err().reason(), Some(AbortCode::BadPreamble));
+    // The attestation is not even read, so the abort costs a small part of a verification.
+    assert!(
+        cycles * 10 < full_cycles,
+        "{} of {} cycles",
+        cycles,
+        full_cycles
+    );
+}
+
+#[test]
+fn misordered_items_abort() {
+    let mut stdin = bench(16);
+    // The key and the attestation swapped, as a host writing them by hand might.
+    stdin.buffer.swap(2, 4);
+
+    assert_eq!(
+        execute(&stdin).0.unwrap_err().reason(),
+        Some(AbortCode::BadPreamble)
+    );
+}
+
+#[test]
+fn corrupted_attestation_fails_the_checksum() {
+    let mut stdin = bench(16);
+    let last = stdin.buffer[4].len() - 1;
+    stdin.buffer[4][last] ^= 1;
+
+    assert_eq!(
+        execute(&stdin).0.unwrap_err().reason(),
+        Some(AbortCode::BadPreamble)
+    );
+}
+
+#[test]
+fn stdin_without_a_preamble_panics() {
+    let mut stdin = bench(16);
+    // The status flag the program read first before it had a preamble.
+    stdin.buffer[0] = bincode::serialize(&true).unwrap();
+
+    let client = ProverClient::builder().cpu().build();
+    assert!(client.execute(ZKTLS_ELF, &stdin).run().is_err());
+}
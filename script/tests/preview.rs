@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::preview::{preview_records, MAX_PREVIEW_BYTES};
+use zktls_lib::{DataFormat, PublicValues, Record};
+use zktls_script::{
+    load_bench, read_public_values, Attestation, InputError, SP1ZktlsProofFixture, ZkTlsInput,
+    GENERATED_KEY_PATH,
+};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+fn load(format: Option<DataFormat>) -> (String, Attestation) {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    load_bench(
+        16,
+        Some(Path::new(GENERATED_KEY_PATH)),
+        Some(Path::new("fixtures/zktls/data/html_page.json")),
+        format,
+    )
+    .unwrap()
+}
+
+fn execute(preview: Option<u32>) -> (Vec<u8>, PublicValues) {
+    let (verifying_key, verifying_data) = load(None);
+    let stdin: SP1Stdin = ZkTlsInput {
+        report_status: true,
+        preview,
+        ..ZkTlsInput::new(verifying_key, verifying_data)
+    }
+    .into();
+
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+    let bytes = public_values.as_slice().to_vec();
+    (bytes, read_public_values(&public_values).unwrap())
+}
+
+#[test]
+fn preview_matches_the_records() {
+    let (_, verifying_data) = load(None);
+    let records: Vec<Record> = verifying_data.records().unwrap();
+    let (_, public_values) = execute(Some(64));
+
+    let preview = public_values.preview.unwrap();
+    assert_eq!(preview, preview_records(&records, 64));
+    assert!(preview.bytes.starts_with(b"GET "));
+    assert_eq!(preview.bytes.len(), 64);
+    // Only the preview is revealed, next to the digest of the whole transcript.
+    assert!(public_values.records.is_empty());
+}
+
+#[test]
+fn preview_is_capped() {
+    let (_, public_values) = execute(Some(4096));
+
+    let preview = public_values.preview.unwrap();
+    assert_eq!(preview.bytes.len(), MAX_PREVIEW_BYTES as usize);
+    assert!(preview.total_bytes > MAX_PREVIEW_BYTES as u64);
+}
+
+#[test]
+fn fixture_decodes_the_preview() {
+    let (bytes, public_values) = execute(Some(32));
+    let fixture: SP1ZktlsProofFixture = serde_json::from_value(serde_json::json!({
+        "recordsDigest": "0x",
+        "publicValues": format!("0x{}", hex::encode(bytes)),
+        "vkey": "0x",
+        "proof": "0x",
+    }))
+    .unwrap();
+
+    assert_eq!(
+        fixture.public_values().unwrap().preview,
+        public_values.preview
+    );
+}
+
+#[test]
+fn without_a_preview_the_records_are_committed() {
+    let (_, public_values) = execute(None);
+
+    assert_eq!(public_values.preview, None);
+    assert!(!public_values.records.is_empty());
+}
+
+#[test]
+fn streamed_attestation_is_rejected() {
+    let (verifying_key, verifying_data) = load(Some(DataFormat::Stream));
+
+    let err = ZkTlsInput::builder()
+        .verifying_key(verifying_key)
+        .data(verifying_data)
+        .preview(Some(64))
+        .build()
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        InputError::StreamedRecords { option: "preview" }
+    ));
+}
@@ -0,0 +1,48 @@
+use sp1_prover::{Groth16Bn254Proof, PlonkBn254Proof};
+use sp1_sdk::{ProverClient, SP1Proof, SP1Stdin};
+use zktls_lib::Record;
+use zktls_script::{load_bench, ProofSystem, ZkTlsInput, ZkTlsProof, ZKTLS_ELF};
+
+fn bench16() -> (String, Vec<Record>, SP1Stdin) {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(16, None, None, None).unwrap();
+    let records = verifying_data.records().unwrap();
+    let stdin = ZkTlsInput::new(verifying_key.clone(), verifying_data).into();
+    (verifying_key, records, stdin)
+}
+
+#[test]
+fn accessors_decode_the_public_values() {
+    let (verifying_key, records, stdin) = bench16();
+    let client = ProverClient::builder().mock().build();
+    let (pk, _) = client.setup(ZKTLS_ELF);
+
+    let proof = ZkTlsProof::from(client.prove(&pk, &stdin).run().unwrap());
+    assert_eq!(
+        proof.verifying_key().unwrap(),
+        verifying_key.parse().unwrap()
+    );
+    assert_eq!(proof.records().unwrap(), records);
+    assert!(proof.public_values().unwrap().verified);
+    assert_eq!(proof.proof_system(), None);
+}
+
+#[test]
+fn proof_system_follows_the_proof_type() {
+    let plonk = SP1Proof::Plonk(PlonkBn254Proof {
+        public_inputs: [String::new(), String::new()],
+        encoded_proof: String::new(),
+        raw_proof: String::new(),
+        plonk_vkey_hash: [0; 32],
+    });
+    let groth16 = SP1Proof::Groth16(Groth16Bn254Proof {
+        public_inputs: [String::new(), String::new()],
+        encoded_proof: String::new(),
+        raw_proof: String::new(),
+        groth16_vkey_hash: [0; 32],
+    });
+
+    assert_eq!(ProofSystem::of(&plonk), Some(ProofSystem::Plonk));
+    assert_eq!(ProofSystem::of(&groth16), Some(ProofSystem::Groth16));
+    assert_eq!(ProofSystem::of(&SP1Proof::Core(Vec::new())), None);
+}
@@ -0,0 +1,40 @@
+//! Proves and verifies the length-16 bench fixture in-process with the CPU prover. This takes
+//! minutes, so it only runs with `cargo test --release --features prove-tests --test prove`.
+#![cfg(feature = "prove-tests")]
+
+use std::path::Path;
+use std::sync::Arc;
+
+use sp1_sdk::{ProverClient, SP1Stdin};
+use zktls_lib::{PublicValues, Record};
+use zktls_script::{load_bench, ZkTlsInput, ZkTlsVerifier};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+#[test]
+fn proof_verifies_and_commits_key_and_records() {
+    let key_path = format!("{}/verifying_k256.key", FIXTURES);
+    let data_path = format!("{}/data/bench16.json", FIXTURES);
+    let (verifying_key, verifying_data) = load_bench(
+        16,
+        Some(Path::new(&key_path)),
+        Some(Path::new(&data_path)),
+        None,
+    )
+    .unwrap();
+    let records: Vec<Record> = verifying_data.records().unwrap();
+    let stdin: SP1Stdin = ZkTlsInput::new(verifying_key.clone(), verifying_data).into();
+
+    let client = Arc::new(ProverClient::from_env());
+    let (verifier, pk) = ZkTlsVerifier::setup(Arc::clone(&client), true);
+    let mut proof = client.prove(&pk, &stdin).run().unwrap();
+    verifier.verify(&proof).unwrap();
+
+    let public_values: PublicValues = proof.public_values.read();
+    assert!(public_values.verified);
+    assert_eq!(
+        public_values.verifying_key.to_string(),
+        verifying_key.trim()
+    );
+    assert_eq!(public_values.records, records);
+}
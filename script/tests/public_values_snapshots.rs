@@ -0,0 +1,61 @@
+//! Pins the public values the program commits for each bench fixture, so a change to their format
+//! cannot reach consumers unnoticed.
+//!
+//! Each test executes the program on a fixture and snapshots the committed bytes in hex under
+//! `tests/snapshots`. A change fails the test; accept it deliberately with `cargo insta review`.
+//! The snapshots must come from the real zkVM, so every test is ignored by a plain `cargo test` and
+//! only runs with `cargo test --release --test public_values_snapshots -- --include-ignored`.
+
+use std::path::Path;
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_script::{load_bench, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+fn assert_public_values_snapshot(length: u32) {
+    let key_path = format!("{}/verifying_k256.key", FIXTURES);
+    let data_path = format!("{}/data/bench{}.json", FIXTURES, length);
+    let (verifying_key, verifying_data) = load_bench(
+        length,
+        Some(Path::new(&key_path)),
+        Some(Path::new(&data_path)),
+        None,
+    )
+    .unwrap();
+    let stdin: SP1Stdin = ZkTlsInput::new(verifying_key, verifying_data).into();
+
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+
+    insta::assert_snapshot!(
+        format!("bench{}", length),
+        hex::encode(public_values.as_slice())
+    );
+}
+
+#[test]
+#[ignore]
+fn bench16_public_values() {
+    assert_public_values_snapshot(16);
+}
+
+#[test]
+#[ignore]
+fn bench256_public_values() {
+    assert_public_values_snapshot(256);
+}
+
+#[test]
+#[ignore]
+fn bench1024_public_values() {
+    assert_public_values_snapshot(1024);
+}
+
+#[test]
+#[ignore]
+fn bench2048_public_values() {
+    assert_public_values_snapshot(2048);
+}
@@ -0,0 +1,74 @@
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::record::{RangeCommitment, RangeRequest};
+use zktls_lib::PublicValues;
+use zktls_script::{load_bench, read_public_values, Aborted, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+fn execute(ranges: Vec<RangeRequest>) -> Result<PublicValues, Aborted> {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(16, None, None, None).unwrap();
+    let stdin: SP1Stdin = ZkTlsInput {
+        report_status: true,
+        ranges,
+        ..ZkTlsInput::new(verifying_key, verifying_data)
+    }
+    .into();
+
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+    read_public_values(&public_values)
+}
+
+#[test]
+fn two_ranges_are_committed() {
+    let ranges = vec![
+        RangeRequest {
+            record: 2,
+            start: 0,
+            end: 6,
+        },
+        RangeRequest {
+            record: 1,
+            start: 1,
+            end: 4,
+        },
+    ];
+    let public_values = execute(ranges).unwrap();
+
+    let records = &public_values.records;
+    assert_eq!(
+        public_values.ranges,
+        [
+            RangeCommitment {
+                record: 2,
+                start: 0,
+                end: 6,
+                digest: records[2].commit_range(0, 6),
+            },
+            RangeCommitment {
+                record: 1,
+                start: 1,
+                end: 4,
+                digest: records[1].commit_range(1, 4),
+            },
+        ]
+    );
+    assert!(execute(Vec::new()).unwrap().ranges.is_empty());
+}
+
+#[test]
+fn out_of_bounds_range_aborts() {
+    // The last record of bench16 holds three bytes.
+    let ranges = vec![RangeRequest {
+        record: 3,
+        start: 2,
+        end: 4,
+    }];
+
+    assert_eq!(
+        execute(ranges).unwrap_err().reason(),
+        Some(AbortCode::RangeOutOfBounds)
+    );
+}
@@ -0,0 +1,85 @@
+use std::path::Path;
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::public_values::{record_digests, DigestMode, RecordCommitment};
+use zktls_lib::PublicValues;
+use zktls_script::{load_bench, read_public_values, Aborted, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+fn bench16() -> ZkTlsInput {
+    let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+    let key_path = format!("{}/verifying_k256.key", fixtures);
+    let data_path = format!("{}/data/bench16.json", fixtures);
+    let (verifying_key, verifying_data) = load_bench(
+        16,
+        Some(Path::new(&key_path)),
+        Some(Path::new(&data_path)),
+        None,
+    )
+    .unwrap();
+
+    ZkTlsInput {
+        report_status: true,
+        ..ZkTlsInput::new(verifying_key, verifying_data)
+    }
+}
+
+fn execute(stdin: &SP1Stdin) -> Result<PublicValues, Aborted> {
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, stdin).run().unwrap();
+    read_public_values(&public_values)
+}
+
+fn execute_with(record_commitment: RecordCommitment, digest_mode: DigestMode) -> PublicValues {
+    let input = ZkTlsInput {
+        record_commitment,
+        digest_mode,
+        ..bench16()
+    };
+    execute(&input.into()).unwrap()
+}
+
+#[test]
+fn raw_commitment_commits_the_records() {
+    let raw = execute_with(RecordCommitment::Raw, DigestMode::Concatenated);
+
+    assert_eq!(raw.record_commitment, RecordCommitment::Raw.tag());
+    assert_eq!(raw.records.len(), 4);
+    assert!(raw.record_digests.is_empty());
+}
+
+#[test]
+fn digest_commitment_leaves_out_the_records() {
+    let raw = execute_with(RecordCommitment::Raw, DigestMode::Concatenated);
+    let digest = execute_with(RecordCommitment::Digest, DigestMode::Concatenated);
+
+    assert_eq!(digest.record_commitment, RecordCommitment::Digest.tag());
+    assert!(digest.records.is_empty());
+    assert!(digest.record_digests.is_empty());
+    assert_eq!(digest.digests, raw.digests);
+}
+
+#[test]
+fn digest_commitment_with_per_record_digests_commits_one_digest_per_record() {
+    let raw = execute_with(RecordCommitment::Raw, DigestMode::Concatenated);
+    let per_record = execute_with(RecordCommitment::Digest, DigestMode::PerRecord);
+
+    assert_eq!(per_record.record_commitment, RecordCommitment::Digest.tag());
+    assert!(per_record.records.is_empty());
+    assert_eq!(per_record.record_digests, record_digests(&raw.records));
+    assert_eq!(per_record.digests, raw.digests);
+}
+
+#[test]
+fn unknown_commitment_aborts() {
+    let mut stdin: SP1Stdin = bench16().into();
+    // The record commitment tag directly follows the preamble.
+    stdin.buffer[1] = bincode::serialize(&2u8).unwrap();
+
+    assert_eq!(
+        execute(&stdin).unwrap_err().reason(),
+        Some(AbortCode::UnknownRecordCommitment)
+    );
+}
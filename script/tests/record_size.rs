@@ -0,0 +1,42 @@
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::{DataFormat, PublicValues};
+use zktls_script::{Attestation, ZkTlsInput, DEFAULT_MAX_RECORD_BYTES};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+fn stdin_for(data_file: &str, max_record_bytes: u32) -> SP1Stdin {
+    let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+    let verifying_key =
+        std::fs::read_to_string(format!("{}/verifying_k256.key", fixtures)).unwrap();
+    let verifying_data =
+        std::fs::read_to_string(format!("{}/data/{}", fixtures, data_file)).unwrap();
+
+    ZkTlsInput {
+        max_record_bytes,
+        ..ZkTlsInput::new(
+            verifying_key,
+            Attestation::parse(&verifying_data, Some(DataFormat::Opt)).unwrap(),
+        )
+    }
+    .into()
+}
+
+#[test]
+fn commits_max_observed_record_size() {
+    let client = ProverClient::builder().cpu().build();
+    let stdin = stdin_for("bench16.json", DEFAULT_MAX_RECORD_BYTES);
+
+    let (mut public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+    let public_values: PublicValues = public_values.read();
+
+    assert_eq!(public_values.largest_record_bytes, 6);
+}
+
+#[test]
+fn rejects_oversized_record() {
+    let client = ProverClient::builder().cpu().build();
+    let stdin = stdin_for("oversized_record.json", DEFAULT_MAX_RECORD_BYTES);
+
+    assert!(client.execute(ZKTLS_ELF, &stdin).run().is_err());
+}
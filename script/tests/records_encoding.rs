@@ -0,0 +1,37 @@
+use sp1_sdk::{include_elf, ProverClient};
+use zktls_lib::record::RecordsEncoding;
+use zktls_lib::{DataFormat, PublicValues};
+use zktls_script::{read_public_values, Attestation, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+fn execute(data_file: &str, records_encoding: RecordsEncoding) -> PublicValues {
+    let verifying_key =
+        std::fs::read_to_string(format!("{}/verifying_k256.key", FIXTURES)).unwrap();
+    let verifying_data =
+        std::fs::read_to_string(format!("{}/data/{}", FIXTURES, data_file)).unwrap();
+    let input = ZkTlsInput {
+        records_encoding,
+        ..ZkTlsInput::new(
+            verifying_key,
+            Attestation::parse(&verifying_data, Some(DataFormat::Opt)).unwrap(),
+        )
+    };
+    let client = ProverClient::builder().cpu().build();
+
+    let (public_values, _) = client.execute(ZKTLS_ELF, &input.into()).run().unwrap();
+    read_public_values(&public_values).unwrap()
+}
+
+#[test]
+fn hex_records_commit_digests_over_decoded_bytes() {
+    let raw = execute("balance_above.json", RecordsEncoding::Raw);
+    let hex = execute("balance_above_hex.json", RecordsEncoding::Hex);
+
+    assert_eq!(hex.records, raw.records);
+    assert_eq!(hex.digests, raw.digests);
+    assert_eq!(hex.records_encoding, RecordsEncoding::Hex.tag());
+    assert_ne!(hex.attestation_digest, raw.attestation_digest);
+}
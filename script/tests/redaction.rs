@@ -0,0 +1,73 @@
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use std::path::Path;
+use zktls_lib::record::{ByteRange, Redaction};
+use zktls_lib::{DataFormat, PublicValues};
+use zktls_script::{load_redactions, Attestation, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+const TOKEN: &[u8] = b"sk_live_4f9a2c7e1b";
+
+fn stdin_for(redactions: &[Redaction]) -> SP1Stdin {
+    let verifying_key =
+        std::fs::read_to_string(format!("{}/verifying_k256.key", FIXTURES)).unwrap();
+    let verifying_data =
+        std::fs::read_to_string(format!("{}/data/redacted_auth.json", FIXTURES)).unwrap();
+
+    // Masking the whole Authorization value would cover the redacted token.
+    ZkTlsInput {
+        redactions: redactions.to_vec(),
+        sanitize: false,
+        ..ZkTlsInput::new(
+            verifying_key,
+            Attestation::parse(&verifying_data, Some(DataFormat::Opt)).unwrap(),
+        )
+    }
+    .into()
+}
+
+#[test]
+fn redacted_authorization_is_absent_from_public_values() {
+    let redactions_path = format!("{}/data/redacted_auth.redactions.json", FIXTURES);
+    let redactions = load_redactions(Some(Path::new(&redactions_path))).unwrap();
+    let client = ProverClient::builder().cpu().build();
+
+    let (mut public_values, _) = client
+        .execute(ZKTLS_ELF, &stdin_for(&redactions))
+        .run()
+        .unwrap();
+    let records = public_values.read::<PublicValues>().records;
+
+    let request = &records[0];
+    assert!(!request
+        .bytes
+        .windows(TOKEN.len())
+        .any(|window| window == TOKEN));
+    assert!(request.bytes.starts_with(b"GET /v1/me HTTP/1.1\r\n"));
+    assert_eq!(request.redacted, vec![ByteRange { start: 66, end: 84 }]);
+    assert!(records[1].redacted.is_empty());
+}
+
+#[test]
+fn rejects_overlapping_redactions() {
+    let redactions = [
+        Redaction {
+            record: 0,
+            start: 66,
+            end: 80,
+        },
+        Redaction {
+            record: 0,
+            start: 70,
+            end: 84,
+        },
+    ];
+    let client = ProverClient::builder().cpu().build();
+
+    assert!(client
+        .execute(ZKTLS_ELF, &stdin_for(&redactions))
+        .run()
+        .is_err());
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "remote")]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_script::{FetchError, FromUrl};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+/// Serve one request with `status`, `content_type` and `body`, returning the URL to GET.
+fn serve(status: &'static str, content_type: &'static str, body: String) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/attestation.json", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        // Read the request head up to the blank line ending it.
+        while reader.read_line(&mut line).unwrap() > 2 {
+            line.clear();
+        }
+        let mut stream = reader.into_inner();
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        )
+        .unwrap();
+    });
+    url
+}
+
+fn bench16() -> String {
+    std::fs::read_to_string(format!("{}/data/bench16.json", FIXTURES)).unwrap()
+}
+
+#[test]
+fn fetched_attestation_matches_the_file() {
+    let url = serve("200 OK", "application/json; charset=utf-8", bench16());
+
+    let fetched = VerifyingDataOpt::from_url(&url).unwrap();
+    let expected: VerifyingDataOpt = serde_json::from_str(&bench16()).unwrap();
+    assert_eq!(fetched.get_records(), expected.get_records());
+
+    let verifying_key =
+        std::fs::read_to_string(format!("{}/verifying_k256.key", FIXTURES)).unwrap();
+    assert!(fetched.verify(&verifying_key).is_ok());
+}
+
+#[test]
+fn other_content_type_is_rejected() {
+    let url = serve("200 OK", "text/html", bench16());
+
+    assert!(matches!(
+        VerifyingDataOpt::from_url(&url),
+        Err(FetchError::ContentType(Some(content_type))) if content_type == "text/html"
+    ));
+}
+
+#[test]
+fn error_status_is_rejected() {
+    let url = serve("404 Not Found", "application/json", "{}".to_string());
+
+    assert!(matches!(
+        VerifyingDataOpt::from_url(&url),
+        Err(FetchError::Status(status)) if status == 404
+    ));
+}
+
+#[test]
+fn body_that_is_not_an_attestation_is_rejected() {
+    let url = serve("200 OK", "application/json", "[1, 2, 3]".to_string());
+
+    assert!(matches!(
+        VerifyingDataOpt::from_url(&url),
+        Err(FetchError::Parse(_))
+    ));
+}
+
+#[test]
+fn silent_server_times_out() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+
+    let err =
+        VerifyingDataOpt::from_url_with_timeout(&url, Duration::from_millis(200)).unwrap_err();
+    assert!(matches!(err, FetchError::Request(err) if err.is_timeout()));
+    drop(listener);
+}
@@ -0,0 +1,31 @@
+use sp1_sdk::{include_elf, ProverClient};
+use zktls_lib::DataFormat;
+use zktls_script::{read_public_values, Attestation, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+#[test]
+fn requested_header_is_committed_with_the_records_digest() {
+    let verifying_key =
+        std::fs::read_to_string(format!("{}/verifying_k256.key", FIXTURES)).unwrap();
+    let verifying_data =
+        std::fs::read_to_string(format!("{}/data/etag_response.json", FIXTURES)).unwrap();
+    let input = ZkTlsInput {
+        header: Some("etag".to_string()),
+        ..ZkTlsInput::new(
+            verifying_key,
+            Attestation::parse(&verifying_data, Some(DataFormat::Opt)).unwrap(),
+        )
+    };
+    let client = ProverClient::builder().cpu().build();
+
+    let (public_values, _) = client.execute(ZKTLS_ELF, &input.into()).run().unwrap();
+    let public_values = read_public_values(&public_values).unwrap();
+
+    let header = public_values.response_header.unwrap();
+    assert_eq!(header.value, "\"33a64df551425fcc\"");
+    assert_eq!(header.count, 1);
+    assert_eq!(header.records_digest, public_values.digests.records_digest);
+}
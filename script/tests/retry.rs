@@ -0,0 +1,101 @@
+use std::cell::Cell;
+use std::thread;
+use std::time::Duration;
+
+use zktls_script::{
+    backoff_delay, is_transient, prove_with_timeout, retry_with_backoff, ProveError, MAX_BACKOFF,
+};
+
+#[test]
+fn backoff_doubles_up_to_the_cap() {
+    let delays: Vec<u64> = (0..8).map(|retry| backoff_delay(retry).as_secs()).collect();
+
+    assert_eq!(delays, [1, 2, 4, 8, 16, 32, 60, 60]);
+    assert_eq!(backoff_delay(u32::MAX), MAX_BACKOFF);
+}
+
+#[test]
+fn only_network_failures_are_transient() {
+    assert!(is_transient(
+        "error sending request: Connection reset by peer"
+    ));
+    assert!(is_transient(
+        "status: Unavailable, message: \"prover network overloaded\""
+    ));
+    assert!(is_transient("429 Too Many Requests"));
+    assert!(!is_transient("program panicked: verification failed"));
+    assert!(!is_transient("invalid proving key"));
+}
+
+#[test]
+fn permanent_failure_is_not_retried() {
+    let attempts = Cell::new(0);
+    let result: Result<(), &str> = retry_with_backoff(3, || {
+        attempts.set(attempts.get() + 1);
+        Err("program panicked")
+    });
+
+    assert_eq!(result, Err("program panicked"));
+    assert_eq!(attempts.get(), 1);
+}
+
+#[test]
+fn transient_failure_is_retried_until_it_succeeds() {
+    let attempts = Cell::new(0);
+    let result = retry_with_backoff(3, || {
+        attempts.set(attempts.get() + 1);
+        if attempts.get() < 2 {
+            Err("connection refused")
+        } else {
+            Ok(attempts.get())
+        }
+    });
+
+    assert_eq!(result, Ok(2));
+}
+
+#[test]
+fn last_failure_is_returned_without_retries_left() {
+    let attempts = Cell::new(0);
+    let result: Result<(), &str> = retry_with_backoff(0, || {
+        attempts.set(attempts.get() + 1);
+        Err("request timed out")
+    });
+
+    assert_eq!(result, Err("request timed out"));
+    assert_eq!(attempts.get(), 1);
+}
+
+#[test]
+fn timeout_applies_to_each_attempt() {
+    let attempts = Cell::new(0);
+    let result = retry_with_backoff(1, || {
+        attempts.set(attempts.get() + 1);
+        let attempt = attempts.get();
+        prove_with_timeout(
+            move || {
+                // The first attempt hangs past its timeout; the retry gets a timeout of its own.
+                if attempt == 1 {
+                    thread::sleep(Duration::from_secs(5));
+                }
+                Ok::<_, String>(attempt)
+            },
+            Some(Duration::from_millis(200)),
+        )
+    });
+
+    assert_eq!(result.unwrap(), 2);
+}
+
+#[test]
+fn timeout_is_reported() {
+    let result = prove_with_timeout(
+        || {
+            thread::sleep(Duration::from_secs(5));
+            Ok::<_, String>(())
+        },
+        Some(Duration::from_millis(50)),
+    );
+
+    assert!(matches!(result, Err(ProveError::Timeout(_))));
+}
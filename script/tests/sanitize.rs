@@ -0,0 +1,61 @@
+use sp1_sdk::{include_elf, ProverClient};
+use zktls_lib::{DataFormat, PublicValues};
+use zktls_script::{Attestation, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+const SECRETS: [&[u8]; 3] = [
+    b"sk_live_9QmX2vR7tK4pL8wZ",
+    b"session=3f1c9e7a5b2d8046",
+    b"ak_51Hd8eP0q3Vz",
+];
+
+fn execute(sanitize: bool) -> PublicValues {
+    let verifying_key =
+        std::fs::read_to_string(format!("{}/verifying_k256.key", FIXTURES)).unwrap();
+    let verifying_data =
+        std::fs::read_to_string(format!("{}/data/sensitive_headers.json", FIXTURES)).unwrap();
+    let input = ZkTlsInput {
+        sanitize,
+        ..ZkTlsInput::new(
+            verifying_key,
+            Attestation::parse(&verifying_data, Some(DataFormat::Opt)).unwrap(),
+        )
+    };
+
+    let client = ProverClient::builder().cpu().build();
+    let (mut public_values, _) = client.execute(ZKTLS_ELF, &input.into()).run().unwrap();
+    public_values.read::<PublicValues>()
+}
+
+fn committed_secrets(public_values: &PublicValues) -> usize {
+    SECRETS
+        .iter()
+        .filter(|secret| {
+            public_values.records.iter().any(|record| {
+                record
+                    .bytes
+                    .windows(secret.len())
+                    .any(|window| window == **secret)
+            })
+        })
+        .count()
+}
+
+#[test]
+fn credentials_are_masked_by_default() {
+    let public_values = execute(true);
+
+    assert!(public_values.sanitized);
+    assert_eq!(committed_secrets(&public_values), 0);
+}
+
+#[test]
+fn opting_out_commits_the_raw_headers() {
+    let public_values = execute(false);
+
+    assert!(!public_values.sanitized);
+    assert_eq!(committed_secrets(&public_values), SECRETS.len());
+}
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use sp1_sdk::SP1Stdin;
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::key::VerifyingKey;
+use zktls_lib::session::ZkTlsSession;
+use zktls_script::{load_verifying_key, new_session, JsonFile, SessionStdin};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+fn bench16_session() -> ZkTlsSession {
+    let verifying_key =
+        load_verifying_key(Path::new(&format!("{}/verifying_k256.key", FIXTURES))).unwrap();
+    let verifying_data =
+        VerifyingDataOpt::from_json_file(Path::new(&format!("{}/data/bench16.json", FIXTURES)))
+            .unwrap();
+    new_session(
+        VerifyingKey::from_hex(&verifying_key).unwrap(),
+        verifying_data,
+    )
+}
+
+fn assert_same(read: &ZkTlsSession, session: &ZkTlsSession) {
+    assert_eq!(read.verifying_key, session.verifying_key);
+    assert_eq!(read.session_id, session.session_id);
+    assert_eq!(read.created_at, session.created_at);
+    assert_eq!(
+        serde_json::to_value(&read.verifying_data).unwrap(),
+        serde_json::to_value(&session.verifying_data).unwrap()
+    );
+}
+
+#[test]
+fn session_reads_back_from_stdin() {
+    let session = bench16_session();
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&true);
+    session.write_to_stdin(&mut stdin);
+
+    // The session is one item, after those already written.
+    assert_eq!(stdin.buffer.len(), 2);
+    stdin.read::<bool>();
+    assert_same(&ZkTlsSession::from_stdin(&stdin).unwrap(), &session);
+}
+
+#[test]
+fn reading_past_the_last_item_fails() {
+    let stdin = SP1Stdin::new();
+
+    assert!(ZkTlsSession::from_stdin(&stdin).is_err());
+}
+
+#[test]
+fn session_round_trips_through_json() {
+    let session = bench16_session();
+    let json = serde_json::to_value(&session).unwrap();
+
+    assert_eq!(json["verifying_key"], session.verifying_key.to_hex());
+    assert_same(&serde_json::from_value(json).unwrap(), &session);
+}
+
+#[test]
+fn sessions_get_distinct_ids() {
+    assert_ne!(bench16_session().session_id, bench16_session().session_id);
+}
@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::{VerifyConfig, VerifyError};
+use zktls_script::{load_bench, read_public_values, Attestation, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+fn load(name: &str) -> (String, Attestation) {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let path = format!("fixtures/zktls/data/{}", name);
+    load_bench(16, None, Some(Path::new(&path)), None).unwrap()
+}
+
+fn abort_code(name: &str) -> Option<AbortCode> {
+    let (verifying_key, verifying_data) = load(name);
+    let stdin: SP1Stdin = ZkTlsInput {
+        report_status: true,
+        ..ZkTlsInput::new(verifying_key, verifying_data)
+    }
+    .into();
+
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+    read_public_values(&public_values).err()?.reason()
+}
+
+#[test]
+fn bench_attestation_is_signed_once() {
+    let (verifying_key, verifying_data) = load("bench16.json");
+
+    assert_eq!(verifying_data.signature_count(), Some(1));
+    assert!(verifying_data.verify(&verifying_key));
+    assert_eq!(abort_code("bench16.json"), None);
+}
+
+#[test]
+fn missing_signature_is_rejected() {
+    let (verifying_key, verifying_data) = load("bench16_missing_signature.json");
+
+    assert_eq!(verifying_data.signature_count(), Some(0));
+    assert!(!verifying_data.verify(&verifying_key));
+    assert_eq!(
+        verifying_data.verify_with_config(&verifying_key, &VerifyConfig::default()),
+        Err(VerifyError::SignatureCountMismatch {
+            records: 4,
+            signatures: 0,
+        })
+    );
+    assert_eq!(
+        abort_code("bench16_missing_signature.json"),
+        Some(AbortCode::SignatureCountMismatch)
+    );
+}
+
+#[test]
+fn duplicated_signature_is_rejected() {
+    let (verifying_key, verifying_data) = load("bench16_extra_signature.json");
+
+    assert_eq!(verifying_data.signature_count(), Some(2));
+    assert!(!verifying_data.verify(&verifying_key));
+    assert_eq!(
+        verifying_data.verify_with_config(&verifying_key, &VerifyConfig::default()),
+        Err(VerifyError::SignatureCountMismatch {
+            records: 4,
+            signatures: 2,
+        })
+    );
+    assert_eq!(
+        abort_code("bench16_extra_signature.json"),
+        Some(AbortCode::SignatureCountMismatch)
+    );
+}
@@ -0,0 +1,108 @@
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::public_values::digest;
+use zktls_lib::Record;
+use zktls_script::{PrefixError, SplitError, SplitVerifyingData};
+
+mod common;
+
+use common::fixture_json;
+
+fn fixture(name: &str) -> VerifyingDataOpt {
+    serde_json::from_value(fixture_json(name)).unwrap()
+}
+
+#[test]
+fn split_then_merge_recovers_records() {
+    // Packets of 4, 2 and 1 records.
+    let data = VerifyingDataOpt::merge(&[
+        fixture("bench16.json"),
+        fixture("bench1024.json"),
+        fixture("bench256.json"),
+    ])
+    .unwrap();
+
+    let chunks = data.split(3).unwrap();
+    let sizes: Vec<usize> = chunks
+        .iter()
+        .map(|chunk| chunk.get_records().len())
+        .collect();
+    assert_eq!(sizes, [4, 3]);
+
+    let merged = VerifyingDataOpt::merge(&chunks).unwrap();
+    assert_eq!(merged.get_records(), data.get_records());
+}
+
+#[test]
+fn halves_verify_and_cover_the_whole() {
+    let verifying_key = std::fs::read_to_string(format!(
+        "{}/../fixtures/zktls/verifying_k256.key",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .unwrap();
+    // Packets of 4 and 2 records.
+    let data =
+        VerifyingDataOpt::merge(&[fixture("bench16.json"), fixture("bench1024.json")]).unwrap();
+
+    let (left, right) = data.split_at(4).unwrap();
+    assert!(left.verify(verifying_key.trim()).is_ok());
+    assert!(right.verify(verifying_key.trim()).is_ok());
+
+    let mut records = left.get_records();
+    records.extend(right.get_records());
+    assert_eq!(records, data.get_records());
+    assert_eq!(left.get_records().len(), 4);
+}
+
+#[test]
+fn split_point_must_be_a_packet_boundary_in_range() {
+    let data =
+        VerifyingDataOpt::merge(&[fixture("bench16.json"), fixture("bench1024.json")]).unwrap();
+
+    assert_eq!(
+        data.split_at(2).err(),
+        Some(SplitError::InsidePacket {
+            n: 2,
+            packet_start: 0,
+            packet_end: 4,
+        })
+    );
+    assert_eq!(
+        data.split_at(7).err(),
+        Some(SplitError::OutOfRange { n: 7, records: 6 })
+    );
+    let (left, right) = data.split_at(6).unwrap();
+    assert_eq!(left.get_records(), data.get_records());
+    assert!(right.get_records().is_empty());
+}
+
+#[test]
+fn full_prefix_digest_matches_the_whole() {
+    let verifying_key = std::fs::read_to_string(format!(
+        "{}/../fixtures/zktls/verifying_k256.key",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .unwrap();
+    // Packets of 4 and 2 records.
+    let data =
+        VerifyingDataOpt::merge(&[fixture("bench16.json"), fixture("bench1024.json")]).unwrap();
+    let records: Vec<Record> = data
+        .get_records()
+        .iter()
+        .map(|record| Record::from_hex(record).unwrap())
+        .collect();
+
+    let whole = data.verify_prefix(verifying_key.trim(), 6).unwrap();
+    assert_eq!(whole, digest(records.iter()));
+
+    let first_packet = data.verify_prefix(verifying_key.trim(), 4).unwrap();
+    assert_eq!(first_packet, digest(records[..4].iter()));
+    assert_eq!(data.prefix(4).unwrap().get_records().len(), 4);
+
+    assert_eq!(
+        data.verify_prefix(verifying_key.trim(), 7),
+        Err(PrefixError::Split(SplitError::OutOfRange {
+            n: 7,
+            records: 6
+        }))
+    );
+}
@@ -0,0 +1,127 @@
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::abort::AbortCode;
+use zktls_lib::predicate::Predicate;
+use zktls_lib::public_values::UNKNOWN_DIRECTION_DIGEST;
+use zktls_lib::{DataFormat, PublicValues};
+use zktls_script::{
+    generate_attestation, read_public_values, Aborted, Attestation, InputError,
+    StreamedAttestation, ZkTlsInput,
+};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+/// Eight 1 KiB records in chunks of 1000 bytes, so most records span two chunks.
+fn streamed() -> (String, Attestation) {
+    let (verifying_key, verifying_data) = generate_attestation(8, 1024);
+    let streamed = StreamedAttestation::new(verifying_data, 1000).unwrap();
+    (verifying_key, streamed.into())
+}
+
+fn execute(stdin: &SP1Stdin) -> Result<PublicValues, Aborted> {
+    let client = ProverClient::builder().cpu().build();
+    let (public_values, _) = client.execute(ZKTLS_ELF, stdin).run().unwrap();
+    read_public_values(&public_values)
+}
+
+#[test]
+fn streamed_attestation_commits_its_length_and_digest() {
+    let (verifying_key, verifying_data) = generate_attestation(8, 1024);
+    let held = execute(&ZkTlsInput::from((verifying_key.clone(), verifying_data)).into()).unwrap();
+    let streamed = execute(&ZkTlsInput::from(streamed()).into()).unwrap();
+
+    assert!(streamed.verified);
+    assert_eq!(streamed.format_tag, DataFormat::Stream.tag());
+    assert_eq!(streamed.streamed_bytes, Some(8 * 1024));
+    assert_eq!(streamed.largest_record_bytes, 1024);
+    assert!(streamed.records.is_empty());
+    // The digest over the records is the one committed when they are held.
+    assert_eq!(streamed.digests.records_digest, held.digests.records_digest);
+    assert_eq!(streamed.digests.response_digest, UNKNOWN_DIRECTION_DIGEST);
+    assert_eq!(held.streamed_bytes, None);
+}
+
+#[test]
+fn chunks_follow_every_other_input() {
+    let (verifying_key, verifying_data) = streamed();
+    let Attestation::Stream(stream) = &verifying_data else {
+        unreachable!()
+    };
+    let chunks: Vec<Vec<u8>> = stream.chunks().map(<[u8]>::to_vec).collect();
+    let stdin: SP1Stdin = ZkTlsInput::new(verifying_key, verifying_data).into();
+
+    assert_eq!(chunks.len(), 9);
+    assert_eq!(
+        stdin.buffer[stdin.buffer.len() - chunks.len()..],
+        chunks[..]
+    );
+}
+
+#[test]
+fn streamed_attestation_verifies_natively_like_its_source() {
+    let (verifying_key, verifying_data) = generate_attestation(8, 1024);
+    let (_, streamed) = streamed();
+    let other_key = include_str!("../../fixtures/zktls/merkle_verifying_k256.key");
+
+    assert!(streamed.verify(&verifying_key));
+    assert!(Attestation::Opt(verifying_data).verify(&verifying_key));
+    assert!(!streamed.verify(other_key.trim()));
+}
+
+#[test]
+fn oversized_chunk_aborts() {
+    let mut stdin: SP1Stdin = ZkTlsInput {
+        report_status: true,
+        ..ZkTlsInput::from(streamed())
+    }
+    .into();
+    let last = stdin.buffer.len() - 1;
+    stdin.buffer[last] = vec![0; 1001];
+
+    assert_eq!(
+        execute(&stdin).unwrap_err().reason(),
+        Some(AbortCode::MalformedInput)
+    );
+}
+
+#[test]
+fn options_needing_the_records_are_rejected() {
+    let (verifying_key, verifying_data) = streamed();
+    let built = ZkTlsInput::builder()
+        .verifying_key(verifying_key)
+        .data(verifying_data)
+        .predicate(Some("$.price >= 1".parse::<Predicate>().unwrap()))
+        .build();
+
+    assert!(matches!(
+        built,
+        Err(InputError::StreamedRecords {
+            option: "predicate"
+        })
+    ));
+}
+
+/// A 4 MiB download proves with only one 64 KiB chunk of it in the program's memory at a time.
+/// This takes a long while, so it only runs with
+/// `cargo test --release --features prove-tests --test streaming`.
+#[cfg(feature = "prove-tests")]
+#[test]
+fn four_mib_stream_proves() {
+    use zktls_lib::stream::STREAM_CHUNK_BYTES;
+
+    let (verifying_key, verifying_data) = generate_attestation(256, 16 * 1024);
+    let streamed = StreamedAttestation::try_from(verifying_data).unwrap();
+    assert_eq!(
+        streamed.chunks().count(),
+        4 * 1024 * 1024 / STREAM_CHUNK_BYTES as usize
+    );
+    let stdin: SP1Stdin = ZkTlsInput::new(verifying_key, streamed.into()).into();
+
+    let client = ProverClient::from_env();
+    let (pk, vk) = client.setup(ZKTLS_ELF);
+    let mut proof = client.prove(&pk, &stdin).run().unwrap();
+    client.verify(&proof, &vk).unwrap();
+
+    let public_values: PublicValues = proof.public_values.read();
+    assert!(public_values.verified);
+    assert_eq!(public_values.streamed_bytes, Some(4 * 1024 * 1024));
+}
@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use zktls_lib::sequence::UNKNOWN_SEQUENCE;
+use zktls_lib::tls::{CipherSuite, TlsVersion};
+use zktls_lib::PublicValues;
+use zktls_script::{load_bench, load_metadata, ZkTlsInput};
+
+const ZKTLS_ELF: &[u8] = include_elf!("zktls-program");
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+/// Execute the program on `data_file` under `key_file` with the metadata of its file, as the
+/// binaries do.
+fn execute(key_file: &str, data_file: &str) -> PublicValues {
+    let key_path = format!("{}/{}", FIXTURES, key_file);
+    let data_path = format!("{}/data/{}", FIXTURES, data_file);
+    let (verifying_key, verifying_data) = load_bench(
+        16,
+        Some(Path::new(&key_path)),
+        Some(Path::new(&data_path)),
+        None,
+    )
+    .unwrap();
+    let stdin: SP1Stdin = ZkTlsInput {
+        metadata: load_metadata(Path::new(&data_path)).unwrap(),
+        ..ZkTlsInput::new(verifying_key, verifying_data)
+    }
+    .into();
+
+    let client = ProverClient::builder().cpu().build();
+    let (mut public_values, _) = client.execute(ZKTLS_ELF, &stdin).run().unwrap();
+    public_values.read()
+}
+
+#[test]
+fn stated_session_is_committed() {
+    let public_values = execute("verifying_k256.key", "tls_session.json");
+
+    assert!(public_values.verified);
+    assert_eq!(public_values.tls_session.version(), Some(TlsVersion::Tls12));
+    assert_eq!(
+        public_values.tls_session.cipher_suite(),
+        Some(CipherSuite::Aes128Gcm)
+    );
+}
+
+#[test]
+fn unstated_version_is_committed_unknown() {
+    let public_values = execute("verifying_k256.key", "bench16.json");
+
+    assert_eq!(
+        public_values.tls_session.version(),
+        Some(TlsVersion::Unknown)
+    );
+    // The cipher suite is recovered from the length of the AES key.
+    assert_eq!(
+        public_values.tls_session.cipher_suite(),
+        Some(CipherSuite::Aes128Gcm)
+    );
+}
+
+#[test]
+fn tls13_session_is_not_rejected_as_reordered() {
+    // The nonces of this session decrease, as TLS 1.3 nonces may.
+    let public_values = execute("generated_k256.key", "tls13_session.json");
+
+    assert!(public_values.verified);
+    assert_eq!(public_values.tls_session.version(), Some(TlsVersion::Tls13));
+    assert_eq!(public_values.first_sequence, UNKNOWN_SEQUENCE);
+    assert_eq!(public_values.last_sequence, UNKNOWN_SEQUENCE);
+}
@@ -0,0 +1,75 @@
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use zktls_script::load_bench;
+
+/// Records the name of every span opened and every field recorded on it.
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<String>>>);
+
+struct Fields(Vec<String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push(format!("{}={:?}", field.name(), value));
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Capture {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _: &Id, _: Context<'_, S>) {
+        let mut fields = Fields(Vec::new());
+        attrs.record(&mut fields);
+        self.0.lock().unwrap().push(format!(
+            "span {} {}",
+            attrs.metadata().name(),
+            fields.0.join(" ")
+        ));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fields(Vec::new());
+        values.record(&mut fields);
+        let span = ctx.span(id).map_or("", |span| span.name());
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("record {} {}", span, fields.0.join(" ")));
+    }
+}
+
+fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let capture = Capture::default();
+    let subscriber = tracing_subscriber::registry().with(capture.clone());
+    let result = tracing::subscriber::with_default(subscriber, f);
+    let spans = capture.0.lock().unwrap().clone();
+    (result, spans)
+}
+
+#[test]
+fn loading_and_verifying_are_instrumented() {
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+
+    let ((verifying_key, data), spans) = capture(|| load_bench(16, None, None, None).unwrap());
+    assert_eq!(
+        spans,
+        ["span load_attestation path=fixtures/zktls/data/bench16.json"]
+    );
+
+    let (verified, spans) = capture(|| data.verify(verifying_key.as_str()));
+    assert!(verified);
+    assert_eq!(spans, ["span verify_attestation format=opt record_count=4"]);
+
+    let (records, spans) = capture(|| data.get_records().unwrap());
+    assert_eq!(records.len(), 4);
+    assert_eq!(
+        spans,
+        [
+            "span get_records format=opt",
+            "record get_records record_count=4",
+        ]
+    );
+}
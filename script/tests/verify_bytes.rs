@@ -0,0 +1,48 @@
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::{Record, VerifyError};
+use zktls_script::VerifyBytes;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fixtures/zktls");
+
+fn verifying_key() -> String {
+    std::fs::read_to_string(format!("{}/verifying_k256.key", FIXTURES))
+        .unwrap()
+        .trim()
+        .to_string()
+}
+
+fn bench16() -> VerifyingDataOpt {
+    let json = std::fs::read_to_string(format!("{}/data/bench16.json", FIXTURES)).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn encoded_attestation_verifies() {
+    let data = bench16();
+    let records: Vec<Record> = data
+        .get_records()
+        .iter()
+        .map(|record| Record::from_hex(record).unwrap())
+        .collect();
+    let bytes = bincode::serialize(&data).unwrap();
+
+    assert_eq!(
+        VerifyingDataOpt::try_verify_bytes(&bytes, &verifying_key()),
+        Ok(records)
+    );
+}
+
+#[test]
+fn truncated_attestation_is_malformed() {
+    let truncated = std::fs::read(format!("{}/data/bench16_truncated.bin", FIXTURES)).unwrap();
+    assert_eq!(
+        VerifyingDataOpt::try_verify_bytes(&truncated, &verifying_key()),
+        Err(VerifyError::MalformedInput)
+    );
+
+    let bytes = bincode::serialize(&bench16()).unwrap();
+    assert_eq!(
+        VerifyingDataOpt::try_verify_bytes(&bytes[..bytes.len() - 1], &verifying_key()),
+        Err(VerifyError::MalformedInput)
+    );
+}
@@ -0,0 +1,74 @@
+//! Runs `verify_offchain` on fixtures. The known-good fixture is proven with Groth16 in-process,
+//! which takes minutes and lots of memory, so that test only runs with
+//! `cargo test --release --features prove-tests --test verify_offchain`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn verify_offchain(fixture: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_verify_offchain"))
+        .args(["--system", "groth16", "--fixture"])
+        .arg(fixture)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn fixture_without_public_values_fails() {
+    // Written before fixtures recorded their public values and digests.
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../contracts/src/fixtures/groth16-fixture.json");
+
+    let output = verify_offchain(&fixture);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Proof failed to verify"));
+}
+
+#[cfg(feature = "prove-tests")]
+#[test]
+fn groth16_fixture_verifies_and_tampered_proof_fails() {
+    use std::sync::Arc;
+
+    use sp1_sdk::{HashableKey, ProverClient, SP1Stdin};
+    use zktls_script::{
+        load_bench, ProofEncoding, SP1ZktlsProofFixture, ZkTlsInput, ZkTlsVerifier,
+    };
+
+    std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap();
+    let (verifying_key, verifying_data) = load_bench(16, None, None, None).unwrap();
+    let stdin: SP1Stdin = ZkTlsInput::new(verifying_key, verifying_data).into();
+
+    let client = Arc::new(ProverClient::from_env());
+    let (verifier, pk) = ZkTlsVerifier::setup(Arc::clone(&client), false);
+    let proof = client.prove(&pk, &stdin).groth16().run().unwrap();
+
+    let fixture: SP1ZktlsProofFixture = serde_json::from_value(serde_json::json!({
+        "requestDigest": "0x",
+        "responseDigest": "0x",
+        "recordsDigest": "0x",
+        "publicValues": format!("0x{}", hex::encode(proof.public_values.as_slice())),
+        "vkey": verifier.vk().bytes32(),
+        "proof": ProofEncoding::Hex.encode(&proof.bytes()),
+    }))
+    .unwrap();
+    let dir = std::env::temp_dir().join(format!("zktls-verify-offchain-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let good = dir.join("good.json");
+    std::fs::write(&good, serde_json::to_string(&fixture).unwrap()).unwrap();
+    assert!(verify_offchain(&good).status.success());
+
+    // Flip a bit of the proof itself, past the four-byte verifier selector.
+    let mut bytes = fixture.proof_bytes().unwrap();
+    bytes[8] ^= 1;
+    let tampered = dir.join("tampered.json");
+    let fixture = SP1ZktlsProofFixture {
+        proof: ProofEncoding::Hex.encode(&bytes),
+        ..fixture
+    };
+    std::fs::write(&tampered, serde_json::to_string(&fixture).unwrap()).unwrap();
+    assert!(!verify_offchain(&tampered).status.success());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
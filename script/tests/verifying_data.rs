@@ -0,0 +1,54 @@
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::verifying_data::{MissingField, VerifyingData};
+
+mod common;
+
+use common::fixture_json;
+
+fn verifying_key() -> String {
+    include_str!("../../fixtures/zktls/verifying_k256.key")
+        .trim()
+        .to_string()
+}
+
+#[test]
+fn complete_attestation_converts() {
+    let data: VerifyingDataOpt = serde_json::from_value(fixture_json("bench256.json")).unwrap();
+    let records = data.get_records();
+
+    let verifying_data = VerifyingData::try_from(data).unwrap();
+    assert_eq!(verifying_data.verify(verifying_key().as_str()), Ok(()));
+    assert_eq!(
+        verifying_data
+            .records()
+            .map(|record| hex::encode(&record.plaintext))
+            .collect::<Vec<_>>(),
+        records
+    );
+}
+
+#[test]
+fn incomplete_attestation_names_the_missing_field() {
+    let mut fixture = fixture_json("bench16.json");
+    fixture["packets"][0]["ecdsa_signature"] = "".into();
+    let data: VerifyingDataOpt = serde_json::from_value(fixture).unwrap();
+    assert_eq!(
+        VerifyingData::try_from(data),
+        Err(MissingField {
+            packet: 0,
+            record: None,
+            field: "ecdsa_signature",
+        })
+    );
+}
+
+#[test]
+fn converts_back_to_an_attestation_that_verifies() {
+    let data: VerifyingDataOpt = serde_json::from_value(fixture_json("bench16.json")).unwrap();
+    let verifying_data = VerifyingData::try_from(data.clone()).unwrap();
+
+    let back = VerifyingDataOpt::from(verifying_data.clone());
+    assert!(back.verify(&verifying_key()).is_ok());
+    assert_eq!(back.get_records(), data.get_records());
+    assert_eq!(VerifyingData::try_from(back), Ok(verifying_data));
+}
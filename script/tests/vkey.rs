@@ -0,0 +1,28 @@
+use std::process::Command;
+use std::sync::Arc;
+
+use sp1_sdk::{HashableKey, ProverClient};
+use zktls_script::ZkTlsVerifier;
+
+#[test]
+fn bytes32_is_a_prefixed_32_byte_hex_string() {
+    let output = Command::new(env!("CARGO_BIN_EXE_vkey")).output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let hash = stdout.trim().strip_prefix("0x").unwrap();
+
+    assert_eq!(hash.len(), 64);
+    assert!(hash.bytes().all(|byte| byte.is_ascii_hexdigit()));
+}
+
+#[test]
+fn bytes32_is_the_verifiers_key() {
+    let output = Command::new(env!("CARGO_BIN_EXE_vkey")).output().unwrap();
+    let (verifier, _) = ZkTlsVerifier::setup(Arc::new(ProverClient::from_env()), true);
+
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        verifier.vk().bytes32()
+    );
+}
@@ -0,0 +1,46 @@
+//! A WebAssembly binding of `VerifyingDataOpt::verify`, for browsers to check an attestation
+//! before sending it to a prover.
+//!
+//! Build it with [wasm-pack](https://rustwasm.github.io/wasm-pack/) from the repository root:
+//!
+//! ```sh
+//! wasm-pack build wasm --target web --out-name zktls
+//! ```
+//!
+//! and call it from JavaScript with the verifying key and attestation as JSON:
+//!
+//! ```js
+//! import init, { verifyAttestation } from "./pkg/zktls.js";
+//!
+//! await init();
+//! const key = JSON.stringify("0352e3fd73b10d77550dba5b8f327a2c9c8b4c980e460b19d96c58529473a60a53");
+//! const data = await (await fetch("bench16.json")).text();
+//! if (!(await verifyAttestation(key, data))) {
+//!     throw new Error("attestation does not verify");
+//! }
+//! ```
+//!
+//! Only the signature is checked; none of the program's size limits, redactions or response checks
+//! run here, so an attestation that passes can still make the program abort.
+
+use wasm_bindgen::prelude::*;
+use zktls_att_verification::verification_data::VerifyingDataOpt;
+use zktls_lib::key::VerifyingKey;
+
+/// Whether `verifying_data`, a `VerifyingDataOpt` as JSON, is signed under `verifying_key`, a JSON
+/// string holding the hex of the key's SEC1 encoding.
+///
+/// The returned promise resolves to `false` when the signature does not verify, and rejects when
+/// either argument does not parse.
+#[wasm_bindgen(js_name = verifyAttestation)]
+pub async fn verify_attestation(
+    verifying_key: String,
+    verifying_data: String,
+) -> Result<bool, JsError> {
+    let verifying_key: VerifyingKey = serde_json::from_str(&verifying_key)
+        .map_err(|err| JsError::new(&format!("verifying key does not parse: {}", err)))?;
+    let verifying_data: VerifyingDataOpt = serde_json::from_str(&verifying_data)
+        .map_err(|err| JsError::new(&format!("attestation does not parse: {}", err)))?;
+
+    Ok(verifying_data.verify(&verifying_key.to_hex()).is_ok())
+}